@@ -174,7 +174,7 @@ pub fn check_alignment_safety(function: &Function, function_safety: SafetyMode)
     // Analyze each statement
     for stmt in &function.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -155,7 +155,7 @@ pub fn check_array_bounds(function: &Function, function_safety: SafetyMode) -> V
     // Analyze each statement
     for stmt in &function.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -267,7 +267,7 @@ fn analyze_statement_bounds(
         }
 
         // EnterLoop/ExitLoop markers are handled through scope tracking
-        Statement::EnterLoop => {
+        Statement::EnterLoop | Statement::EnterDoLoop => {
             tracker.enter_scope();
         }
 
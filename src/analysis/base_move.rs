@@ -0,0 +1,563 @@
+//! Base Subobject Moves
+//!
+//! Moving out of a base-class subobject moves part of the derived object,
+//! leaving it sliced: the base members are in a moved-from state while the
+//! derived members are not.
+//!
+//! ```cpp
+//! class Derived : public Base { ... };
+//!
+//! Derived d;
+//! Base b = std::move(static_cast<Base&>(d));
+//! d.describe();  // ERROR: d was partially moved
+//!
+//! void Derived::hand_off(Sink& sink) {
+//!     sink.take(std::move(static_cast<Base&>(*this)));
+//!     sink.log(*this);  // ERROR: *this was partially moved
+//! }
+//! ```
+//!
+//! A move whose operand is a cast of a variable (or `*this`) to one of its
+//! base classes, direct or indirect, counts as a partial move of the whole
+//! object. Any later use of the object is reported until it is assigned
+//! again, except for members the base doesn't have: like Rust's partial
+//! moves, the derived part is still intact (a move assignment typically
+//! moves those next).
+
+use super::at_line;
+use crate::parser::ast_visitor::Class;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement, Variable};
+use std::collections::HashMap;
+
+/// Where a base subobject was moved out of an object
+#[derive(Clone)]
+struct BaseMove {
+    base: String,
+    line: u32,
+}
+
+struct MoveState<'a> {
+    classes: &'a [Class],
+    /// Declared (non-pointer) type of each local and parameter
+    types: HashMap<String, String>,
+    /// Objects whose base subobject has been moved, keyed by variable name
+    /// (`this` for `*this`)
+    moved: HashMap<String, BaseMove>,
+}
+
+impl MoveState<'_> {
+    fn declare(&mut self, var: &Variable) {
+        self.moved.remove(&var.name);
+        if var.is_pointer {
+            self.types.remove(&var.name);
+        } else {
+            self.types.insert(var.name.clone(), var.type_name.clone());
+        }
+    }
+
+    /// `(object, base)` when `expr` is `static_cast<Base&>(d)` and Base is a
+    /// base class of d's type
+    fn base_subobject<'e>(&self, expr: &'e Expression) -> Option<(&'e str, String)> {
+        let Expression::Cast {
+            inner,
+            target_type: Some(target_type),
+            ..
+        } = expr
+        else {
+            return None;
+        };
+        let object = match inner.as_ref() {
+            Expression::Variable(name) => name.as_str(),
+            Expression::Dereference(inner) => match inner.as_ref() {
+                Expression::Variable(name) if name == "this" => "this",
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let object_type = self.types.get(object)?;
+        let base = class_name(target_type);
+        if class_name(object_type) != base && derives_from(object_type, &base, self.classes) {
+            Some((object, base))
+        } else {
+            None
+        }
+    }
+}
+
+/// `Base` for `const Base &`, `struct Base &&`, `ns::Base`
+fn class_name(type_name: &str) -> String {
+    type_name
+        .trim()
+        .trim_start_matches("const ")
+        .trim_start_matches("struct ")
+        .trim_start_matches("class ")
+        .trim_end_matches(['&', ' '])
+        .trim_end_matches(" const")
+        .to_string()
+}
+
+fn find_class<'c>(name: &str, classes: &'c [Class]) -> Option<&'c Class> {
+    classes.iter().find(|class| {
+        class.name == name
+            || class.name.ends_with(&format!("::{}", name))
+            || name.ends_with(&format!("::{}", class.name))
+    })
+}
+
+fn same_class(a: &str, b: &str) -> bool {
+    a == b || a.ends_with(&format!("::{}", b)) || b.ends_with(&format!("::{}", a))
+}
+
+/// Whether `type_name` has `base` among its direct or indirect base classes
+fn derives_from(type_name: &str, base: &str, classes: &[Class]) -> bool {
+    let mut pending = vec![class_name(type_name)];
+    let mut seen = Vec::new();
+    while let Some(name) = pending.pop() {
+        let Some(class) = find_class(&name, classes) else {
+            continue;
+        };
+        if seen.contains(&class.name) {
+            continue;
+        }
+        seen.push(class.name.clone());
+        for parent in &class.base_classes {
+            let parent = class_name(parent);
+            if same_class(&parent, base) {
+                return true;
+            }
+            pending.push(parent);
+        }
+    }
+    false
+}
+
+/// Whether `class` or one of its bases declares the member `field`
+fn has_member(class: &str, field: &str, classes: &[Class]) -> bool {
+    let mut pending = vec![class.to_string()];
+    let mut seen = Vec::new();
+    while let Some(name) = pending.pop() {
+        let Some(class) = find_class(&name, classes) else {
+            continue;
+        };
+        if seen.contains(&class.name) {
+            continue;
+        }
+        seen.push(class.name.clone());
+        if class.members.iter().any(|member| member.name == field) {
+            return true;
+        }
+        pending.extend(class.base_classes.iter().map(|base| class_name(base)));
+    }
+    false
+}
+
+/// Check a function for uses of an object after one of its base subobjects
+/// was moved out
+pub fn check_base_move(
+    function: &Function,
+    classes: &[Class],
+    function_safety: SafetyMode,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut state = MoveState {
+        classes,
+        types: HashMap::new(),
+        moved: HashMap::new(),
+    };
+    for param in &function.parameters {
+        state.declare(param);
+    }
+    if function.is_method {
+        if let Some((class_name, _)) = function.name.rsplit_once("::") {
+            state
+                .types
+                .insert("this".to_string(), class_name.to_string());
+        }
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        &mut state,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    state: &mut MoveState,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => *unsafe_depth += 1,
+            Statement::ExitUnsafe => *unsafe_depth = unsafe_depth.saturating_sub(1),
+            Statement::VariableDecl(var) => state.declare(var),
+            Statement::Assignment { lhs, rhs, location } => {
+                check_expr(rhs, func_name, location.line, state, *unsafe_depth, errors);
+                match lhs {
+                    // `d = Derived{}` makes d whole again
+                    Expression::Variable(name) => {
+                        state.moved.remove(name);
+                    }
+                    _ => check_expr(lhs, func_name, location.line, state, *unsafe_depth, errors),
+                }
+            }
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => check_expr(
+                target,
+                func_name,
+                location.line,
+                state,
+                *unsafe_depth,
+                errors,
+            ),
+            Statement::Return(Some(expr)) => {
+                check_expr(expr, func_name, 0, state, *unsafe_depth, errors)
+            }
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                let call = Expression::FunctionCall {
+                    name: name.clone(),
+                    args: args.clone(),
+                };
+                check_expr(
+                    &call,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+            }
+            Statement::ExpressionStatement { expr, location } => {
+                check_expr(expr, func_name, location.line, state, *unsafe_depth, errors)
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                check_expr(
+                    condition,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+                let before = state.moved.clone();
+                check_statements(then_branch, func_name, state, unsafe_depth, errors);
+                let after_then = std::mem::replace(&mut state.moved, before);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, state, unsafe_depth, errors);
+                }
+                // Moved on either path means possibly moved afterwards
+                for (name, base_move) in after_then {
+                    state.moved.entry(name).or_insert(base_move);
+                }
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                check_expr(
+                    condition,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+                let before = state.moved.clone();
+                let mut after = before.clone();
+                for case in cases {
+                    state.moved = before.clone();
+                    check_statements(&case.statements, func_name, state, unsafe_depth, errors);
+                    for (name, base_move) in state.moved.drain() {
+                        after.entry(name).or_insert(base_move);
+                    }
+                }
+                state.moved = after;
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, state, unsafe_depth, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Report uses of partially moved objects in `expr`, then record the base
+/// subobjects it moves out
+fn check_expr(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    state: &mut MoveState,
+    unsafe_depth: usize,
+    errors: &mut Vec<String>,
+) {
+    let mut used = Vec::new();
+    let mut moves = Vec::new();
+    scan(expr, state, &mut used, &mut moves);
+
+    for name in used {
+        // Report each object once; later uses would only repeat it
+        let Some(base_move) = state.moved.remove(&name) else {
+            continue;
+        };
+        if unsafe_depth > 0 {
+            continue;
+        }
+        let object = if name == "this" {
+            "*this".to_string()
+        } else {
+            name
+        };
+        // The use comes last: the final "line N" is where the error is
+        errors.push(format!(
+            "In function '{}': use of partially moved '{}': base subobject '{}' has been moved{}, then '{}' is used{}",
+            func_name,
+            object,
+            base_move.base,
+            at_line(base_move.line),
+            object,
+            at_line(line)
+        ));
+    }
+    for (name, base) in moves {
+        state.moved.insert(name, BaseMove { base, line });
+    }
+}
+
+/// Collect the objects an expression uses and the base subobjects it moves
+fn scan(
+    expr: &Expression,
+    state: &MoveState,
+    used: &mut Vec<String>,
+    moves: &mut Vec<(String, String)>,
+) {
+    match expr {
+        Expression::Variable(name) => used.push(name.clone()),
+        Expression::Move { inner, .. } => match state.base_subobject(inner) {
+            Some((object, base)) => moves.push((object.to_string(), base)),
+            None => scan(inner, state, used, moves),
+        },
+        Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => scan(inner, state, used, moves),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                scan(arg, state, used, moves);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            scan(left, state, used, moves);
+            scan(right, state, used, moves);
+        }
+        Expression::MemberAccess { object, field } => match object.as_ref() {
+            Expression::Variable(name)
+                if state.moved.get(name).is_some_and(|base_move| {
+                    !has_member(&base_move.base, field, state.classes)
+                }) => {}
+            _ => scan(object, state, used, moves),
+        },
+        Expression::PointerArithmetic { pointer, .. } => scan(pointer, state, used, moves),
+        Expression::ArraySubscript { array, index } => {
+            scan(array, state, used, moves);
+            scan(index, state, used, moves);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location, variable};
+    use crate::parser::ast_visitor::{CastKind, MoveKind};
+
+    fn class(name: &str, bases: &[&str], members: &[&str]) -> Class {
+        Class {
+            members: members
+                .iter()
+                .map(|member| variable(member, "int"))
+                .collect(),
+            base_classes: bases.iter().map(|base| base.to_string()).collect(),
+            ..test_fixtures::class(name)
+        }
+    }
+
+    fn move_base(object: Expression, base: &str, line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: Expression::Variable("b".to_string()),
+            rhs: Expression::Move {
+                inner: Box::new(Expression::Cast {
+                    inner: Box::new(object),
+                    kind: CastKind::StaticCast,
+                    target_type: Some(format!("{} &", base)),
+                }),
+                kind: MoveKind::StdMove,
+            },
+            location: location(line),
+        }
+    }
+
+    fn use_var(name: &str, line: u32) -> Statement {
+        Statement::FunctionCall {
+            name: "Derived::describe".to_string(),
+            args: vec![Expression::Variable(name.to_string())],
+            location: location(line),
+        }
+    }
+
+    fn check(body: Vec<Statement>, method_of: Option<&str>) -> Vec<String> {
+        let classes = vec![
+            class("Root", &[], &["id"]),
+            class("Base", &["Root"], &[]),
+            class("Derived", &["Base"], &["extra"]),
+        ];
+        let mut state = MoveState {
+            classes: &classes,
+            types: [("d".to_string(), "Derived".to_string())]
+                .into_iter()
+                .collect(),
+            moved: HashMap::new(),
+        };
+        if let Some(class_name) = method_of {
+            state
+                .types
+                .insert("this".to_string(), class_name.to_string());
+        }
+        let mut errors = Vec::new();
+        check_statements(&body, "f", &mut state, &mut 0, &mut errors);
+        errors
+    }
+
+    #[test]
+    fn test_use_after_base_move() {
+        let errors = check(
+            vec![
+                move_base(Expression::Variable("d".to_string()), "Base", 3),
+                use_var("d", 4),
+            ],
+            None,
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].ends_with(
+                "use of partially moved 'd': base subobject 'Base' has been moved at line 3, then 'd' is used at line 4"
+            ),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_use_in_return_names_only_the_move_line() {
+        let errors = check(
+            vec![
+                move_base(Expression::Variable("d".to_string()), "Base", 3),
+                Statement::Return(Some(Expression::Variable("d".to_string()))),
+            ],
+            None,
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].ends_with("has been moved at line 3, then 'd' is used"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_indirect_base_of_this() {
+        let this = Expression::Dereference(Box::new(Expression::Variable("this".to_string())));
+        let errors = check(
+            vec![move_base(this, "Root", 3), use_var("this", 4)],
+            Some("Derived"),
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].contains("use of partially moved '*this'"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_reassignment_makes_object_whole() {
+        let errors = check(
+            vec![
+                move_base(Expression::Variable("d".to_string()), "Base", 3),
+                Statement::Assignment {
+                    lhs: Expression::Variable("d".to_string()),
+                    rhs: Expression::Variable("fresh".to_string()),
+                    location: location(4),
+                },
+                use_var("d", 5),
+            ],
+            None,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_derived_members_stay_usable() {
+        let read = |field: &str, line: u32| Statement::Assignment {
+            lhs: Expression::Variable("out".to_string()),
+            rhs: Expression::MemberAccess {
+                object: Box::new(Expression::Variable("d".to_string())),
+                field: field.to_string(),
+            },
+            location: location(line),
+        };
+        let errors = check(
+            vec![
+                move_base(Expression::Variable("d".to_string()), "Base", 3),
+                read("extra", 4),
+                read("id", 5),
+            ],
+            None,
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].ends_with("'d' is used at line 5"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_cast_to_unrelated_type_is_not_a_base_move() {
+        let errors = check(
+            vec![
+                move_base(Expression::Variable("d".to_string()), "Other", 3),
+                use_var("d", 4),
+            ],
+            None,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
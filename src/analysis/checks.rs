@@ -0,0 +1,358 @@
+//! Registry of Analysis Passes
+//!
+//! Every pass the checker runs has a stable kebab-case name so it can be
+//! listed with `--list-checks` and toggled with `--disable` / `--enable`:
+//!
+//! ```text
+//! rusty-cpp-checker --disable pointer-safety,const-propagation file.cpp
+//! ```
+//!
+//! Heuristic passes that are useful but noisy are off by default; `--pedantic`
+//! turns all of them on (`--disable` still wins for a named pass).
+//!
+//! The dispatch in `analyze_file` (and the IR phase in
+//! `check_borrows_with_safety_context`) consults [`EnabledChecks`] before
+//! running each pass.
+
+use super::ErrorKind;
+use std::collections::HashSet;
+
+/// A named analysis pass
+#[derive(Debug)]
+pub struct Check {
+    /// Stable name used on the command line
+    pub name: &'static str,
+    /// One-line description shown by `--list-checks`
+    pub description: &'static str,
+    /// Kinds of violation the pass reports
+    pub kinds: &'static [ErrorKind],
+    /// Whether the pass runs without an explicit `--enable`; passes that
+    /// don't are pedantic-only and turned on by `--pedantic`
+    pub default_enabled: bool,
+}
+
+/// All passes, in the order they run
+pub const CHECKS: &[Check] = &[
+    Check {
+        name: "pointer-safety",
+        description: "Raw pointer address-of, dereference and arithmetic in @safe code",
+        kinds: &[ErrorKind::UnsafePointer],
+        default_enabled: true,
+    },
+    Check {
+        name: "null-safety",
+        description: "Dereference of possibly-null pointers",
+        kinds: &[ErrorKind::NullDereference, ErrorKind::UseAfterMove],
+        default_enabled: true,
+    },
+    Check {
+        name: "initialization",
+        description: "Reads of variables that are not initialized on every path",
+        kinds: &[ErrorKind::Uninitialized],
+        default_enabled: true,
+    },
+    Check {
+        name: "pointer-provenance",
+        description: "Subtraction/comparison of pointers into different allocations",
+        kinds: &[ErrorKind::UnsafePointer],
+        default_enabled: true,
+    },
+    Check {
+        name: "alignment",
+        description: "Casts and dereferences that may produce misaligned pointers",
+        kinds: &[ErrorKind::UnsafePointer],
+        default_enabled: true,
+    },
+    Check {
+        name: "array-bounds",
+        description: "Constant out-of-bounds array indexing",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "move-from-reference",
+        description: "std::move applied to a reference in @safe code",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "move-from-const",
+        description: "std::move of a const object, element or member, which copies",
+        kinds: &[ErrorKind::Other],
+        default_enabled: false,
+    },
+    Check {
+        name: "pessimizing-move",
+        description: "return std::move(local), which prevents copy elision",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "move-in-call",
+        description: "A variable moved and read in the same call's arguments",
+        kinds: &[ErrorKind::UseAfterMove],
+        default_enabled: true,
+    },
+    Check {
+        name: "base-move",
+        description: "Use of an object after one of its base subobjects was moved",
+        kinds: &[ErrorKind::UseAfterMove],
+        default_enabled: true,
+    },
+    Check {
+        name: "self-assignment",
+        description: "operator= that frees a member before copying it without a self-assignment guard",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "deleted-copy",
+        description: "Copy of a type whose copy constructor or assignment is deleted",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "delete-this",
+        description: "Use of a method's object after `delete this`",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "param-invalidation",
+        description: "Reference parameter used after a same-typed container parameter was resized",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: false,
+    },
+    Check {
+        name: "self-insert",
+        description: "push_back/emplace_back passed an element of its own container",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "range-for-mutation",
+        description: "Containers modified inside a range-for loop over them",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "union-access",
+        description: "Reads of a union member other than the one last written",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "view-lifetime",
+        description: "string_view/span viewing a temporary, or a local returned as a view",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "tuple-lifetime",
+        description: "Returned pair/tuple whose reference elements refer to locals",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "escaping-args",
+        description: "Address of a local passed to an @escapes parameter",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "coroutine-refs",
+        description: "Reference parameters used after a coroutine suspends",
+        kinds: &[ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "lambda-capture",
+        description: "Reference and move-only captures in escaping lambdas",
+        kinds: &[ErrorKind::DanglingReference, ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "unsafe-propagation",
+        description: "Calls from @safe code into unsafe or unannotated functions",
+        kinds: &[ErrorKind::UnsafeCall],
+        default_enabled: true,
+    },
+    Check {
+        name: "mutable-fields",
+        description: "`mutable` fields in @safe classes",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "inheritance",
+        description: "@interface rules and safe inheritance",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "struct-pointer-members",
+        description: "Pointer members of @safe structs that may be null",
+        kinds: &[ErrorKind::UnsafePointer],
+        default_enabled: true,
+    },
+    Check {
+        name: "const-propagation",
+        description: "Mutation through pointer members of const objects",
+        kinds: &[ErrorKind::Other],
+        default_enabled: true,
+    },
+    Check {
+        name: "borrows",
+        description: "Ownership and borrow checking: moves, aliasing, scope exit",
+        kinds: &[
+            ErrorKind::UseAfterMove,
+            ErrorKind::DoubleBorrow,
+            ErrorKind::MutableBorrowWhileImmutable,
+            ErrorKind::BorrowConflict,
+            ErrorKind::DanglingReference,
+        ],
+        default_enabled: true,
+    },
+    Check {
+        name: "lifetimes",
+        description: "@lifetime annotation requirements, inference and validation",
+        kinds: &[ErrorKind::LifetimeViolation, ErrorKind::DanglingReference],
+        default_enabled: true,
+    },
+    Check {
+        name: "raii",
+        description: "Destruction order, double free and containers of dangling pointers",
+        kinds: &[ErrorKind::DanglingReference, ErrorKind::BorrowConflict],
+        default_enabled: true,
+    },
+    Check {
+        name: "unnecessary-unsafe",
+        description: "@unsafe blocks that suppress no diagnostic",
+        kinds: &[ErrorKind::Other],
+        default_enabled: false,
+    },
+];
+
+/// Look up a pass by its command-line name
+pub fn find_check(name: &str) -> Option<&'static Check> {
+    CHECKS.iter().find(|check| check.name == name)
+}
+
+/// The set of passes that run for this invocation
+#[derive(Debug, Clone)]
+pub struct EnabledChecks {
+    enabled: HashSet<&'static str>,
+    pedantic: bool,
+}
+
+impl Default for EnabledChecks {
+    fn default() -> Self {
+        EnabledChecks {
+            enabled: CHECKS
+                .iter()
+                .filter(|check| check.default_enabled)
+                .map(|check| check.name)
+                .collect(),
+            pedantic: false,
+        }
+    }
+}
+
+impl EnabledChecks {
+    /// Start from the defaults (plus the pedantic-only passes with
+    /// `pedantic`), turn off every `disable` entry, then turn on every
+    /// `enable` entry (so `--enable` wins when both name a pass)
+    pub fn from_flags(
+        enable: &[String],
+        disable: &[String],
+        pedantic: bool,
+    ) -> Result<Self, String> {
+        let mut checks = EnabledChecks::default();
+        if pedantic {
+            checks.enabled.extend(CHECKS.iter().map(|check| check.name));
+            checks.pedantic = true;
+        }
+        for name in disable {
+            checks.enabled.remove(lookup(name)?.name);
+        }
+        for name in enable {
+            checks.enabled.insert(lookup(name)?.name);
+        }
+        Ok(checks)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        debug_assert!(find_check(name).is_some(), "unregistered check '{}'", name);
+        self.enabled.contains(name)
+    }
+
+    /// Whether these are the `--pedantic` checks
+    pub fn is_pedantic(&self) -> bool {
+        self.pedantic
+    }
+}
+
+fn lookup(name: &str) -> Result<&'static Check, String> {
+    find_check(name).ok_or_else(|| format!("Unknown check '{}' (see --list-checks)", name))
+}
+
+/// Table printed by `--list-checks`
+pub fn format_check_list() -> String {
+    let mut out = String::from("Available checks:\n");
+    for check in CHECKS {
+        let kinds: Vec<&str> = check.kinds.iter().map(|kind| kind.name()).collect();
+        out.push_str(&format!(
+            "  {:<24} {:<8} {}\n      {}\n",
+            check.name,
+            if check.default_enabled {
+                "on"
+            } else {
+                "pedantic"
+            },
+            kinds.join(", "),
+            check.description
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disable_and_enable() {
+        let checks =
+            EnabledChecks::from_flags(&[], &["pointer-safety".to_string()], false).unwrap();
+        assert!(!checks.is_enabled("pointer-safety"));
+        assert!(checks.is_enabled("borrows"));
+
+        let checks = EnabledChecks::from_flags(
+            &["pointer-safety".to_string()],
+            &["pointer-safety".to_string()],
+            false,
+        )
+        .unwrap();
+        assert!(checks.is_enabled("pointer-safety"));
+    }
+
+    #[test]
+    fn test_pedantic_enables_off_by_default_checks() {
+        let checks = EnabledChecks::from_flags(&[], &[], false).unwrap();
+        assert!(!checks.is_enabled("param-invalidation"));
+        assert!(!checks.is_enabled("move-from-const"));
+
+        let checks =
+            EnabledChecks::from_flags(&[], &["move-from-const".to_string()], true).unwrap();
+        assert!(checks.is_enabled("param-invalidation"));
+        assert!(!checks.is_enabled("move-from-const"));
+        assert!(checks.is_enabled("borrows"));
+    }
+
+    #[test]
+    fn test_unknown_check_rejected() {
+        let err =
+            EnabledChecks::from_flags(&[], &["no-such-check".to_string()], false).unwrap_err();
+        assert!(err.contains("Unknown check 'no-such-check'"));
+    }
+}
@@ -144,7 +144,7 @@ fn check_function_for_const_violations(
 
     for stmt in &function.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
             }
             Statement::ExitUnsafe => {
@@ -253,7 +253,7 @@ fn check_statements_for_const_violations(
 
     for stmt in statements {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
             }
             Statement::ExitUnsafe => {
@@ -458,10 +458,13 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: crate::parser::SourceLocation {
                 file: "test.cpp".to_string(),
                 line: 1,
                 column: 1,
+                end_line: 0,
+                end_column: 0,
             },
             is_pack: false,
             pack_element_type: None,
@@ -480,10 +483,13 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: crate::parser::SourceLocation {
                 file: "test.cpp".to_string(),
                 line: 1,
                 column: 1,
+                end_line: 0,
+                end_column: 0,
             },
             is_pack: false,
             pack_element_type: None,
@@ -0,0 +1,258 @@
+//! Reference Parameters Held Across Coroutine Suspension
+//!
+//! A coroutine's parameters are copied into its frame, but for a reference
+//! parameter only the reference is copied. The caller may destroy the
+//! referent (often a temporary) while the coroutine is suspended, so any use
+//! after a `co_await` or `co_yield` may read a dangling reference:
+//!
+//! ```cpp
+//! task<void> log(const std::string& msg) {
+//!     co_await flush();
+//!     write(msg); // WARNING: msg may be gone by the time we resume
+//! }
+//!
+//! auto t = log("hello"); // the temporary string dies here
+//! ```
+//!
+//! Take such parameters by value instead.
+
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::HashSet;
+
+struct State<'a> {
+    /// Reference parameters not shadowed by a local
+    params: HashSet<&'a str>,
+    /// Line of the first suspension point reached so far
+    suspended_at: Option<u32>,
+    reported: HashSet<String>,
+    unsafe_depth: usize,
+}
+
+/// Check a coroutine for reference parameters used after it may have suspended
+pub fn check_coroutine_refs(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let params: HashSet<&str> = function
+        .parameters
+        .iter()
+        .filter(|param| param.is_reference)
+        .map(|param| param.name.as_str())
+        .collect();
+    if params.is_empty() {
+        return errors;
+    }
+
+    let mut state = State {
+        params,
+        suspended_at: None,
+        reported: HashSet::new(),
+        unsafe_depth: 0,
+    };
+    check_statements(&function.body, &function.name, &mut state, &mut errors);
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    state: &mut State,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::Suspend { location } => {
+                state.suspended_at.get_or_insert(location.line);
+                continue;
+            }
+            Statement::EnterUnsafe { .. } => {
+                state.unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                state.unsafe_depth = state.unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            Statement::VariableDecl(var) => {
+                state.params.remove(var.name.as_str());
+                continue;
+            }
+            Statement::Assignment { lhs, rhs, location } => (vec![lhs, rhs], location.line),
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => (vec![target], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            Statement::FunctionCall { args, location, .. } => {
+                (args.iter().collect(), location.line)
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                check_uses(condition, func_name, location.line, state, errors);
+                // A suspension in either branch may have happened afterwards
+                let before = state.suspended_at;
+                check_statements(then_branch, func_name, state, errors);
+                let after_then = state.suspended_at;
+                state.suspended_at = before;
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, state, errors);
+                }
+                state.suspended_at = before.or(after_then).or(state.suspended_at);
+                continue;
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                check_uses(condition, func_name, location.line, state, errors);
+                for case in cases {
+                    check_statements(&case.statements, func_name, state, errors);
+                }
+                continue;
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, state, errors);
+                continue;
+            }
+            _ => continue,
+        };
+        for expr in exprs {
+            check_uses(expr, func_name, line, state, errors);
+        }
+    }
+}
+
+/// Report reference parameters read by `expr` after a suspension point
+fn check_uses(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    state: &mut State,
+    errors: &mut Vec<String>,
+) {
+    let Some(suspended_at) = state.suspended_at else {
+        return;
+    };
+    if state.unsafe_depth > 0 {
+        return;
+    }
+    let mut vars = Vec::new();
+    collect_variables(expr, &mut vars);
+    for var in vars {
+        if state.params.contains(var) && state.reported.insert(var.to_string()) {
+            errors.push(format!(
+                "In function '{}': warning: reference parameter '{}' used at line {} after the coroutine suspends at line {}; the coroutine frame holds only the reference, so its referent may be gone on resumption",
+                func_name, var, line, suspended_at
+            ));
+        }
+    }
+}
+
+fn collect_variables<'a>(expr: &'a Expression, vars: &mut Vec<&'a str>) {
+    match expr {
+        Expression::Variable(name) => vars.push(name),
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => collect_variables(inner, vars),
+        Expression::MemberAccess { object, .. } => collect_variables(object, vars),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_variables(arg, vars);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            collect_variables(left, vars);
+            collect_variables(right, vars);
+        }
+        Expression::ArraySubscript { array, index } => {
+            collect_variables(array, vars);
+            collect_variables(index, vars);
+        }
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => {
+            collect_variables(pointer, vars);
+            if let Some(offset) = offset {
+                collect_variables(offset, vars);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location, variable};
+    use crate::parser::Variable;
+
+    fn param(name: &str, is_reference: bool) -> Variable {
+        Variable {
+            is_reference,
+            is_const: true,
+            ..variable(name, "const std::string &")
+        }
+    }
+
+    fn write(var: &str, line: u32) -> Statement {
+        Statement::FunctionCall {
+            name: "write".to_string(),
+            args: vec![Expression::Variable(var.to_string())],
+            location: location(line),
+        }
+    }
+
+    fn function(parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+        test_fixtures::function("log", parameters, body)
+    }
+
+    #[test]
+    fn test_reference_used_after_co_await() {
+        let func = function(
+            vec![param("msg", true)],
+            vec![
+                write("msg", 2),
+                Statement::Suspend {
+                    location: location(3),
+                },
+                write("msg", 4),
+                write("msg", 5),
+            ],
+        );
+
+        let errors = check_coroutine_refs(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "reference parameter 'msg' used at line 4 after the coroutine suspends at line 3"
+        ));
+    }
+
+    #[test]
+    fn test_value_parameter_or_no_suspension_not_flagged() {
+        let func = function(
+            vec![param("msg", false)],
+            vec![
+                Statement::Suspend {
+                    location: location(2),
+                },
+                write("msg", 3),
+            ],
+        );
+        assert!(check_coroutine_refs(&func, SafetyMode::Safe).is_empty());
+
+        let func = function(vec![param("msg", true)], vec![write("msg", 2)]);
+        assert!(check_coroutine_refs(&func, SafetyMode::Safe).is_empty());
+    }
+}
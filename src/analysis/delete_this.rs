@@ -0,0 +1,304 @@
+//! Use of the Object After `delete this`
+//!
+//! A method may destroy its own object with `delete this`, but from then on
+//! every member access, method call or use of `this` reads freed memory:
+//!
+//! ```cpp
+//! void Connection::close() {
+//!     delete this;
+//!     open_ = false; // ERROR: use after free
+//! }
+//! ```
+//!
+//! Only paths that fall through a `delete this` are followed: a branch that
+//! returns right after it doesn't reach the code below. Like `delete` itself,
+//! the accesses are tracked inside `@unsafe` blocks too.
+
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+
+/// Check a method for uses of `this` after `delete this`
+pub fn check_delete_this(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+    if !function.is_method {
+        return errors;
+    }
+
+    let class = function
+        .name
+        .rsplit_once("::")
+        .map_or("", |(class, _)| class);
+    let mut deleted_at = None;
+    check_statements(
+        &function.body,
+        &function.name,
+        class,
+        &mut deleted_at,
+        &mut errors,
+    );
+    errors
+}
+
+/// Walk `stmts` with `deleted_at` holding the line of a `delete this` that
+/// reaches them. Returns whether the statements always return.
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    class: &str,
+    deleted_at: &mut Option<u32>,
+    errors: &mut Vec<String>,
+) -> bool {
+    for stmt in stmts {
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::Assignment { lhs, rhs, location } => (vec![rhs, lhs], location.line),
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => (vec![target], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                if let Some(deleted_line) = deleted_at.filter(|_| errors.is_empty()) {
+                    if let Some(what) = call_access(name, args, class) {
+                        report(func_name, &what, location.line, deleted_line, errors);
+                    }
+                }
+                continue;
+            }
+            Statement::Return(value) => {
+                if let (Some(line), Some(value)) = (*deleted_at, value) {
+                    if errors.is_empty() && this_access(value, class).is_some() {
+                        errors.push(format!(
+                            "In function '{}': use after free: the object is returned through 'this' after `delete this` at line {}",
+                            func_name, line
+                        ));
+                    }
+                }
+                return true;
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                if let (Some(line), Some(what)) = (*deleted_at, this_access(condition, class)) {
+                    if errors.is_empty() {
+                        report(func_name, &what, location.line, line, errors);
+                    }
+                }
+                let mut then_deleted = *deleted_at;
+                let then_returns =
+                    check_statements(then_branch, func_name, class, &mut then_deleted, errors);
+                let mut else_deleted = *deleted_at;
+                let else_returns = match else_branch {
+                    Some(else_stmts) => {
+                        check_statements(else_stmts, func_name, class, &mut else_deleted, errors)
+                    }
+                    None => false,
+                };
+                if then_returns && else_returns {
+                    return true;
+                }
+                // A deletion on either path that falls through reaches what follows
+                let then_deleted = then_deleted.filter(|_| !then_returns);
+                let else_deleted = else_deleted.filter(|_| !else_returns);
+                *deleted_at = then_deleted.or(else_deleted);
+                continue;
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                if let (Some(line), Some(what)) = (*deleted_at, this_access(condition, class)) {
+                    if errors.is_empty() {
+                        report(func_name, &what, location.line, line, errors);
+                    }
+                }
+                let before = *deleted_at;
+                for case in cases {
+                    let mut case_deleted = before;
+                    if !check_statements(
+                        &case.statements,
+                        func_name,
+                        class,
+                        &mut case_deleted,
+                        errors,
+                    ) {
+                        *deleted_at = deleted_at.or(case_deleted);
+                    }
+                }
+                continue;
+            }
+            Statement::Block(stmts) => {
+                if check_statements(stmts, func_name, class, deleted_at, errors) {
+                    return true;
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        if let Some(deleted_line) = *deleted_at {
+            if errors.is_empty() {
+                if let Some(what) = exprs.iter().find_map(|expr| this_access(expr, class)) {
+                    report(func_name, &what, line, deleted_line, errors);
+                }
+            }
+        }
+        if exprs.iter().any(|expr| is_delete_this(expr)) {
+            deleted_at.get_or_insert(line);
+        }
+    }
+    false
+}
+
+fn report(func_name: &str, what: &str, line: u32, deleted_line: u32, errors: &mut Vec<String>) {
+    errors.push(format!(
+        "In function '{}': use after free: {} is used at line {} after `delete this` at line {}",
+        func_name, what, line, deleted_line
+    ));
+}
+
+/// `delete this;`
+fn is_delete_this(expr: &Expression) -> bool {
+    match expr {
+        Expression::Delete(inner) => is_this(inner),
+        Expression::Cast { inner, .. } => is_delete_this(inner),
+        _ => false,
+    }
+}
+
+fn is_this(expr: &Expression) -> bool {
+    matches!(expr, Expression::Variable(name) if name == "this")
+}
+
+/// Description of the first use of the object in `expr`: a member (explicit
+/// or implicit `this->`), a method call, or `this` itself
+fn this_access(expr: &Expression, class: &str) -> Option<String> {
+    match expr {
+        Expression::Variable(name) if name == "this" => Some("'this'".to_string()),
+        Expression::MemberAccess { object, field } if is_this(object) => {
+            Some(format!("member '{}'", field))
+        }
+        Expression::MemberAccess { object, .. } => this_access(object, class),
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner) => this_access(inner, class),
+        // `delete this` itself is the release, not a use
+        Expression::Delete(inner) if !is_this(inner) => this_access(inner, class),
+        Expression::FunctionCall { name, args } => call_access(name, args, class),
+        Expression::BinaryOp { left, right, .. } => {
+            this_access(left, class).or_else(|| this_access(right, class))
+        }
+        Expression::ArraySubscript { array, index } => {
+            this_access(array, class).or_else(|| this_access(index, class))
+        }
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => this_access(pointer, class).or_else(|| {
+            offset
+                .as_ref()
+                .and_then(|offset| this_access(offset, class))
+        }),
+        _ => None,
+    }
+}
+
+/// A use of the object by a call: `this` among the arguments, or a method of
+/// the same class called with an implicit `this->`
+fn call_access(name: &str, args: &[Expression], class: &str) -> Option<String> {
+    if let Some(what) = args.iter().find_map(|arg| this_access(arg, class)) {
+        return Some(what);
+    }
+    let (callee_class, method) = name.rsplit_once("::")?;
+    let same_class = !class.is_empty()
+        && (callee_class == class || class.rsplit("::").next() == callee_class.rsplit("::").next());
+    let constructor = callee_class.rsplit("::").next() == Some(method);
+    (same_class && !constructor).then(|| format!("method '{}'", method))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location};
+
+    fn this_member(field: &str) -> Expression {
+        Expression::MemberAccess {
+            object: Box::new(Expression::Variable("this".to_string())),
+            field: field.to_string(),
+        }
+    }
+
+    fn delete_this(line: u32) -> Statement {
+        Statement::ExpressionStatement {
+            expr: Expression::Delete(Box::new(Expression::Variable("this".to_string()))),
+            location: location(line),
+        }
+    }
+
+    fn reset_member(line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: this_member("member_"),
+            rhs: Expression::Literal("0".to_string()),
+            location: location(line),
+        }
+    }
+
+    fn method(body: Vec<Statement>) -> Function {
+        test_fixtures::method("Widget::destroy", vec![], body)
+    }
+
+    #[test]
+    fn test_member_write_after_delete_this() {
+        let func = method(vec![delete_this(2), reset_member(3)]);
+
+        let errors = check_delete_this(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "use after free: member 'member_' is used at line 3 after `delete this` at line 2"
+        ));
+
+        // Calling another method of the object is a use too
+        let func = method(vec![
+            delete_this(2),
+            Statement::FunctionCall {
+                name: "Widget::notify".to_string(),
+                args: vec![],
+                location: location(3),
+            },
+        ]);
+        let errors = check_delete_this(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("method 'notify' is used at line 3"));
+    }
+
+    #[test]
+    fn test_delete_this_then_return_not_flagged() {
+        // if (done_) { delete this; return; } member_ = 0;
+        let func = method(vec![
+            Statement::If {
+                condition: this_member("done_"),
+                then_branch: vec![delete_this(3), Statement::Return(None)],
+                else_branch: None,
+                location: location(2),
+            },
+            reset_member(5),
+        ]);
+        assert!(check_delete_this(&func, SafetyMode::Safe).is_empty());
+
+        // Access before the deletion is fine
+        let func = method(vec![reset_member(2), delete_this(3)]);
+        assert!(check_delete_this(&func, SafetyMode::Safe).is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+//! Copies of Non-Copyable Types
+//!
+//! A class whose copy constructor or copy assignment operator is `= delete`
+//! can only be moved. libclang reports copying one as a (suppressed) error
+//! and still hands us the AST, so flag the copy here:
+//!
+//! ```cpp
+//! struct Handle {
+//!     Handle(const Handle&) = delete;
+//! };
+//!
+//! Handle a;
+//! Handle b = a;            // ERROR: copy of non-copyable type 'Handle'
+//! Handle c = std::move(a); // OK
+//! ```
+
+use crate::parser::ast_visitor::Class;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement, Variable};
+use std::collections::HashMap;
+
+/// Check a function for copy construction or copy assignment of a class
+/// whose copy operation is deleted
+pub fn check_deleted_copy(
+    function: &Function,
+    classes: &[Class],
+    function_safety: SafetyMode,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+    if !classes
+        .iter()
+        .any(|class| class.copy_constructor_deleted || class.copy_assignment_deleted)
+    {
+        return errors;
+    }
+
+    let mut vars = HashMap::new();
+    for param in &function.parameters {
+        declare(&mut vars, param);
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        classes,
+        &mut vars,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+/// Track the declared type of a non-pointer variable
+fn declare<'a>(vars: &mut HashMap<String, &'a Variable>, var: &'a Variable) {
+    if var.is_pointer {
+        vars.remove(&var.name);
+    } else {
+        vars.insert(var.name.clone(), var);
+    }
+}
+
+fn check_statements<'a>(
+    stmts: &'a [Statement],
+    func_name: &str,
+    classes: &[Class],
+    vars: &mut HashMap<String, &'a Variable>,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    // The variable declared by the previous statement: an assignment to it
+    // is its initializer, so it copy-constructs rather than copy-assigns
+    let mut just_declared: Option<&str> = None;
+    for stmt in stmts {
+        let declared = just_declared.take();
+        match stmt {
+            Statement::EnterUnsafe { .. } => *unsafe_depth += 1,
+            Statement::ExitUnsafe => *unsafe_depth = unsafe_depth.saturating_sub(1),
+            Statement::VariableDecl(var) => {
+                declare(vars, var);
+                just_declared = Some(&var.name);
+            }
+            Statement::Assignment {
+                lhs: Expression::Variable(dst),
+                rhs: Expression::Variable(src),
+                location,
+            } if *unsafe_depth == 0 => {
+                let (Some(dst_var), Some(src_var)) = (vars.get(dst), vars.get(src)) else {
+                    continue;
+                };
+                if dst_var.is_reference {
+                    continue;
+                }
+                let Some(class) = find_class(&src_var.type_name, classes) else {
+                    continue;
+                };
+                let deleted = if declared == Some(dst.as_str()) {
+                    class.copy_constructor_deleted.then_some("constructor")
+                } else {
+                    class
+                        .copy_assignment_deleted
+                        .then_some("assignment operator")
+                };
+                if let Some(operation) = deleted {
+                    errors.push(format!(
+                        "In function '{}': copy of non-copyable type '{}': '{}' is copied into '{}' at line {}, but its copy {} is deleted; use std::move",
+                        func_name, class.name, src, dst, location.line, operation
+                    ));
+                }
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, classes, vars, unsafe_depth, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, classes, vars, unsafe_depth, errors);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_statements(
+                        &case.statements,
+                        func_name,
+                        classes,
+                        vars,
+                        unsafe_depth,
+                        errors,
+                    );
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, classes, vars, unsafe_depth, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The class named by a variable's type: `Handle` for `const ns::Handle &`
+fn find_class<'a>(type_name: &str, classes: &'a [Class]) -> Option<&'a Class> {
+    let base = type_name
+        .trim()
+        .trim_start_matches("const ")
+        .trim_end_matches(['&', ' '])
+        .trim();
+    let base = base.strip_prefix("struct ").unwrap_or(base);
+    let base = base.strip_prefix("class ").unwrap_or(base);
+    classes.iter().find(|class| {
+        class.name == base
+            || class.name.rsplit("::").next() == Some(base)
+            || base.rsplit("::").next() == Some(class.name.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location, variable};
+
+    fn handle_class() -> Class {
+        Class {
+            has_copy_constructor: true,
+            copy_constructor_deleted: true,
+            has_user_defined_constructor: true,
+            ..test_fixtures::class("Handle")
+        }
+    }
+
+    fn assign(dst: &str, rhs: Expression, line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: Expression::Variable(dst.to_string()),
+            rhs,
+            location: location(line),
+        }
+    }
+
+    fn function(body: Vec<Statement>) -> Function {
+        test_fixtures::function("copy", vec![variable("a", "Handle")], body)
+    }
+
+    #[test]
+    fn test_copy_construction_of_deleted_copy_type() {
+        let func = function(vec![
+            Statement::VariableDecl(variable("b", "Handle")),
+            assign("b", Expression::Variable("a".to_string()), 3),
+        ]);
+
+        let errors = check_deleted_copy(&func, &[handle_class()], SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "copy of non-copyable type 'Handle': 'a' is copied into 'b' at line 3, but its copy constructor is deleted"
+        ));
+    }
+
+    #[test]
+    fn test_move_or_copy_assignment_allowed() {
+        // Moving is fine, and only the copy constructor is deleted
+        let func = function(vec![
+            Statement::VariableDecl(variable("b", "Handle")),
+            assign(
+                "b",
+                Expression::Move {
+                    inner: Box::new(Expression::Variable("a".to_string())),
+                    kind: crate::parser::MoveKind::StdMove,
+                },
+                3,
+            ),
+            assign("b", Expression::Variable("a".to_string()), 4),
+        ]);
+
+        assert!(check_deleted_copy(&func, &[handle_class()], SafetyMode::Safe).is_empty());
+    }
+}
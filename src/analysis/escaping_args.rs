@@ -0,0 +1,285 @@
+//! Local Addresses Passed to Escaping Parameters
+//!
+//! A parameter annotated `@escapes` is kept by the callee after it returns,
+//! typically stored in a registry or a member. Passing it the address of a
+//! local (or a reference to one) leaves the callee holding a dangling
+//! pointer once the caller returns:
+//!
+//! ```cpp
+//! // @escapes(handler)
+//! void register_handler(Handler* handler);
+//!
+//! void setup() {
+//!     Handler h;
+//!     register_handler(&h); // ERROR: h is destroyed when setup returns
+//! }
+//! ```
+//!
+//! Locals and by-value parameters have automatic storage; `static` locals
+//! and whatever reference parameters refer to don't.
+
+use crate::parser::HeaderCache;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::{HashMap, HashSet};
+
+/// Automatic-storage variables of the function being checked
+#[derive(Default)]
+struct Locals {
+    /// Locals and by-value parameters
+    values: HashSet<String>,
+    /// The subset of `values` that are pointers: passing one hands out its
+    /// pointee, not its own address
+    pointers: HashSet<String>,
+    /// `T& r = local;` references, mapped to the local they alias
+    aliases: HashMap<String, String>,
+}
+
+/// Check calls for the address of a local passed to an `@escapes` parameter
+pub fn check_escaping_args(
+    function: &Function,
+    header_cache: &HeaderCache,
+    function_safety: SafetyMode,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut locals = Locals::default();
+    for param in &function.parameters {
+        if !param.is_reference {
+            locals.values.insert(param.name.clone());
+        }
+        if param.is_pointer {
+            locals.pointers.insert(param.name.clone());
+        }
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        header_cache,
+        &mut locals,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    header_cache: &HeaderCache,
+    locals: &mut Locals,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => {
+                *unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                *unsafe_depth = unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            Statement::VariableDecl(var) => {
+                locals.aliases.remove(&var.name);
+                locals.pointers.remove(&var.name);
+                if var.is_reference || var.is_static {
+                    locals.values.remove(&var.name);
+                } else {
+                    locals.values.insert(var.name.clone());
+                    if var.is_pointer {
+                        locals.pointers.insert(var.name.clone());
+                    }
+                }
+                continue;
+            }
+            Statement::ReferenceBinding { name, target, .. } => {
+                match local_target(target, locals) {
+                    Some(local) => locals.aliases.insert(name.clone(), local),
+                    None => locals.aliases.remove(name),
+                };
+            }
+            _ => {}
+        }
+
+        // Skip checking in unsafe blocks
+        if *unsafe_depth > 0 {
+            continue;
+        }
+
+        let mut check = |expr: &Expression, line: u32| {
+            check_expr(expr, func_name, line, header_cache, locals, errors)
+        };
+        match stmt {
+            Statement::Assignment { rhs, location, .. } => check(rhs, location.line),
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => check(target, location.line),
+            Statement::ExpressionStatement { expr, location } => check(expr, location.line),
+            Statement::Return(Some(expr)) => check(expr, 0),
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => check_call(
+                name,
+                args,
+                func_name,
+                location.line,
+                header_cache,
+                locals,
+                errors,
+            ),
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                check(condition, location.line);
+                check_statements(
+                    then_branch,
+                    func_name,
+                    header_cache,
+                    locals,
+                    unsafe_depth,
+                    errors,
+                );
+                if let Some(else_stmts) = else_branch {
+                    check_statements(
+                        else_stmts,
+                        func_name,
+                        header_cache,
+                        locals,
+                        unsafe_depth,
+                        errors,
+                    );
+                }
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                check(condition, location.line);
+                for case in cases {
+                    check_statements(
+                        &case.statements,
+                        func_name,
+                        header_cache,
+                        locals,
+                        unsafe_depth,
+                        errors,
+                    );
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, header_cache, locals, unsafe_depth, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find calls anywhere inside an expression
+fn check_expr(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    header_cache: &HeaderCache,
+    locals: &Locals,
+    errors: &mut Vec<String>,
+) {
+    match expr {
+        Expression::FunctionCall { name, args } => {
+            check_call(name, args, func_name, line, header_cache, locals, errors)
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. } => {
+            check_expr(inner, func_name, line, header_cache, locals, errors)
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            check_expr(left, func_name, line, header_cache, locals, errors);
+            check_expr(right, func_name, line, header_cache, locals, errors);
+        }
+        Expression::MemberAccess { object, .. } => {
+            check_expr(object, func_name, line, header_cache, locals, errors)
+        }
+        _ => {}
+    }
+}
+
+fn check_call(
+    name: &str,
+    args: &[Expression],
+    func_name: &str,
+    line: u32,
+    header_cache: &HeaderCache,
+    locals: &Locals,
+    errors: &mut Vec<String>,
+) {
+    for arg in args {
+        check_expr(arg, func_name, line, header_cache, locals, errors);
+    }
+    let Some(signature) = header_cache.get_call_signature(name) else {
+        return;
+    };
+    let escapes = &signature.param_escapes;
+    // Method calls pass the receiver first
+    let offset = args.len().saturating_sub(escapes.len()).min(1);
+    for (arg, _) in args[offset..]
+        .iter()
+        .zip(escapes)
+        .filter(|(_, escapes)| **escapes)
+    {
+        if let Some(what) = local_address(arg, locals) {
+            errors.push(format!(
+                "In function '{}': Dangling pointer: '{}' keeps its argument after returning (@escapes), but is passed {} at line {}",
+                func_name, name, what, line
+            ));
+        }
+    }
+}
+
+/// Description of `arg` when it hands out the address of a local: `&x`, `x`
+/// itself bound to a reference parameter, or a reference aliasing `x`
+fn local_address(arg: &Expression, locals: &Locals) -> Option<String> {
+    match arg {
+        Expression::AddressOf(inner) => {
+            local_target(inner, locals).map(|local| format!("the address of local '{}'", local))
+        }
+        Expression::Variable(name)
+            if locals.values.contains(name) && !locals.pointers.contains(name) =>
+        {
+            Some(format!("local '{}'", name))
+        }
+        Expression::Variable(name) => locals
+            .aliases
+            .get(name)
+            .map(|local| format!("reference '{}' to local '{}'", name, local)),
+        Expression::Cast { inner, .. } => local_address(inner, locals),
+        _ => None,
+    }
+}
+
+/// The local a reference or address-of expression refers to: `x`, `x.field`
+/// or `x[i]` for a local `x`, or a reference aliasing one
+fn local_target(expr: &Expression, locals: &Locals) -> Option<String> {
+    match expr {
+        Expression::Variable(name) if locals.values.contains(name) => Some(name.clone()),
+        Expression::Variable(name) => locals.aliases.get(name).cloned(),
+        Expression::MemberAccess { object, .. } => local_target(object, locals),
+        Expression::ArraySubscript { array, .. } => local_target(array, locals),
+        _ => None,
+    }
+}
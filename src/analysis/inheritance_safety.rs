@@ -424,9 +424,13 @@ fn check_statement_safety(
         Statement::EnterScope
         | Statement::ExitScope
         | Statement::EnterLoop
+        | Statement::EnterDoLoop
         | Statement::ExitLoop
-        | Statement::EnterUnsafe
-        | Statement::ExitUnsafe => {}
+        | Statement::RangeFor { .. }
+        | Statement::EnterUnsafe { .. }
+        | Statement::ExitUnsafe
+        | Statement::Break
+        | Statement::Suspend { .. } => {}
     }
 
     errors
@@ -772,6 +776,8 @@ mod tests {
             file: "test.cpp".to_string(),
             line: 1,
             column: 1,
+            end_line: 0,
+            end_column: 0,
         }
     }
 
@@ -782,6 +788,7 @@ mod tests {
             methods: Vec::new(),
             base_classes: Vec::new(),
             location: make_location(),
+            is_union: false,
             has_destructor: true,
             is_interface: true,
             has_virtual_destructor: true,
@@ -793,6 +800,7 @@ mod tests {
             has_copy_assignment: false,
             copy_constructor_deleted: false,
             copy_assignment_deleted: false,
+            is_move_only: false,
             has_user_defined_constructor: false,
             has_default_constructor: true,
             default_constructor_deleted: false,
@@ -806,6 +814,7 @@ mod tests {
             methods: Vec::new(),
             base_classes,
             location: make_location(),
+            is_union: false,
             has_destructor: false,
             is_interface: false,
             has_virtual_destructor: false,
@@ -817,6 +826,7 @@ mod tests {
             has_copy_assignment: false,
             copy_constructor_deleted: false,
             copy_assignment_deleted: false,
+            is_move_only: false,
             has_user_defined_constructor: false,
             has_default_constructor: true,
             default_constructor_deleted: false,
@@ -846,6 +856,7 @@ mod tests {
                 is_shared_ptr: false,
                 is_static: false,
                 is_mutable: false,
+                is_volatile: false,
                 location: make_location(),
                 is_pack: false,
                 pack_element_type: None,
@@ -8,6 +8,11 @@
 //! - Initialized: Has been assigned a value
 //! - MaybeUninitialized: Assigned in some paths but not all
 //!
+//! A `for`/`while` body may run zero times, so its assignments merge with
+//! the state before the loop; a `do-while` body, or the body of a loop
+//! without an exit condition (`for (;;)`, `while (true)`), always runs once,
+//! so its assignments are definite afterward.
+//!
 //! Rules:
 //! - Taking address of uninitialized variable is flagged
 //! - Dereferencing pointer to uninitialized memory is an error
@@ -47,6 +52,9 @@ pub struct InitTracker {
     points_to: HashMap<String, String>,
     /// Stack of scopes for handling blocks
     scope_stack: Vec<HashMap<String, InitState>>,
+    /// State on entry to each enclosing loop; `None` for one whose body
+    /// always runs
+    loop_stack: Vec<Option<HashMap<String, InitState>>>,
 }
 
 impl InitTracker {
@@ -55,6 +63,7 @@ impl InitTracker {
             states: HashMap::new(),
             points_to: HashMap::new(),
             scope_stack: Vec::new(),
+            loop_stack: Vec::new(),
         }
     }
 
@@ -109,6 +118,23 @@ impl InitTracker {
             states: self.states.clone(),
             points_to: self.points_to.clone(),
             scope_stack: Vec::new(),
+            loop_stack: Vec::new(),
+        }
+    }
+
+    /// Enter a loop body; `runs_at_least_once` for a do-while or infinite loop
+    pub fn enter_loop(&mut self, runs_at_least_once: bool) {
+        let entry = (!runs_at_least_once).then(|| self.states.clone());
+        self.loop_stack.push(entry);
+    }
+
+    /// Exit a loop body: a loop that may not have run merges with its entry
+    pub fn exit_loop(&mut self) {
+        if let Some(Some(entry)) = self.loop_stack.pop() {
+            for (var, before) in entry {
+                let after = self.get_state(&var);
+                self.states.insert(var, before.merge(after));
+            }
         }
     }
 
@@ -144,7 +170,7 @@ pub fn check_initialization_safety(
     // Analyze each statement
     for stmt in &function.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -307,6 +333,10 @@ fn analyze_statement_init(
             tracker.exit_scope();
         }
 
+        Statement::EnterLoop => tracker.enter_loop(false),
+        Statement::EnterDoLoop => tracker.enter_loop(true),
+        Statement::ExitLoop => tracker.exit_loop(),
+
         _ => {}
     }
 }
@@ -547,6 +577,8 @@ mod tests {
             file: "test.cpp".to_string(),
             line: 1,
             column: 1,
+            end_line: 0,
+            end_column: 0,
         }
     }
 
@@ -564,6 +596,7 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: loc(),
             is_pack: false,
             pack_element_type: None,
@@ -645,4 +678,40 @@ mod tests {
         let errors = check_initialization_safety(&safe_fn("f", body), SafetyMode::Safe);
         assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
     }
+
+    #[test]
+    fn test_do_while_body_initializes_but_while_body_may_not() {
+        // `int x; do { x = 1; } while (c); return x;` is fine; the same body
+        // under `while (c)` may never run.
+        let body = |enter: Statement| {
+            vec![
+                Statement::VariableDecl(primitive_var("x", "int", false)),
+                enter,
+                Statement::Assignment {
+                    lhs: crate::parser::Expression::Variable("x".to_string()),
+                    rhs: crate::parser::Expression::Literal("1".to_string()),
+                    location: loc(),
+                },
+                Statement::ExpressionStatement {
+                    expr: crate::parser::Expression::Variable("c".to_string()),
+                    location: loc(),
+                },
+                Statement::ExitLoop,
+                Statement::Return(Some(crate::parser::Expression::Variable("x".to_string()))),
+            ]
+        };
+
+        let errors = check_initialization_safety(
+            &safe_fn("f", body(Statement::EnterDoLoop)),
+            SafetyMode::Safe,
+        );
+        assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
+
+        let errors = check_initialization_safety(
+            &safe_fn("f", body(Statement::EnterLoop)),
+            SafetyMode::Safe,
+        );
+        assert_eq!(errors.len(), 1, "expected 1 error, got: {:?}", errors);
+        assert!(errors[0].contains("potentially uninitialized variable 'x'"));
+    }
 }
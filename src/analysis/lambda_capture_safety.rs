@@ -1,3 +1,4 @@
+use crate::analysis::raii_tracking::RaiiTracker;
 use crate::debug_println;
 use crate::parser::ast_visitor::LambdaCaptureKind;
 use crate::parser::safety_annotations::SafetyMode;
@@ -8,13 +9,15 @@ use crate::parser::safety_annotations::SafetyMode;
 /// - Reference captures that ESCAPE are FORBIDDEN - can create dangling references
 /// - Copy captures ([x], [=]) are ALWAYS ALLOWED - safe copy semantics
 /// - Move captures ([x = std::move(y)]) are ALWAYS ALLOWED - ownership transfer is safe
+/// - Default copy [=] of a move-only variable (unique_ptr, rusty::Box) is FORBIDDEN -
+///   it must be moved in with an init-capture
 /// - 'this' capture is FORBIDDEN - 'this' is a raw pointer that can dangle
 ///
 /// Escape means:
 /// - Lambda is returned from function
 /// - Lambda is stored in a variable/container that outlives captured variables
 /// - Lambda is passed to a function that takes ownership (stores it)
-use crate::parser::{Expression, Function, Statement};
+use crate::parser::{Expression, Function, Statement, Variable};
 use std::collections::{HashMap, HashSet};
 
 /// Context for tracking lambdas and their escape status
@@ -129,6 +132,16 @@ pub fn check_lambda_capture_safety(
     // Check for 'this' captures (always forbidden)
     check_this_captures_errors(&function.body, &function.name, &mut errors, &mut 0);
 
+    // Check for [=] lambdas that would copy a move-only variable
+    let mut move_only_vars: HashMap<String, String> = HashMap::new();
+    for param in &function.parameters {
+        register_move_only_variable(param, &mut move_only_vars);
+    }
+    collect_move_only_variables(&function.body, &mut move_only_vars);
+    if !move_only_vars.is_empty() {
+        check_default_copy_move_only_captures(&function.body, &move_only_vars, &mut errors, &mut 0);
+    }
+
     // Report errors for escaped lambdas with reference captures
     for (lambda_name, ref_captures, has_default_ref) in
         lambda_context.get_escaped_lambdas_with_ref_captures()
@@ -159,7 +172,7 @@ fn collect_lambdas_and_escapes(
 ) {
     for stmt in statements {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 *unsafe_depth += 1;
             }
             Statement::ExitUnsafe => {
@@ -258,7 +271,7 @@ fn check_this_captures_errors(
 ) {
     for stmt in statements {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 *unsafe_depth += 1;
             }
             Statement::ExitUnsafe => {
@@ -282,6 +295,8 @@ fn check_this_captures_errors(
                         file: "unknown".to_string(),
                         line: 0,
                         column: 0,
+                        end_line: 0,
+                        end_column: 0,
                     };
                     check_expression_for_this_capture(
                         expr,
@@ -357,6 +372,289 @@ fn check_expression_for_this_capture(
     }
 }
 
+fn register_move_only_variable(var: &Variable, move_only_vars: &mut HashMap<String, String>) {
+    if var.is_reference || var.is_pointer {
+        return;
+    }
+    if var.is_unique_ptr || RaiiTracker::is_unique_ptr_type(&var.type_name) {
+        move_only_vars.insert(var.name.clone(), var.type_name.clone());
+    }
+}
+
+/// Collect every local declared with a move-only type (unique_ptr, rusty::Box).
+/// Lambda bodies are not descended into - their locals are not capturable.
+fn collect_move_only_variables(
+    statements: &[Statement],
+    move_only_vars: &mut HashMap<String, String>,
+) {
+    for stmt in statements {
+        match stmt {
+            Statement::VariableDecl(var) => register_move_only_variable(var, move_only_vars),
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                collect_move_only_variables(then_branch, move_only_vars);
+                if let Some(else_stmts) = else_branch {
+                    collect_move_only_variables(else_stmts, move_only_vars);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    collect_move_only_variables(&case.statements, move_only_vars);
+                }
+            }
+            Statement::Block(inner_stmts) => {
+                collect_move_only_variables(inner_stmts, move_only_vars);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_default_copy_move_only_captures(
+    statements: &[Statement],
+    move_only_vars: &HashMap<String, String>,
+    errors: &mut Vec<String>,
+    unsafe_depth: &mut usize,
+) {
+    for stmt in statements {
+        match stmt {
+            Statement::EnterUnsafe { .. } => {
+                *unsafe_depth += 1;
+            }
+            Statement::ExitUnsafe => {
+                *unsafe_depth = unsafe_depth.saturating_sub(1);
+            }
+            Statement::Assignment { rhs, location, .. } if *unsafe_depth == 0 => {
+                check_expression_for_move_only_capture(rhs, move_only_vars, Some(location), errors);
+            }
+            Statement::ReferenceBinding {
+                target, location, ..
+            } if *unsafe_depth == 0 => {
+                check_expression_for_move_only_capture(
+                    target,
+                    move_only_vars,
+                    Some(location),
+                    errors,
+                );
+            }
+            Statement::ExpressionStatement { expr, location } if *unsafe_depth == 0 => {
+                check_expression_for_move_only_capture(
+                    expr,
+                    move_only_vars,
+                    Some(location),
+                    errors,
+                );
+            }
+            Statement::FunctionCall { args, location, .. } if *unsafe_depth == 0 => {
+                for arg in args {
+                    check_expression_for_move_only_capture(
+                        arg,
+                        move_only_vars,
+                        Some(location),
+                        errors,
+                    );
+                }
+            }
+            Statement::Return(Some(expr)) if *unsafe_depth == 0 => {
+                check_expression_for_move_only_capture(expr, move_only_vars, None, errors);
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_default_copy_move_only_captures(
+                    then_branch,
+                    move_only_vars,
+                    errors,
+                    unsafe_depth,
+                );
+                if let Some(else_stmts) = else_branch {
+                    check_default_copy_move_only_captures(
+                        else_stmts,
+                        move_only_vars,
+                        errors,
+                        unsafe_depth,
+                    );
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_default_copy_move_only_captures(
+                        &case.statements,
+                        move_only_vars,
+                        errors,
+                        unsafe_depth,
+                    );
+                }
+            }
+            Statement::Block(inner_stmts) => {
+                check_default_copy_move_only_captures(
+                    inner_stmts,
+                    move_only_vars,
+                    errors,
+                    unsafe_depth,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_expression_for_move_only_capture(
+    expr: &Expression,
+    move_only_vars: &HashMap<String, String>,
+    location: Option<&crate::parser::ast_visitor::SourceLocation>,
+    errors: &mut Vec<String>,
+) {
+    match expr {
+        Expression::Lambda { captures, body, .. } => {
+            if !captures
+                .iter()
+                .any(|c| matches!(c, LambdaCaptureKind::DefaultCopy))
+            {
+                return;
+            }
+
+            // Explicitly listed captures take precedence over the default
+            let explicit: HashSet<&str> = captures
+                .iter()
+                .filter_map(|c| match c {
+                    LambdaCaptureKind::ByRef(name)
+                    | LambdaCaptureKind::ByCopy(name)
+                    | LambdaCaptureKind::Init { name, .. } => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+
+            let mut used = Vec::new();
+            let mut declared = HashSet::new();
+            collect_used_variables(body, &mut used, &mut declared);
+
+            let mut reported = HashSet::new();
+            for name in used {
+                if declared.contains(&name) || explicit.contains(name.as_str()) {
+                    continue;
+                }
+                if let Some(type_name) = move_only_vars.get(&name) {
+                    if !reported.insert(name.clone()) {
+                        continue;
+                    }
+                    let at = location
+                        .map(|loc| format!(" at {}:{}", loc.file, loc.line))
+                        .unwrap_or_default();
+                    errors.push(format!(
+                        "Move-only capture in @safe code{}: Lambda default copy capture [=] would copy move-only variable '{}' of type '{}' - use an init-capture [{} = std::move({})] instead",
+                        at, name, type_name, name, name
+                    ));
+                }
+            }
+        }
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                check_expression_for_move_only_capture(arg, move_only_vars, location, errors);
+            }
+        }
+        Expression::Move { inner, .. } | Expression::Cast { inner, .. } => {
+            check_expression_for_move_only_capture(inner, move_only_vars, location, errors);
+        }
+        _ => {}
+    }
+}
+
+/// Collect variable names referenced by a lambda body, along with the names
+/// the body declares itself (which shadow outer variables).
+pub(crate) fn collect_used_variables(
+    statements: &[Statement],
+    used: &mut Vec<String>,
+    declared: &mut HashSet<String>,
+) {
+    for stmt in statements {
+        match stmt {
+            Statement::VariableDecl(var) => {
+                declared.insert(var.name.clone());
+            }
+            Statement::Assignment { lhs, rhs, .. } => {
+                collect_expression_variables(lhs, used);
+                collect_expression_variables(rhs, used);
+            }
+            Statement::ReferenceBinding { target, .. } => {
+                collect_expression_variables(target, used);
+            }
+            Statement::Return(Some(expr)) | Statement::ExpressionStatement { expr, .. } => {
+                collect_expression_variables(expr, used);
+            }
+            Statement::FunctionCall { args, .. } => {
+                for arg in args {
+                    collect_expression_variables(arg, used);
+                }
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                collect_expression_variables(condition, used);
+                collect_used_variables(then_branch, used, declared);
+                if let Some(else_stmts) = else_branch {
+                    collect_used_variables(else_stmts, used, declared);
+                }
+            }
+            Statement::Switch {
+                condition, cases, ..
+            } => {
+                collect_expression_variables(condition, used);
+                for case in cases {
+                    collect_used_variables(&case.statements, used, declared);
+                }
+            }
+            Statement::Block(inner_stmts) => {
+                collect_used_variables(inner_stmts, used, declared);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_expression_variables(expr: &Expression, used: &mut Vec<String>) {
+    match expr {
+        Expression::Variable(name) => used.push(name.clone()),
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => collect_expression_variables(inner, used),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_expression_variables(arg, used);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            collect_expression_variables(left, used);
+            collect_expression_variables(right, used);
+        }
+        Expression::MemberAccess { object, .. } => collect_expression_variables(object, used),
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => {
+            collect_expression_variables(pointer, used);
+            if let Some(offset) = offset {
+                collect_expression_variables(offset, used);
+            }
+        }
+        Expression::ArraySubscript { array, index } => {
+            collect_expression_variables(array, used);
+            collect_expression_variables(index, used);
+        }
+        _ => {}
+    }
+}
+
 fn check_for_escape_via_call(expr: &Expression, ctx: &mut LambdaContext, function_name: &str) {
     if let Expression::FunctionCall { name, args, .. } = expr {
         // Check if passing a lambda to a function that stores it
@@ -438,6 +736,8 @@ mod tests {
             file: "test.cpp".to_string(),
             line: 1,
             column: 1,
+            end_line: 0,
+            end_column: 0,
         }
     }
 
@@ -488,6 +788,7 @@ mod tests {
         let lambda = Expression::Lambda {
             captures: vec![LambdaCaptureKind::Init {
                 name: "y".to_string(),
+                is_move: true,
             }],
             capture_initializers: Vec::new(),
             body: Vec::new(),
@@ -524,6 +825,60 @@ mod tests {
         assert!(has_default_ref);
     }
 
+    #[test]
+    fn test_default_copy_capture_of_unique_ptr_is_error() {
+        let ptr = Variable {
+            name: "p".to_string(),
+            type_name: "std::unique_ptr<int>".to_string(),
+            is_reference: false,
+            is_rvalue_reference: false,
+            is_pointer: false,
+            is_const: false,
+            is_unique_ptr: true,
+            is_shared_ptr: false,
+            is_static: false,
+            is_mutable: false,
+            is_volatile: false,
+            location: make_location(),
+            is_pack: false,
+            pack_element_type: None,
+            has_initializer: true,
+        };
+        let lambda = Expression::Lambda {
+            captures: vec![LambdaCaptureKind::DefaultCopy],
+            capture_initializers: Vec::new(),
+            body: vec![Statement::Return(Some(Expression::Dereference(Box::new(
+                Expression::Variable("p".to_string()),
+            ))))],
+        };
+        let function = Function {
+            name: "test".to_string(),
+            parameters: Vec::new(),
+            return_type: "void".to_string(),
+            body: vec![
+                Statement::VariableDecl(ptr),
+                Statement::Assignment {
+                    lhs: Expression::Variable("f".to_string()),
+                    rhs: lambda,
+                    location: make_location(),
+                },
+            ],
+            location: make_location(),
+            is_method: false,
+            method_qualifier: None,
+            template_parameters: Vec::new(),
+            safety_annotation: None,
+            has_explicit_safety_annotation: false,
+            is_deleted: false,
+            member_initializers: Vec::new(),
+        };
+
+        let errors = check_lambda_capture_safety(&function, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "Expected one error, got: {:?}", errors);
+        assert!(errors[0].contains("move-only variable 'p'"));
+        assert!(errors[0].contains("[p = std::move(p)]"));
+    }
+
     #[test]
     fn test_lambda_context_escape_tracking() {
         let mut ctx = LambdaContext::new();
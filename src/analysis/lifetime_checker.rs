@@ -139,51 +139,17 @@ impl LifetimeScope {
 }
 
 /// Check lifetime constraints in a program using header annotations
-/// Check if a file path is from a system header (not user code)
-fn is_system_header(file_path: &str) -> bool {
-    let system_paths = [
-        "/usr/include",
-        "/usr/local/include",
-        "/opt/homebrew/include",
-        "/Library/Developer",
-        "C:\\Program Files",
-        "/Applications/Xcode.app",
-    ];
-
-    for path in &system_paths {
-        if file_path.starts_with(path) {
-            return true;
-        }
-    }
-
-    // STL and system library patterns (works for relative paths too)
-    if file_path.contains("/include/c++/")
-        || file_path.contains("/bits/")
-        || file_path.contains("/ext/")
-        || file_path.contains("stl_")
-        || file_path.contains("/lib/gcc/")
-    {
-        return true;
-    }
-
-    // Also skip project include directory
-    if file_path.contains("/include/rusty/") || file_path.contains("/include/unified_") {
-        return true;
-    }
-
-    false
-}
-
 pub fn check_lifetimes_with_annotations(
     program: &IrProgram,
     header_cache: &HeaderCache,
     safety_context: &SafetyContext,
+    include_system: bool,
 ) -> Result<Vec<String>, String> {
     let mut errors = Vec::new();
 
     for function in &program.functions {
         // Skip system header functions
-        if is_system_header(&function.source_file) {
+        if !include_system && super::is_system_header(&function.source_file) {
             continue;
         }
 
@@ -192,10 +158,8 @@ pub fn check_lifetimes_with_annotations(
         if !safety_context.should_check_function(&function.name) {
             continue;
         }
-        let mut scope = LifetimeScope::new();
-        let function_errors = check_function_lifetimes(
+        let function_errors = check_function_with_annotations(
             function,
-            &mut scope,
             header_cache,
             &program.types_with_ref_members,
         )?;
@@ -205,6 +169,16 @@ pub fn check_lifetimes_with_annotations(
     Ok(errors)
 }
 
+/// Check lifetime constraints in one function using header annotations
+pub fn check_function_with_annotations(
+    function: &IrFunction,
+    header_cache: &HeaderCache,
+    types_with_ref_members: &std::collections::HashSet<String>,
+) -> Result<Vec<String>, String> {
+    let mut scope = LifetimeScope::new();
+    check_function_lifetimes(function, &mut scope, header_cache, types_with_ref_members)
+}
+
 fn check_function_lifetimes(
     function: &IrFunction,
     scope: &mut LifetimeScope,
@@ -325,6 +299,7 @@ fn check_function_lifetimes(
                     args,
                     result,
                     receiver_is_temporary,
+                    ..
                 } => {
                     // Check if we have annotations for this function
                     if let Some(signature) = header_cache.get_signature(func) {
@@ -404,24 +379,6 @@ fn check_function_call(
 ) -> Vec<String> {
     let mut errors = Vec::new();
 
-    // Check that we have the right number of arguments
-    if args.len() != signature.param_lifetimes.len() {
-        // Signature doesn't match, skip lifetime checking
-        return errors;
-    }
-
-    // Collect the actual lifetimes of arguments
-    let mut arg_lifetimes = Vec::new();
-    for (i, arg) in args.iter().enumerate() {
-        if let Some(lifetime) = scope.get_lifetime(arg) {
-            arg_lifetimes.push(Some(lifetime.clone()));
-        } else if scope.is_owned(arg) {
-            arg_lifetimes.push(None); // Owned value
-        } else {
-            arg_lifetimes.push(Some(format!("'arg{}", i)));
-        }
-    }
-
     // Check for temporary receiver with 'self lifetime in return
     // If the method returns &'self and receiver is temporary, result is dangling
     if receiver_is_temporary {
@@ -442,6 +399,24 @@ fn check_function_call(
         }
     }
 
+    // Check that we have the right number of arguments
+    if args.len() != signature.param_lifetimes.len() {
+        // Signature doesn't match, skip lifetime checking
+        return errors;
+    }
+
+    // Collect the actual lifetimes of arguments
+    let mut arg_lifetimes = Vec::new();
+    for (i, arg) in args.iter().enumerate() {
+        if let Some(lifetime) = scope.get_lifetime(arg) {
+            arg_lifetimes.push(Some(lifetime.clone()));
+        } else if scope.is_owned(arg) {
+            arg_lifetimes.push(None); // Owned value
+        } else {
+            arg_lifetimes.push(Some(format!("'arg{}", i)));
+        }
+    }
+
     // Check parameter lifetime requirements
     // Note: In C++, passing owned values to const reference parameters is legal (creates temporary)
     // So we only check for ownership transfer violations
@@ -545,7 +520,10 @@ fn check_return_lifetime(
                         // Only flag if the dependency is an OWNED local variable
                         // Reference aliases that depend on other references are OK
                         // (they inherit the lifetime of whatever they're bound to)
-                        let is_owned_type = matches!(other_var_info.ty, VariableType::Owned(_));
+                        let is_owned_type = matches!(
+                            other_var_info.ty,
+                            VariableType::Owned(_) | VariableType::SharedPtr(_)
+                        );
                         if is_owned_type
                             && lifetime.contains(var_name)
                             && !is_parameter(var_name, function)
@@ -558,12 +536,12 @@ fn check_return_lifetime(
                     }
                 }
             }
-            VariableType::Owned(_) => {
+            VariableType::Owned(_) | VariableType::SharedPtr(_) => {
                 // Variable is an OWNED local object - returning a reference to it is dangerous
                 // (This case is handled elsewhere - the function returns a reference but
                 // the variable itself is not a reference type, so we're taking &local)
             }
-            // Pointer types (Raw, UniquePtr, SharedPtr) are safe to return
+            // Pointer types (Raw, UniquePtr) are safe to return
             // The pointer value is copied, heap memory persists after function return
             _ => {}
         }
@@ -592,8 +570,10 @@ fn check_return_lifetime(
         // The 'value' passed to this function is the source of the constructor (e.g., "x" for Holder{x})
         // If this source is a local owned variable, the struct's reference will dangle
         if let Some(var_info) = function.variables.get(value) {
-            let is_local_owned =
-                matches!(var_info.ty, VariableType::Owned(_)) && !var_info.is_parameter;
+            let is_local_owned = matches!(
+                var_info.ty,
+                VariableType::Owned(_) | VariableType::SharedPtr(_)
+            ) && !var_info.is_parameter;
             if is_local_owned {
                 errors.push(format!(
                     "Returning struct '{}' with reference member initialized from local variable '{}' - \
@@ -684,6 +664,7 @@ mod tests {
             variables,
             return_type: "void*".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -747,6 +728,7 @@ mod tests {
             variables,
             return_type: "int&".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -800,6 +782,7 @@ mod tests {
             variables,
             return_type: "std::unique_ptr<int>".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -820,4 +803,58 @@ mod tests {
             errors
         );
     }
+
+    #[test]
+    fn test_system_header_function_checked_with_include_system() {
+        use crate::analysis::test_fixtures::{ir_function, ir_variable};
+        use crate::ir::{IrExpression, OwnershipGraph};
+        use crate::parser::safety_annotations::SafetyMode;
+
+        // { x = 1; } read(x); in a header under /usr/include
+        let function = IrFunction {
+            source_file: "/usr/include/vendored.h".to_string(),
+            ..ir_function(
+                vec![ir_variable("x", VariableType::Owned("int".to_string()), 0)],
+                vec![
+                    IrStatement::EnterScope,
+                    IrStatement::Assign {
+                        lhs: "x".to_string(),
+                        rhs: IrExpression::Literal("1".to_string()),
+                        line: 2,
+                    },
+                    IrStatement::ExitScope,
+                    IrStatement::UseVariable {
+                        var: "x".to_string(),
+                        operation: "read".to_string(),
+                    },
+                ],
+            )
+        };
+        let program = IrProgram {
+            functions: vec![function],
+            ownership_graph: OwnershipGraph::new(),
+            types_with_ref_members: HashSet::new(),
+            method_qualifiers: HashMap::new(),
+        };
+        let safety_context = SafetyContext {
+            file_default: SafetyMode::Safe,
+            ..SafetyContext::new()
+        };
+        let header_cache = HeaderCache::new();
+
+        let errors =
+            check_lifetimes_with_annotations(&program, &header_cache, &safety_context, false)
+                .unwrap();
+        assert!(
+            errors.is_empty(),
+            "system headers are skipped: {:?}",
+            errors
+        );
+
+        let errors =
+            check_lifetimes_with_annotations(&program, &header_cache, &safety_context, true)
+                .unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Use of 'x' after its lifetime has expired"));
+    }
 }
@@ -405,6 +405,7 @@ mod tests {
             variables: HashMap::new(),
             return_type: "void".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -89,6 +89,15 @@ impl LivenessAnalyzer {
         }
     }
 
+    /// Uses inside an if/switch branch count at the index of the top-level
+    /// statement holding it: the checker clears borrows only after the
+    /// whole statement, and a branch-local index could point before it
+    fn collect_nested_uses(&mut self, statements: &[IrStatement]) {
+        for stmt in statements {
+            self.collect_uses_from_statement(stmt);
+        }
+    }
+
     fn collect_uses_from_statement(&mut self, stmt: &IrStatement) {
         match stmt {
             IrStatement::Borrow { from, to, .. } => {
@@ -197,11 +206,11 @@ impl LivenessAnalyzer {
                 self.in_conditional_depth += 1;
 
                 // Analyze then branch
-                self.collect_uses(then_branch);
+                self.collect_nested_uses(then_branch);
 
                 // Analyze else branch
                 if let Some(else_stmts) = else_branch {
-                    self.collect_uses(else_stmts);
+                    self.collect_nested_uses(else_stmts);
                 }
 
                 self.in_conditional_depth -= 1;
@@ -210,7 +219,7 @@ impl LivenessAnalyzer {
                 self.in_conditional_depth += 1;
 
                 for case in cases {
-                    self.collect_uses(case);
+                    self.collect_nested_uses(case);
                 }
 
                 self.in_conditional_depth -= 1;
@@ -229,6 +238,12 @@ impl LivenessAnalyzer {
                 self.record_use(pack_name, use_type);
             }
 
+            IrStatement::LambdaCapture { lambda_var, .. } => {
+                // The closure variable is created here; its by-reference
+                // captures stay borrowed until its last use
+                self.record_use(lambda_var, UseType::Write);
+            }
+
             IrStatement::StructBorrow {
                 struct_var,
                 borrowed_from,
@@ -242,11 +257,10 @@ impl LivenessAnalyzer {
             // These don't use variables
             IrStatement::EnterScope
             | IrStatement::ExitScope
-            | IrStatement::EnterUnsafe
+            | IrStatement::EnterUnsafe { .. }
             | IrStatement::ExitUnsafe
             | IrStatement::Drop(_)
             | IrStatement::ImplicitDrop { .. }
-            | IrStatement::LambdaCapture { .. }
             | IrStatement::VarDecl { .. } => {}
         }
     }
@@ -395,4 +409,42 @@ mod tests {
         // Should have last use at index 4
         assert_eq!(last_uses.get("r"), Some(&4));
     }
+
+    #[test]
+    fn test_use_in_branch_counts_at_enclosing_statement() {
+        use crate::ir::BorrowKind;
+
+        let use_var = |var: &str| IrStatement::UseVariable {
+            var: var.to_string(),
+            operation: "read".to_string(),
+        };
+        // auto& r = x; z; y = std::move(x); if (c) { z; read(r); }
+        let statements = vec![
+            IrStatement::Borrow {
+                from: "x".to_string(),
+                to: "r".to_string(),
+                kind: BorrowKind::Immutable,
+                line: 2,
+                is_pointer: false,
+            },
+            use_var("z"),
+            IrStatement::Move {
+                from: "x".to_string(),
+                to: "y".to_string(),
+                line: 4,
+            },
+            IrStatement::If {
+                then_branch: vec![use_var("z"), use_var("r")],
+                else_branch: None,
+            },
+        ];
+
+        let mut analyzer = LivenessAnalyzer::new();
+        analyzer.collect_uses(&statements);
+        let last_uses = analyzer.compute_last_uses();
+
+        // Not index 1, the branch-local index, which would clear the borrow
+        // before the move
+        assert_eq!(last_uses.get("r"), Some(&3));
+    }
 }
@@ -1,10 +1,12 @@
 use crate::debug_println;
 use crate::ir::{BorrowKind, IrFunction, IrProgram, OwnershipState};
-use crate::parser::HeaderCache;
+use crate::parser::{HeaderCache, MethodQualifier};
 use std::collections::{HashMap, HashSet};
 
 /// Check if a file path is from a system header (not user code)
-/// System headers are from standard library or third-party installations
+/// System headers are from standard library or third-party installations.
+/// Under `--include-system` (`CheckOptions::include_system`) callers check
+/// them anyway.
 fn is_system_header(file_path: &str) -> bool {
     // Common system header paths (absolute)
     let system_paths = [
@@ -121,8 +123,14 @@ fn is_mutating_method_name(method_name: &str) -> bool {
 
 pub mod alignment_safety;
 pub mod array_bounds;
+pub mod base_move;
 pub mod borrows;
+pub mod checks;
 pub mod const_propagation;
+pub mod coroutine_refs;
+pub mod delete_this;
+pub mod deleted_copy;
+pub mod escaping_args;
 pub mod inheritance_safety;
 pub mod initialization_tracking;
 pub mod lambda_capture_safety;
@@ -130,42 +138,298 @@ pub mod lifetime_checker;
 pub mod lifetime_inference;
 pub mod lifetimes;
 pub mod liveness;
+pub mod move_from_const;
+pub mod move_in_call;
 pub mod mutable_checker;
 pub mod null_safety;
 pub mod ownership;
+pub mod param_invalidation;
+pub mod pessimizing_move;
 pub mod pointer_provenance;
 pub mod pointer_safety;
 pub mod raii_tracking;
+pub mod range_for_mutation;
 pub mod scope_lifetime;
+pub mod self_assignment;
+pub mod self_insert;
 pub mod struct_pointer_safety;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
 pub mod this_tracking;
+pub mod tuple_lifetime;
+pub mod union_safety;
+pub mod unnecessary_unsafe;
 pub mod unsafe_propagation;
+pub mod view_lifetime;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct BorrowCheckError {
     pub kind: ErrorKind,
+    pub severity: Severity,
     pub location: String,
     pub message: String,
 }
 
-#[derive(Debug, Clone)]
+impl BorrowCheckError {
+    /// Structure a violation message: its kind, the line it points at (empty
+    /// when it names none), and the text without the "In function '...': "
+    /// prefix and with whitespace collapsed
+    pub fn from_message(message: &str) -> Self {
+        let location = message
+            .match_indices("line ")
+            .filter_map(|(i, _)| {
+                let digits: String = message[i + 5..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                (!digits.is_empty()).then_some(digits)
+            })
+            .last()
+            .unwrap_or_default();
+
+        let text = strip_function_prefix(message.trim());
+
+        BorrowCheckError {
+            kind: ErrorKind::from_message(message),
+            severity: Severity::from_message(message),
+            location,
+            message: text.split_whitespace().collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// " at line N" for a violation message, or nothing for a statement that
+/// carries no location (a `return`): a "line 0" would be read as the location
+pub(crate) fn at_line(line: u32) -> String {
+    if line == 0 {
+        String::new()
+    } else {
+        format!(" at line {}", line)
+    }
+}
+
+/// `message` without a leading "In function '...': "
+fn strip_function_prefix(message: &str) -> &str {
+    message
+        .strip_prefix("In function '")
+        .and_then(|rest| rest.find("': ").map(|end| &rest[end + 3..]))
+        .unwrap_or(message)
+}
+
+/// `message` with each quoted name, such as `'x'` in "variable 'x'",
+/// removed. An apostrophe inside a word ("doesn't") opens no quote.
+fn without_quoted_names(message: &str) -> String {
+    let mut text = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(open) = rest.find('\'') {
+        let opens_quote = rest[..open]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let close = rest[open + 1..].find('\'').map(|close| open + 1 + close);
+        match close {
+            Some(close) if opens_quote => {
+                text.push_str(&rest[..open]);
+                text.push_str("''");
+                rest = &rest[close + 1..];
+            }
+            _ => {
+                text.push_str(&rest[..=open]);
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Several passes (borrows, lifetime inference, scope lifetime) can report
+/// the same problem. Keep the first of each `(location, kind, message)`
+/// among one function's violations. Violations without a line are always
+/// kept, since nothing tells two of them apart.
+fn dedup_violations(errors: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    errors
+        .into_iter()
+        .filter(|error| {
+            let violation = BorrowCheckError::from_message(error);
+            violation.location.is_empty()
+                || seen.insert((violation.location, violation.kind, violation.message))
+        })
+        .collect()
+}
+
+/// A fix-it carried by a violation message: insert `text` as a new line
+/// above `line` of `file`. Checkers append it as
+/// "; add `TEXT` ... (insert above line N of FILE)".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub text: String,
+    pub file: String,
+    pub line: u32,
+}
+
+impl Suggestion {
+    pub fn from_message(message: &str) -> Option<Suggestion> {
+        let start = message.find("; add `")? + "; add `".len();
+        let end = start + message[start..].find('`')?;
+        let (_, location) = message[end..].rsplit_once(" (insert above line ")?;
+        let (line, file) = location.strip_suffix(')')?.split_once(" of ")?;
+        Some(Suggestion {
+            text: message[start..end].to_string(),
+            file: file.to_string(),
+            line: line.parse().ok()?,
+        })
+    }
+}
+
+/// Errors always fail the run; warnings are reported but only fail it
+/// under `--deny-warnings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 2] = [Severity::Error, Severity::Warning];
+
+    /// Checkers mark advisory findings by starting the message (after any
+    /// "In function '...': " prefix) with `warning: `
+    pub fn from_message(message: &str) -> Severity {
+        if strip_function_prefix(message.trim_start()).starts_with("warning: ") {
+            Severity::Warning
+        } else {
+            Severity::Error
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[allow(dead_code)]
 pub enum ErrorKind {
     UseAfterMove,
     DoubleBorrow,
     MutableBorrowWhileImmutable,
+    BorrowConflict,
     DanglingReference,
     LifetimeViolation,
+    NullDereference,
+    Uninitialized,
+    UnsafeCall,
+    UnsafePointer,
+    Other,
+}
+
+impl ErrorKind {
+    /// Classify a violation message produced by one of the checkers.
+    /// More specific patterns are tested first. Only the checker's own words
+    /// count: the "In function '...': " prefix and quoted names are left
+    /// out, so a function named `get_uninitialized_value` isn't a kind.
+    pub fn from_message(message: &str) -> ErrorKind {
+        let lower = without_quoted_names(strip_function_prefix(message.trim())).to_lowercase();
+        if lower.contains("use after move")
+            || lower.contains("has been moved")
+            || lower.contains("already been moved")
+            || lower.contains("partially moved")
+            || lower.contains("same call that moves")
+        {
+            ErrorKind::UseAfterMove
+        } else if lower.contains("null pointer")
+            || lower.contains("possibly-empty")
+            || lower.contains("disengaged")
+        {
+            ErrorKind::NullDereference
+        } else if lower.contains("uninitialized") {
+            ErrorKind::Uninitialized
+        } else if lower.contains("dangling")
+            || lower.contains("outlives")
+            || lower.contains("goes out of scope")
+            || lower.contains("invalidated")
+            || lower.contains("use after free")
+            || lower.contains("double free")
+            || lower.contains("reference to local")
+            || lower.contains("temporary")
+            || lower.contains("not alive")
+        {
+            ErrorKind::DanglingReference
+        } else if lower.contains("lifetime") {
+            ErrorKind::LifetimeViolation
+        } else if lower.contains("already mutably borrowed") || lower.contains("borrowed mutably") {
+            ErrorKind::DoubleBorrow
+        } else if lower.contains("immutabl") && lower.contains("borrow") {
+            ErrorKind::MutableBorrowWhileImmutable
+        } else if lower.contains("borrow") {
+            ErrorKind::BorrowConflict
+        } else if lower.contains("unsafe function")
+            || lower.contains("non-safe function")
+            || lower.contains("unsafe memory function")
+        {
+            ErrorKind::UnsafeCall
+        } else if lower.contains("pointer") {
+            ErrorKind::UnsafePointer
+        } else {
+            ErrorKind::Other
+        }
+    }
+
+    pub const ALL: [ErrorKind; 11] = [
+        ErrorKind::UseAfterMove,
+        ErrorKind::DoubleBorrow,
+        ErrorKind::MutableBorrowWhileImmutable,
+        ErrorKind::BorrowConflict,
+        ErrorKind::DanglingReference,
+        ErrorKind::LifetimeViolation,
+        ErrorKind::NullDereference,
+        ErrorKind::Uninitialized,
+        ErrorKind::UnsafeCall,
+        ErrorKind::UnsafePointer,
+        ErrorKind::Other,
+    ];
+
+    /// Look up a kind by its kebab-case name
+    pub fn from_name(name: &str) -> Option<ErrorKind> {
+        ErrorKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Short kebab-case name used in summaries and machine-readable output
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::UseAfterMove => "use-after-move",
+            ErrorKind::DoubleBorrow => "double-borrow",
+            ErrorKind::MutableBorrowWhileImmutable => "mutable-borrow-while-immutable",
+            ErrorKind::BorrowConflict => "borrow-conflict",
+            ErrorKind::DanglingReference => "dangling-reference",
+            ErrorKind::LifetimeViolation => "lifetime-violation",
+            ErrorKind::NullDereference => "null-dereference",
+            ErrorKind::Uninitialized => "uninitialized",
+            ErrorKind::UnsafeCall => "unsafe-call",
+            ErrorKind::UnsafePointer => "unsafe-pointer",
+            ErrorKind::Other => "other",
+        }
+    }
 }
 
 #[allow(dead_code)]
 pub fn check_borrows(program: IrProgram) -> Result<Vec<String>, String> {
     let mut errors = Vec::new();
+    let mutable_params = this_tracking::mutable_reference_params(&program, &HeaderCache::new());
 
     for function in &program.functions {
-        let function_errors = check_function(function)?;
+        let function_errors = check_function(function, &program.method_qualifiers)?;
         errors.extend(function_errors);
+        errors.extend(this_tracking::check_mutable_arguments(
+            function,
+            &mutable_params,
+        ));
     }
 
     Ok(errors)
@@ -176,22 +440,28 @@ pub fn check_borrows_with_annotations_and_safety(
     program: IrProgram,
     header_cache: HeaderCache,
     file_safe: bool,
+    include_system: bool,
 ) -> Result<Vec<String>, String> {
     // If file is marked unsafe and no functions are marked safe, skip checking
     if !file_safe && !has_any_safe_functions(&program, &header_cache) {
         return Ok(Vec::new()); // No checking for unsafe code
     }
 
-    check_borrows_with_annotations(program, header_cache)
+    check_borrows_with_annotations(program, header_cache, include_system)
 }
 
+/// Run the IR passes over `program`. Returns the violations, and whether
+/// `--max-violations` stopped the passes before every function was checked.
 pub fn check_borrows_with_safety_context(
     program: IrProgram,
     header_cache: HeaderCache,
     safety_context: crate::parser::safety_annotations::SafetyContext,
-) -> Result<Vec<String>, String> {
+    options: &crate::check::CheckOptions,
+) -> Result<(Vec<String>, bool), String> {
     use crate::parser::safety_annotations::SafetyMode;
 
+    let checks = &options.checks;
+
     // NOTE: We no longer skip borrow checking for all-unsafe files.
     // Borrow conflict detection (mixing pointers and references) should run
     // on ALL code to catch memory safety issues. The @unsafe annotation only
@@ -208,21 +478,41 @@ pub fn check_borrows_with_safety_context(
 
     // PHASE 1: Check that safe functions returning references have lifetime annotations
     // Skip this check for all-unsafe files since it only applies to @safe code
-    if !all_unsafe {
-        let annotation_errors =
-            check_lifetime_annotation_requirements(&program, &header_cache, &safety_context)?;
+    if !all_unsafe && checks.is_enabled("lifetimes") {
+        let annotation_errors = check_lifetime_annotation_requirements(
+            &program,
+            &header_cache,
+            &safety_context,
+            options,
+        )?;
         errors.extend(annotation_errors);
     }
 
-    // Check each function for borrow conflicts
+    // Members handed to `T&` parameters are checked against the method qualifier
+    let mutable_params = this_tracking::mutable_reference_params(&program, &header_cache);
+
+    // Header annotations enable the signature-based lifetime passes
+    let check_annotations = header_cache.has_signatures() && checks.is_enabled("lifetimes");
+
+    // Check each function for borrow conflicts and lifetime issues
     // NOTE: Borrow checking is performed for ALL functions (including @unsafe), because
     // borrow rules apply uniformly to both pointers and references. The @unsafe annotation
     // only allows pointer operations (address-of, dereference), not borrow rule violations.
     // This matches Rust's behavior where unsafe blocks don't bypass the borrow checker.
+    let mut stopped_early = false;
     for function in &program.functions {
+        if options.violation_limit_reached(errors.len()) {
+            stopped_early = true;
+            break;
+        }
+        // `--function` narrows what is reported; the rest of the program
+        // still informs cross-function facts such as `mutable_params`
+        if !options.selects_function(&function.name) {
+            continue;
+        }
         // Skip borrow checking for system header functions
         // They are tracked for safety status but not analyzed internally
-        if is_system_header(&function.source_file) {
+        if !options.include_system && is_system_header(&function.source_file) {
             debug_println!(
                 "DEBUG: Skipping system header function '{}' from {}",
                 function.name,
@@ -240,56 +530,74 @@ pub fn check_borrows_with_safety_context(
             continue;
         }
 
-        debug_println!(
-            "DEBUG: Checking function '{}' for borrow conflicts",
-            function.name
-        );
+        let mut function_errors = Vec::new();
 
-        // Phase 2: Use version with header_cache for return value borrow detection
-        let function_errors = check_function_with_header_cache(function, &header_cache)?;
-        errors.extend(function_errors);
-    }
+        // Turned off with --disable borrows
+        if checks.is_enabled("borrows") {
+            debug_println!(
+                "DEBUG: Checking function '{}' for borrow conflicts",
+                function.name
+            );
 
-    // Run lifetime inference and validation for safe functions
-    for function in &program.functions {
-        // Skip system headers
-        if is_system_header(&function.source_file) {
-            continue;
+            // Phase 2: Use version with header_cache for return value borrow detection
+            let borrow_errors = crate::timings::time("borrows", || {
+                check_function_with_header_cache(
+                    function,
+                    &header_cache,
+                    &program.method_qualifiers,
+                )
+            })?;
+            function_errors.extend(borrow_errors);
+            function_errors.extend(this_tracking::check_mutable_arguments(
+                function,
+                &mutable_params,
+            ));
         }
 
-        if safety_context.should_check_function(&function.name) {
-            // Inference runs unconditionally on every @safe function that
-            // reaches the IR: an annotation must never exempt a body from
-            // implementation-level checking, or a wrong @lifetime could hide
-            // a dangling reference. Library-tier code (system headers,
-            // include/rusty/) never reaches this loop — the TU scoping in
-            // main.rs keeps it out of the IR passes entirely.
-            let inference_errors = lifetime_inference::infer_and_validate_lifetimes(function)?;
-            errors.extend(inference_errors);
+        // Inference runs unconditionally on every @safe function that
+        // reaches the IR: an annotation must never exempt a body from
+        // implementation-level checking, or a wrong @lifetime could hide
+        // a dangling reference. Library-tier code (system headers,
+        // include/rusty/) never reaches this loop — the TU scoping in
+        // main.rs keeps it out of the IR passes entirely.
+        if checks.is_enabled("lifetimes") {
+            let inference_errors = crate::timings::time("lifetimes", || {
+                lifetime_inference::infer_and_validate_lifetimes(function)
+            })?;
+            function_errors.extend(inference_errors);
+        }
 
-            // Phase 1-7: Run RAII tracking checks
-            let raii_errors = raii_tracking::check_raii_issues(function, &header_cache)?;
-            errors.extend(raii_errors);
+        // Phase 1-7: Run RAII tracking checks
+        if checks.is_enabled("raii") {
+            let raii_errors = crate::timings::time("raii", || {
+                raii_tracking::check_raii_issues(function, &header_cache)
+            })?;
+            function_errors.extend(raii_errors);
         }
-    }
 
-    // If we have header annotations, also check lifetime constraints
-    if header_cache.has_signatures() {
-        // Bug #9 fix: pass safety_context to filter by @safe functions only
-        let lifetime_errors = lifetime_checker::check_lifetimes_with_annotations(
-            &program,
-            &header_cache,
-            &safety_context,
-        )?;
-        errors.extend(lifetime_errors);
+        // If we have header annotations, also check lifetime constraints
+        if check_annotations {
+            let lifetime_errors = crate::timings::time("lifetimes", || {
+                lifetime_checker::check_function_with_annotations(
+                    function,
+                    &header_cache,
+                    &program.types_with_ref_members,
+                )
+            })?;
+            function_errors.extend(lifetime_errors);
+
+            // Also run scope-based lifetime checking
+            let scope_errors = crate::timings::time("lifetimes", || {
+                scope_lifetime::analyze_function_scopes(function, &header_cache)
+            })?;
+            function_errors.extend(scope_errors);
+        }
 
-        // Also run scope-based lifetime checking
-        let scope_errors =
-            scope_lifetime::check_scoped_lifetimes(&program, &header_cache, &safety_context)?;
-        errors.extend(scope_errors);
+        // The passes above overlap, so collapse repeats within this function
+        errors.extend(dedup_violations(function_errors));
     }
 
-    Ok(errors)
+    Ok((errors, stopped_early))
 }
 
 fn has_any_safe_functions(program: &IrProgram, header_cache: &HeaderCache) -> bool {
@@ -310,12 +618,16 @@ fn check_lifetime_annotation_requirements(
     program: &IrProgram,
     header_cache: &HeaderCache,
     safety_context: &crate::parser::safety_annotations::SafetyContext,
+    options: &crate::check::CheckOptions,
 ) -> Result<Vec<String>, String> {
     let mut errors = Vec::new();
 
     for function in &program.functions {
         // Skip system header functions
-        if is_system_header(&function.source_file) {
+        if !options.include_system && is_system_header(&function.source_file) {
+            continue;
+        }
+        if !options.selects_function(&function.name) {
             continue;
         }
 
@@ -340,16 +652,137 @@ fn check_lifetime_annotation_requirements(
         let returns_ref = check_if_function_returns_reference(function);
 
         if returns_ref && !has_lifetime_annotation {
-            errors.push(format!(
+            let mut message = format!(
                 "Safe function '{}' returns a reference but has no @lifetime annotation",
                 function.name
-            ));
+            );
+            if let Some((annotation, param)) = elided_lifetime_annotation(function) {
+                message.push_str(&format!(
+                    "; add `{}` with parameter '{}' (insert above line {} of {})",
+                    annotation, param, function.source_line, function.source_file
+                ));
+            }
+            errors.push(message);
+        }
+
+        if let Some(sig) = header_cache.get_signature(&function.name) {
+            errors.extend(check_mutable_return_binding(function, sig));
         }
     }
 
     Ok(errors)
 }
 
+/// The annotation Rust's elision rules would infer: a free function with a
+/// single reference parameter (and no pointers) can only return into it.
+/// Returns the annotation text and the parameter name.
+fn elided_lifetime_annotation(function: &IrFunction) -> Option<(String, String)> {
+    use crate::ir::VariableType;
+
+    if function.is_method {
+        return None;
+    }
+    let mut borrowed = function.variables.values().filter(|info| {
+        info.is_parameter
+            && match &info.ty {
+                VariableType::Reference(_) | VariableType::MutableReference(_) => true,
+                VariableType::Owned(type_name) => type_name.contains('*'),
+                _ => false,
+            }
+    });
+    let param = borrowed.next()?;
+    if borrowed.next().is_some() {
+        return None;
+    }
+
+    let return_mut = !function
+        .return_type
+        .split('&')
+        .next()
+        .unwrap_or_default()
+        .contains("const");
+    let param_annotation = match (&param.ty, return_mut) {
+        (VariableType::Reference(_), false) => "&'a",
+        (VariableType::MutableReference(_), _) => "&'a mut",
+        // A mutable reference can't come from a const one
+        _ => return None,
+    };
+    let return_annotation = if return_mut { "&'a mut" } else { "&'a" };
+    Some((
+        format!(
+            "// @lifetime: ({}) -> {}",
+            param_annotation, return_annotation
+        ),
+        param.name.clone(),
+    ))
+}
+
+/// A return annotated `&'a mut` can't be produced from a const input, so
+/// every parameter that carries `'a` must be mutable both in its annotation
+/// and in its declared C++ type
+fn check_mutable_return_binding(
+    function: &IrFunction,
+    sig: &crate::parser::annotations::FunctionSignature,
+) -> Option<String> {
+    use crate::ir::VariableType;
+    use crate::parser::annotations::LifetimeAnnotation;
+
+    let return_lifetime = match &sig.return_lifetime {
+        Some(annotation @ LifetimeAnnotation::MutRef(_)) => annotation.lifetime_name()?,
+        _ => return None,
+    };
+
+    // Parameters are registered in declaration order after the body's locals
+    let mut params: Vec<&crate::ir::VariableInfo> = function
+        .variables
+        .values()
+        .filter(|info| info.is_parameter)
+        .collect();
+    params.sort_by_key(|info| info.declaration_index);
+    // A leading 'self entry annotates the receiver, which is not a parameter
+    let receiver_offset = usize::from(
+        sig.param_lifetimes
+            .first()
+            .and_then(|annotation| annotation.as_ref()?.lifetime_name())
+            == Some("self"),
+    );
+
+    for (i, annotation) in sig.param_lifetimes.iter().enumerate() {
+        let Some(annotation) = annotation else {
+            continue;
+        };
+        if annotation.lifetime_name() != Some(return_lifetime) {
+            continue;
+        }
+        let param = i
+            .checked_sub(receiver_offset)
+            .and_then(|index| params.get(index));
+        let annotated_const = matches!(
+            annotation,
+            LifetimeAnnotation::Ref(_) | LifetimeAnnotation::ConstPtr(_)
+        );
+        let declared_const = param.is_some_and(|info| match &info.ty {
+            VariableType::Reference(_) => true,
+            VariableType::Owned(type_name) => {
+                type_name.starts_with("const ") && type_name.contains('*')
+            }
+            _ => false,
+        });
+        if annotated_const || declared_const {
+            let param_name = match param {
+                Some(info) => info.name.clone(),
+                None if i < receiver_offset => "this".to_string(),
+                None => format!("#{}", i + 1),
+            };
+            return Some(format!(
+                "Safe function '{}': @lifetime return annotated mutable (&'{} mut) but bound to const parameter '{}'",
+                function.name, return_lifetime, param_name
+            ));
+        }
+    }
+    None
+}
+
 /// Check if a function returns a reference by analyzing its return type
 fn check_if_function_returns_reference(function: &IrFunction) -> bool {
     contains_top_level_lvalue_reference(&function.return_type)
@@ -381,6 +814,7 @@ fn contains_top_level_lvalue_reference(type_name: &str) -> bool {
 pub fn check_borrows_with_annotations(
     program: IrProgram,
     header_cache: HeaderCache,
+    include_system: bool,
 ) -> Result<Vec<String>, String> {
     use crate::parser::annotations::SafetyAnnotation;
     use crate::parser::safety_annotations::SafetyContext;
@@ -402,7 +836,7 @@ pub fn check_borrows_with_annotations(
 
         // Skip checking if function is marked unsafe
         if !is_unsafe {
-            let function_errors = check_function(function)?;
+            let function_errors = check_function(function, &program.method_qualifiers)?;
             errors.extend(function_errors);
         }
     }
@@ -423,12 +857,17 @@ pub fn check_borrows_with_annotations(
             &program,
             &header_cache,
             &safety_context,
+            include_system,
         )?;
         errors.extend(lifetime_errors);
 
         // Also run scope-based lifetime checking
-        let scope_errors =
-            scope_lifetime::check_scoped_lifetimes(&program, &header_cache, &safety_context)?;
+        let scope_errors = scope_lifetime::check_scoped_lifetimes(
+            &program,
+            &header_cache,
+            &safety_context,
+            include_system,
+        )?;
         errors.extend(scope_errors);
     }
 
@@ -436,17 +875,83 @@ pub fn check_borrows_with_annotations(
 }
 
 // Phase 2: Wrapper for backward compatibility
-fn check_function(function: &IrFunction) -> Result<Vec<String>, String> {
+fn check_function(
+    function: &IrFunction,
+    method_qualifiers: &HashMap<String, MethodQualifier>,
+) -> Result<Vec<String>, String> {
     // Create an empty HeaderCache for functions that don't have annotations
     let empty_cache = HeaderCache::new();
-    check_function_with_header_cache(function, &empty_cache)
+    check_function_with_header_cache(function, &empty_cache, method_qualifiers)
 }
 
 // Phase 2: Added header_cache parameter for return value borrow detection
 fn check_function_with_header_cache(
     function: &IrFunction,
     header_cache: &HeaderCache,
+    method_qualifiers: &HashMap<String, MethodQualifier>,
 ) -> Result<Vec<String>, String> {
+    check_function_probing_unsafe(function, header_cache, method_qualifiers, false)
+        .map(|(errors, _)| errors)
+}
+
+/// The unsafe blocks of the checked functions that hide a borrow checker
+/// diagnostic, as (function name, line of the block's `EnterUnsafe`), for
+/// `--pedantic`'s unnecessary-unsafe check
+pub fn unsafe_blocks_suppressing(
+    program: &IrProgram,
+    header_cache: &HeaderCache,
+    safety_context: &crate::parser::safety_annotations::SafetyContext,
+    include_system: bool,
+) -> HashSet<(String, usize)> {
+    program
+        .functions
+        .iter()
+        .filter(|function| {
+            safety_context.should_check_function(&function.name)
+                && (include_system || !is_system_header(&function.source_file))
+                && function
+                    .cfg
+                    .node_indices()
+                    .any(|node| contains_unsafe_block(&function.cfg[node].statements))
+        })
+        .flat_map(|function| {
+            check_function_probing_unsafe(function, header_cache, &program.method_qualifiers, true)
+                .map(|(_, blocks)| blocks)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|line| (function.name.clone(), line))
+        })
+        .collect()
+}
+
+fn contains_unsafe_block(statements: &[crate::ir::IrStatement]) -> bool {
+    statements.iter().any(|statement| match statement {
+        crate::ir::IrStatement::EnterUnsafe { .. } => true,
+        crate::ir::IrStatement::If {
+            then_branch,
+            else_branch,
+        } => {
+            contains_unsafe_block(then_branch)
+                || else_branch
+                    .as_ref()
+                    .is_some_and(|else_stmts| contains_unsafe_block(else_stmts))
+        }
+        crate::ir::IrStatement::Switch { cases } => {
+            cases.iter().any(|case| contains_unsafe_block(case))
+        }
+        _ => false,
+    })
+}
+
+/// Borrow check a function. With `probe_unsafe`, unsafe blocks are checked
+/// as safe code instead, and the lines of the blocks that would have hidden
+/// a diagnostic are returned with the (then meaningless) errors.
+fn check_function_probing_unsafe(
+    function: &IrFunction,
+    header_cache: &HeaderCache,
+    method_qualifiers: &HashMap<String, MethodQualifier>,
+    probe_unsafe: bool,
+) -> Result<(Vec<String>, HashSet<usize>), String> {
     let mut errors = Vec::new();
 
     // NEW: Run liveness analysis first
@@ -454,7 +959,8 @@ fn check_function_with_header_cache(
     let last_uses = liveness_analyzer.analyze(function);
 
     // Create ownership tracker with liveness information
-    let mut ownership_tracker = OwnershipTracker::with_liveness(last_uses);
+    let mut ownership_tracker = OwnershipTracker::with_liveness(last_uses, method_qualifiers);
+    ownership_tracker.probe_unsafe = probe_unsafe;
 
     // Create this pointer tracker if this is a method
     let mut this_tracker = if function.is_method {
@@ -512,7 +1018,7 @@ fn check_function_with_header_cache(
 
                 // Track variables declared in the loop (including nested If/else blocks)
                 let mut loop_local_vars = HashSet::new();
-                collect_loop_local_vars(&loop_body, &mut loop_local_vars);
+                collect_loop_local_vars(&loop_body, function, &mut loop_local_vars);
 
                 // First iteration: process all statements
                 for (loop_idx, loop_stmt) in loop_body.iter().enumerate() {
@@ -531,21 +1037,21 @@ fn check_function_with_header_cache(
                     // NEW: Check for last uses (after processing statement)
                     // Statement index is i+1+loop_idx (i is EnterLoop, +1 for first statement)
                     ownership_tracker.check_and_clear_last_uses(i + 1 + loop_idx);
+                    ownership_tracker.report_join_conflicts(i + 1 + loop_idx, &mut errors);
                 }
 
-                // Save state after first iteration (but only for non-loop-local variables)
-                let state_after_first = ownership_tracker.ownership.clone();
-
                 // Clear loop-local borrows at end of first iteration
                 ownership_tracker.clear_loop_locals(&loop_local_vars);
 
                 // Second iteration - check for use-after-move
                 for (loop_idx, loop_stmt) in loop_body.iter().enumerate() {
                     // Before processing each statement in second iteration,
-                    // check if it would cause use-after-move (but only for non-loop-local vars)
+                    // check if it would cause use-after-move (but only for non-loop-local vars).
+                    // The live state is used so an outer variable re-initialized earlier
+                    // in the body (`b = make(); consume(std::move(b));`) is not reported.
                     check_statement_for_loop_errors(
                         loop_stmt,
-                        &state_after_first,
+                        &ownership_tracker.ownership,
                         &loop_local_vars,
                         &mut errors,
                     );
@@ -560,6 +1066,7 @@ fn check_function_with_header_cache(
 
                     // NEW: Check for last uses (after processing statement)
                     ownership_tracker.check_and_clear_last_uses(i + 1 + loop_idx);
+                    ownership_tracker.report_join_conflicts(i + 1 + loop_idx, &mut errors);
                 }
 
                 // Clear loop-local borrows at end of second iteration
@@ -582,53 +1089,56 @@ fn check_function_with_header_cache(
 
                 // NEW: Check for last uses (after processing statement)
                 ownership_tracker.check_and_clear_last_uses(i);
+                ownership_tracker.report_join_conflicts(i, &mut errors);
 
                 i += 1;
             }
         }
     }
 
-    Ok(errors)
+    Ok((errors, ownership_tracker.suppressing_blocks))
 }
 
 /// Recursively collect loop-local variables from statements, including nested If/else blocks.
-/// A loop-local variable is any variable declared/initialized inside the loop body.
+/// A loop-local variable is any variable declared inside the loop body, plus the
+/// compiler temporaries (`_moved_x`, `_temp_...`) its statements write to.
+/// Variables declared outside the loop stay tracked across iterations even when
+/// the body assigns them, so `consume(std::move(outer))` is caught on the
+/// second iteration unless every path re-initializes `outer`.
 fn collect_loop_local_vars(
     statements: &[crate::ir::IrStatement],
+    function: &IrFunction,
     loop_local_vars: &mut HashSet<String>,
 ) {
+    let insert_target = |var: &String, loop_local_vars: &mut HashSet<String>| {
+        if !function.variables.contains_key(var) {
+            loop_local_vars.insert(var.clone());
+        }
+    };
     for stmt in statements {
         match stmt {
             crate::ir::IrStatement::VarDecl { name, .. } => {
                 loop_local_vars.insert(name.clone());
             }
-            crate::ir::IrStatement::Borrow { to, .. } => {
-                loop_local_vars.insert(to.clone());
-            }
-            crate::ir::IrStatement::Move { to, .. } => {
-                loop_local_vars.insert(to.clone());
-            }
-            crate::ir::IrStatement::Assign { lhs, .. } => {
-                loop_local_vars.insert(lhs.clone());
-            }
+            crate::ir::IrStatement::Borrow { to, .. } => insert_target(to, loop_local_vars),
+            crate::ir::IrStatement::Move { to, .. } => insert_target(to, loop_local_vars),
+            crate::ir::IrStatement::Assign { lhs, .. } => insert_target(lhs, loop_local_vars),
             crate::ir::IrStatement::CallExpr {
                 result: Some(var), ..
-            } => {
-                loop_local_vars.insert(var.clone());
-            }
+            } => insert_target(var, loop_local_vars),
             // Recursively search nested blocks
             crate::ir::IrStatement::If {
                 then_branch,
                 else_branch,
             } => {
-                collect_loop_local_vars(then_branch, loop_local_vars);
+                collect_loop_local_vars(then_branch, function, loop_local_vars);
                 if let Some(else_stmts) = else_branch {
-                    collect_loop_local_vars(else_stmts, loop_local_vars);
+                    collect_loop_local_vars(else_stmts, function, loop_local_vars);
                 }
             }
             crate::ir::IrStatement::Switch { cases } => {
                 for case in cases {
-                    collect_loop_local_vars(case, loop_local_vars);
+                    collect_loop_local_vars(case, function, loop_local_vars);
                 }
             }
             _ => {}
@@ -723,7 +1233,7 @@ fn check_loop_local_escape(
 /// Recursively checks nested If/else blocks.
 fn check_statement_for_loop_errors(
     statement: &crate::ir::IrStatement,
-    state_after_first: &HashMap<String, OwnershipState>,
+    ownership: &HashMap<String, OwnershipState>,
     loop_local_vars: &HashSet<String>,
     errors: &mut Vec<String>,
 ) {
@@ -735,7 +1245,7 @@ fn check_statement_for_loop_errors(
             if loop_local_vars.contains(from) {
                 return;
             }
-            if let Some(state) = state_after_first.get(from) {
+            if let Some(state) = ownership.get(from) {
                 if *state == OwnershipState::Moved {
                     errors.push(format!(
                         "Use after move in loop: variable '{}' was moved in first iteration and used again in second iteration",
@@ -750,7 +1260,7 @@ fn check_statement_for_loop_errors(
                 if loop_local_vars.contains(var) {
                     return;
                 }
-                if let Some(state) = state_after_first.get(var) {
+                if let Some(state) = ownership.get(var) {
                     if *state == OwnershipState::Moved {
                         errors.push(format!(
                             "Use after move in loop: variable '{}' was moved in first iteration and used again in second iteration",
@@ -766,28 +1276,18 @@ fn check_statement_for_loop_errors(
             else_branch,
         } => {
             for stmt in then_branch {
-                check_statement_for_loop_errors(stmt, state_after_first, loop_local_vars, errors);
+                check_statement_for_loop_errors(stmt, ownership, loop_local_vars, errors);
             }
             if let Some(else_stmts) = else_branch {
                 for stmt in else_stmts {
-                    check_statement_for_loop_errors(
-                        stmt,
-                        state_after_first,
-                        loop_local_vars,
-                        errors,
-                    );
+                    check_statement_for_loop_errors(stmt, ownership, loop_local_vars, errors);
                 }
             }
         }
         crate::ir::IrStatement::Switch { cases } => {
             for case in cases {
                 for stmt in case {
-                    check_statement_for_loop_errors(
-                        stmt,
-                        state_after_first,
-                        loop_local_vars,
-                        errors,
-                    );
+                    check_statement_for_loop_errors(stmt, ownership, loop_local_vars, errors);
                 }
             }
         }
@@ -796,6 +1296,9 @@ fn check_statement_for_loop_errors(
 }
 
 // Phase 3: Helper function to check for borrow conflicts
+// A borrow of the whole object overlaps every field of it, so live field
+// borrows of `from` are checked too (the field side is handled by
+// `check_field_borrow_conflicts`)
 fn check_borrow_conflicts(
     from: &str,
     kind: &BorrowKind,
@@ -833,7 +1336,147 @@ fn check_borrow_conflicts(
         }
     }
 
-    true
+    check_whole_object_vs_field_borrows(from, kind, ownership_tracker, errors)
+}
+
+/// Borrow taken by a `rusty::RefCell` guard method: `borrow()` or `borrow_mut()`
+fn refcell_guard_kind(func: &str) -> Option<BorrowKind> {
+    let mut parts = func.rsplit("::");
+    let method = parts.next()?;
+    if !parts.next()?.starts_with("RefCell") {
+        return None;
+    }
+    match method {
+        "borrow" => Some(BorrowKind::Immutable),
+        "borrow_mut" => Some(BorrowKind::Mutable),
+        _ => None,
+    }
+}
+
+/// Whether `func` is the constructor of a guard that locks its mutex
+/// arguments: `std::lock_guard`, `std::unique_lock` or `std::scoped_lock`
+fn is_lock_guard_constructor(func: &str) -> bool {
+    let mut parts = func.rsplit("::");
+    let (Some(constructor), Some(class)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    let class = class.split('<').next().unwrap_or(class);
+    class == constructor && matches!(constructor, "lock_guard" | "unique_lock" | "scoped_lock")
+}
+
+/// Whether `type_name` names a `std::recursive_mutex` or
+/// `std::recursive_timed_mutex`, which its owner may lock again
+fn is_recursive_mutex(type_name: &str) -> bool {
+    type_name
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|word| matches!(word, "recursive_mutex" | "recursive_timed_mutex"))
+}
+
+/// The locking strategy tag a guard constructor argument names, if any
+fn lock_tag(arg: &str) -> Option<&str> {
+    arg.rsplit("::")
+        .next()
+        .filter(|name| matches!(*name, "defer_lock" | "try_to_lock" | "adopt_lock"))
+}
+
+/// `(source, out, kind)` for each argument an out-parameter is set to borrow
+/// from through a `@lifetime: (&'a, out &'a) -> void` annotation, e.g.
+/// `get(x, ptr);` makes `ptr` borrow `x`.
+pub(crate) fn out_param_borrows<'a>(
+    func: &str,
+    args: &'a [String],
+    header_cache: &HeaderCache,
+) -> Vec<(&'a String, &'a String, BorrowKind)> {
+    use crate::parser::annotations::LifetimeAnnotation;
+
+    let Some(signature) = header_cache.get_call_signature(func) else {
+        return Vec::new();
+    };
+    if !signature.param_outputs.contains(&true) {
+        return Vec::new();
+    }
+
+    // Method calls pass the receiver first
+    let offset = args
+        .len()
+        .saturating_sub(signature.param_lifetimes.len())
+        .min(1);
+    let params: Vec<(&LifetimeAnnotation, &String, bool)> = signature
+        .param_lifetimes
+        .iter()
+        .zip(&args[offset..])
+        .enumerate()
+        .filter(|(_, (_, arg))| !arg.starts_with("_temp_"))
+        .filter_map(|(i, (lifetime, arg))| {
+            Some((lifetime.as_ref()?, arg, signature.is_output_param(i)))
+        })
+        .collect();
+
+    let mut borrows = Vec::new();
+    for (out_lifetime, out, _) in params.iter().filter(|(_, _, is_output)| *is_output) {
+        for (lifetime, source, _) in params.iter().filter(|(_, _, is_output)| !is_output) {
+            if lifetime.lifetime_name().is_none()
+                || lifetime.lifetime_name() != out_lifetime.lifetime_name()
+            {
+                continue;
+            }
+            let kind = match lifetime {
+                LifetimeAnnotation::MutRef(_) | LifetimeAnnotation::Ptr(_) => BorrowKind::Mutable,
+                _ => BorrowKind::Immutable,
+            };
+            borrows.push((*source, *out, kind));
+        }
+    }
+    borrows
+}
+
+/// Arguments a constructor call ties to the constructed object through a
+/// `@lifetime: (&'a) -> 'a` annotation on the constructor, e.g. `Observer o(x);`
+/// makes `o` borrow `x` for as long as `o` lives.
+pub(crate) fn constructor_lifetime_args<'a>(
+    func: &str,
+    args: &'a [String],
+    header_cache: &HeaderCache,
+) -> Vec<&'a String> {
+    let Some(signature) = header_cache.get_call_signature(func) else {
+        return Vec::new();
+    };
+
+    // Only constructors (`Class::Class`) bind arguments to the new object
+    let mut name_parts = signature.name.rsplit("::");
+    let is_constructor = match (name_parts.next(), name_parts.next()) {
+        (Some(method), Some(class)) => method == strip_template_args(class),
+        _ => false,
+    };
+    if !is_constructor {
+        return Vec::new();
+    }
+
+    let Some(object_lifetime) = signature
+        .return_lifetime
+        .as_ref()
+        .and_then(|lifetime| lifetime.lifetime_name())
+    else {
+        return Vec::new();
+    };
+
+    signature
+        .param_lifetimes
+        .iter()
+        .zip(args)
+        .filter(|(param_lifetime, arg)| {
+            param_lifetime
+                .as_ref()
+                .and_then(|lifetime| lifetime.lifetime_name())
+                == Some(object_lifetime)
+                && !arg.starts_with("_temp_")
+        })
+        .map(|(_, arg)| arg)
+        .collect()
+}
+
+fn strip_template_args(name: &str) -> &str {
+    name.split('<').next().unwrap_or(name)
 }
 
 /// Check for field-level borrow conflicts (partial borrow tracking)
@@ -937,9 +1580,37 @@ fn check_whole_object_vs_field_borrows(
     true
 }
 
+/// Process one statement. In a probe run, the diagnostics it reports after
+/// getting past an `is_in_unsafe_block()` check are ones the open unsafe
+/// blocks would have hidden, so those blocks are noted as needed.
+fn process_statement(
+    statement: &crate::ir::IrStatement,
+    ownership_tracker: &mut OwnershipTracker,
+    this_tracker: &mut Option<this_tracking::ThisPointerTracker>,
+    errors: &mut Vec<String>,
+    header_cache: &HeaderCache,
+    function: &IrFunction,
+) {
+    let first_error = errors.len();
+    let enclosing_bypassed = std::mem::take(&mut ownership_tracker.unsafe_bypassed);
+    apply_statement(
+        statement,
+        ownership_tracker,
+        this_tracker,
+        errors,
+        header_cache,
+        function,
+    );
+    if ownership_tracker.unsafe_bypassed && errors.len() > first_error {
+        let open_blocks = ownership_tracker.unsafe_blocks.clone();
+        ownership_tracker.suppressing_blocks.extend(open_blocks);
+    }
+    ownership_tracker.unsafe_bypassed |= enclosing_bypassed;
+}
+
 // Extract statement processing logic into a separate function
 // Phase 2: Added header_cache and function parameters for return value borrow detection
-fn process_statement(
+fn apply_statement(
     statement: &crate::ir::IrStatement,
     ownership_tracker: &mut OwnershipTracker,
     this_tracker: &mut Option<this_tracking::ThisPointerTracker>,
@@ -948,7 +1619,7 @@ fn process_statement(
     function: &IrFunction,      // Phase 2: For checking variable types
 ) {
     match statement {
-        crate::ir::IrStatement::Move { from, to, .. } => {
+        crate::ir::IrStatement::Move { from, to, line } => {
             debug_println!(
                 "DEBUG ANALYSIS: Processing Move from '{}' to '{}'",
                 from,
@@ -958,6 +1629,20 @@ fn process_statement(
             if ownership_tracker.is_in_unsafe_block() {
                 // Still update ownership state for consistency
                 ownership_tracker.set_ownership(from.clone(), OwnershipState::Moved);
+                ownership_tracker.record_move_site(from, *line);
+                ownership_tracker.set_ownership(to.clone(), OwnershipState::Owned);
+                return;
+            }
+
+            // `std::move(*this)`: whether the object may give itself away
+            // depends only on the method qualifier
+            if from == "this" {
+                if let Some(tracker) = this_tracker {
+                    if let Err(err) = tracker.can_move_self() {
+                        errors.push(err);
+                        return;
+                    }
+                }
                 ownership_tracker.set_ownership(to.clone(), OwnershipState::Owned);
                 return;
             }
@@ -979,6 +1664,20 @@ fn process_statement(
             // Check both direct borrows AND transitive borrows (borrow chains)
             if ownership_tracker.is_transitively_borrowed(from) {
                 let borrowers = ownership_tracker.get_transitive_borrowers(from);
+                // A string_view/span left pointing at moved-from storage dangles
+                let view = borrowers.iter().find(|b| {
+                    matches!(
+                        function.variables.get(b.as_str()).map(|info| &info.ty),
+                        Some(crate::ir::VariableType::Owned(t)) if crate::ir::is_view_type(t)
+                    )
+                });
+                if let Some(view) = view {
+                    errors.push(format!(
+                        "Dangling reference: '{}' views '{}', which is moved while the view is still in use",
+                        view, from
+                    ));
+                    return;
+                }
                 errors.push(format!(
                     "Cannot move '{}' because it is borrowed by: {}",
                     from,
@@ -987,6 +1686,18 @@ fn process_statement(
                 return;
             }
 
+            // Can't move a whole object while one of its fields is borrowed
+            // Example: auto& r = obj.field; Obj o2 = std::move(obj);
+            if let Some((field, borrowers)) = ownership_tracker.get_field_borrowers(from).first() {
+                errors.push(format!(
+                    "Cannot move '{}' because field '{}' is borrowed by: {}",
+                    from,
+                    field,
+                    borrowers.join(", ")
+                ));
+                return;
+            }
+
             // REASSIGNMENT TRACKING: Check if 'to' has active borrows
             // In Rust, assignment drops the old value first, so we can't assign if borrowed
             // Example: box1 = std::move(box2); drops old value of box1
@@ -1030,6 +1741,7 @@ fn process_statement(
                 ownership_tracker.set_ownership(from.clone(), OwnershipState::Moved);
                 ownership_tracker.set_ownership(to.clone(), OwnershipState::Owned);
             }
+            ownership_tracker.record_move_site(from, *line);
         }
 
         // NEW: Handle field-level operations
@@ -1083,6 +1795,21 @@ fn process_statement(
                 return;
             }
 
+            // Check if the field itself is borrowed (e.g., auto& r = obj.field)
+            if let Some((_, borrowers)) = ownership_tracker
+                .get_field_borrowers(object)
+                .into_iter()
+                .find(|(borrowed_field, _)| borrowed_field == field)
+            {
+                errors.push(format!(
+                    "Cannot move field '{}.{}' because it is borrowed by: {}",
+                    object,
+                    field,
+                    borrowers.join(", ")
+                ));
+                return;
+            }
+
             // NEW: Check method qualifier restrictions on field moves
             // If object is "this" (or we're in a method context), check this pointer rules
             if let Some(tracker) = this_tracker {
@@ -1133,14 +1860,19 @@ fn process_statement(
                 return;
             }
 
-            // Check if the field has been moved
+            // Check if the field has been moved; assigning a fresh value
+            // (e.g. resetting `other.a` in a move constructor) re-initializes it
             let field_state = ownership_tracker.get_field_ownership(object, field);
             if field_state == OwnershipState::Moved {
-                errors.push(format!(
-                    "Cannot {} field '{}.{}' because it has been moved",
-                    operation, object, field
-                ));
-                return;
+                if operation == "write" {
+                    ownership_tracker.mark_field_owned(object, field);
+                } else {
+                    errors.push(format!(
+                        "Cannot {} field '{}.{}' because it has been moved",
+                        operation, object, field
+                    ));
+                    return;
+                }
             }
 
             // NEW: Check for borrow conflicts when calling methods on fields
@@ -1433,18 +2165,41 @@ fn process_statement(
                 return;
             }
 
-            // NEW: Check if whole-object borrow conflicts with existing field borrows
-            if !check_whole_object_vs_field_borrows(from, kind, ownership_tracker, errors) {
-                return;
-            }
-
             // Record the borrow
             ownership_tracker.add_borrow(from.clone(), to.clone(), kind.clone());
             ownership_tracker.mark_as_reference(to.clone(), *kind == BorrowKind::Mutable);
         }
 
-        crate::ir::IrStatement::StructBorrow {
-            struct_var,
+        crate::ir::IrStatement::LambdaCapture {
+            lambda_var,
+            captures,
+        } => {
+            // A by-reference capture [&x] is a mutable borrow of x held by the
+            // closure, so x cannot be reassigned while the closure is live:
+            //   auto f = [&x]() { x += 1; };
+            //   x = 5;   // ERROR: x is borrowed by f
+            //   f();
+            for capture in captures {
+                if !capture.is_ref || capture.name == "this" || capture.name == "<default>" {
+                    continue;
+                }
+
+                let kind = BorrowKind::Mutable;
+                if !check_borrow_conflicts(&capture.name, &kind, ownership_tracker, errors) {
+                    continue;
+                }
+
+                debug_println!(
+                    "DEBUG ANALYSIS: Lambda '{}' borrows '{}' by reference",
+                    lambda_var,
+                    capture.name
+                );
+                ownership_tracker.add_borrow(capture.name.clone(), lambda_var.clone(), kind);
+            }
+        }
+
+        crate::ir::IrStatement::StructBorrow {
+            struct_var,
             borrowed_from,
             struct_type,
             ..
@@ -1474,6 +2229,11 @@ fn process_statement(
                 return;
             }
 
+            // Already recorded from an annotated constructor
+            if ownership_tracker.is_borrowed_by(borrowed_from, struct_var) {
+                return;
+            }
+
             let kind = BorrowKind::Immutable;
             if !check_borrow_conflicts(borrowed_from, &kind, ownership_tracker, errors) {
                 return;
@@ -1483,6 +2243,8 @@ fn process_statement(
         }
 
         crate::ir::IrStatement::Assign { lhs, rhs, .. } => {
+            ownership_tracker.release_pointee(lhs);
+
             // Skip checks if we're in an unsafe block
             if ownership_tracker.is_in_unsafe_block() {
                 return;
@@ -1742,14 +2504,12 @@ fn process_statement(
             // Handled at the higher level
         }
 
-        crate::ir::IrStatement::EnterUnsafe => {
-            ownership_tracker.unsafe_depth += 1;
+        crate::ir::IrStatement::EnterUnsafe { line } => {
+            ownership_tracker.unsafe_blocks.push(*line);
         }
 
         crate::ir::IrStatement::ExitUnsafe => {
-            if ownership_tracker.unsafe_depth > 0 {
-                ownership_tracker.unsafe_depth -= 1;
-            }
+            ownership_tracker.unsafe_blocks.pop();
         }
 
         crate::ir::IrStatement::If {
@@ -1876,7 +2636,11 @@ fn process_statement(
                 let var_state = ownership_tracker.get_ownership(val);
 
                 if var_state == Some(&OwnershipState::Moved) {
-                    errors.push(format!("Cannot return '{}' because it has been moved", val));
+                    let mut msg = format!("Cannot return '{}' because it has been moved", val);
+                    if let Some(move_line) = ownership_tracker.move_site(val) {
+                        msg.push_str(&format!(" (moved at line {})", move_line));
+                    }
+                    errors.push(msg);
                 }
             }
         }
@@ -1941,7 +2705,11 @@ fn process_statement(
 
         // Phase 2: Handle CallExpr - detect return value borrows
         crate::ir::IrStatement::CallExpr {
-            func, args, result, ..
+            func,
+            args,
+            result,
+            receiver_is_temporary,
+            ..
         } => {
             debug_println!(
                 "DEBUG ANALYSIS PHASE2: CallExpr func='{}', args={:?}, result={:?}",
@@ -1955,6 +2723,88 @@ fn process_statement(
                 return;
             }
 
+            // `m.lock()` while a guard already holds `m` locks it a second time;
+            // `g.unlock()` releases the guard's lock early and `g.lock()` takes
+            // it again
+            if let Some(receiver) = args.first() {
+                match func.rsplit("::").next() {
+                    Some("lock") => {
+                        if let Some(guard) = ownership_tracker.lock_holder(receiver) {
+                            errors.push(format!(
+                                "Mutex '{}' is already locked by '{}': calling 'lock' again would deadlock",
+                                receiver, guard
+                            ));
+                            return;
+                        }
+                        if let Some(mutexes) = ownership_tracker.lock_guards.get(receiver) {
+                            for mutex in mutexes.clone() {
+                                if let Some(guard) = ownership_tracker.lock_holder(&mutex) {
+                                    errors.push(format!(
+                                        "Mutex '{}' is already locked by '{}': calling '{}.lock' would deadlock",
+                                        mutex, guard, receiver
+                                    ));
+                                    continue;
+                                }
+                                ownership_tracker.add_borrow_with_source(
+                                    mutex.clone(),
+                                    receiver.clone(),
+                                    BorrowKind::Immutable,
+                                    BorrowSource::LockGuard { mutex },
+                                );
+                            }
+                            return;
+                        }
+                    }
+                    Some("unlock") if ownership_tracker.lock_guards.contains_key(receiver) => {
+                        ownership_tracker.clear_borrows_from(receiver);
+                        return;
+                    }
+                    _ => {}
+                }
+
+                // `const auto& r = obj.peek(); obj.mutate();` - a non-const method
+                // needs the object mutably while `r` still reads it
+                if ownership_tracker.method_qualifier(function, receiver, func)
+                    == Some(&MethodQualifier::NonConst)
+                {
+                    let readers: Vec<String> = ownership_tracker
+                        .get_active_borrows(receiver)
+                        .into_iter()
+                        .flatten()
+                        .filter(|borrow| borrow.kind == BorrowKind::Immutable)
+                        .map(|borrow| borrow.borrower.clone())
+                        .collect();
+                    if !readers.is_empty() {
+                        errors.push(format!(
+                            "Cannot call non-const method '{}' on '{}' because it is immutably borrowed by: {}",
+                            func,
+                            receiver,
+                            readers.join(", ")
+                        ));
+                        return;
+                    }
+                }
+            }
+
+            // An annotated out-parameter borrows the inputs sharing its lifetime
+            // for as long as the out-argument lives: get(x, ptr);
+            for (source, out, kind) in out_param_borrows(func, args, header_cache) {
+                if ownership_tracker.is_borrowed_by(source, out) {
+                    continue;
+                }
+                if !check_borrow_conflicts(source, &kind, ownership_tracker, errors) {
+                    continue;
+                }
+                // The tracker's root scope is level 1; the IR counts from 0
+                let scope = function
+                    .variables
+                    .get(out)
+                    .map_or(ownership_tracker.scope_stack.len(), |info| {
+                        info.scope_level + 1
+                    });
+                ownership_tracker.add_borrow_in_scope(source.clone(), out.clone(), kind, scope);
+            }
+
             // Skip if no result variable (void return)
             let result_var = match result {
                 Some(r) => r,
@@ -1966,6 +2816,134 @@ fn process_statement(
                 result_var
             );
 
+            // `auto& x = Builder().with(a).build_ref();` - the method returns a
+            // reference (by-value results are lifetime-extended locals, not
+            // bindings) into a chain rooted in a temporary that dies at the
+            // end of the statement. An annotated return lifetime is left to
+            // the lifetime checker.
+            let annotated = header_cache
+                .get_signature(func)
+                .is_some_and(|signature| signature.return_lifetime.is_some());
+            if *receiver_is_temporary && !annotated {
+                errors.push(format!(
+                    "Reference '{}' is bound to a temporary object that will be destroyed at the end of the statement: '{}' is called on a temporary",
+                    result_var, func
+                ));
+                return;
+            }
+
+            // Assigning a call result re-initializes a moved-from value: b = make();
+            // The new value is whole, so fields moved out of the old one are back too
+            if !ownership_tracker.is_reference(result_var) {
+                if ownership_tracker.get_ownership(result_var) == Some(&OwnershipState::Moved) {
+                    ownership_tracker.set_ownership(result_var.clone(), OwnershipState::Owned);
+                }
+                ownership_tracker.reset_fields(result_var);
+            }
+
+            // `auto g = cell.borrow_mut();` - the guard borrows the RefCell until
+            // it goes out of scope. A conflicting guard panics at runtime.
+            if let Some(kind) = refcell_guard_kind(func) {
+                if let Some(cell) = args.first() {
+                    let current = ownership_tracker.get_borrows(cell);
+                    let conflict = if current.has_mutable {
+                        Some("mutably")
+                    } else if kind == BorrowKind::Mutable && current.immutable_count > 0 {
+                        Some("immutably")
+                    } else {
+                        None
+                    };
+                    if let Some(held) = conflict {
+                        errors.push(format!(
+                            "RefCell '{}' is already {} borrowed: '{}' would panic at runtime",
+                            cell,
+                            held,
+                            func.rsplit("::").next().unwrap_or(func)
+                        ));
+                        return;
+                    }
+                    ownership_tracker.add_borrow_with_source(
+                        cell.clone(),
+                        result_var.clone(),
+                        kind,
+                        BorrowSource::RefCellGuard { cell: cell.clone() },
+                    );
+                }
+                return;
+            }
+
+            // `std::lock_guard<std::mutex> g(m);` - the guard holds `m` locked until
+            // it goes out of scope. Locking `m` again meanwhile deadlocks.
+            // A recursive mutex may be locked again by the thread that holds it.
+            if is_lock_guard_constructor(func) {
+                if is_recursive_mutex(func) {
+                    return;
+                }
+                let mutexes: Vec<String> = args
+                    .iter()
+                    .filter(|arg| lock_tag(arg).is_none())
+                    .filter(|arg| {
+                        !matches!(
+                            function.variables.get(arg.as_str()).map(|info| &info.ty),
+                            Some(crate::ir::VariableType::Owned(t)) if is_recursive_mutex(t)
+                        )
+                    })
+                    .cloned()
+                    .collect();
+                // std::defer_lock / std::try_to_lock don't block on the mutex;
+                // a later `g.lock()` does
+                if args
+                    .iter()
+                    .any(|arg| matches!(lock_tag(arg), Some("defer_lock" | "try_to_lock")))
+                {
+                    ownership_tracker
+                        .lock_guards
+                        .insert(result_var.clone(), mutexes);
+                    return;
+                }
+                for mutex in &mutexes {
+                    if let Some(guard) = ownership_tracker.lock_holder(mutex) {
+                        errors.push(format!(
+                            "Mutex '{}' is already locked by '{}': constructing '{}' would deadlock",
+                            mutex, guard, result_var
+                        ));
+                        continue;
+                    }
+                    ownership_tracker.add_borrow_with_source(
+                        mutex.clone(),
+                        result_var.clone(),
+                        BorrowKind::Immutable,
+                        BorrowSource::LockGuard {
+                            mutex: mutex.clone(),
+                        },
+                    );
+                }
+                return;
+            }
+
+            // An annotated constructor makes the new object borrow its arguments.
+            // Like StructBorrow, the object itself is not marked as a reference.
+            let constructor_borrows = constructor_lifetime_args(func, args, header_cache);
+            if !constructor_borrows.is_empty() {
+                for source in constructor_borrows {
+                    if ownership_tracker.is_borrowed_by(source, result_var) {
+                        continue;
+                    }
+                    let kind = BorrowKind::Immutable;
+                    if !check_borrow_conflicts(source, &kind, ownership_tracker, errors) {
+                        continue;
+                    }
+                    debug_println!(
+                        "DEBUG ANALYSIS: Constructor '{}' ties '{}' to '{}'",
+                        func,
+                        source,
+                        result_var
+                    );
+                    ownership_tracker.add_borrow(source.clone(), result_var.clone(), kind);
+                }
+                return;
+            }
+
             // Phase 2: Detect return value borrows from lifetime annotations
             // Try to get the function signature from HeaderCache
             if let Some(signature) = header_cache.get_signature(func) {
@@ -1981,8 +2959,13 @@ fn process_statement(
                         func
                     );
 
-                    // Check if return type has a lifetime annotation
-                    if let Some(ret_lifetime) = &signature.return_lifetime {
+                    // Check if return type has a lifetime annotation. A
+                    // 'static return borrows from no argument.
+                    if let Some(ret_lifetime) = signature
+                        .return_lifetime
+                        .as_ref()
+                        .filter(|lifetime| !lifetime.is_static())
+                    {
                         debug_println!("DEBUG ANALYSIS PHASE2: Return lifetime annotation found");
 
                         // Find which parameter has a matching lifetime
@@ -2106,13 +3089,31 @@ fn process_statement(
                                                 );
 
                                                 let is_complex = matches!(&var_info.ty, crate::ir::VariableType::Owned(type_name)
-                                                    if !is_primitive_type(type_name));
+                                                    if !is_primitive_type(type_name))
+                                                    || matches!(
+                                                        &var_info.ty,
+                                                        crate::ir::VariableType::SharedPtr(_)
+                                                    );
                                                 debug_println!(
                                                     "DEBUG ANALYSIS PHASE2: is_complex = {}",
                                                     is_complex
                                                 );
 
-                                                is_ref || is_complex
+                                                // A reference return bound to a value
+                                                // (`auto x = obj.get_ref()`) is a copy
+                                                let is_copy = matches!(
+                                                    ret_lifetime,
+                                                    crate::parser::annotations::LifetimeAnnotation::Ref(_)
+                                                        | crate::parser::annotations::LifetimeAnnotation::MutRef(_)
+                                                ) && (matches!(&var_info.ty, crate::ir::VariableType::Owned(type_name)
+                                                    if !type_name.contains('*'))
+                                                    || matches!(&var_info.ty, crate::ir::VariableType::SharedPtr(_)));
+                                                debug_println!(
+                                                    "DEBUG ANALYSIS PHASE2: is_copy = {}",
+                                                    is_copy
+                                                );
+
+                                                is_ref || (is_complex && !is_copy)
                                             } else {
                                                 debug_println!(
                                                     "DEBUG ANALYSIS PHASE2: No var_info found for '{}', assuming complex",
@@ -2134,6 +3135,13 @@ fn process_statement(
                                             should_create_borrow
                                         );
 
+                                        // Already recorded as a borrow of the receiver
+                                        if ownership_tracker
+                                            .is_borrowed_by(borrowed_var, result_var)
+                                        {
+                                            break;
+                                        }
+
                                         if should_create_borrow {
                                             debug_println!(
                                                 "DEBUG ANALYSIS PHASE2: Adding {} borrow: '{}' -> '{}' (result is reference type)",
@@ -2199,7 +3207,7 @@ fn process_statement(
     }
 }
 
-struct OwnershipTracker {
+struct OwnershipTracker<'a> {
     ownership: HashMap<String, OwnershipState>,
     borrows: HashMap<String, BorrowInfo>,
     reference_info: HashMap<String, ReferenceInfo>,
@@ -2209,8 +3217,15 @@ struct OwnershipTracker {
     loop_depth: usize,
     // Save state when entering a loop (for 2nd iteration checking)
     loop_entry_states: Vec<LoopEntryState>,
-    // Track if we're in an unsafe block
-    unsafe_depth: usize,
+    // Lines of the unsafe blocks we're in, innermost last
+    unsafe_blocks: Vec<usize>,
+    // Probe run for --pedantic's unnecessary-unsafe check: unsafe blocks are
+    // checked as safe code. `unsafe_bypassed` is set when the statement being
+    // processed gets past an unsafe block check, and `suppressing_blocks`
+    // collects the blocks that would have hidden a diagnostic.
+    probe_unsafe: bool,
+    unsafe_bypassed: bool,
+    suppressing_blocks: HashSet<usize>,
     // Track active borrows: which variables are currently borrowed from
     // Key: variable being borrowed from, Value: list of active borrows on it
     active_borrows: HashMap<String, Vec<ActiveBorrow>>,
@@ -2223,6 +3238,18 @@ struct OwnershipTracker {
     // NEW: Liveness analysis - track last use of variables
     // Key: variable name, Value: statement index of last use
     last_use_map: HashMap<String, usize>,
+    // Line of the most recent move out of each variable, for diagnostics
+    move_sites: HashMap<String, usize>,
+    // RefCell guards: their borrow lasts until scope exit, not their last use
+    refcell_guards: HashSet<String>,
+    // Mutex guards and the mutexes they lock: like RefCell guards, they hold
+    // their lock until scope exit
+    lock_guards: HashMap<String, Vec<String>>,
+    // Borrows of the same variable with different kinds on the paths into a
+    // join: (variable, mutable borrower, immutable borrower)
+    join_conflicts: Vec<(String, String, String)>,
+    // Qualifiers of the program's methods, to tell mutating calls from reads
+    method_qualifiers: &'a HashMap<String, MethodQualifier>,
 }
 
 #[derive(Clone)]
@@ -2285,15 +3312,28 @@ enum BorrowSource {
         // T* q = p;  (q aliases what p borrows from)
         source_pointer: String, // The pointer being aliased (e.g., "p")
     },
+    RefCellGuard {
+        // auto g = cell.borrow_mut();  (g holds the borrow until its scope ends)
+        cell: String, // The RefCell being borrowed
+    },
+    LockGuard {
+        // std::lock_guard<std::mutex> g(m);  (g holds m locked until its scope ends)
+        mutex: String, // The mutex being locked
+    },
 }
 
-impl OwnershipTracker {
+impl<'a> OwnershipTracker<'a> {
     #[cfg(test)]
     fn new() -> Self {
-        Self::with_liveness(HashMap::new())
+        static NO_METHODS: std::sync::LazyLock<HashMap<String, MethodQualifier>> =
+            std::sync::LazyLock::new(HashMap::new);
+        Self::with_liveness(HashMap::new(), &NO_METHODS)
     }
 
-    fn with_liveness(last_use_map: HashMap<String, usize>) -> Self {
+    fn with_liveness(
+        last_use_map: HashMap<String, usize>,
+        method_qualifiers: &'a HashMap<String, MethodQualifier>,
+    ) -> Self {
         let mut tracker = Self {
             ownership: HashMap::new(),
             borrows: HashMap::new(),
@@ -2301,19 +3341,36 @@ impl OwnershipTracker {
             scope_stack: Vec::new(),
             loop_depth: 0,
             loop_entry_states: Vec::new(),
-            unsafe_depth: 0,
+            unsafe_blocks: Vec::new(),
+            probe_unsafe: false,
+            unsafe_bypassed: false,
+            suppressing_blocks: HashSet::new(),
             active_borrows: HashMap::new(),
             field_ownership: HashMap::new(), // NEW
             field_borrows: HashMap::new(),   // NEW: Partial borrow tracking
             last_use_map,                    // NEW: Liveness analysis
+            move_sites: HashMap::new(),
+            refcell_guards: HashSet::new(),
+            lock_guards: HashMap::new(),
+            join_conflicts: Vec::new(),
+            method_qualifiers,
         };
         // Start with a root scope
         tracker.scope_stack.push(ScopeInfo::default());
         tracker
     }
 
-    fn is_in_unsafe_block(&self) -> bool {
-        self.unsafe_depth > 0
+    /// Whether checks are skipped here. A probe run checks unsafe blocks
+    /// anyway, and notes that it did.
+    fn is_in_unsafe_block(&mut self) -> bool {
+        if self.unsafe_blocks.is_empty() {
+            return false;
+        }
+        if self.probe_unsafe {
+            self.unsafe_bypassed = true;
+            return false;
+        }
+        true
     }
 
     fn set_ownership(&mut self, var: String, state: OwnershipState) {
@@ -2324,6 +3381,41 @@ impl OwnershipTracker {
         self.ownership.get(var)
     }
 
+    fn record_move_site(&mut self, var: &str, line: usize) {
+        if line > 0 {
+            self.move_sites.insert(var.to_string(), line);
+        }
+    }
+
+    fn move_site(&self, var: &str) -> Option<usize> {
+        self.move_sites.get(var).copied()
+    }
+
+    /// Qualifier of `func` called on `receiver`, looked up as a method of the
+    /// receiver's declared class: `b.get()` on a `Box<int> b` is `Box::get`
+    fn method_qualifier(
+        &self,
+        function: &IrFunction,
+        receiver: &str,
+        func: &str,
+    ) -> Option<&'a MethodQualifier> {
+        let class = match &function.variables.get(receiver)?.ty {
+            crate::ir::VariableType::Owned(ty)
+            | crate::ir::VariableType::Reference(ty)
+            | crate::ir::VariableType::MutableReference(ty) => ty,
+            _ => return None,
+        };
+        let class = class
+            .trim()
+            .trim_start_matches("const ")
+            .trim_end_matches(['&', ' '])
+            .split('<')
+            .next()?;
+        let method = func.rsplit("::").next()?;
+        self.method_qualifiers
+            .get(&format!("{}::{}", class, method))
+    }
+
     fn get_borrows(&self, var: &str) -> BorrowInfo {
         self.borrows.get(var).cloned().unwrap_or_default()
     }
@@ -2334,8 +3426,20 @@ impl OwnershipTracker {
         from: String,
         to: String,
         kind: BorrowKind,
-        _source: BorrowSource,
+        source: BorrowSource,
     ) {
+        match source {
+            BorrowSource::RefCellGuard { .. } => {
+                self.refcell_guards.insert(to.clone());
+            }
+            BorrowSource::LockGuard { ref mutex } => {
+                let mutexes = self.lock_guards.entry(to.clone()).or_default();
+                if !mutexes.contains(mutex) {
+                    mutexes.push(mutex.clone());
+                }
+            }
+            _ => {}
+        }
         let borrow_info = self.borrows.entry(from.clone()).or_default();
         borrow_info.borrowers.insert(to.clone());
 
@@ -2362,11 +3466,54 @@ impl OwnershipTracker {
             .push(active_borrow);
     }
 
+    /// Record a borrow held by `to` until the scope at level `scope` (the one
+    /// `to` was declared in) exits, rather than the current scope
+    fn add_borrow_in_scope(&mut self, from: String, to: String, kind: BorrowKind, scope: usize) {
+        let borrow_info = self.borrows.entry(from.clone()).or_default();
+        borrow_info.borrowers.insert(to.clone());
+        match kind {
+            BorrowKind::Immutable => borrow_info.immutable_count += 1,
+            BorrowKind::Mutable => borrow_info.has_mutable = true,
+        }
+
+        if let Some(declared_in) = scope
+            .checked_sub(1)
+            .and_then(|index| self.scope_stack.get_mut(index))
+        {
+            declared_in.local_borrows.insert(to.clone());
+        }
+
+        self.active_borrows
+            .entry(from)
+            .or_default()
+            .push(ActiveBorrow {
+                borrower: to,
+                kind,
+                scope,
+            });
+    }
+
     // Convenience function for direct reference borrows (most common case)
     fn add_borrow(&mut self, from: String, to: String, kind: BorrowKind) {
         self.add_borrow_with_source(from, to, kind, BorrowSource::DirectReference);
     }
 
+    /// Whether `borrower` currently holds a borrow of `var`
+    fn is_borrowed_by(&self, var: &str, borrower: &str) -> bool {
+        self.active_borrows
+            .get(var)
+            .is_some_and(|borrows| borrows.iter().any(|b| b.borrower == borrower))
+    }
+
+    /// The guard currently holding `mutex` locked, if any
+    fn lock_holder(&self, mutex: &str) -> Option<&str> {
+        self.active_borrows
+            .get(mutex)?
+            .iter()
+            .find(|borrow| self.lock_guards.contains_key(&borrow.borrower))
+            .map(|borrow| borrow.borrower.as_str())
+    }
+
     // NEW: Get active borrows for a variable
     fn get_active_borrows(&self, var: &str) -> Option<&Vec<ActiveBorrow>> {
         self.active_borrows.get(var)
@@ -2484,6 +3631,17 @@ impl OwnershipTracker {
 
         // Clean up empty borrow lists
         self.active_borrows.retain(|_, borrows| !borrows.is_empty());
+
+        // A dead reference no longer holds the field it borrowed
+        self.release_field_borrows(var);
+    }
+
+    // Re-seating pointer `p` leaves references bound to `*p` on the old
+    // target, so they no longer conflict with writes through `p`
+    fn release_pointee(&mut self, pointer: &str) {
+        let pointee = format!("*{}", pointer);
+        self.borrows.remove(&pointee);
+        self.active_borrows.remove(&pointee);
     }
 
     // NEW: Check if any variable reached its last use at this statement index
@@ -2494,6 +3652,7 @@ impl OwnershipTracker {
             .iter()
             .filter(|&(_, &last_use_idx)| last_use_idx == statement_idx)
             .map(|(var, _)| var.clone())
+            .filter(|var| !self.refcell_guards.contains(var) && !self.lock_guards.contains_key(var))
             .collect();
 
         for var in vars_to_clear {
@@ -2506,6 +3665,49 @@ impl OwnershipTracker {
         }
     }
 
+    /// A variable mutably borrowed on one path and immutably on the other has
+    /// no single borrow state after the join. The intersection kept by
+    /// `merge_states` would drop both, so remember the pair instead.
+    fn record_join_conflicts(&mut self, state: &TrackerState, other: &TrackerState) {
+        for (var, borrows) in &state.active_borrows {
+            let Some(other_borrows) = other.active_borrows.get(var) else {
+                continue;
+            };
+            let only_in = |list: &[ActiveBorrow], rest: &[ActiveBorrow], kind: BorrowKind| {
+                list.iter()
+                    .find(|b| b.kind == kind && !rest.iter().any(|r| r.borrower == b.borrower))
+                    .map(|b| b.borrower.clone())
+            };
+            if let (Some(mutable), Some(immutable)) = (
+                only_in(borrows, other_borrows, BorrowKind::Mutable),
+                only_in(other_borrows, borrows, BorrowKind::Immutable),
+            ) {
+                self.join_conflicts.push((var.clone(), mutable, immutable));
+            }
+        }
+    }
+
+    /// Report join conflicts whose borrowers are both still used after the
+    /// top-level statement at `statement_idx`
+    fn report_join_conflicts(&mut self, statement_idx: usize, errors: &mut Vec<String>) {
+        let live_after = |var: &str| {
+            self.last_use_map
+                .get(var)
+                .is_some_and(|&last_use| last_use > statement_idx)
+        };
+        for (var, mutable, immutable) in std::mem::take(&mut self.join_conflicts) {
+            if live_after(&mutable) && live_after(&immutable) {
+                let error = format!(
+                    "Conflicting borrows of '{}' after branch: mutably borrowed by '{}' on one path and immutably borrowed by '{}' on another",
+                    var, mutable, immutable
+                );
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+    }
+
     // NEW: Helper methods for field-level ownership tracking
 
     /// Get ownership state of a specific field
@@ -2525,6 +3727,20 @@ impl OwnershipTracker {
             .insert(field, OwnershipState::Moved);
     }
 
+    /// Mark a previously moved field as holding a value again
+    fn mark_field_owned(&mut self, object: &str, field: &str) {
+        if let Some(fields) = self.field_ownership.get_mut(object) {
+            fields.insert(field.to_string(), OwnershipState::Owned);
+        }
+    }
+
+    /// Forget field states of an object that was given a whole new value
+    fn reset_fields(&mut self, object: &str) {
+        let nested = format!("{}.", object);
+        self.field_ownership
+            .retain(|key, _| key != object && !key.starts_with(&nested));
+    }
+
     /// Check if object has any moved fields (including nested paths)
     /// For object "o", checks if "o" has direct moved fields,
     /// and also checks if any "o.X" has moved fields (nested)
@@ -2617,6 +3833,51 @@ impl OwnershipTracker {
         }
     }
 
+    /// Remove a borrower from field borrow tracking (scope exit or last use)
+    fn release_field_borrows(&mut self, borrower: &str) {
+        for field_map in self.field_borrows.values_mut() {
+            for borrow_info in field_map.values_mut() {
+                if borrow_info.borrowers.remove(borrower) {
+                    // If this borrower was removed, update the counts
+                    // We need to track if this was a mutable or immutable borrow
+                    // For simplicity, we'll reset counts based on remaining borrowers
+                    // This is conservative - a more complete impl would track borrow kinds per borrower
+                    if borrow_info.borrowers.is_empty() {
+                        borrow_info.has_mutable = false;
+                        borrow_info.immutable_count = 0;
+                    }
+                }
+            }
+        }
+
+        // Clean up empty field borrow entries
+        for field_map in self.field_borrows.values_mut() {
+            field_map.retain(|_, info| !info.borrowers.is_empty());
+        }
+        self.field_borrows.retain(|_, fields| !fields.is_empty());
+    }
+
+    /// Get the live borrowers of each borrowed field of an object, sorted by field name
+    fn get_field_borrowers(&self, object: &str) -> Vec<(String, Vec<String>)> {
+        let mut result: Vec<(String, Vec<String>)> = self
+            .field_borrows
+            .get(object)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter(|(_, info)| !info.borrowers.is_empty())
+                    .map(|(field, info)| {
+                        let mut borrowers: Vec<String> = info.borrowers.iter().cloned().collect();
+                        borrowers.sort();
+                        (field.clone(), borrowers)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        result.sort();
+        result
+    }
+
     /// Get list of borrowed fields for an object
     fn get_borrowed_fields(&self, object: &str) -> Vec<(String, bool)> {
         let mut result = Vec::new();
@@ -2660,20 +3921,7 @@ impl OwnershipTracker {
 
                 // NEW: Remove from field borrows (Partial Borrow Tracking)
                 // When a borrower goes out of scope, remove it from field borrow tracking
-                for field_map in self.field_borrows.values_mut() {
-                    for borrow_info in field_map.values_mut() {
-                        if borrow_info.borrowers.remove(borrow_name) {
-                            // If this borrower was removed, update the counts
-                            // We need to track if this was a mutable or immutable borrow
-                            // For simplicity, we'll reset counts based on remaining borrowers
-                            // This is conservative - a more complete impl would track borrow kinds per borrower
-                            if borrow_info.borrowers.is_empty() {
-                                borrow_info.has_mutable = false;
-                                borrow_info.immutable_count = 0;
-                            }
-                        }
-                    }
-                }
+                self.release_field_borrows(borrow_name);
             }
 
             // Clean up empty borrow entries
@@ -2681,12 +3929,6 @@ impl OwnershipTracker {
 
             // NEW: Clean up empty active borrow entries
             self.active_borrows.retain(|_, borrows| !borrows.is_empty());
-
-            // NEW: Clean up empty field borrow entries
-            for field_map in self.field_borrows.values_mut() {
-                field_map.retain(|_, info| !info.borrowers.is_empty());
-            }
-            self.field_borrows.retain(|_, fields| !fields.is_empty());
         }
     }
 
@@ -2777,6 +4019,9 @@ impl OwnershipTracker {
     }
 
     fn merge_states(&mut self, then_state: &TrackerState, else_state: &TrackerState) {
+        self.record_join_conflicts(then_state, else_state);
+        self.record_join_conflicts(else_state, then_state);
+
         // Merge ownership states aggressively (matching Rust's behavior)
         // A variable is considered moved if moved in ANY branch
         for (var, then_ownership) in &then_state.ownership {
@@ -2994,6 +4239,7 @@ mod tests {
             functions: vec![],
             ownership_graph: DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         }
     }
 
@@ -3012,6 +4258,7 @@ mod tests {
             variables: HashMap::new(),
             return_type: "void".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -3031,6 +4278,24 @@ mod tests {
         assert_eq!(errors.len(), 0);
     }
 
+    #[test]
+    fn test_error_kind_ignores_function_and_variable_names() {
+        assert_eq!(
+            ErrorKind::from_message(
+                "In function 'get_uninitialized_value': Use after move: variable 'x' has been moved"
+            ),
+            ErrorKind::UseAfterMove
+        );
+        assert_eq!(
+            ErrorKind::from_message("Cannot borrow 'uninitialized_ptr' mutably: already borrowed"),
+            ErrorKind::BorrowConflict
+        );
+        assert_eq!(
+            ErrorKind::from_message("Use of uninitialized variable 'x'"),
+            ErrorKind::Uninitialized
+        );
+    }
+
     #[test]
     fn test_ownership_tracker_initialization() {
         let mut tracker = OwnershipTracker::new();
@@ -3148,7 +4413,7 @@ mod tests {
     }
 
     #[test]
-    fn test_multiple_immutable_borrows_allowed() {
+    fn test_return_moved_local_reports_move_site() {
         let mut program = create_test_program();
         let mut func = create_test_function("test");
 
@@ -3156,92 +4421,1009 @@ mod tests {
             "x".to_string(),
             crate::ir::VariableInfo {
                 name: "x".to_string(),
-                ty: crate::ir::VariableType::Owned("int".to_string()),
+                ty: crate::ir::VariableType::Owned("Widget".to_string()),
                 ownership: OwnershipState::Owned,
                 lifetime: None,
                 is_parameter: false,
                 is_static: false,
                 scope_level: 0,
-                has_destructor: false,
+                has_destructor: true,
                 declaration_index: 0,
             },
         );
 
+        // consume(std::move(x)); return x;
         let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
-        block.statements.push(IrStatement::Borrow {
-            line: 0,
+        block.statements.push(IrStatement::Move {
+            line: 7,
             from: "x".to_string(),
-            to: "ref1".to_string(),
-            kind: BorrowKind::Immutable,
-            is_pointer: false,
+            to: "_moved_x".to_string(),
         });
-
-        block.statements.push(IrStatement::Borrow {
-            line: 0,
-            from: "x".to_string(),
-            to: "ref2".to_string(),
-            kind: BorrowKind::Immutable,
-            is_pointer: false,
+        block.statements.push(IrStatement::Return {
+            value: Some("x".to_string()),
+            line: 8,
         });
 
         program.functions.push(func);
 
-        let result = check_borrows(program);
-        assert!(result.is_ok());
-
-        let errors = result.unwrap();
-        assert_eq!(errors.len(), 0); // Multiple immutable borrows are OK
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].contains("Cannot return 'x' because it has been moved (moved at line 7)"),
+            "unexpected error: {}",
+            errors[0]
+        );
     }
 
-    #[test]
-    fn test_mutable_borrow_while_immutable_fails() {
-        let mut program = create_test_program();
-        let mut func = create_test_function("test");
-
+    fn outer_box(func: &mut IrFunction) {
         func.variables.insert(
-            "x".to_string(),
+            "b".to_string(),
             crate::ir::VariableInfo {
-                name: "x".to_string(),
-                ty: crate::ir::VariableType::Owned("int".to_string()),
+                name: "b".to_string(),
+                ty: crate::ir::VariableType::Owned("Box".to_string()),
                 ownership: OwnershipState::Owned,
                 lifetime: None,
                 is_parameter: false,
                 is_static: false,
                 scope_level: 0,
-                has_destructor: false,
+                has_destructor: true,
                 declaration_index: 0,
             },
         );
+    }
 
-        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+    #[test]
+    fn test_outer_variable_moved_in_loop_body() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+        outer_box(&mut func);
 
-        // First, immutable borrow
-        block.statements.push(IrStatement::Borrow {
-            line: 0,
-            from: "x".to_string(),
-            to: "ref1".to_string(),
-            kind: BorrowKind::Immutable,
-            is_pointer: false,
+        // Box b; while (cond) { consume(std::move(b)); }
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::EnterLoop);
+        block.statements.push(IrStatement::Move {
+            from: "b".to_string(),
+            to: "_moved_b".to_string(),
+            line: 5,
         });
-
-        // Then try mutable borrow (should fail)
-        block.statements.push(IrStatement::Borrow {
+        block.statements.push(IrStatement::CallExpr {
+            func: "consume".to_string(),
+            args: vec!["_moved_b".to_string()],
+            result: None,
+            receiver_is_temporary: false,
             line: 0,
-            from: "x".to_string(),
-            to: "mut_ref".to_string(),
-            kind: BorrowKind::Mutable,
-            is_pointer: false,
         });
+        block.statements.push(IrStatement::ExitLoop);
 
         program.functions.push(func);
 
-        let result = check_borrows(program);
-        assert!(result.is_ok());
-
-        let errors = result.unwrap();
-        assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("Cannot"));
-        assert!(errors[0].contains("mutable"));
+        let errors = check_borrows(program).unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Use after move in loop: variable 'b'")),
+            "outer variable moved in the loop body must be reported: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_outer_variable_reinitialized_before_move_in_loop_ok() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+        outer_box(&mut func);
+
+        // Box b; while (cond) { b = make(); consume(std::move(b)); }
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::EnterLoop);
+        block.statements.push(IrStatement::CallExpr {
+            func: "make".to_string(),
+            args: vec![],
+            result: Some("b".to_string()),
+            receiver_is_temporary: false,
+            line: 0,
+        });
+        block.statements.push(IrStatement::Move {
+            from: "b".to_string(),
+            to: "_moved_b".to_string(),
+            line: 5,
+        });
+        block.statements.push(IrStatement::ExitLoop);
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert!(
+            errors.is_empty(),
+            "re-initialized outer variable is fine: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_reference_into_temporary_receiver() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // int& ref = Builder().set(42).get_value();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::CallExpr {
+            func: "Builder::get_value".to_string(),
+            args: vec!["_temp_call_Builder_set".to_string()],
+            result: Some("ref".to_string()),
+            receiver_is_temporary: true,
+            line: 0,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Reference 'ref' is bound to a temporary object"));
+    }
+
+    fn refcell_call(method: &str, guard: &str) -> IrStatement {
+        IrStatement::CallExpr {
+            func: format!("rusty::RefCell::{}", method),
+            args: vec!["cell".to_string()],
+            result: Some(guard.to_string()),
+            receiver_is_temporary: false,
+            line: 0,
+        }
+    }
+
+    #[test]
+    fn test_refcell_borrow_mut_while_borrowed() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // auto r = cell.borrow(); auto w = cell.borrow_mut();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(refcell_call("borrow", "r"));
+        block.statements.push(refcell_call("borrow", "r2"));
+        block.statements.push(refcell_call("borrow_mut", "w"));
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("RefCell 'cell' is already immutably borrowed"));
+    }
+
+    #[test]
+    fn test_refcell_guard_released_at_scope_exit() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // { auto w = cell.borrow_mut(); } auto r = cell.borrow();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::EnterScope);
+        block.statements.push(refcell_call("borrow_mut", "w"));
+        block.statements.push(IrStatement::ExitScope);
+        block.statements.push(refcell_call("borrow", "r"));
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    }
+
+    fn lock_guard(guard: &str, mutex: &str) -> IrStatement {
+        IrStatement::CallExpr {
+            func: "std::lock_guard::lock_guard".to_string(),
+            args: vec![mutex.to_string()],
+            result: Some(guard.to_string()),
+            receiver_is_temporary: false,
+            line: 0,
+        }
+    }
+
+    #[test]
+    fn test_second_lock_guard_on_locked_mutex() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // std::lock_guard<std::mutex> g1(m); { std::lock_guard<std::mutex> g2(m); }
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(lock_guard("g1", "m"));
+        block.statements.push(IrStatement::EnterScope);
+        block.statements.push(lock_guard("g2", "m"));
+        block.statements.push(IrStatement::ExitScope);
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Mutex 'm' is already locked by 'g1'"));
+    }
+
+    #[test]
+    fn test_lock_guard_released_at_scope_exit() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // { std::lock_guard<std::mutex> g1(m); } std::lock_guard<std::mutex> g2(m); m.lock();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::EnterScope);
+        block.statements.push(lock_guard("g1", "m"));
+        block.statements.push(IrStatement::ExitScope);
+        block.statements.push(lock_guard("g2", "m"));
+        block.statements.push(IrStatement::CallExpr {
+            func: "std::mutex::lock".to_string(),
+            args: vec!["m".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("calling 'lock' again would deadlock"));
+    }
+
+    fn guard_call(method: &str, guard: &str) -> IrStatement {
+        IrStatement::CallExpr {
+            func: format!("std::unique_lock::{}", method),
+            args: vec![guard.to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        }
+    }
+
+    #[test]
+    fn test_relocking_guard_locks_mutex_again() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // std::unique_lock<std::mutex> g1(m); g1.unlock();
+        // { std::lock_guard<std::mutex> g2(m); g1.lock(); }
+        // g1.lock(); m.lock();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(lock_guard("g1", "m"));
+        block.statements.push(guard_call("unlock", "g1"));
+        block.statements.push(IrStatement::EnterScope);
+        block.statements.push(lock_guard("g2", "m"));
+        block.statements.push(guard_call("lock", "g1"));
+        block.statements.push(IrStatement::ExitScope);
+        block.statements.push(guard_call("lock", "g1"));
+        block.statements.push(IrStatement::CallExpr {
+            func: "std::mutex::lock".to_string(),
+            args: vec!["m".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 2, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Mutex 'm' is already locked by 'g2': calling 'g1.lock'"));
+        assert!(errors[1].contains("Mutex 'm' is already locked by 'g1'"));
+    }
+
+    #[test]
+    fn test_recursive_mutex_may_be_locked_again() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+        func.variables.insert(
+            "m".to_string(),
+            crate::ir::VariableInfo {
+                name: "m".to_string(),
+                ty: crate::ir::VariableType::Owned("std::recursive_mutex".to_string()),
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: false,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: true,
+                declaration_index: 0,
+            },
+        );
+
+        // std::recursive_mutex m; std::lock_guard<std::recursive_mutex> g1(m);
+        // std::lock_guard<std::recursive_mutex> g2(m); m.lock();
+        // std::lock_guard<std::recursive_timed_mutex> g3(t); t.lock();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(lock_guard("g1", "m"));
+        block.statements.push(lock_guard("g2", "m"));
+        block.statements.push(IrStatement::CallExpr {
+            func: "std::recursive_mutex::lock".to_string(),
+            args: vec!["m".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        });
+        block.statements.push(IrStatement::CallExpr {
+            func: "std::lock_guard<std::recursive_timed_mutex>::lock_guard".to_string(),
+            args: vec!["t".to_string()],
+            result: Some("g3".to_string()),
+            receiver_is_temporary: false,
+            line: 0,
+        });
+        block.statements.push(IrStatement::CallExpr {
+            func: "std::recursive_timed_mutex::lock".to_string(),
+            args: vec!["t".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    }
+
+    fn move_self_method(qualifier: crate::parser::MethodQualifier) -> IrFunction {
+        let mut func = create_test_function("Widget::take");
+        func.is_method = true;
+        func.method_qualifier = Some(qualifier);
+
+        // return std::move(*this);
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::Move {
+            from: "this".to_string(),
+            to: "_returned_this".to_string(),
+            line: 3,
+        });
+        func
+    }
+
+    #[test]
+    fn test_move_self_requires_rvalue_method() {
+        let mut program = create_test_program();
+        program
+            .functions
+            .push(move_self_method(crate::parser::MethodQualifier::NonConst));
+        program
+            .functions
+            .push(move_self_method(crate::parser::MethodQualifier::Const));
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 2, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Cannot move '*this' from &mut self method"));
+        assert!(errors[1].contains("Cannot move '*this' from const method"));
+    }
+
+    #[test]
+    fn test_move_self_in_rvalue_method_ok() {
+        let mut program = create_test_program();
+        program
+            .functions
+            .push(move_self_method(crate::parser::MethodQualifier::RvalueRef));
+
+        let errors = check_borrows(program).unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    }
+
+    #[test]
+    fn test_const_method_passes_member_as_mutable_reference() {
+        let mut program = create_test_program();
+
+        // void bump(int& x);
+        let mut bump = create_test_function("bump");
+        bump.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::MutableReference("int".to_string()),
+                ownership: OwnershipState::Borrowed(BorrowKind::Mutable),
+                lifetime: None,
+                is_parameter: true,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+        program.functions.push(bump);
+
+        for qualifier in [
+            crate::parser::MethodQualifier::Const,
+            crate::parser::MethodQualifier::NonConst,
+        ] {
+            // bump(this->count_);
+            let mut method = create_test_function("Counter::touch");
+            method.is_method = true;
+            method.method_qualifier = Some(qualifier);
+            let block = &mut method.cfg[petgraph::graph::NodeIndex::new(0)];
+            block.statements.push(IrStatement::CallExpr {
+                func: "bump".to_string(),
+                args: vec!["this.count_".to_string()],
+                result: None,
+                receiver_is_temporary: false,
+                line: 0,
+            });
+            program.functions.push(method);
+        }
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "Cannot pass field 'count_' to non-const reference parameter of 'bump' in const method"
+        ));
+    }
+
+    #[test]
+    fn test_mutable_reference_params_keep_overloads_apart() {
+        let param =
+            |name: &str, ty: crate::ir::VariableType, index: usize| crate::ir::VariableInfo {
+                name: name.to_string(),
+                ty,
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: true,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: index,
+            };
+        let mut program = create_test_program();
+
+        // void bump(int& x);
+        let mut bump = create_test_function("bump");
+        bump.variables.insert(
+            "x".to_string(),
+            param(
+                "x",
+                crate::ir::VariableType::MutableReference("int".to_string()),
+                0,
+            ),
+        );
+        program.functions.push(bump);
+
+        // void bump(const int& x, int by);
+        let mut bump_by = create_test_function("bump");
+        bump_by.variables.insert(
+            "x".to_string(),
+            param(
+                "x",
+                crate::ir::VariableType::Reference("int".to_string()),
+                0,
+            ),
+        );
+        bump_by.variables.insert(
+            "by".to_string(),
+            param("by", crate::ir::VariableType::Owned("int".to_string()), 1),
+        );
+        program.functions.push(bump_by);
+
+        // bump(this->count_); in a const method
+        let mut method = create_test_function("Counter::peek");
+        method.is_method = true;
+        method.method_qualifier = Some(crate::parser::MethodQualifier::Const);
+        let block = &mut method.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::CallExpr {
+            func: "bump".to_string(),
+            args: vec!["this.count_".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        });
+        program.functions.push(method);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Cannot pass field 'count_'"));
+    }
+
+    #[test]
+    fn test_multiple_immutable_borrows_allowed() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        func.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::Owned("int".to_string()),
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: false,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::Borrow {
+            line: 0,
+            from: "x".to_string(),
+            to: "ref1".to_string(),
+            kind: BorrowKind::Immutable,
+            is_pointer: false,
+        });
+
+        block.statements.push(IrStatement::Borrow {
+            line: 0,
+            from: "x".to_string(),
+            to: "ref2".to_string(),
+            kind: BorrowKind::Immutable,
+            is_pointer: false,
+        });
+
+        program.functions.push(func);
+
+        let result = check_borrows(program);
+        assert!(result.is_ok());
+
+        let errors = result.unwrap();
+        assert_eq!(errors.len(), 0); // Multiple immutable borrows are OK
+    }
+
+    #[test]
+    fn test_mutable_borrow_while_immutable_fails() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        func.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::Owned("int".to_string()),
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: false,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+
+        // First, immutable borrow
+        block.statements.push(IrStatement::Borrow {
+            line: 0,
+            from: "x".to_string(),
+            to: "ref1".to_string(),
+            kind: BorrowKind::Immutable,
+            is_pointer: false,
+        });
+
+        // Then try mutable borrow (should fail)
+        block.statements.push(IrStatement::Borrow {
+            line: 0,
+            from: "x".to_string(),
+            to: "mut_ref".to_string(),
+            kind: BorrowKind::Mutable,
+            is_pointer: false,
+        });
+
+        program.functions.push(func);
+
+        let result = check_borrows(program);
+        assert!(result.is_ok());
+
+        let errors = result.unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Cannot"));
+        assert!(errors[0].contains("mutable"));
+    }
+
+    #[test]
+    fn test_mutable_pointer_while_mutably_referenced_fails() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // int& r = x; int* p = &x; r = 1; *p = 2;
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::Borrow {
+            line: 1,
+            from: "x".to_string(),
+            to: "r".to_string(),
+            kind: BorrowKind::Mutable,
+            is_pointer: false,
+        });
+        block.statements.push(IrStatement::Borrow {
+            line: 2,
+            from: "x".to_string(),
+            to: "p".to_string(),
+            kind: BorrowKind::Mutable,
+            is_pointer: true,
+        });
+        for var in ["r", "p"] {
+            block.statements.push(IrStatement::UseVariable {
+                var: var.to_string(),
+                operation: "write".to_string(),
+            });
+        }
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(
+            errors[0].contains("Cannot create mutable reference to 'x': already mutably borrowed")
+        );
+    }
+
+    #[test]
+    fn test_moved_field_reinitialized_by_write() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // a = std::move(other.a); other.a = {}; use(other.a); use(other.b);
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        for field in ["a", "b"] {
+            block.statements.push(IrStatement::MoveField {
+                object: "other".to_string(),
+                field: field.to_string(),
+                to: format!("this.{}", field),
+                line: 1,
+            });
+        }
+        block.statements.push(IrStatement::UseField {
+            object: "other".to_string(),
+            field: "a".to_string(),
+            operation: "write".to_string(),
+        });
+        for field in ["a", "b"] {
+            block.statements.push(IrStatement::UseField {
+                object: "other".to_string(),
+                field: field.to_string(),
+                operation: "read".to_string(),
+            });
+        }
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Cannot read field 'other.b' because it has been moved"));
+    }
+
+    #[test]
+    fn test_assign_while_captured_by_reference_fails() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        func.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::Owned("int".to_string()),
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: false,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+
+        // auto f = [&x]() { ... }; x = 5; f();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::LambdaCapture {
+            lambda_var: "f".to_string(),
+            captures: vec![crate::ir::LambdaCaptureInfo {
+                name: "x".to_string(),
+                is_ref: true,
+            }],
+        });
+
+        block.statements.push(IrStatement::Assign {
+            lhs: "x".to_string(),
+            rhs: crate::ir::IrExpression::Literal("5".to_string()),
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::UseVariable {
+            var: "f".to_string(),
+            operation: "call".to_string(),
+        });
+
+        program.functions.push(func);
+
+        let result = check_borrows(program);
+        assert!(result.is_ok());
+
+        let errors = result.unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Cannot assign to 'x' because it is borrowed by: f"));
+    }
+
+    #[test]
+    fn test_move_object_while_field_borrowed_fails() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // auto& r = obj.data; Obj o2 = std::move(obj); use(r);
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::BorrowField {
+            object: "obj".to_string(),
+            field: "data".to_string(),
+            to: "r".to_string(),
+            kind: BorrowKind::Mutable,
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::Move {
+            from: "obj".to_string(),
+            to: "o2".to_string(),
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::UseVariable {
+            var: "r".to_string(),
+            operation: "read".to_string(),
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Cannot move 'obj' because field 'data' is borrowed by: r"));
+    }
+
+    #[test]
+    fn test_move_while_direct_reference_used_in_later_branch() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // auto& r = x; log(); T y = std::move(x); if (c) { log(); read(r); }
+        let read = |var: &str| IrStatement::UseVariable {
+            var: var.to_string(),
+            operation: "read".to_string(),
+        };
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::Borrow {
+            from: "x".to_string(),
+            to: "r".to_string(),
+            kind: BorrowKind::Immutable,
+            line: 2,
+            is_pointer: false,
+        });
+        block.statements.push(read("z"));
+        block.statements.push(IrStatement::Move {
+            from: "x".to_string(),
+            to: "y".to_string(),
+            line: 4,
+        });
+        block.statements.push(IrStatement::If {
+            then_branch: vec![read("z"), read("r")],
+            else_branch: None,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Cannot move 'x' because it is borrowed by: r")),
+            "r is still used after the move: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_missing_lifetime_suggests_elided_annotation() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("first");
+        func.return_type = "const int &".to_string();
+        func.source_line = 3;
+        func.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::Reference("const int &".to_string()),
+                ownership: OwnershipState::Borrowed(BorrowKind::Immutable),
+                lifetime: None,
+                is_parameter: true,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+        program.functions.push(func);
+
+        let mut safety_context = crate::parser::safety_annotations::SafetyContext::new();
+        safety_context.file_default = crate::parser::safety_annotations::SafetyMode::Safe;
+        let errors = check_lifetime_annotation_requirements(
+            &program,
+            &HeaderCache::new(),
+            &safety_context,
+            &crate::check::CheckOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].contains("add `// @lifetime: (&'a) -> &'a` with parameter 'x'"),
+            "{}",
+            errors[0]
+        );
+        assert_eq!(
+            Suggestion::from_message(&errors[0]),
+            Some(Suggestion {
+                text: "// @lifetime: (&'a) -> &'a".to_string(),
+                file: "test.cpp".to_string(),
+                line: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_non_const_method_while_immutably_borrowed() {
+        use crate::ir::VariableType;
+
+        let errors_for = |receiver: VariableType, method: &str, qualifier: MethodQualifier| {
+            let mut program = create_test_program();
+            program
+                .method_qualifiers
+                .insert(format!("Widget::{}", method), qualifier);
+            let mut func = create_test_function("test");
+            func.variables.insert(
+                "w".to_string(),
+                crate::analysis::test_fixtures::ir_variable("w", receiver, 0),
+            );
+
+            // const auto& r = w.peek(); w.<method>(); use(r);
+            let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+            block.statements.push(IrStatement::Borrow {
+                from: "w".to_string(),
+                to: "r".to_string(),
+                kind: BorrowKind::Immutable,
+                line: 0,
+                is_pointer: false,
+            });
+            block.statements.push(IrStatement::CallExpr {
+                func: format!("Widget::{}", method),
+                args: vec!["w".to_string()],
+                result: None,
+                receiver_is_temporary: false,
+                line: 0,
+            });
+            block.statements.push(IrStatement::UseVariable {
+                var: "r".to_string(),
+                operation: "read".to_string(),
+            });
+
+            program.functions.push(func);
+            check_borrows(program).unwrap()
+        };
+
+        let widget = || VariableType::Owned("Widget".to_string());
+        let errors = errors_for(widget(), "mutate", MethodQualifier::NonConst);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains(
+            "Cannot call non-const method 'Widget::mutate' on 'w' because it is immutably borrowed by: r"
+        ));
+        assert!(errors_for(widget(), "size", MethodQualifier::Const).is_empty());
+
+        // The qualifier is looked up under the receiver's class, without its
+        // template arguments, never by the method name alone
+        let template_ref = VariableType::Reference("const Widget<int> &".to_string());
+        assert_eq!(
+            errors_for(template_ref, "mutate", MethodQualifier::NonConst).len(),
+            1
+        );
+        let gadget = VariableType::Owned("Gadget".to_string());
+        assert!(errors_for(gadget, "mutate", MethodQualifier::NonConst).is_empty());
+    }
+
+    #[test]
+    fn test_move_object_after_field_borrow_dead_allowed() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // auto& r = obj.data; use(r); Obj o2 = std::move(obj);
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::BorrowField {
+            object: "obj".to_string(),
+            field: "data".to_string(),
+            to: "r".to_string(),
+            kind: BorrowKind::Immutable,
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::UseVariable {
+            var: "r".to_string(),
+            operation: "read".to_string(),
+        });
+
+        block.statements.push(IrStatement::Move {
+            from: "obj".to_string(),
+            to: "o2".to_string(),
+            line: 0,
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+    }
+
+    #[test]
+    fn test_mutable_field_borrow_while_object_immutably_borrowed() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // const auto& whole = obj; auto& f = obj.data; use(whole); use(f);
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::Borrow {
+            from: "obj".to_string(),
+            to: "whole".to_string(),
+            kind: BorrowKind::Immutable,
+            line: 0,
+            is_pointer: false,
+        });
+
+        block.statements.push(IrStatement::BorrowField {
+            object: "obj".to_string(),
+            field: "data".to_string(),
+            to: "f".to_string(),
+            kind: BorrowKind::Mutable,
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::UseVariable {
+            var: "whole".to_string(),
+            operation: "read".to_string(),
+        });
+        block.statements.push(IrStatement::UseVariable {
+            var: "f".to_string(),
+            operation: "read".to_string(),
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "Expected one conflict: {:?}", errors);
+        assert!(errors[0].contains(
+            "Cannot mutably borrow field 'obj.data': 'obj' is already immutably borrowed"
+        ));
+    }
+
+    #[test]
+    fn test_lambda_capture_of_object_while_field_borrowed() {
+        let mut program = create_test_program();
+        let mut func = create_test_function("test");
+
+        // auto& f = obj.data; auto g = [&obj]() { ... }; use(f); g();
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::BorrowField {
+            object: "obj".to_string(),
+            field: "data".to_string(),
+            to: "f".to_string(),
+            kind: BorrowKind::Mutable,
+            line: 0,
+        });
+
+        block.statements.push(IrStatement::LambdaCapture {
+            lambda_var: "g".to_string(),
+            captures: vec![crate::ir::LambdaCaptureInfo {
+                name: "obj".to_string(),
+                is_ref: true,
+            }],
+        });
+
+        block.statements.push(IrStatement::UseVariable {
+            var: "f".to_string(),
+            operation: "read".to_string(),
+        });
+        block.statements.push(IrStatement::UseVariable {
+            var: "g".to_string(),
+            operation: "call".to_string(),
+        });
+
+        program.functions.push(func);
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "Expected one conflict: {:?}", errors);
+        assert!(
+            errors[0].contains("Cannot mutably borrow 'obj': fields are already borrowed (data)")
+        );
     }
 
     #[test]
@@ -3695,6 +5877,206 @@ mod tests {
         assert!(errors[0].contains("'b'"));
         assert!(errors[0].contains("already mutably borrowed"));
     }
+
+    fn divergent_borrow_function(later_uses: &[&str]) -> IrFunction {
+        let borrow = |to: &str, kind: BorrowKind| IrStatement::Borrow {
+            line: 0,
+            from: "x".to_string(),
+            to: to.to_string(),
+            kind,
+            is_pointer: true,
+        };
+        let mut func = create_test_function("test");
+        let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+        block.statements.push(IrStatement::If {
+            then_branch: vec![borrow("p", BorrowKind::Mutable)],
+            else_branch: Some(vec![borrow("q", BorrowKind::Immutable)]),
+        });
+        for var in later_uses {
+            block.statements.push(IrStatement::UseVariable {
+                var: var.to_string(),
+                operation: "read".to_string(),
+            });
+        }
+        func
+    }
+
+    #[test]
+    fn test_divergent_borrow_kinds_across_if_else() {
+        let mut program = create_test_program();
+        program.functions.push(divergent_borrow_function(&["p", "q"]));
+
+        let errors = check_borrows(program).unwrap();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("Conflicting borrows of 'x' after branch"));
+    }
+
+    #[test]
+    fn test_divergent_borrows_dead_after_if_are_ok() {
+        let mut program = create_test_program();
+        program.functions.push(divergent_borrow_function(&["p"]));
+
+        let errors = check_borrows(program).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_duplicate_violations_collapsed() {
+        let errors = vec![
+            "In function 'f': Dangling reference: 'r' outlives 'x' (line 7)".to_string(),
+            "Dangling reference:  'r' outlives 'x' (line 7)".to_string(),
+            "Dangling reference: 'r' outlives 'x' (line 9)".to_string(),
+            "Use after move: variable 'x' has been moved".to_string(),
+            "Use after move: variable 'x' has been moved".to_string(),
+        ];
+
+        // Without a line there is no telling two violations apart, so both stay
+        let deduped = dedup_violations(errors);
+        assert_eq!(
+            deduped,
+            vec![
+                "In function 'f': Dangling reference: 'r' outlives 'x' (line 7)".to_string(),
+                "Dangling reference: 'r' outlives 'x' (line 9)".to_string(),
+                "Use after move: variable 'x' has been moved".to_string(),
+                "Use after move: variable 'x' has been moved".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_lineless_violation_in_two_functions_reported_twice() {
+        let mut program = create_test_program();
+        for name in ["first", "second"] {
+            let mut func = create_test_function(name);
+            for var in ["x", "y", "z"] {
+                func.variables.insert(
+                    var.to_string(),
+                    crate::ir::VariableInfo {
+                        name: var.to_string(),
+                        ty: crate::ir::VariableType::Owned("std::string".to_string()),
+                        ownership: OwnershipState::Owned,
+                        lifetime: None,
+                        is_parameter: false,
+                        is_static: false,
+                        scope_level: 0,
+                        has_destructor: true,
+                        declaration_index: 0,
+                    },
+                );
+            }
+            let block = &mut func.cfg[petgraph::graph::NodeIndex::new(0)];
+            block.statements.push(IrStatement::Move {
+                line: 0,
+                from: "x".to_string(),
+                to: "y".to_string(),
+            });
+            block.statements.push(IrStatement::Assign {
+                lhs: "z".to_string(),
+                rhs: crate::ir::IrExpression::Variable("x".to_string()),
+                line: 0,
+            });
+            program.functions.push(func);
+        }
+
+        let mut safety_context = crate::parser::safety_annotations::SafetyContext::new();
+        safety_context.file_default = crate::parser::safety_annotations::SafetyMode::Safe;
+        let (errors, _) = check_borrows_with_safety_context(
+            program,
+            HeaderCache::new(),
+            safety_context,
+            &crate::check::CheckOptions::default(),
+        )
+        .unwrap();
+
+        let moves: Vec<_> = errors
+            .iter()
+            .filter(|e| e.contains("Use after move: variable 'x' has been moved"))
+            .collect();
+        assert_eq!(moves.len(), 2, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_mutable_return_bound_to_const_parameter() {
+        use crate::parser::annotations::{FunctionSignature, LifetimeAnnotation};
+
+        let mut func = create_test_function("get");
+        func.variables.insert(
+            "x".to_string(),
+            crate::ir::VariableInfo {
+                name: "x".to_string(),
+                ty: crate::ir::VariableType::Reference("const int&".to_string()),
+                ownership: OwnershipState::Owned,
+                lifetime: None,
+                is_parameter: true,
+                is_static: false,
+                scope_level: 0,
+                has_destructor: false,
+                declaration_index: 0,
+            },
+        );
+        let mut sig = FunctionSignature {
+            name: "get".to_string(),
+            return_lifetime: Some(LifetimeAnnotation::MutRef("a".to_string())),
+            param_lifetimes: vec![Some(LifetimeAnnotation::MutRef("a".to_string()))],
+            lifetime_bounds: Vec::new(),
+            safety: None,
+            param_escapes: Vec::new(),
+            param_outputs: Vec::new(),
+        };
+
+        // The annotation claims &'a mut, but the declared parameter is const
+        let error = check_mutable_return_binding(&func, &sig).unwrap();
+        assert!(error.contains("bound to const parameter 'x'"));
+
+        // A mutable return with an immutable input annotation is also rejected
+        func.variables.get_mut("x").unwrap().ty =
+            crate::ir::VariableType::MutableReference("int&".to_string());
+        sig.param_lifetimes = vec![Some(LifetimeAnnotation::Ref("a".to_string()))];
+        assert!(check_mutable_return_binding(&func, &sig).is_some());
+
+        sig.param_lifetimes = vec![Some(LifetimeAnnotation::MutRef("a".to_string()))];
+        assert!(check_mutable_return_binding(&func, &sig).is_none());
+    }
+
+    #[test]
+    fn test_unsafe_blocks_suppressing_is_per_block() {
+        use crate::analysis::test_fixtures::{ir_function, ir_variable};
+        use crate::ir::VariableType;
+
+        let int = || VariableType::Owned("int".to_string());
+        let mut program = create_test_program();
+        program.functions.push(ir_function(
+            vec![ir_variable("a", int(), 0), ir_variable("b", int(), 1)],
+            vec![
+                IrStatement::Move {
+                    from: "a".to_string(),
+                    to: "b".to_string(),
+                    line: 1,
+                },
+                // Hides the use-after-move of 'a'
+                IrStatement::EnterUnsafe { line: 2 },
+                IrStatement::UseVariable {
+                    var: "a".to_string(),
+                    operation: "read".to_string(),
+                },
+                IrStatement::ExitUnsafe,
+                // 'b' is valid, so this block hides nothing
+                IrStatement::EnterUnsafe { line: 5 },
+                IrStatement::UseVariable {
+                    var: "b".to_string(),
+                    operation: "read".to_string(),
+                },
+                IrStatement::ExitUnsafe,
+            ],
+        ));
+
+        let mut safety_context = crate::parser::safety_annotations::SafetyContext::new();
+        safety_context.file_default = crate::parser::safety_annotations::SafetyMode::Safe;
+        let suppressing =
+            unsafe_blocks_suppressing(&program, &HeaderCache::new(), &safety_context, false);
+
+        assert_eq!(suppressing, HashSet::from([("test".to_string(), 2)]));
+    }
 }
 #[cfg(test)]
 mod scope_tests {
@@ -3718,6 +6100,7 @@ mod scope_tests {
             variables: HashMap::new(),
             return_type: "void".to_string(),
             source_file: "test.cpp".to_string(),
+            source_line: 1,
             is_method: false,
             method_qualifier: None,
             lifetime_params: HashMap::new(),
@@ -3754,6 +6137,7 @@ mod scope_tests {
             functions: vec![func],
             ownership_graph: petgraph::graph::DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         };
 
         let result = check_borrows(program);
@@ -3804,6 +6188,7 @@ mod scope_tests {
             functions: vec![func],
             ownership_graph: petgraph::graph::DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         };
 
         let result = check_borrows(program);
@@ -3835,6 +6220,7 @@ mod scope_tests {
             functions: vec![func],
             ownership_graph: petgraph::graph::DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         };
 
         let result = check_borrows(program);
@@ -3887,6 +6273,7 @@ mod scope_tests {
             functions: vec![func],
             ownership_graph: petgraph::graph::DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         };
 
         let result = check_borrows(program);
@@ -3922,6 +6309,7 @@ mod scope_tests {
             functions: vec![func],
             ownership_graph: petgraph::graph::DiGraph::new(),
             types_with_ref_members: std::collections::HashSet::new(),
+            method_qualifiers: HashMap::new(),
         };
 
         let result = check_borrows(program);
@@ -0,0 +1,372 @@
+//! Move-From-Const Detection
+//!
+//! `std::move` of a const object yields a `const T&&`, which binds to the
+//! copy constructor rather than the move constructor, so the "move" is a
+//! silent copy:
+//!
+//! ```cpp
+//! const std::vector<std::string> names = load();
+//! auto first = std::move(names[0]);  // WARNING: copies the element
+//!
+//! void Widget::take() const {
+//!     auto s = std::move(label_);    // WARNING: members are const here
+//! }
+//! ```
+//!
+//! The moved path is const when it is rooted in a const variable (an element
+//! or member of it, or the variable itself), in a const member, in any
+//! non-`mutable` member inside a const method, or in the target of a pointer
+//! to const. A whole
+//! const reference is left to the `move-from-reference` pass.
+
+use super::at_line;
+use crate::parser::ast_visitor::Class;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, MethodQualifier, MoveKind, Statement, Variable};
+use std::collections::HashSet;
+
+/// What is known to be const in the function being checked
+#[derive(Default)]
+struct ConstContext {
+    /// `const T v` locals and parameters
+    values: HashSet<String>,
+    /// `const T& r` locals and parameters
+    references: HashSet<String>,
+    /// `const T* p`: the pointee is const
+    pointers: HashSet<String>,
+    /// Members declared const in the enclosing class
+    members: HashSet<String>,
+    /// `mutable` members, which stay writable in a const method
+    mutable_members: HashSet<String>,
+    /// Inside a const method every other member is const
+    const_this: bool,
+}
+
+impl ConstContext {
+    fn declare(&mut self, var: &Variable) {
+        self.values.remove(&var.name);
+        self.references.remove(&var.name);
+        self.pointers.remove(&var.name);
+        if !var.is_const {
+            return;
+        }
+        if var.is_pointer {
+            self.pointers.insert(var.name.clone());
+        } else if var.is_reference {
+            self.references.insert(var.name.clone());
+        } else {
+            self.values.insert(var.name.clone());
+        }
+    }
+}
+
+/// Check a function for `std::move` of const paths
+pub fn check_move_from_const(
+    function: &Function,
+    classes: &[Class],
+    function_safety: SafetyMode,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut ctx = ConstContext::default();
+    for param in &function.parameters {
+        ctx.declare(param);
+    }
+    if function.is_method {
+        ctx.const_this = matches!(function.method_qualifier, Some(MethodQualifier::Const));
+        if let Some((class_name, _)) = function.name.rsplit_once("::") {
+            if let Some(class) = classes.iter().find(|class| {
+                class.name == class_name || class_name.ends_with(&format!("::{}", class.name))
+            }) {
+                ctx.members = class
+                    .members
+                    .iter()
+                    .filter(|member| member.is_const && !member.is_pointer)
+                    .map(|member| member.name.clone())
+                    .collect();
+                ctx.mutable_members = class
+                    .members
+                    .iter()
+                    .filter(|member| member.is_mutable)
+                    .map(|member| member.name.clone())
+                    .collect();
+            }
+        }
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        &mut ctx,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    ctx: &mut ConstContext,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => {
+                *unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                *unsafe_depth = unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            Statement::VariableDecl(var) => {
+                ctx.declare(var);
+                continue;
+            }
+            _ => {}
+        }
+
+        // Skip checking in unsafe blocks
+        if *unsafe_depth > 0 {
+            continue;
+        }
+
+        match stmt {
+            Statement::Assignment { rhs, location, .. } => {
+                check_expr(rhs, func_name, location.line, ctx, errors);
+            }
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => check_expr(target, func_name, location.line, ctx, errors),
+            Statement::Return(Some(expr)) => check_expr(expr, func_name, 0, ctx, errors),
+            Statement::FunctionCall { args, location, .. } => {
+                for arg in args {
+                    check_expr(arg, func_name, location.line, ctx, errors);
+                }
+            }
+            Statement::ExpressionStatement { expr, location } => {
+                check_expr(expr, func_name, location.line, ctx, errors);
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                check_expr(condition, func_name, location.line, ctx, errors);
+                check_statements(then_branch, func_name, ctx, unsafe_depth, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, ctx, unsafe_depth, errors);
+                }
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                check_expr(condition, func_name, location.line, ctx, errors);
+                for case in cases {
+                    check_statements(&case.statements, func_name, ctx, unsafe_depth, errors);
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, ctx, unsafe_depth, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find `std::move` anywhere inside an expression
+fn check_expr(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    ctx: &ConstContext,
+    errors: &mut Vec<String>,
+) {
+    match expr {
+        Expression::Move {
+            inner,
+            kind: MoveKind::StdMove,
+        } => {
+            if let Some(path) = const_path(inner, ctx, false) {
+                errors.push(format!(
+                    "In function '{}': warning: std::move of const '{}'{} copies instead of moving",
+                    func_name,
+                    path,
+                    at_line(line)
+                ));
+            }
+            check_expr(inner, func_name, line, ctx, errors);
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. } => check_expr(inner, func_name, line, ctx, errors),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                check_expr(arg, func_name, line, ctx, errors);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            check_expr(left, func_name, line, ctx, errors);
+            check_expr(right, func_name, line, ctx, errors);
+        }
+        Expression::MemberAccess { object, .. } => check_expr(object, func_name, line, ctx, errors),
+        Expression::ArraySubscript { array, index } => {
+            check_expr(array, func_name, line, ctx, errors);
+            check_expr(index, func_name, line, ctx, errors);
+        }
+        _ => {}
+    }
+}
+
+/// The rendered path if `expr` denotes a const object. `nested` is set for
+/// the base of an element or member access, where a const reference makes
+/// the part const too.
+fn const_path(expr: &Expression, ctx: &ConstContext, nested: bool) -> Option<String> {
+    match expr {
+        Expression::Variable(name) => (ctx.values.contains(name)
+            || (nested && ctx.references.contains(name)))
+        .then(|| name.clone()),
+        Expression::MemberAccess { object, field } => {
+            if matches!(object.as_ref(), Expression::Variable(this) if this == "this") {
+                let const_member = (ctx.const_this && !ctx.mutable_members.contains(field))
+                    || ctx.members.contains(field);
+                return const_member.then(|| field.clone());
+            }
+            const_path(object, ctx, true).map(|base| format!("{}.{}", base, field))
+        }
+        Expression::ArraySubscript { array, .. } => {
+            const_path(array, ctx, true).map(|base| format!("{}[...]", base))
+        }
+        Expression::FunctionCall { name, args } => {
+            let method = name.rsplit("::").next().unwrap_or(name);
+            let receiver = args.first()?;
+            let base = const_path(receiver, ctx, true)?;
+            match method {
+                "operator[]" => Some(format!("{}[...]", base)),
+                "at" => Some(format!("{}.at(...)", base)),
+                "front" | "back" => Some(format!("{}.{}()", base, method)),
+                _ => None,
+            }
+        }
+        Expression::Dereference(inner) => match inner.as_ref() {
+            Expression::Variable(ptr) if ctx.pointers.contains(ptr) => Some(format!("*{}", ptr)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str) -> Expression {
+        Expression::Variable(name.to_string())
+    }
+
+    fn moved(inner: Expression) -> Expression {
+        Expression::Move {
+            inner: Box::new(inner),
+            kind: MoveKind::StdMove,
+        }
+    }
+
+    fn ctx_with_const_vector() -> ConstContext {
+        let mut ctx = ConstContext::default();
+        ctx.values.insert("v".to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_move_of_const_vector_element() {
+        let element = Expression::FunctionCall {
+            name: "std::vector<std::string>::operator[]".to_string(),
+            args: vec![var("v"), Expression::Literal("0".to_string())],
+        };
+        let mut errors = Vec::new();
+        check_expr(
+            &moved(element),
+            "f",
+            3,
+            &ctx_with_const_vector(),
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("warning: std::move of const 'v[...]'"));
+    }
+
+    #[test]
+    fn test_move_of_non_const_element_ok() {
+        let element = Expression::ArraySubscript {
+            array: Box::new(var("w")),
+            index: Box::new(Expression::Literal("0".to_string())),
+        };
+        let mut errors = Vec::new();
+        check_expr(
+            &moved(element),
+            "f",
+            3,
+            &ctx_with_const_vector(),
+            &mut errors,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_move_of_member_in_const_method() {
+        let ctx = ConstContext {
+            const_this: true,
+            ..Default::default()
+        };
+        let member = Expression::MemberAccess {
+            object: Box::new(var("this")),
+            field: "label_".to_string(),
+        };
+        let mut errors = Vec::new();
+        check_expr(&moved(member), "Widget::take", 5, &ctx, &mut errors);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("const 'label_'"));
+    }
+
+    #[test]
+    fn test_move_of_mutable_member_in_const_method_ok() {
+        let ctx = ConstContext {
+            const_this: true,
+            mutable_members: HashSet::from(["cache_".to_string()]),
+            ..Default::default()
+        };
+        let member = Expression::MemberAccess {
+            object: Box::new(var("this")),
+            field: "cache_".to_string(),
+        };
+        let mut errors = Vec::new();
+        check_expr(&moved(member), "Widget::take", 5, &ctx, &mut errors);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_move_in_return_names_no_line() {
+        let mut errors = Vec::new();
+        check_expr(
+            &moved(var("v")),
+            "f",
+            0,
+            &ctx_with_const_vector(),
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(!errors[0].contains("line"), "{}", errors[0]);
+    }
+}
@@ -0,0 +1,271 @@
+//! Same-Call Move Detection
+//!
+//! Flags a call whose argument list both moves a variable and reads it in a
+//! sibling argument:
+//!
+//! ```cpp
+//! f(std::move(x), g(x));     // ERROR: x is read in the call that moves it
+//! f(std::move(x), x.size()); // ERROR
+//! ```
+//!
+//! Argument evaluation order is unspecified, but whichever order the compiler
+//! picks, the read observes a value that is being moved out of the same call.
+
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::HashSet;
+
+/// Check a function for calls that move and read the same variable
+pub fn check_move_in_call(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => {
+                *unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                *unsafe_depth = unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            _ => {}
+        }
+
+        // Skip checking in unsafe blocks
+        if *unsafe_depth > 0 {
+            continue;
+        }
+
+        match stmt {
+            Statement::Assignment { lhs, rhs, .. } => {
+                check_expr(lhs, func_name, errors);
+                check_expr(rhs, func_name, errors);
+            }
+            Statement::ReferenceBinding { target, .. } => check_expr(target, func_name, errors),
+            Statement::Return(Some(expr)) => check_expr(expr, func_name, errors),
+            Statement::FunctionCall { name, args, .. } => {
+                check_call(name, args, func_name, errors);
+            }
+            Statement::ExpressionStatement { expr, .. } => check_expr(expr, func_name, errors),
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_expr(condition, func_name, errors);
+                check_statements(then_branch, func_name, unsafe_depth, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, unsafe_depth, errors);
+                }
+            }
+            Statement::Switch {
+                condition, cases, ..
+            } => {
+                check_expr(condition, func_name, errors);
+                for case in cases {
+                    check_statements(&case.statements, func_name, unsafe_depth, errors);
+                }
+            }
+            Statement::Block(stmts) => check_statements(stmts, func_name, unsafe_depth, errors),
+            _ => {}
+        }
+    }
+}
+
+/// Find calls anywhere inside an expression
+fn check_expr(expr: &Expression, func_name: &str, errors: &mut Vec<String>) {
+    match expr {
+        Expression::FunctionCall { name, args } => check_call(name, args, func_name, errors),
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => check_expr(inner, func_name, errors),
+        Expression::BinaryOp { left, right, .. } => {
+            check_expr(left, func_name, errors);
+            check_expr(right, func_name, errors);
+        }
+        Expression::MemberAccess { object, .. } => check_expr(object, func_name, errors),
+        Expression::PointerArithmetic { pointer, .. } => check_expr(pointer, func_name, errors),
+        Expression::ArraySubscript { array, index } => {
+            check_expr(array, func_name, errors);
+            check_expr(index, func_name, errors);
+        }
+        _ => {}
+    }
+}
+
+/// Compare each argument's moves against the reads of its sibling arguments
+fn check_call(callee: &str, args: &[Expression], func_name: &str, errors: &mut Vec<String>) {
+    let mut reported = HashSet::new();
+    for (i, arg) in args.iter().enumerate() {
+        let mut moved = HashSet::new();
+        collect_moves(arg, &mut moved);
+        for var in moved {
+            let read_elsewhere = args.iter().enumerate().any(|(j, other)| {
+                if i == j {
+                    return false;
+                }
+                let mut reads = HashSet::new();
+                collect_reads(other, &mut reads);
+                reads.contains(&var)
+            });
+            if read_elsewhere && reported.insert(var.clone()) {
+                errors.push(format!(
+                    "In function '{}': use of '{}' in the same call that moves it (call to '{}')",
+                    func_name, var, callee
+                ));
+            }
+        }
+    }
+
+    // Nested calls are checked on their own argument lists
+    for arg in args {
+        check_expr(arg, func_name, errors);
+    }
+}
+
+/// Variables moved by `std::move(x)` / `rusty::move(x)` anywhere in an argument
+fn collect_moves(expr: &Expression, moved: &mut HashSet<String>) {
+    match expr {
+        Expression::Move { inner, .. } => {
+            if let Expression::Variable(name) = inner.as_ref() {
+                moved.insert(name.clone());
+            } else {
+                collect_moves(inner, moved);
+            }
+        }
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_moves(arg, moved);
+            }
+        }
+        Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. } => collect_moves(inner, moved),
+        Expression::BinaryOp { left, right, .. } => {
+            collect_moves(left, moved);
+            collect_moves(right, moved);
+        }
+        Expression::MemberAccess { object, .. } => collect_moves(object, moved),
+        _ => {}
+    }
+}
+
+/// Variables read anywhere in an argument, excluding the operand of a move
+fn collect_reads(expr: &Expression, reads: &mut HashSet<String>) {
+    match expr {
+        Expression::Variable(name) => {
+            reads.insert(name.clone());
+        }
+        Expression::Move { inner, .. } if !matches!(inner.as_ref(), Expression::Variable(_)) => {
+            collect_reads(inner, reads);
+        }
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_reads(arg, reads);
+            }
+        }
+        Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. } => collect_reads(inner, reads),
+        Expression::BinaryOp { left, right, .. } => {
+            collect_reads(left, reads);
+            collect_reads(right, reads);
+        }
+        Expression::MemberAccess { object, .. } => collect_reads(object, reads),
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => {
+            collect_reads(pointer, reads);
+            if let Some(offset) = offset {
+                collect_reads(offset, reads);
+            }
+        }
+        Expression::ArraySubscript { array, index } => {
+            collect_reads(array, reads);
+            collect_reads(index, reads);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str) -> Expression {
+        Expression::Variable(name.to_string())
+    }
+
+    fn call(name: &str, args: Vec<Expression>) -> Expression {
+        Expression::FunctionCall {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    fn moved(name: &str) -> Expression {
+        Expression::Move {
+            inner: Box::new(var(name)),
+            kind: crate::parser::MoveKind::StdMove,
+        }
+    }
+
+    #[test]
+    fn test_move_and_nested_read_in_same_call() {
+        let mut errors = Vec::new();
+        check_call(
+            "f",
+            &[moved("x"), call("g", vec![var("x")])],
+            "test",
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("use of 'x' in the same call that moves it"));
+    }
+
+    #[test]
+    fn test_move_of_different_variable_ok() {
+        let mut errors = Vec::new();
+        check_call(
+            "f",
+            &[moved("x"), call("g", vec![var("y")])],
+            "test",
+            &mut errors,
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_nested_call_checked() {
+        let mut errors = Vec::new();
+        let inner = call("h", vec![moved("x"), var("x")]);
+        check_expr(&call("f", vec![inner]), "test", &mut errors);
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -11,10 +11,21 @@
 //! Rules:
 //! - Dereferencing a MaybeNull pointer is an error in @safe code
 //! - Null checks (if (ptr != nullptr)) narrow the state to NonNull in the true branch
-
+//! - Smart pointers (unique_ptr, shared_ptr) are only reported when provably Null:
+//!   default-constructed, assigned nullptr, or reset() without a new value
+//! - The exception is a `shared_ptr` obtained from `weak_ptr::lock()`: it is empty
+//!   whenever the object has expired, so it must be checked before dereferencing
+//! - `gsl::not_null<T*>` is always NonNull: its constructor rejects nullptr
+//! - `std::optional` is tracked the same way, with "disengaged" as Null:
+//!   default construction, `= std::nullopt` and `reset()` disengage it,
+//!   `emplace()` and assigning a value engage it, and `if (opt)` /
+//!   `opt.has_value()` narrow it. `value()`, `*opt` and `opt->` on a provably
+//!   disengaged optional are reported
+
+use crate::parser::ast_visitor::is_gsl_not_null_type;
 use crate::parser::safety_annotations::SafetyMode;
 use crate::parser::{Expression, Function, Statement};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Represents the null state of a pointer variable
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,6 +57,16 @@ pub struct NullStateTracker {
     states: HashMap<String, NullState>,
     /// Stack of scopes for handling blocks
     scope_stack: Vec<HashMap<String, NullState>>,
+    /// Variables declared with a smart pointer type
+    smart_pointers: HashSet<String>,
+    /// Smart pointers currently holding the result of `weak_ptr::lock()`
+    locked_weak_ptrs: HashSet<String>,
+    /// Variables declared `gsl::not_null<T*>`
+    not_null: HashSet<String>,
+    /// Variables declared `std::optional<T>` (Null = disengaged)
+    optionals: HashSet<String>,
+    /// Optionals still engaged, but whose contained value was moved out
+    moved_values: HashSet<String>,
 }
 
 impl NullStateTracker {
@@ -53,14 +74,29 @@ impl NullStateTracker {
         NullStateTracker {
             states: HashMap::new(),
             scope_stack: Vec::new(),
+            smart_pointers: HashSet::new(),
+            locked_weak_ptrs: HashSet::new(),
+            not_null: HashSet::new(),
+            optionals: HashSet::new(),
+            moved_values: HashSet::new(),
         }
     }
 
-    /// Set the null state for a variable
+    /// Set the null state for a variable; a `gsl::not_null` stays NonNull
     pub fn set_state(&mut self, var: &str, state: NullState) {
+        let state = if self.not_null.contains(var) {
+            NullState::NonNull
+        } else {
+            state
+        };
         self.states.insert(var.to_string(), state);
     }
 
+    pub fn mark_not_null(&mut self, var: &str) {
+        self.not_null.insert(var.to_string());
+        self.states.insert(var.to_string(), NullState::NonNull);
+    }
+
     /// Get the null state for a variable
     pub fn get_state(&self, var: &str) -> NullState {
         self.states
@@ -87,6 +123,8 @@ impl NullStateTracker {
             let current = self.get_state(var);
             self.set_state(var, current.merge(*state));
         }
+        // Moved out on either path counts as moved out
+        self.moved_values.extend(other.moved_values.iter().cloned());
     }
 
     /// Create a snapshot for branch analysis
@@ -94,8 +132,61 @@ impl NullStateTracker {
         NullStateTracker {
             states: self.states.clone(),
             scope_stack: Vec::new(),
+            smart_pointers: self.smart_pointers.clone(),
+            locked_weak_ptrs: self.locked_weak_ptrs.clone(),
+            not_null: self.not_null.clone(),
+            optionals: self.optionals.clone(),
+            moved_values: self.moved_values.clone(),
+        }
+    }
+
+    /// Record that a variable holds a smart pointer
+    pub fn mark_smart_pointer(&mut self, var: &str) {
+        self.smart_pointers.insert(var.to_string());
+    }
+
+    /// Check if a variable holds a smart pointer
+    pub fn is_smart_pointer(&self, var: &str) -> bool {
+        self.smart_pointers.contains(var)
+    }
+
+    /// Record that a variable holds a `std::optional`
+    pub fn mark_optional(&mut self, var: &str) {
+        self.optionals.insert(var.to_string());
+    }
+
+    /// Check if a variable holds a `std::optional`
+    pub fn is_optional(&self, var: &str) -> bool {
+        self.optionals.contains(var)
+    }
+
+    /// Record whether the value inside an optional was moved out
+    pub fn set_value_moved(&mut self, var: &str, moved: bool) {
+        if moved {
+            self.moved_values.insert(var.to_string());
+        } else {
+            self.moved_values.remove(var);
+        }
+    }
+
+    /// Check if the value inside an optional was moved out
+    pub fn is_value_moved(&self, var: &str) -> bool {
+        self.moved_values.contains(var)
+    }
+
+    /// Record whether a smart pointer was last assigned from `weak_ptr::lock()`
+    pub fn set_locked_weak_ptr(&mut self, var: &str, locked: bool) {
+        if locked {
+            self.locked_weak_ptrs.insert(var.to_string());
+        } else {
+            self.locked_weak_ptrs.remove(var);
         }
     }
+
+    /// Check if a smart pointer holds a `weak_ptr::lock()` result
+    pub fn is_locked_weak_ptr(&self, var: &str) -> bool {
+        self.locked_weak_ptrs.contains(var)
+    }
 }
 
 /// Check for null safety violations in a parsed function
@@ -113,20 +204,28 @@ pub fn check_null_safety(function: &Function, function_safety: SafetyMode) -> Ve
     // Initialize parameters as MaybeNull (we don't know what caller passes)
     // Exception: parameters with NonNull annotation would be NonNull
     for param in &function.parameters {
-        if is_pointer_type(&param.type_name) {
+        if is_gsl_not_null_type(&param.type_name) {
+            tracker.mark_not_null(&param.name);
+        } else if is_pointer_type(&param.type_name) {
             // Check for _Nonnull or similar annotation
             if param.type_name.contains("_Nonnull") || param.type_name.contains("nonnull") {
                 tracker.set_state(&param.name, NullState::NonNull);
             } else {
                 tracker.set_state(&param.name, NullState::MaybeNull);
             }
+        } else if is_optional_type(&param.type_name) {
+            tracker.mark_optional(&param.name);
+            tracker.set_state(&param.name, NullState::MaybeNull);
+        } else if is_smart_pointer_type(&param.type_name) {
+            tracker.mark_smart_pointer(&param.name);
+            tracker.set_state(&param.name, NullState::MaybeNull);
         }
     }
 
     // Analyze each statement
     for stmt in &function.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -161,22 +260,45 @@ fn analyze_statement_null_safety(
     match stmt {
         Statement::VariableDecl(var) => {
             // Track pointer variables
-            if is_pointer_type(&var.type_name) {
+            if is_gsl_not_null_type(&var.type_name) {
+                tracker.mark_not_null(&var.name);
+            } else if is_pointer_type(&var.type_name) {
                 // Variable declarations don't have initializers in this AST,
                 // so we'll track based on subsequent assignments
                 // Default to MaybeNull unless we can prove otherwise
                 tracker.set_state(&var.name, NullState::MaybeNull);
+            } else if is_optional_type(&var.type_name) && !var.is_reference {
+                // Disengaged without an initializer. An initializer that is
+                // modeled shows up as the following Assignment; one that is
+                // not (`std::optional<T> o(a, b)`) could engage it.
+                tracker.mark_optional(&var.name);
+                tracker.set_state(&var.name, declared_state(var));
+            } else if is_smart_pointer_type(&var.type_name) && !var.is_reference {
+                // Same for smart pointers: empty when default-constructed
+                tracker.mark_smart_pointer(&var.name);
+                tracker.set_state(&var.name, declared_state(var));
             }
         }
 
         Statement::Assignment { lhs, rhs, .. } => {
             // First check for null safety violations in rhs
             check_expr_null_safety(rhs, tracker, func_name, errors);
+            // `*p = v` through a smart pointer dereferences p
+            if matches!(lhs, Expression::FunctionCall { .. }) {
+                check_expr_null_safety(lhs, tracker, func_name, errors);
+            }
+            record_optional_moves(rhs, tracker);
 
             // Update null state on assignment
             if let Some(var_name) = extract_var_name(lhs) {
-                let state = determine_null_state_from_expr(rhs, tracker);
+                let state = if tracker.is_optional(&var_name) {
+                    determine_optional_state_from_expr(rhs, tracker)
+                } else {
+                    determine_null_state_from_expr(rhs, tracker)
+                };
                 tracker.set_state(&var_name, state);
+                tracker.set_value_moved(&var_name, false);
+                tracker.set_locked_weak_ptr(&var_name, is_weak_ptr_lock(rhs));
             }
         }
 
@@ -185,11 +307,15 @@ fn analyze_statement_null_safety(
             check_expr_null_safety(target, tracker, func_name, errors);
         }
 
-        Statement::FunctionCall { args, .. } => {
+        Statement::FunctionCall { name, args, .. } => {
+            check_smart_pointer_deref(name, args, tracker, func_name, errors);
+            check_optional_access(name, args, tracker, func_name, errors);
             // Check all arguments
             for arg in args {
                 check_expr_null_safety(arg, tracker, func_name, errors);
+                record_optional_moves(arg, tracker);
             }
+            apply_smart_pointer_update(name, args, tracker);
         }
 
         Statement::Return(Some(expr)) => {
@@ -198,6 +324,10 @@ fn analyze_statement_null_safety(
 
         Statement::ExpressionStatement { expr, .. } => {
             check_expr_null_safety(expr, tracker, func_name, errors);
+            record_optional_moves(expr, tracker);
+            if let Expression::FunctionCall { name, args } = expr {
+                apply_smart_pointer_update(name, args, tracker);
+            }
         }
 
         Statement::If {
@@ -212,12 +342,19 @@ fn analyze_statement_null_safety(
             // Check condition itself
             check_expr_null_safety(condition, tracker, func_name, errors);
 
+            // Narrow the checked pointer in each branch:
+            // `if (p)` / `if (p != nullptr)` -> then: NonNull, else: Null
+            // `if (!p)` / `if (p == nullptr)` -> then: Null, else: NonNull
+            let (then_state, else_state) = if narrowed_to_nonnull {
+                (NullState::NonNull, NullState::Null)
+            } else {
+                (NullState::Null, NullState::NonNull)
+            };
+
             // Analyze then branch with potentially narrowed state
             let mut then_tracker = tracker.snapshot();
             if let Some(ref var) = narrowed_var {
-                if narrowed_to_nonnull {
-                    then_tracker.set_state(var, NullState::NonNull);
-                }
+                then_tracker.set_state(var, then_state);
             }
             for stmt in then_branch {
                 analyze_statement_null_safety(stmt, &mut then_tracker, func_name, errors);
@@ -226,10 +363,7 @@ fn analyze_statement_null_safety(
             // Analyze else branch
             let mut else_tracker = tracker.snapshot();
             if let Some(ref var) = narrowed_var {
-                if !narrowed_to_nonnull {
-                    // In else branch of "if (ptr != nullptr)", ptr could be null
-                    else_tracker.set_state(var, NullState::MaybeNull);
-                }
+                else_tracker.set_state(var, else_state);
             }
             if let Some(else_stmts) = else_branch {
                 for stmt in else_stmts {
@@ -237,10 +371,15 @@ fn analyze_statement_null_safety(
                 }
             }
 
-            // Merge states from both branches
-            tracker.merge_branch(&then_tracker);
-            if else_branch.is_some() {
-                tracker.merge_branch(&else_tracker);
+            if else_branch.is_none() && ends_with_return(then_branch) {
+                // `if (!p) return;` - only the fall-through path continues
+                *tracker = else_tracker;
+            } else {
+                // Merge states from both branches
+                tracker.merge_branch(&then_tracker);
+                if else_branch.is_some() {
+                    tracker.merge_branch(&else_tracker);
+                }
             }
         }
         Statement::Switch {
@@ -309,7 +448,7 @@ fn check_expr_null_safety(
                         }
                         NullState::Null => {
                             errors.push(format!(
-                                "In function '{}': Dereferencing null pointer '{}'",
+                                "In function '{}': dereference of null pointer '{}'",
                                 func_name, var_name
                             ));
                         }
@@ -357,7 +496,9 @@ fn check_expr_null_safety(
             check_expr_null_safety(object, tracker, func_name, errors);
         }
 
-        Expression::FunctionCall { args, .. } => {
+        Expression::FunctionCall { name, args } => {
+            check_smart_pointer_deref(name, args, tracker, func_name, errors);
+            check_optional_access(name, args, tracker, func_name, errors);
             // Check all arguments
             for arg in args {
                 check_expr_null_safety(arg, tracker, func_name, errors);
@@ -385,6 +526,194 @@ fn check_expr_null_safety(
     }
 }
 
+/// Report `*p` / `p->m` on a smart pointer that is provably null.
+/// Smart pointers are only flagged when Null, never when MaybeNull, unless
+/// they hold an unchecked `weak_ptr::lock()` result.
+fn check_smart_pointer_deref(
+    name: &str,
+    args: &[Expression],
+    tracker: &NullStateTracker,
+    func_name: &str,
+    errors: &mut Vec<String>,
+) {
+    let method = method_base_name(name);
+    if method != "operator*" && method != "operator->" {
+        return;
+    }
+    let Some(receiver) = args.first() else {
+        return;
+    };
+    // wp.lock()->m() dereferences the locked pointer without any check
+    if let Some(lock_args) = weak_ptr_lock_args(receiver) {
+        let weak = lock_args.first().and_then(extract_var_name_from_expr);
+        errors.push(format!(
+            "In function '{}': dereference of possibly-empty locked weak_ptr '{}' - check the result of lock() first",
+            func_name,
+            weak.as_deref().unwrap_or("lock()")
+        ));
+        return;
+    }
+    if let Some(var_name) = extract_var_name_from_expr(receiver) {
+        if !tracker.is_smart_pointer(&var_name) {
+            return;
+        }
+        match tracker.get_state(&var_name) {
+            NullState::Null => errors.push(format!(
+                "In function '{}': dereference of null pointer '{}'",
+                func_name, var_name
+            )),
+            NullState::MaybeNull if tracker.is_locked_weak_ptr(&var_name) => {
+                errors.push(format!(
+                    "In function '{}': dereference of possibly-empty locked weak_ptr '{}' - check the result of lock() first",
+                    func_name, var_name
+                ))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Report `value()`, `*opt` and `opt->` on a provably disengaged optional
+fn check_optional_access(
+    name: &str,
+    args: &[Expression],
+    tracker: &NullStateTracker,
+    func_name: &str,
+    errors: &mut Vec<String>,
+) {
+    if !matches!(method_base_name(name), "value" | "operator*" | "operator->") {
+        return;
+    }
+    let Some(var_name) = args.first().and_then(extract_var_name_from_expr) else {
+        return;
+    };
+    if !tracker.is_optional(&var_name) {
+        return;
+    }
+    if tracker.get_state(&var_name) == NullState::Null {
+        errors.push(format!(
+            "In function '{}': access to disengaged optional '{}' - it was reset or never given a value",
+            func_name, var_name
+        ));
+    } else if tracker.is_value_moved(&var_name) {
+        errors.push(format!(
+            "In function '{}': use after move: the value in optional '{}' was moved out - assign or emplace a new value first",
+            func_name, var_name
+        ));
+    }
+}
+
+/// Mark optionals whose value `expr` moves out: `std::move(*o)`,
+/// `std::move(o.value())` and `std::move(o).value()`
+fn record_optional_moves(expr: &Expression, tracker: &mut NullStateTracker) {
+    match expr {
+        Expression::Move { inner, .. } => {
+            if let Some(var_name) = optional_value_access(inner, tracker) {
+                tracker.set_value_moved(&var_name, true);
+            }
+            record_optional_moves(inner, tracker);
+        }
+        Expression::FunctionCall { name, args } => {
+            if let (Some(Expression::Move { inner, .. }), "value" | "operator*") =
+                (args.first(), method_base_name(name))
+            {
+                if let Some(var_name) = extract_var_name_from_expr(inner) {
+                    if tracker.is_optional(&var_name) {
+                        tracker.set_value_moved(&var_name, true);
+                    }
+                }
+            }
+            for arg in args {
+                record_optional_moves(arg, tracker);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            record_optional_moves(left, tracker);
+            record_optional_moves(right, tracker);
+        }
+        Expression::Cast { inner, .. } => record_optional_moves(inner, tracker),
+        _ => {}
+    }
+}
+
+/// The optional whose value `expr` reads: `*o` or `o.value()`
+fn optional_value_access(expr: &Expression, tracker: &NullStateTracker) -> Option<String> {
+    let Expression::FunctionCall { name, args } = expr else {
+        return None;
+    };
+    if !matches!(method_base_name(name), "value" | "operator*") {
+        return None;
+    }
+    args.first()
+        .and_then(extract_var_name)
+        .filter(|var_name| tracker.is_optional(var_name))
+}
+
+/// `wp.lock()`, possibly wrapped in a cast or move
+fn is_weak_ptr_lock(expr: &Expression) -> bool {
+    weak_ptr_lock_args(expr).is_some()
+}
+
+/// Arguments of a `weak_ptr::lock()` call (the receiver first)
+fn weak_ptr_lock_args(expr: &Expression) -> Option<&[Expression]> {
+    match expr {
+        Expression::FunctionCall { name, args }
+            if method_base_name(name) == "lock" && name.contains("weak_ptr") =>
+        {
+            Some(args)
+        }
+        Expression::Cast { inner, .. } | Expression::Move { inner, .. } => {
+            weak_ptr_lock_args(inner)
+        }
+        _ => None,
+    }
+}
+
+/// Update smart pointer state for `p.reset(...)` and `p = value` calls,
+/// and optional state for `o.reset()`, `o.emplace(...)` and `o = value`
+fn apply_smart_pointer_update(name: &str, args: &[Expression], tracker: &mut NullStateTracker) {
+    let Some(var_name) = args.first().and_then(extract_var_name) else {
+        return;
+    };
+    if tracker.is_optional(&var_name) {
+        let state = match (method_base_name(name), args.get(1)) {
+            ("reset", _) => NullState::Null,
+            ("emplace", _) => NullState::NonNull,
+            ("operator=", Some(value)) => determine_optional_state_from_expr(value, tracker),
+            _ => return,
+        };
+        tracker.set_state(&var_name, state);
+        tracker.set_value_moved(&var_name, false);
+        return;
+    }
+    if !tracker.is_smart_pointer(&var_name) {
+        return;
+    }
+    match method_base_name(name) {
+        "reset" => {
+            let state = match args.get(1) {
+                None => NullState::Null,
+                Some(value) => determine_null_state_from_expr(value, tracker),
+            };
+            tracker.set_state(&var_name, state);
+            tracker.set_locked_weak_ptr(&var_name, false);
+        }
+        "operator=" => {
+            if let Some(value) = args.get(1) {
+                let state = determine_null_state_from_expr(value, tracker);
+                tracker.set_state(&var_name, state);
+                tracker.set_locked_weak_ptr(&var_name, is_weak_ptr_lock(value));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Last `::` segment of a (possibly qualified) function name
+fn method_base_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 /// Determine the null state from an expression
 fn determine_null_state_from_expr(expr: &Expression, tracker: &NullStateTracker) -> NullState {
     match expr {
@@ -404,10 +733,13 @@ fn determine_null_state_from_expr(expr: &Expression, tracker: &NullStateTracker)
         Expression::Variable(name) => tracker.get_state(name),
 
         // Function call - assume MaybeNull unless we know better
-        Expression::FunctionCall { name, .. } => {
+        Expression::FunctionCall { name, args } => {
             // Some functions are known to return non-null
             if is_known_nonnull_function(name) {
                 NullState::NonNull
+            } else if is_smart_pointer_type(name) && args.iter().all(is_null_expr) {
+                // unique_ptr<T>() or unique_ptr<T>(nullptr) constructs an empty pointer
+                NullState::Null
             } else {
                 NullState::MaybeNull
             }
@@ -424,47 +756,111 @@ fn determine_null_state_from_expr(expr: &Expression, tracker: &NullStateTracker)
     }
 }
 
-/// Check if a condition performs null checking and return the variable being checked
+/// Determine whether an optional is engaged after being assigned `expr`:
+/// `std::nullopt` and `std::optional<T>()` disengage it, another optional
+/// passes on its state, and a plain value engages it
+fn determine_optional_state_from_expr(expr: &Expression, tracker: &NullStateTracker) -> NullState {
+    match expr {
+        Expression::Variable(name) if is_nullopt(name) => NullState::Null,
+        Expression::Variable(name) if tracker.is_optional(name) => tracker.get_state(name),
+        Expression::FunctionCall { name, args } if is_optional_type(name) => {
+            if args
+                .iter()
+                .all(|arg| matches!(arg, Expression::Variable(v) if is_nullopt(v)))
+            {
+                NullState::Null
+            } else {
+                NullState::NonNull
+            }
+        }
+        Expression::FunctionCall { name, .. } if name.contains("make_optional") => {
+            NullState::NonNull
+        }
+        // A call may itself return an optional
+        Expression::FunctionCall { .. } => NullState::MaybeNull,
+        Expression::Cast { inner, .. } | Expression::Move { inner, .. } => {
+            determine_optional_state_from_expr(inner, tracker)
+        }
+        Expression::Variable(_)
+        | Expression::Literal(_)
+        | Expression::StringLiteral(_)
+        | Expression::BinaryOp { .. } => NullState::NonNull,
+        _ => NullState::MaybeNull,
+    }
+}
+
+/// `std::nullopt`, however it was qualified
+fn is_nullopt(name: &str) -> bool {
+    name == "nullopt" || name.ends_with("::nullopt")
+}
+
+/// Check if a condition performs null checking and return the variable being checked.
+/// The flag is true when the then-branch proves the pointer non-null.
 fn check_null_narrowing(condition: &Expression) -> (Option<String>, bool) {
     match condition {
         // ptr != nullptr or ptr != NULL or ptr != 0
         Expression::BinaryOp { left, right, op } if op == "!=" => {
-            if is_null_expr(right) {
-                if let Some(var) = extract_var_name_from_expr(left) {
-                    return (Some(var), true); // narrowed to NonNull in true branch
-                }
-            }
-            if is_null_expr(left) {
-                if let Some(var) = extract_var_name_from_expr(right) {
-                    return (Some(var), true);
-                }
-            }
-            (None, false)
+            (null_comparison_operand(left, right), true)
         }
 
-        // ptr == nullptr (in true branch, it's definitely null)
+        // ptr == nullptr / !ptr (in true branch, it's definitely null)
         Expression::BinaryOp { left, right, op } if op == "==" => {
-            if is_null_expr(right) {
-                if let Some(var) = extract_var_name_from_expr(left) {
-                    return (Some(var), false); // narrowed to Null in true branch
-                }
-            }
-            if is_null_expr(left) {
-                if let Some(var) = extract_var_name_from_expr(right) {
-                    return (Some(var), false);
-                }
+            (null_comparison_operand(left, right), false)
+        }
+
+        // Smart pointer comparisons go through operator== / operator!=
+        Expression::FunctionCall { name, args } if args.len() == 2 => {
+            match method_base_name(name) {
+                "operator!=" => (null_comparison_operand(&args[0], &args[1]), true),
+                "operator==" => (null_comparison_operand(&args[0], &args[1]), false),
+                _ => (None, false),
             }
-            (None, false)
         }
 
         // Just a variable as condition: if (ptr) { ... }
         // This is equivalent to ptr != nullptr
         Expression::Variable(name) => (Some(name.clone()), true),
 
+        // if (smart_ptr) { ... } goes through operator bool
+        Expression::FunctionCall { name, .. }
+            if matches!(method_base_name(name), "operator bool" | "has_value") =>
+        {
+            (narrowable_var_name(condition), true)
+        }
+
         _ => (None, false),
     }
 }
 
+/// If one side of a comparison is null, return the pointer on the other side
+fn null_comparison_operand(left: &Expression, right: &Expression) -> Option<String> {
+    if is_null_expr(right) {
+        narrowable_var_name(left)
+    } else if is_null_expr(left) {
+        narrowable_var_name(right)
+    } else {
+        None
+    }
+}
+
+/// Variable whose nullness a condition tests: `p`, or `p` in
+/// `p.operator bool()` / `p.has_value()`
+fn narrowable_var_name(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::FunctionCall { name, args }
+            if matches!(method_base_name(name), "operator bool" | "has_value") =>
+        {
+            args.first().and_then(extract_var_name_from_expr)
+        }
+        _ => extract_var_name_from_expr(expr),
+    }
+}
+
+/// Check if a branch always leaves the function
+fn ends_with_return(stmts: &[Statement]) -> bool {
+    matches!(stmts.last(), Some(Statement::Return(_)))
+}
+
 /// Check if an expression is a null expression
 fn is_null_expr(expr: &Expression) -> bool {
     match expr {
@@ -485,6 +881,38 @@ fn is_pointer_type(type_name: &str) -> bool {
     type_name.contains('*') && !type_name.contains("&")
 }
 
+/// Check if a type is `std::optional<T>`
+fn is_optional_type(type_name: &str) -> bool {
+    let type_name = type_name.trim_start_matches("const ");
+    type_name.starts_with("std::optional<") || type_name.starts_with("optional<")
+}
+
+/// Check if a type is an owning smart pointer type: `std::unique_ptr<T>` or
+/// `std::shared_ptr<T>` itself (or a constructor of one), not a type that
+/// merely has one as a template argument such as
+/// `std::vector<std::unique_ptr<T>>`
+fn is_smart_pointer_type(type_name: &str) -> bool {
+    let outer = type_name
+        .split('<')
+        .next()
+        .unwrap_or(type_name)
+        .trim()
+        .trim_start_matches("const ")
+        .trim_start_matches("::");
+    let name = outer.rsplit("::").next().unwrap_or(outer);
+    matches!(name, "unique_ptr" | "shared_ptr") && (outer == name || outer.starts_with("std::"))
+}
+
+/// State of a just-declared optional or smart pointer: empty unless it has
+/// an initializer
+fn declared_state(var: &crate::parser::Variable) -> NullState {
+    if var.has_initializer {
+        NullState::MaybeNull
+    } else {
+        NullState::Null
+    }
+}
+
 /// Extract variable name from a target expression (for assignments)
 fn extract_var_name(expr: &Expression) -> Option<String> {
     match expr {
@@ -518,6 +946,7 @@ fn is_known_nonnull_function(name: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::MoveKind;
 
     #[test]
     fn test_null_state_merge() {
@@ -570,6 +999,317 @@ mod tests {
         assert!(!is_null_literal("ptr"));
     }
 
+    #[test]
+    fn test_smart_pointer_reset_and_reassign() {
+        let mut tracker = NullStateTracker::new();
+        tracker.mark_smart_pointer("p");
+        tracker.set_state("p", NullState::NonNull);
+        let receiver = Expression::Variable("p".to_string());
+
+        apply_smart_pointer_update(
+            "std::unique_ptr<int>::reset",
+            std::slice::from_ref(&receiver),
+            &mut tracker,
+        );
+        assert_eq!(tracker.get_state("p"), NullState::Null);
+
+        let mut errors = Vec::new();
+        check_smart_pointer_deref(
+            "std::unique_ptr<int>::operator*",
+            std::slice::from_ref(&receiver),
+            &tracker,
+            "f",
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("dereference of null pointer 'p'"));
+
+        let fresh = Expression::FunctionCall {
+            name: "std::make_unique".to_string(),
+            args: vec![],
+        };
+        apply_smart_pointer_update("operator=", &[receiver.clone(), fresh], &mut tracker);
+        assert_eq!(tracker.get_state("p"), NullState::NonNull);
+    }
+
+    #[test]
+    fn test_optional_reset_then_value() {
+        let mut tracker = NullStateTracker::new();
+        tracker.mark_optional("o");
+        tracker.set_state("o", NullState::Null);
+        let receiver = Expression::Variable("o".to_string());
+        let access = |tracker: &NullStateTracker| {
+            let mut errors = Vec::new();
+            check_optional_access(
+                "std::optional<Box>::value",
+                std::slice::from_ref(&receiver),
+                tracker,
+                "f",
+                &mut errors,
+            );
+            errors
+        };
+
+        apply_smart_pointer_update(
+            "std::optional<Box>::emplace",
+            std::slice::from_ref(&receiver),
+            &mut tracker,
+        );
+        assert!(access(&tracker).is_empty());
+
+        apply_smart_pointer_update(
+            "std::optional<Box>::reset",
+            std::slice::from_ref(&receiver),
+            &mut tracker,
+        );
+        let errors = access(&tracker);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("access to disengaged optional 'o'"));
+
+        let value = Expression::Literal("42".to_string());
+        apply_smart_pointer_update("operator=", &[receiver.clone(), value], &mut tracker);
+        assert_eq!(tracker.get_state("o"), NullState::NonNull);
+
+        let nullopt = Expression::Variable("std::nullopt".to_string());
+        apply_smart_pointer_update("operator=", &[receiver.clone(), nullopt], &mut tracker);
+        assert_eq!(tracker.get_state("o"), NullState::Null);
+
+        let has_value = Expression::FunctionCall {
+            name: "std::optional<Box>::has_value".to_string(),
+            args: vec![receiver.clone()],
+        };
+        assert_eq!(
+            check_null_narrowing(&has_value),
+            (Some("o".to_string()), true)
+        );
+    }
+
+    #[test]
+    fn test_optional_value_moved_out() {
+        let mut tracker = NullStateTracker::new();
+        tracker.mark_optional("o");
+        tracker.set_state("o", NullState::NonNull);
+        let receiver = Expression::Variable("o".to_string());
+        let access = |tracker: &NullStateTracker| {
+            let mut errors = Vec::new();
+            check_optional_access(
+                "std::optional<Box>::value",
+                std::slice::from_ref(&receiver),
+                tracker,
+                "f",
+                &mut errors,
+            );
+            errors
+        };
+
+        // std::move(*o)
+        record_optional_moves(
+            &Expression::Move {
+                inner: Box::new(Expression::FunctionCall {
+                    name: "operator*".to_string(),
+                    args: vec![receiver.clone()],
+                }),
+                kind: MoveKind::StdMove,
+            },
+            &mut tracker,
+        );
+        let errors = access(&tracker);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("use after move: the value in optional 'o' was moved out"));
+
+        apply_smart_pointer_update(
+            "std::optional<Box>::emplace",
+            std::slice::from_ref(&receiver),
+            &mut tracker,
+        );
+        assert!(access(&tracker).is_empty());
+
+        // std::move(o).value()
+        record_optional_moves(
+            &Expression::FunctionCall {
+                name: "std::optional<Box>::value".to_string(),
+                args: vec![Expression::Move {
+                    inner: Box::new(receiver.clone()),
+                    kind: MoveKind::StdMove,
+                }],
+            },
+            &mut tracker,
+        );
+        assert_eq!(access(&tracker).len(), 1);
+    }
+
+    #[test]
+    fn test_smart_pointer_maybe_null_not_reported() {
+        let mut tracker = NullStateTracker::new();
+        tracker.mark_smart_pointer("p");
+        let mut errors = Vec::new();
+        check_smart_pointer_deref(
+            "operator->",
+            &[Expression::Variable("p".to_string())],
+            &tracker,
+            "f",
+            &mut errors,
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_unchecked_weak_ptr_lock_reported() {
+        let mut tracker = NullStateTracker::new();
+        tracker.mark_smart_pointer("sp");
+        let lock = Expression::FunctionCall {
+            name: "std::weak_ptr<Widget>::lock".to_string(),
+            args: vec![Expression::Variable("wp".to_string())],
+        };
+        let assign = Statement::Assignment {
+            lhs: Expression::Variable("sp".to_string()),
+            rhs: lock.clone(),
+            location: crate::parser::SourceLocation {
+                file: "test.cpp".to_string(),
+                line: 1,
+                column: 1,
+                end_line: 0,
+                end_column: 0,
+            },
+        };
+        let mut errors = Vec::new();
+        analyze_statement_null_safety(&assign, &mut tracker, "f", &mut errors);
+        assert_eq!(tracker.get_state("sp"), NullState::MaybeNull);
+
+        let sp = Expression::Variable("sp".to_string());
+        check_smart_pointer_deref(
+            "std::shared_ptr<Widget>::operator->",
+            std::slice::from_ref(&sp),
+            &tracker,
+            "f",
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("dereference of possibly-empty locked weak_ptr 'sp'"));
+
+        // After `if (sp)` narrows it, the dereference is fine
+        errors.clear();
+        tracker.set_state("sp", NullState::NonNull);
+        check_smart_pointer_deref(
+            "std::shared_ptr<Widget>::operator->",
+            std::slice::from_ref(&sp),
+            &tracker,
+            "f",
+            &mut errors,
+        );
+        assert!(errors.is_empty());
+
+        // wp.lock()->m() is never checked
+        check_smart_pointer_deref(
+            "std::shared_ptr<Widget>::operator->",
+            &[lock],
+            &tracker,
+            "f",
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("locked weak_ptr 'wp'"));
+    }
+
+    #[test]
+    fn test_not_null_parameter_deref_accepted() {
+        use crate::analysis::test_fixtures::{self, location, variable};
+
+        // int read(gsl::not_null<int*> p, int* q) { int a = *p; int b = *q; }
+        let deref = |var: &str, into: &str| Statement::Assignment {
+            lhs: Expression::Variable(into.to_string()),
+            rhs: Expression::Dereference(Box::new(Expression::Variable(var.to_string()))),
+            location: location(1),
+        };
+        let function = Function {
+            return_type: "int".to_string(),
+            safety_annotation: Some(SafetyMode::Safe),
+            has_explicit_safety_annotation: true,
+            ..test_fixtures::function(
+                "read",
+                vec![
+                    variable("p", "gsl::not_null<int *>"),
+                    variable("q", "int *"),
+                ],
+                vec![deref("p", "a"), deref("q", "b")],
+            )
+        };
+
+        let errors = check_null_safety(&function, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("'q'"));
+    }
+
+    #[test]
+    fn test_declared_smart_pointers_and_optionals() {
+        let decl = |name: &str, type_name: &str, has_initializer: bool| {
+            Statement::VariableDecl(crate::parser::Variable {
+                has_initializer,
+                ..crate::analysis::test_fixtures::variable(name, type_name)
+            })
+        };
+        let mut tracker = NullStateTracker::new();
+        let mut errors = Vec::new();
+        for stmt in [
+            // std::unique_ptr<int> p(new int(1)); std::unique_ptr<int> q;
+            decl("p", "std::unique_ptr<int>", true),
+            decl("q", "std::unique_ptr<int>", false),
+            // std::optional<Box> o(std::in_place, 1); std::optional<Box> e;
+            decl("o", "std::optional<Box>", true),
+            decl("e", "std::optional<Box>", false),
+            decl("v", "std::vector<std::unique_ptr<int>>", false),
+        ] {
+            analyze_statement_null_safety(&stmt, &mut tracker, "f", &mut errors);
+        }
+        assert!(errors.is_empty());
+        assert_eq!(tracker.get_state("p"), NullState::MaybeNull);
+        assert_eq!(tracker.get_state("q"), NullState::Null);
+        assert_eq!(tracker.get_state("o"), NullState::MaybeNull);
+        assert_eq!(tracker.get_state("e"), NullState::Null);
+        assert!(!tracker.is_smart_pointer("v"));
+
+        assert!(is_smart_pointer_type("const std::shared_ptr<Widget> &"));
+        assert!(is_smart_pointer_type("std::unique_ptr<int>::unique_ptr"));
+        assert!(!is_smart_pointer_type("std::map<int, std::shared_ptr<W>>"));
+        assert!(!is_smart_pointer_type("my::unique_ptr<int>"));
+    }
+
+    #[test]
+    fn test_null_narrowing_direction() {
+        let p = || Box::new(Expression::Variable("p".to_string()));
+        assert_eq!(
+            check_null_narrowing(&Expression::Variable("p".to_string())),
+            (Some("p".to_string()), true)
+        );
+        // `!p` is represented as `p == nullptr`
+        let not_p = Expression::BinaryOp {
+            left: p(),
+            op: "==".to_string(),
+            right: Box::new(Expression::Nullptr),
+        };
+        assert_eq!(check_null_narrowing(&not_p), (Some("p".to_string()), false));
+        let smart_ne = Expression::FunctionCall {
+            name: "std::operator!=".to_string(),
+            args: vec![Expression::Variable("p".to_string()), Expression::Nullptr],
+        };
+        assert_eq!(
+            check_null_narrowing(&smart_ne),
+            (Some("p".to_string()), true)
+        );
+        let not_smart = Expression::BinaryOp {
+            left: Box::new(Expression::FunctionCall {
+                name: "std::unique_ptr<int>::operator bool".to_string(),
+                args: vec![Expression::Variable("p".to_string())],
+            }),
+            op: "==".to_string(),
+            right: Box::new(Expression::Nullptr),
+        };
+        assert_eq!(
+            check_null_narrowing(&not_smart),
+            (Some("p".to_string()), false)
+        );
+    }
+
     #[test]
     fn test_is_pointer_type() {
         assert!(is_pointer_type("int*"));
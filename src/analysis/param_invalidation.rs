@@ -0,0 +1,373 @@
+//! Reference Parameters Invalidated Through a Container Parameter
+//!
+//! A function that takes both a mutable reference and a mutable container of
+//! the same element type can't know whether the reference points into the
+//! container. Growing or shrinking the container may reallocate its storage,
+//! leaving the reference dangling:
+//!
+//! ```cpp
+//! void append_twice(std::vector<int>& v, int& x) {
+//!     v.push_back(x);
+//!     v.push_back(x); // WARNING: x may refer to an element of v
+//! }
+//!
+//! append_twice(v, v[0]); // reads freed memory on reallocation
+//! ```
+//!
+//! Most callers pass unrelated objects, so this check is off by default.
+
+use super::at_line;
+use super::raii_tracking::RaiiTracker;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement, Variable};
+
+/// Check for uses of a reference parameter after a container parameter it may
+/// point into has been modified
+pub fn check_param_invalidation(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let containers: Vec<(&Variable, String)> = function
+        .parameters
+        .iter()
+        .filter(|param| param.is_reference && !param.is_const)
+        .filter_map(|param| Some((param, element_type(&param.type_name)?)))
+        .collect();
+    if containers.is_empty() {
+        return errors;
+    }
+    let references: Vec<(&Variable, &Variable)> = function
+        .parameters
+        .iter()
+        .filter(|param| param.is_reference && !param.is_rvalue_reference && !param.is_const)
+        .flat_map(|param| {
+            containers
+                .iter()
+                .filter(move |(_, element)| base_type(&param.type_name) == element)
+                .map(move |(container, _)| (param, *container))
+        })
+        .collect();
+    if references.is_empty() {
+        return errors;
+    }
+
+    let mut events = Vec::new();
+    collect_events(&function.body, &mut events);
+
+    let mut reported: Vec<&str> = Vec::new();
+    for (reference, container) in references {
+        let mut modified: Option<(&str, u32)> = None;
+        for event in &events {
+            match event {
+                Event::Use { var, line } if var == &reference.name => {
+                    let Some((method, modified_line)) = modified else {
+                        continue;
+                    };
+                    if reported.contains(&reference.name.as_str()) {
+                        break;
+                    }
+                    errors.push(format!(
+                        "In function '{}': warning: reference parameter '{}' used{} may be invalidated by '{}.{}()'{} if it refers to an element of '{}'",
+                        function.name,
+                        reference.name,
+                        at_line(*line),
+                        container.name,
+                        method,
+                        at_line(modified_line),
+                        container.name
+                    ));
+                    reported.push(&reference.name);
+                    break;
+                }
+                Event::Modify {
+                    container: name,
+                    method,
+                    line,
+                } if name == &container.name && modified.is_none() => {
+                    modified = Some((method, *line));
+                }
+                _ => {}
+            }
+        }
+    }
+    errors
+}
+
+enum Event {
+    /// A variable is read or written
+    Use { var: String, line: u32 },
+    /// A container is resized by a method call
+    Modify {
+        container: String,
+        method: String,
+        line: u32,
+    },
+}
+
+/// Events in source order; within one statement the uses come first, since
+/// arguments are evaluated before the call (`v.push_back(x)` is fine)
+fn collect_events(stmts: &[Statement], events: &mut Vec<Event>) {
+    for stmt in stmts {
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::Assignment { lhs, rhs, location } => (vec![lhs, rhs], location.line),
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => (vec![target], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            Statement::Return(Some(expr)) => (vec![expr], 0),
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                for arg in args {
+                    collect_uses(arg, location.line, events);
+                }
+                collect_modification(name, args, location.line, events);
+                continue;
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                collect_uses(condition, location.line, events);
+                collect_events(then_branch, events);
+                if let Some(else_stmts) = else_branch {
+                    collect_events(else_stmts, events);
+                }
+                continue;
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                collect_uses(condition, location.line, events);
+                for case in cases {
+                    collect_events(&case.statements, events);
+                }
+                continue;
+            }
+            Statement::Block(stmts) => {
+                collect_events(stmts, events);
+                continue;
+            }
+            _ => continue,
+        };
+        for expr in &exprs {
+            collect_uses(expr, line, events);
+        }
+        for expr in exprs {
+            collect_modifications(expr, line, events);
+        }
+    }
+}
+
+fn collect_uses(expr: &Expression, line: u32, events: &mut Vec<Event>) {
+    match expr {
+        Expression::Variable(name) => events.push(Event::Use {
+            var: name.clone(),
+            line,
+        }),
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => collect_uses(inner, line, events),
+        Expression::MemberAccess { object, .. } => collect_uses(object, line, events),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_uses(arg, line, events);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            collect_uses(left, line, events);
+            collect_uses(right, line, events);
+        }
+        Expression::ArraySubscript { array, index } => {
+            collect_uses(array, line, events);
+            collect_uses(index, line, events);
+        }
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => {
+            collect_uses(pointer, line, events);
+            if let Some(offset) = offset {
+                collect_uses(offset, line, events);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_modifications(expr: &Expression, line: u32, events: &mut Vec<Event>) {
+    match expr {
+        Expression::FunctionCall { name, args } => {
+            for arg in args {
+                collect_modifications(arg, line, events);
+            }
+            collect_modification(name, args, line, events);
+        }
+        Expression::Cast { inner, .. } => collect_modifications(inner, line, events),
+        Expression::BinaryOp { left, right, .. } => {
+            collect_modifications(left, line, events);
+            collect_modifications(right, line, events);
+        }
+        _ => {}
+    }
+}
+
+/// `v.push_back(...)`, `v.erase(...)` and other calls that may reallocate or
+/// remove elements of the receiver
+fn collect_modification(name: &str, args: &[Expression], line: u32, events: &mut Vec<Event>) {
+    let method = name.rsplit("::").next().unwrap_or(name);
+    if !RaiiTracker::is_container_modifying_method(method) {
+        return;
+    }
+    if let Some(Expression::Variable(container)) = args.first() {
+        events.push(Event::Modify {
+            container: container.clone(),
+            method: method.to_string(),
+            line,
+        });
+    }
+}
+
+/// Element type of a vector-like container type, whose element references
+/// are invalidated by reallocation: `std::vector<int> &` gives `int`
+fn element_type(type_name: &str) -> Option<String> {
+    let base = base_type(type_name);
+    let (container, args) = base.split_once('<')?;
+    let container = container.rsplit("::").next().unwrap_or(container);
+    if !matches!(container, "vector" | "deque" | "Vec") {
+        return None;
+    }
+    let args = args.strip_suffix('>')?;
+    let mut depth = 0;
+    let end = args
+        .char_indices()
+        .find(|&(_, c)| {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => return true,
+                _ => {}
+            }
+            false
+        })
+        .map_or(args.len(), |(i, _)| i);
+    Some(args[..end].trim().to_string())
+}
+
+/// `int` for `const int &`
+fn base_type(type_name: &str) -> &str {
+    type_name
+        .trim()
+        .trim_start_matches("const ")
+        .trim_end_matches(['&', ' '])
+        .trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location, variable};
+
+    fn param(name: &str, type_name: &str) -> Variable {
+        Variable {
+            is_reference: true,
+            ..variable(name, type_name)
+        }
+    }
+
+    fn push_back(container: &str, value: &str, line: u32) -> Statement {
+        Statement::FunctionCall {
+            name: "std::vector<int>::push_back".to_string(),
+            args: vec![
+                Expression::Variable(container.to_string()),
+                Expression::Variable(value.to_string()),
+            ],
+            location: location(line),
+        }
+    }
+
+    fn function(parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+        test_fixtures::function("append_twice", parameters, body)
+    }
+
+    #[test]
+    fn test_reference_used_after_container_grows() {
+        let func = function(
+            vec![param("v", "std::vector<int> &"), param("x", "int &")],
+            vec![push_back("v", "x", 2), push_back("v", "x", 3)],
+        );
+
+        let errors = check_param_invalidation(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains(
+            "reference parameter 'x' used at line 3 may be invalidated by 'v.push_back()' at line 2"
+        ));
+    }
+
+    #[test]
+    fn test_reference_returned_after_container_grows() {
+        let func = function(
+            vec![param("v", "std::vector<int> &"), param("x", "int &")],
+            vec![
+                push_back("v", "x", 2),
+                Statement::Return(Some(Expression::Variable("x".to_string()))),
+            ],
+        );
+
+        let errors = check_param_invalidation(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(
+            errors[0].contains("'x' used may be invalidated by 'v.push_back()' at line 2"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_unrelated_or_const_parameters_are_not_flagged() {
+        // Different element type
+        let func = function(
+            vec![param("v", "std::vector<double> &"), param("x", "int &")],
+            vec![push_back("v", "x", 2), push_back("v", "x", 3)],
+        );
+        assert!(check_param_invalidation(&func, SafetyMode::Safe).is_empty());
+
+        // Reference only used before the container changes
+        let func = function(
+            vec![param("v", "std::vector<int> &"), param("x", "int &")],
+            vec![push_back("v", "x", 2)],
+        );
+        assert!(check_param_invalidation(&func, SafetyMode::Safe).is_empty());
+
+        // Const reference
+        let mut x = param("x", "const int &");
+        x.is_const = true;
+        let func = function(
+            vec![param("v", "std::vector<int> &"), x],
+            vec![push_back("v", "x", 2), push_back("v", "x", 3)],
+        );
+        assert!(check_param_invalidation(&func, SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_element_type() {
+        assert_eq!(element_type("std::vector<int> &").as_deref(), Some("int"));
+        assert_eq!(
+            element_type("std::vector<std::pair<int, int>, Alloc> &").as_deref(),
+            Some("std::pair<int, int>")
+        );
+        assert_eq!(element_type("std::map<int, int> &"), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Pessimizing Move in Return
+//!
+//! Returning a local by name lets the compiler construct it directly in the
+//! caller's storage (NRVO), and falls back to an implicit move otherwise.
+//! Wrapping it in `std::move` turns the operand into an xvalue, which rules
+//! out the elision and always costs a move:
+//!
+//! ```cpp
+//! std::string build() {
+//!     std::string s = "a";
+//!     return std::move(s); // WARNING: prevents copy elision
+//! }
+//! ```
+//!
+//! Only locals whose type is the return type are flagged: for a parameter,
+//! or a local of another type, there is no elision to lose.
+
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, MoveKind, Statement};
+use std::collections::HashSet;
+
+/// Check a function for `return std::move(local);`
+pub fn check_pessimizing_move(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let return_type = normalize_type(&function.return_type);
+    let mut locals = HashSet::new();
+    check_statements(
+        &function.body,
+        &function.name,
+        &return_type,
+        &mut locals,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    return_type: &str,
+    locals: &mut HashSet<String>,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::VariableDecl(var) => {
+                // A later declaration shadows an earlier one of the same name
+                if !var.is_reference
+                    && !var.is_static
+                    && normalize_type(&var.type_name) == return_type
+                {
+                    locals.insert(var.name.clone());
+                } else {
+                    locals.remove(&var.name);
+                }
+            }
+            Statement::Return(Some(Expression::Move {
+                inner,
+                kind: MoveKind::StdMove,
+            })) => {
+                if let Expression::Variable(name) = inner.as_ref() {
+                    if locals.contains(name) {
+                        errors.push(format!(
+                            "In function '{}': warning: std::move in return prevents copy elision of local '{}'; return it by name",
+                            func_name, name
+                        ));
+                    }
+                }
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, return_type, locals, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, return_type, locals, errors);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_statements(&case.statements, func_name, return_type, locals, errors);
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, return_type, locals, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `std::string` for `const std::string` and `std::string `
+fn normalize_type(type_name: &str) -> String {
+    type_name
+        .trim()
+        .trim_start_matches("const ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location};
+    use crate::parser::Variable;
+
+    fn variable(name: &str, type_name: &str) -> Variable {
+        Variable {
+            location: location(2),
+            has_initializer: true,
+            ..test_fixtures::variable(name, type_name)
+        }
+    }
+
+    fn return_move(name: &str) -> Statement {
+        Statement::Return(Some(Expression::Move {
+            inner: Box::new(Expression::Variable(name.to_string())),
+            kind: MoveKind::StdMove,
+        }))
+    }
+
+    fn function(parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+        Function {
+            return_type: "std::string".to_string(),
+            ..test_fixtures::function("build", parameters, body)
+        }
+    }
+
+    #[test]
+    fn test_return_std_move_of_local() {
+        let func = function(
+            vec![],
+            vec![
+                Statement::VariableDecl(variable("s", "std::string")),
+                return_move("s"),
+            ],
+        );
+
+        let errors = check_pessimizing_move(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("std::move in return prevents copy elision of local 's'"));
+    }
+
+    #[test]
+    fn test_parameter_or_other_type_not_flagged() {
+        let func = function(vec![variable("p", "std::string")], vec![return_move("p")]);
+        assert!(check_pessimizing_move(&func, SafetyMode::Safe).is_empty());
+
+        let func = function(
+            vec![],
+            vec![
+                Statement::VariableDecl(variable("s", "const char *")),
+                return_move("s"),
+            ],
+        );
+        assert!(check_pessimizing_move(&func, SafetyMode::Safe).is_empty());
+    }
+}
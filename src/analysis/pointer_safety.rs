@@ -1,5 +1,5 @@
 use crate::parser::safety_annotations::SafetyMode;
-use crate::parser::{Expression, Function, MoveKind, Statement};
+use crate::parser::{Expression, Function, MoveKind, Statement, Variable};
 use std::collections::HashSet;
 
 /// Check if a type is a safe rusty pointer type (Ptr<T> or MutPtr<T>)
@@ -108,14 +108,12 @@ pub fn check_parsed_function_for_pointers(
     let skip_pointer_checks = function_safety != SafetyMode::Safe;
 
     // Track variables that are safe pointer types (rusty::Ptr<T>, rusty::MutPtr<T>)
-    // These are allowed to be dereferenced in @safe code
-    let mut safe_pointer_vars: HashSet<String> = HashSet::new();
+    // and pointers to volatile objects
+    let mut safe_pointer_vars = PointerVars::default();
 
-    // Collect safe pointer variables from function parameters
+    // Collect pointer variables from function parameters
     for param in &function.parameters {
-        if is_rusty_safe_pointer_type(&param.type_name) {
-            safe_pointer_vars.insert(param.name.clone());
-        }
+        safe_pointer_vars.track(param);
     }
 
     // Note: We do NOT check function parameters for char* types.
@@ -132,7 +130,7 @@ pub fn check_parsed_function_for_pointers(
     for (i, stmt) in stmts.iter().enumerate() {
         // Track unsafe scope depth
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -150,9 +148,7 @@ pub fn check_parsed_function_for_pointers(
 
         // Track safe pointer variable declarations (even in unsafe scope for consistency)
         if let Statement::VariableDecl(var) = stmt {
-            if is_rusty_safe_pointer_type(&var.type_name) {
-                safe_pointer_vars.insert(var.name.clone());
-            }
+            safe_pointer_vars.track(var);
         }
 
         // Check for uninitialized pointer declarations in @safe code
@@ -335,7 +331,7 @@ fn check_statements_for_std_move_on_ref(
     for stmt in statements {
         // Track unsafe scope depth
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 *unsafe_depth += 1;
                 continue;
             }
@@ -522,11 +518,43 @@ fn check_expression_for_std_move_on_ref(
     }
 }
 
+/// Pointer variables whose dereferences need special handling in @safe code
+#[derive(Debug, Default)]
+pub struct PointerVars {
+    /// rusty::Ptr<T> / rusty::MutPtr<T> variables, which may be dereferenced
+    safe: HashSet<String>,
+    /// Pointers to volatile-qualified objects, which may never be dereferenced
+    volatile: HashSet<String>,
+}
+
+impl PointerVars {
+    fn track(&mut self, var: &Variable) {
+        if var.is_volatile && (var.is_pointer || is_rusty_safe_pointer_type(&var.type_name)) {
+            self.volatile.insert(var.name.clone());
+        } else if is_rusty_safe_pointer_type(&var.type_name) {
+            self.safe.insert(var.name.clone());
+        }
+    }
+
+    #[cfg(test)]
+    fn insert_safe(&mut self, name: &str) {
+        self.safe.insert(name.to_string());
+    }
+
+    fn is_safe(&self, name: &str) -> bool {
+        self.safe.contains(name)
+    }
+
+    fn is_volatile(&self, name: &str) -> bool {
+        self.volatile.contains(name)
+    }
+}
+
 /// Process a list of statements while tracking unsafe depth for pointer safety
 fn check_statements_for_pointers_with_unsafe_tracking(
     statements: &[Statement],
     initial_unsafe_depth: usize,
-    safe_pointer_vars: &HashSet<String>,
+    safe_pointer_vars: &PointerVars,
 ) -> Vec<String> {
     let mut errors = Vec::new();
     let mut unsafe_depth = initial_unsafe_depth;
@@ -534,7 +562,7 @@ fn check_statements_for_pointers_with_unsafe_tracking(
     for stmt in statements {
         // Track unsafe scope depth
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -646,7 +674,7 @@ fn is_unsafe_memory_function(name: &str) -> bool {
 pub fn check_parsed_statement_for_pointers(
     stmt: &Statement,
     in_unsafe_scope: bool,
-    safe_pointer_vars: &HashSet<String>,
+    safe_pointer_vars: &PointerVars,
 ) -> Option<String> {
     check_parsed_statement_for_pointers_with_return_type(
         stmt,
@@ -660,7 +688,7 @@ pub fn check_parsed_statement_for_pointers(
 pub fn check_parsed_statement_for_pointers_with_return_type(
     stmt: &Statement,
     in_unsafe_scope: bool,
-    safe_pointer_vars: &HashSet<String>,
+    safe_pointer_vars: &PointerVars,
     return_type: Option<&str>,
 ) -> Option<String> {
     use crate::parser::Statement;
@@ -675,7 +703,7 @@ pub fn check_parsed_statement_for_pointers_with_return_type(
             // Check if assigning to a safe pointer variable (Ptr<T> or MutPtr<T>)
             // If so, address-of operations in the rhs are allowed
             let assigning_to_safe_ptr = if let Expression::Variable(name) = lhs {
-                safe_pointer_vars.contains(name)
+                safe_pointer_vars.is_safe(name)
             } else {
                 false
             };
@@ -917,7 +945,7 @@ pub fn check_parsed_statement_for_pointers_with_return_type(
 
 fn contains_pointer_operation(
     expr: &Expression,
-    safe_pointer_vars: &HashSet<String>,
+    safe_pointer_vars: &PointerVars,
 ) -> Option<&'static str> {
     use crate::parser::Expression;
 
@@ -928,8 +956,12 @@ fn contains_pointer_operation(
                 if name == "this" {
                     return None; // *this is safe
                 }
+                // Volatile accesses have side effects the checker can't reason about
+                if safe_pointer_vars.is_volatile(name) {
+                    return Some("volatile dereference");
+                }
                 // Check if this is a safe pointer variable (rusty::Ptr<T> or rusty::MutPtr<T>)
-                if safe_pointer_vars.contains(name) {
+                if safe_pointer_vars.is_safe(name) {
                     return None; // Dereferencing safe pointer is allowed
                 }
             }
@@ -1061,8 +1093,8 @@ mod tests {
     use crate::parser::{Expression, SourceLocation, Statement, Variable};
 
     /// Helper to create empty safe pointer vars set
-    fn empty_safe_vars() -> HashSet<String> {
-        HashSet::new()
+    fn empty_safe_vars() -> PointerVars {
+        PointerVars::default()
     }
 
     #[test]
@@ -1098,6 +1130,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1115,6 +1149,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 20,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1134,6 +1170,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 15,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1185,10 +1223,13 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: SourceLocation {
                 file: "test.cpp".to_string(),
                 line: 5,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
             is_pack: false,
             pack_element_type: None,
@@ -1217,10 +1258,13 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: SourceLocation {
                 file: "test.cpp".to_string(),
                 line: 5,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
             is_pack: false,
             pack_element_type: None,
@@ -1244,6 +1288,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 25,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1405,6 +1451,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1421,6 +1469,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 20,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1441,6 +1491,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1454,6 +1506,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 20,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1490,6 +1544,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1513,6 +1569,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1532,6 +1590,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1552,6 +1612,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1572,6 +1634,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1595,6 +1659,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1615,6 +1681,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1721,8 +1789,8 @@ mod tests {
     #[test]
     fn test_safe_pointer_dereference_is_allowed() {
         // Dereferencing a safe pointer variable (tracked in safe_pointer_vars) is allowed
-        let mut safe_vars = HashSet::new();
-        safe_vars.insert("safe_ptr".to_string());
+        let mut safe_vars = PointerVars::default();
+        safe_vars.insert_safe("safe_ptr");
 
         let expr = Expression::Dereference(Box::new(Expression::Variable("safe_ptr".to_string())));
         assert_eq!(
@@ -1735,7 +1803,7 @@ mod tests {
     #[test]
     fn test_raw_pointer_dereference_is_forbidden() {
         // Dereferencing a raw pointer (not in safe_pointer_vars) is forbidden
-        let safe_vars = HashSet::new(); // Empty - no safe pointers
+        let safe_vars = PointerVars::default(); // Empty - no safe pointers
 
         let expr = Expression::Dereference(Box::new(Expression::Variable("raw_ptr".to_string())));
         assert_eq!(
@@ -1745,6 +1813,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_volatile_pointer_dereference_is_forbidden() {
+        // A rusty::Ptr to a volatile object is still not safe to dereference
+        let mut var = Variable {
+            name: "reg".to_string(),
+            type_name: "rusty::Ptr<volatile int>".to_string(),
+            is_reference: false,
+            is_rvalue_reference: false,
+            is_pointer: false,
+            is_const: false,
+            is_unique_ptr: false,
+            is_shared_ptr: false,
+            is_static: false,
+            is_mutable: false,
+            is_volatile: true,
+            location: SourceLocation {
+                file: "test.cpp".to_string(),
+                line: 3,
+                column: 5,
+                end_line: 0,
+                end_column: 0,
+            },
+            is_pack: false,
+            pack_element_type: None,
+            has_initializer: true,
+        };
+        let mut vars = PointerVars::default();
+        vars.track(&var);
+        var.name = "plain".to_string();
+        var.is_volatile = false;
+        vars.track(&var);
+
+        let deref =
+            |name: &str| Expression::Dereference(Box::new(Expression::Variable(name.to_string())));
+        assert_eq!(
+            contains_pointer_operation(&deref("reg"), &vars),
+            Some("volatile dereference")
+        );
+        assert_eq!(contains_pointer_operation(&deref("plain"), &vars), None);
+    }
+
     #[test]
     fn test_safe_pointer_function_call_allowed() {
         // rusty::addr_of should be allowed in @safe code
@@ -1755,6 +1864,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1775,6 +1886,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1795,6 +1908,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1816,6 +1931,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1830,8 +1947,8 @@ mod tests {
     fn test_address_of_allowed_when_assigning_to_safe_pointer() {
         // When assigning to a safe pointer variable (Ptr<T>/MutPtr<T>),
         // address-of is allowed because the result is stored in a safe wrapper
-        let mut safe_vars = HashSet::new();
-        safe_vars.insert("safe_ptr".to_string());
+        let mut safe_vars = PointerVars::default();
+        safe_vars.insert_safe("safe_ptr");
 
         let stmt = Statement::Assignment {
             lhs: Expression::Variable("safe_ptr".to_string()),
@@ -1840,6 +1957,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1854,7 +1973,7 @@ mod tests {
     fn test_address_of_still_forbidden_for_raw_pointer() {
         // When assigning to a variable NOT in safe_pointer_vars,
         // address-of is still forbidden
-        let safe_vars = HashSet::new(); // Empty - no safe pointers
+        let safe_vars = PointerVars::default(); // Empty - no safe pointers
 
         let stmt = Statement::Assignment {
             lhs: Expression::Variable("raw_ptr".to_string()),
@@ -1863,6 +1982,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1882,8 +2003,8 @@ mod tests {
         // When assigning to safe pointer, address-of is allowed BUT
         // unsafe operations inside the address-of should still be caught
         // e.g., &(*raw_ptr) - the dereference inside should be flagged
-        let mut safe_vars = HashSet::new();
-        safe_vars.insert("safe_ptr".to_string());
+        let mut safe_vars = PointerVars::default();
+        safe_vars.insert_safe("safe_ptr");
 
         let stmt = Statement::Assignment {
             lhs: Expression::Variable("safe_ptr".to_string()),
@@ -1894,6 +2015,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
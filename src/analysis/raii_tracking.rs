@@ -185,6 +185,8 @@ pub struct RaiiTracker {
     pub member_borrows: Vec<MemberBorrow>,
     /// General reference borrows (Phase 8: Lifetime Checking)
     pub reference_borrows: Vec<ReferenceBorrow>,
+    /// Objects borrowing an argument of their @lifetime-annotated constructor
+    pub constructor_borrows: Vec<ReferenceBorrow>,
     /// Heap allocations for new/delete tracking
     pub heap_allocations: HashMap<String, HeapAllocation>,
     /// Current scope level
@@ -193,6 +195,8 @@ pub struct RaiiTracker {
     pub variable_scopes: HashMap<String, usize>,
     /// Variables that are containers (vector, map, etc.)
     pub container_variables: HashSet<String>,
+    /// The `std::string` subset of `container_variables`
+    pub string_variables: HashSet<String>,
     /// Variables that are iterators
     pub iterator_variables: HashSet<String>,
     /// Variables that are references to container elements
@@ -209,6 +213,8 @@ pub struct RaiiTracker {
     pub invalidated_element_refs: HashMap<String, IteratorInvalidation>,
     /// Track invalidated unique_ptr references (ref name -> invalidation info)
     pub invalidated_unique_ptr_refs: HashMap<String, UniquePtrInvalidation>,
+    /// (holder, referent) pairs already reported as destroyed in the wrong order
+    pub drop_order_reported: HashSet<(String, String)>,
 }
 
 /// Information about why a unique_ptr reference was invalidated
@@ -244,10 +250,13 @@ impl RaiiTracker {
             lambda_captures: Vec::new(),
             member_borrows: Vec::new(),
             reference_borrows: Vec::new(),
+            constructor_borrows: Vec::new(),
+            drop_order_reported: HashSet::new(),
             heap_allocations: HashMap::new(),
             current_scope: 0,
             variable_scopes: HashMap::new(),
             container_variables: HashSet::new(),
+            string_variables: HashSet::new(),
             iterator_variables: HashSet::new(),
             element_ref_variables: HashSet::new(),
             unique_ptr_variables: HashSet::new(),
@@ -273,6 +282,25 @@ impl RaiiTracker {
             || type_name.contains("span<")
     }
 
+    /// Check if a type is `std::string` (or another `basic_string`). Its
+    /// `c_str()`/`data()` pointers are invalidated by growing the string,
+    /// just like a vector's element pointers. Accepts the `Owned("...")`
+    /// form `register_variable` is given, too.
+    pub fn is_string_type(type_name: &str) -> bool {
+        let base = type_name
+            .split('"')
+            .nth(1)
+            .unwrap_or(type_name)
+            .trim_start_matches("const ")
+            .trim_end_matches(['&', '*', ' '])
+            .trim();
+        matches!(
+            base,
+            "std::string" | "string" | "std::wstring" | "wstring" | "std::u8string"
+        ) || base.starts_with("std::basic_string<")
+            || base.starts_with("std::__cxx11::basic_string<")
+    }
+
     /// Check if a type is an iterator type
     pub fn is_iterator_type(type_name: &str) -> bool {
         type_name.contains("iterator")
@@ -322,7 +350,12 @@ impl RaiiTracker {
         method_name == "resize" ||
         method_name == "reserve" ||  // Can reallocate for vector
         method_name == "assign" ||
-        method_name == "swap"
+        method_name == "swap" ||
+        // std::string growth
+        method_name == "append" ||
+        method_name == "operator+=" ||
+        method_name == "replace" ||
+        method_name == "shrink_to_fit"
     }
 
     /// Check if a function returns a reference/pointer to a container element
@@ -333,6 +366,7 @@ impl RaiiTracker {
         method_name == "front" ||
         method_name == "back" ||
         method_name == "data" ||
+        method_name == "c_str" || // std::string
         // Some containers have additional element access methods
         method_name == "top" ||   // stack, priority_queue
         method_name == "peek" // custom containers
@@ -364,6 +398,9 @@ impl RaiiTracker {
 
         if Self::is_container_type(type_name) {
             self.container_variables.insert(name.to_string());
+        } else if Self::is_string_type(type_name) {
+            self.container_variables.insert(name.to_string());
+            self.string_variables.insert(name.to_string());
         }
 
         if Self::is_iterator_type(type_name) {
@@ -694,6 +731,63 @@ impl RaiiTracker {
             .push(reference.to_string());
     }
 
+    /// Check whether `holder` borrows `referent` although it was declared first
+    /// in the same scope. Locals are destroyed in reverse declaration order, so
+    /// the referent is gone while the holder still points at it.
+    pub fn check_drop_order(
+        &mut self,
+        holder: &str,
+        referent: &str,
+        function: &IrFunction,
+    ) -> Option<String> {
+        let holder_root = holder.split('.').next().unwrap_or(holder);
+        let holder_info = function.variables.get(holder_root)?;
+        let referent_info = function.variables.get(referent)?;
+
+        if holder_info.is_parameter
+            || holder_info.is_static
+            || referent_info.is_parameter
+            || referent_info.is_static
+        {
+            return None;
+        }
+        if holder_info.scope_level != referent_info.scope_level
+            || holder_info.declaration_index >= referent_info.declaration_index
+        {
+            return None;
+        }
+        if !self
+            .drop_order_reported
+            .insert((holder_root.to_string(), referent.to_string()))
+        {
+            return None;
+        }
+
+        Some(format!(
+            "'{}' declared before '{}' but borrows it; '{}' is destroyed first",
+            holder_root, referent, referent
+        ))
+    }
+
+    /// Record that `object` was constructed from `source` by a constructor whose
+    /// @lifetime annotation ties the argument to the object
+    pub fn record_constructor_borrow(&mut self, object: &str, source: &str, line: usize) {
+        let reference_scope = *self
+            .variable_scopes
+            .get(object)
+            .unwrap_or(&self.current_scope);
+        let source_scope = *self.variable_scopes.get(source).unwrap_or(&0);
+
+        self.constructor_borrows.push(ReferenceBorrow {
+            reference: object.to_string(),
+            source: source.to_string(),
+            reference_scope,
+            source_scope,
+            line,
+            is_returned: false,
+        });
+    }
+
     /// Mark a reference as returned (escapes function scope)
     pub fn mark_reference_returned(&mut self, reference: &str) {
         for borrow in &mut self.reference_borrows {
@@ -841,6 +935,16 @@ impl RaiiTracker {
             }
         }
 
+        // Objects that outlive the argument their constructor borrowed
+        for borrow in &self.constructor_borrows {
+            if borrow.source_scope == dying_scope && borrow.reference_scope < dying_scope {
+                errors.push(format!(
+                    "Dangling reference: '{}' was constructed from '{}' which goes out of scope",
+                    borrow.reference, borrow.source
+                ));
+            }
+        }
+
         // Clean up borrows from dying scope
         // For container borrows: keep if pointee survives OR container dies with it
         self.container_borrows
@@ -854,6 +958,8 @@ impl RaiiTracker {
         // For reference borrows: remove if reference OR source dies
         self.reference_borrows
             .retain(|b| b.reference_scope != dying_scope && b.source_scope != dying_scope);
+        self.constructor_borrows
+            .retain(|b| b.reference_scope != dying_scope && b.source_scope != dying_scope);
 
         // Clean up active borrows for dying references
         for (source, borrowers) in self.active_borrows.iter_mut() {
@@ -884,7 +990,7 @@ impl RaiiTracker {
 /// Check for RAII-related issues in a function
 pub fn check_raii_issues(
     function: &IrFunction,
-    _header_cache: &HeaderCache,
+    header_cache: &HeaderCache,
 ) -> Result<Vec<String>, String> {
     let mut errors = Vec::new();
     let mut tracker = RaiiTracker::new();
@@ -893,13 +999,20 @@ pub fn check_raii_issues(
     for (name, info) in &function.variables {
         let type_name = format!("{:?}", info.ty);
         tracker.register_variable(name, &type_name, info.scope_level);
+
+        // A `gsl::owner<T*>` holds an allocation from the start
+        if let crate::ir::VariableType::UniquePtr(owned) = &info.ty {
+            if crate::parser::ast_visitor::is_gsl_owner_type(owned) {
+                tracker.record_allocation(name, 0);
+            }
+        }
     }
 
     // Process statements in the CFG
     for node_idx in function.cfg.node_indices() {
         let block = &function.cfg[node_idx];
         for stmt in &block.statements {
-            let stmt_errors = process_raii_statement(stmt, &mut tracker, function);
+            let stmt_errors = process_raii_statement(stmt, &mut tracker, function, header_cache);
             errors.extend(stmt_errors);
         }
     }
@@ -912,6 +1025,7 @@ fn process_raii_statement(
     stmt: &IrStatement,
     tracker: &mut RaiiTracker,
     function: &IrFunction,
+    header_cache: &HeaderCache,
 ) -> Vec<String> {
     let mut errors = Vec::new();
 
@@ -926,8 +1040,24 @@ fn process_raii_statement(
         }
 
         IrStatement::CallExpr {
-            func, args, result, ..
+            func,
+            args,
+            result,
+            line,
+            ..
         } => {
+            // An annotated constructor ties its arguments to the new object
+            if let Some(object) = result {
+                for source in super::constructor_lifetime_args(func, args, header_cache) {
+                    tracker.record_constructor_borrow(object, source, 0);
+                }
+            }
+
+            // An annotated out-parameter borrows the inputs sharing its lifetime
+            for (source, out, kind) in super::out_param_borrows(func, args, header_cache) {
+                tracker.record_reference_borrow(out, source, kind == BorrowKind::Mutable, *line);
+            }
+
             // Check for container store methods (push_back, insert, etc.)
             let method_name = func.split("::").last().unwrap_or(func);
 
@@ -946,26 +1076,33 @@ fn process_raii_statement(
                 }
             }
 
-            // Check for iterator-returning methods
+            // Check for iterator-returning methods (`std::string::find`
+            // returns an index, not an iterator)
+            let container = container_receiver(func, args, tracker);
             if RaiiTracker::is_iterator_returning_method(method_name) {
-                if let (Some(result_var), Some(container)) = (result, extract_receiver(func)) {
-                    tracker.record_iterator_creation(result_var, &container, 0);
+                if let (Some(result_var), Some(container)) = (result, &container) {
+                    if method_name != "find" || !tracker.string_variables.contains(container) {
+                        tracker.record_iterator_creation(result_var, container, 0);
+                    }
                 }
             }
 
             // Check for container-modifying methods that invalidate iterators
             if RaiiTracker::is_container_modifying_method(method_name) {
-                if let Some(container) = extract_receiver(func) {
+                if let Some(container) = &container {
                     // Invalidate all iterators for this container
                     let _invalidated =
-                        tracker.record_container_modification(&container, method_name, 0);
+                        tracker.record_container_modification(container, method_name, 0);
                 }
             }
 
-            // Check for element-returning methods (operator[], at(), front(), back(), data())
+            // Check for element-returning methods (operator[], at(), front(), back(), data()).
+            // `int x = v[0]` copies the element; only a reference or pointer dangles.
             if RaiiTracker::is_container_element_method(method_name) {
-                if let (Some(result_var), Some(container)) = (result, extract_receiver(func)) {
-                    tracker.record_container_element_ref(result_var, &container, method_name, 0);
+                if let (Some(result_var), Some(container)) = (result, &container) {
+                    if holds_address(function, result_var) {
+                        tracker.record_container_element_ref(result_var, container, method_name, 0);
+                    }
                 }
             }
 
@@ -1117,6 +1254,20 @@ fn process_raii_statement(
                 // Phase 8: Check if returning a reference to a local variable
                 tracker.mark_reference_returned(val);
 
+                // Check if the returned object was constructed from a local that will die
+                for borrow in &tracker.constructor_borrows {
+                    if borrow.reference == *val {
+                        if let Some(var_info) = function.variables.get(&borrow.source) {
+                            if !var_info.is_parameter && !var_info.is_static {
+                                errors.push(format!(
+                                    "Returning '{}' constructed from local variable '{}' - the borrow will be dangling after function return",
+                                    val, borrow.source
+                                ));
+                            }
+                        }
+                    }
+                }
+
                 // Check if this reference borrows from a local that will die
                 for borrow in &tracker.reference_borrows {
                     if borrow.reference == *val {
@@ -1156,7 +1307,7 @@ fn process_raii_statement(
             }
         }
 
-        IrStatement::LambdaCapture { captures } => {
+        IrStatement::LambdaCapture { captures, .. } => {
             let ref_captures: Vec<String> = captures
                 .iter()
                 .filter(|c| c.is_ref)
@@ -1180,6 +1331,29 @@ fn process_raii_statement(
         } => {
             let is_mutable = matches!(kind, BorrowKind::Mutable);
             tracker.record_reference_borrow(to, from, is_mutable, *line);
+            // A plain pointer or reference dangling at scope exit is never
+            // dereferenced again; only a holder whose destructor runs can
+            // still reach the destroyed referent
+            let holder_has_destructor = function
+                .variables
+                .get(to.split('.').next().unwrap_or(to))
+                .is_some_and(|info| info.has_destructor);
+            if holder_has_destructor {
+                if let Some(err) = tracker.check_drop_order(to, from, function) {
+                    errors.push(err);
+                }
+            }
+        }
+
+        // A struct holding a reference/pointer to a local declared after it
+        IrStatement::StructBorrow {
+            struct_var,
+            borrowed_from,
+            ..
+        } => {
+            if let Some(err) = tracker.check_drop_order(struct_var, borrowed_from, function) {
+                errors.push(err);
+            }
         }
 
         // Phase 8: Check for assignment to borrowed variable
@@ -1237,9 +1411,31 @@ fn extract_receiver(func: &str) -> Option<String> {
     None
 }
 
+/// The container a method is called on: `vec` in "vec.push_back", or the
+/// receiver argument of a qualified call ("std::vector<int>::push_back")
+fn container_receiver(func: &str, args: &[String], tracker: &RaiiTracker) -> Option<String> {
+    extract_receiver(func).or_else(|| {
+        args.first()
+            .filter(|receiver| tracker.container_variables.contains(*receiver))
+            .cloned()
+    })
+}
+
+/// Whether `var` is a reference or pointer (unknown variables are assumed to be)
+fn holds_address(function: &IrFunction, var: &str) -> bool {
+    match function.variables.get(var).map(|info| &info.ty) {
+        Some(crate::ir::VariableType::Owned(type_name)) => type_name.contains('*'),
+        // A shared_ptr copy shares ownership of the element's pointee
+        Some(crate::ir::VariableType::SharedPtr(_)) => false,
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::test_fixtures::{ir_function, ir_variable};
+    use crate::ir::{VariableInfo, VariableType};
 
     #[test]
     fn test_is_container_type() {
@@ -1279,6 +1475,70 @@ mod tests {
         assert!(errors[0].contains("Dangling pointer"));
     }
 
+    #[test]
+    fn test_constructed_object_outlives_argument() {
+        let mut tracker = RaiiTracker::new();
+
+        tracker.register_variable("view", "Observer", 0);
+        tracker.register_variable("local", "int", 1);
+
+        // Simulate: view = Observer(local) in inner scope
+        tracker.current_scope = 1;
+        tracker.record_constructor_borrow("view", "local", 12);
+
+        let errors = tracker.exit_scope();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'view' was constructed from 'local'"));
+    }
+
+    #[test]
+    fn test_holder_declared_before_referent() {
+        let function = ir_function(
+            vec![
+                ir_variable("b", VariableType::Owned("B".to_string()), 0),
+                ir_variable("a", VariableType::Owned("A".to_string()), 1),
+            ],
+            vec![],
+        );
+
+        let mut tracker = RaiiTracker::new();
+        // B b; A a; b.ref = &a;
+        let err = tracker.check_drop_order("b.ref", "a", &function);
+        assert_eq!(
+            err.as_deref(),
+            Some("'b' declared before 'a' but borrows it; 'a' is destroyed first")
+        );
+        // Reported once per pair
+        assert!(tracker.check_drop_order("b", "a", &function).is_none());
+        // Declared in the right order: a outlives b
+        assert!(tracker.check_drop_order("a", "b", &function).is_none());
+    }
+
+    #[test]
+    fn test_raw_pointer_declared_before_referent_not_reported() {
+        // int* p; int x = 1; p = &x;
+        let function = ir_function(
+            vec![
+                ir_variable("p", VariableType::Raw("int *".to_string()), 0),
+                ir_variable("x", VariableType::Owned("int".to_string()), 1),
+            ],
+            vec![IrStatement::Borrow {
+                from: "x".to_string(),
+                to: "p".to_string(),
+                kind: BorrowKind::Mutable,
+                line: 4,
+                is_pointer: true,
+            }],
+        );
+
+        let errors = check_raii_issues(&function, &HeaderCache::new()).unwrap();
+        assert!(
+            !errors.iter().any(|e| e.contains("declared before")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
+
     #[test]
     fn test_iterator_outlives_container() {
         let mut tracker = RaiiTracker::new();
@@ -1322,6 +1582,109 @@ mod tests {
         assert!(err2.unwrap().contains("Double free"));
     }
 
+    #[test]
+    fn test_gsl_owner_double_delete() {
+        // void release(gsl::owner<int*> p) { delete p; delete p; }
+        let delete = || IrStatement::CallExpr {
+            func: "operator delete".to_string(),
+            args: vec!["p".to_string()],
+            result: None,
+            receiver_is_temporary: false,
+            line: 0,
+        };
+        let parameter = VariableInfo {
+            is_parameter: true,
+            scope_level: 0,
+            ..ir_variable(
+                "p",
+                VariableType::UniquePtr("gsl::owner<int *>".to_string()),
+                0,
+            )
+        };
+        let function = ir_function(vec![parameter], vec![delete(), delete()]);
+
+        let errors = check_raii_issues(&function, &HeaderCache::new()).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Double free: 'p'"));
+    }
+
+    #[test]
+    fn test_data_pointer_invalidated_by_push_back() {
+        // int* d = v.data(); int first = v[0]; v.push_back(x); use(d); use(first);
+        let call = |func: &str, args: &[&str], result: Option<&str>| IrStatement::CallExpr {
+            func: func.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            result: result.map(str::to_string),
+            receiver_is_temporary: false,
+            line: 0,
+        };
+        let function = ir_function(
+            vec![
+                ir_variable("v", VariableType::Owned("std::vector<int>".to_string()), 0),
+                ir_variable("d", VariableType::Owned("int *".to_string()), 1),
+                ir_variable("first", VariableType::Owned("int".to_string()), 2),
+            ],
+            vec![
+                call("std::vector<int>::data", &["v"], Some("d")),
+                call(
+                    "std::vector<int>::operator[]",
+                    &["v", "_temp_literal_0_0"],
+                    Some("first"),
+                ),
+                call("std::vector<int>::push_back", &["v", "x"], None),
+                call("use", &["d"], None),
+                call("use", &["first"], None),
+            ],
+        );
+
+        let errors = check_raii_issues(&function, &HeaderCache::new()).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("invalidated element reference 'd'"));
+        assert!(errors[0].contains("push_back()"));
+    }
+
+    #[test]
+    fn test_c_str_pointer_invalidated_by_append() {
+        // const char* c = s.c_str(); s += "x"; use(c);
+        let call = |func: &str, args: &[&str], result: Option<&str>| IrStatement::CallExpr {
+            func: func.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            result: result.map(str::to_string),
+            receiver_is_temporary: false,
+            line: 0,
+        };
+        let function = ir_function(
+            vec![
+                ir_variable("s", VariableType::Owned("std::string".to_string()), 0),
+                ir_variable("c", VariableType::Owned("const char *".to_string()), 1),
+            ],
+            vec![
+                call("std::basic_string<char>::c_str", &["s"], Some("c")),
+                call(
+                    "std::basic_string<char>::operator+=",
+                    &["s", "_temp_literal_0_0"],
+                    None,
+                ),
+                call("use", &["c"], None),
+            ],
+        );
+
+        let errors = check_raii_issues(&function, &HeaderCache::new()).unwrap();
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("invalidated element reference 'c'"));
+        assert!(errors[0].contains("operator+=()"));
+    }
+
+    #[test]
+    fn test_is_string_type() {
+        assert!(RaiiTracker::is_string_type("std::string"));
+        assert!(RaiiTracker::is_string_type("std::string &"));
+        assert!(RaiiTracker::is_string_type("std::basic_string<char>"));
+        assert!(RaiiTracker::is_string_type(r#"Owned("std::string")"#));
+        assert!(!RaiiTracker::is_string_type("std::string_view"));
+        assert!(!RaiiTracker::is_string_type("std::vector<std::string>"));
+    }
+
     #[test]
     fn test_use_after_free() {
         let mut tracker = RaiiTracker::new();
@@ -0,0 +1,312 @@
+//! Modifying a Container While a Range-For Iterates Over It
+//!
+//! A range-for holds iterators into its range for the whole loop, and the
+//! loop variable is usually a reference to the current element. Growing or
+//! shrinking the container from inside the body invalidates both:
+//!
+//! ```cpp
+//! // @safe
+//! void extend(std::vector<int>& v, int x) {
+//!     for (auto& e : v) {
+//!         if (e == x) {
+//!             v.push_back(x); // ERROR: invalidates `e` and the loop iterators
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Any container-modifying call (`push_back`, `insert`, `erase`, `clear`,
+//! ...) on the range variable inside the loop body is reported, including
+//! in nested loops and branches. Calls inside `@unsafe` blocks are skipped,
+//! as are calls that leave the loop right away: those directly followed by
+//! `break` or `return`, and those in a `return` itself.
+
+use crate::analysis::raii_tracking::RaiiTracker;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+
+/// Check a function for containers modified inside a range-for over them
+pub fn check_range_for_mutation(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut state = LoopState::default();
+    check_statements(&function.body, &function.name, &mut state, &mut errors);
+    errors
+}
+
+#[derive(Default)]
+struct LoopState {
+    /// Container named by the last `RangeFor`, until its `EnterLoop`
+    pending_range: Option<String>,
+    /// One entry per enclosing loop or switch: the container a range-for
+    /// iterates over
+    loops: Vec<Option<String>>,
+    /// How many of the innermost loops the current statement is directly
+    /// followed out of
+    leaving: usize,
+    unsafe_depth: usize,
+}
+
+impl LoopState {
+    /// Whether a loop the current statement stays in iterates over `container`
+    fn is_iterating(&self, container: &str) -> bool {
+        let staying = self.loops.len().saturating_sub(self.leaving);
+        self.loops[..staying]
+            .iter()
+            .flatten()
+            .any(|range| range == container)
+    }
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    state: &mut LoopState,
+    errors: &mut Vec<String>,
+) {
+    for (i, stmt) in stmts.iter().enumerate() {
+        // `break` leaves the innermost loop (or switch), `return` all of them
+        state.leaving = match stmts.get(i + 1) {
+            Some(Statement::Break) => 1,
+            Some(Statement::Return(_)) => state.loops.len(),
+            _ => 0,
+        };
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::RangeFor { range, .. } => {
+                state.pending_range = match range {
+                    Expression::Variable(name) => Some(name.clone()),
+                    _ => None,
+                };
+                continue;
+            }
+            Statement::EnterLoop | Statement::EnterDoLoop => {
+                let range = state.pending_range.take();
+                state.loops.push(range);
+                continue;
+            }
+            Statement::ExitLoop => {
+                state.loops.pop();
+                continue;
+            }
+            Statement::EnterUnsafe { .. } => {
+                state.unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                state.unsafe_depth = state.unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                if state.unsafe_depth == 0 {
+                    check_call(name, args, func_name, location.line, state, errors);
+                    for arg in args {
+                        check_expression(arg, func_name, location.line, state, errors);
+                    }
+                }
+                continue;
+            }
+            Statement::Assignment { lhs, rhs, location } => (vec![lhs, rhs], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            // Returning leaves every loop before its iterators are used again
+            Statement::Return(_) => continue,
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, state, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, state, errors);
+                }
+                continue;
+            }
+            Statement::Switch { cases, .. } => {
+                // A `break` in a case leaves the switch, not the loop
+                state.loops.push(None);
+                for case in cases {
+                    check_statements(&case.statements, func_name, state, errors);
+                }
+                state.loops.pop();
+                continue;
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, state, errors);
+                continue;
+            }
+            _ => continue,
+        };
+        if state.unsafe_depth == 0 {
+            for expr in exprs {
+                check_expression(expr, func_name, line, state, errors);
+            }
+        }
+    }
+}
+
+fn check_expression(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    state: &LoopState,
+    errors: &mut Vec<String>,
+) {
+    match expr {
+        Expression::FunctionCall { name, args } => {
+            check_call(name, args, func_name, line, state, errors);
+            for arg in args {
+                check_expression(arg, func_name, line, state, errors);
+            }
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::Cast { inner, .. } => check_expression(inner, func_name, line, state, errors),
+        Expression::BinaryOp { left, right, .. } => {
+            check_expression(left, func_name, line, state, errors);
+            check_expression(right, func_name, line, state, errors);
+        }
+        _ => {}
+    }
+}
+
+fn check_call(
+    name: &str,
+    args: &[Expression],
+    func_name: &str,
+    line: u32,
+    state: &LoopState,
+    errors: &mut Vec<String>,
+) {
+    let method = name.rsplit("::").next().unwrap_or(name);
+    if !RaiiTracker::is_container_modifying_method(method) {
+        return;
+    }
+    let Some(Expression::Variable(container)) = args.first() else {
+        return;
+    };
+    if state.is_iterating(container) {
+        errors.push(format!(
+            "In function '{}': Container '{}' is modified by '{}()' at line {} inside a range-for loop over it; the loop's iterators and element references are invalidated",
+            func_name, container, method, line
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location};
+
+    fn method_call(container: &str, method: &str, line: u32) -> Statement {
+        Statement::FunctionCall {
+            name: format!("std::vector<int>::{}", method),
+            args: vec![
+                Expression::Variable(container.to_string()),
+                Expression::Variable("x".to_string()),
+            ],
+            location: location(line),
+        }
+    }
+
+    fn range_for(container: &str, body: Vec<Statement>) -> Vec<Statement> {
+        let mut stmts = vec![
+            Statement::ExpressionStatement {
+                expr: Expression::Variable(container.to_string()),
+                location: location(2),
+            },
+            Statement::RangeFor {
+                range: Expression::Variable(container.to_string()),
+                location: location(2),
+            },
+            Statement::EnterLoop,
+        ];
+        stmts.extend(body);
+        stmts.push(Statement::ExitLoop);
+        stmts
+    }
+
+    fn function(body: Vec<Statement>) -> Function {
+        test_fixtures::function("extend", vec![], body)
+    }
+
+    #[test]
+    fn test_push_back_inside_range_for_over_same_container() {
+        let func = function(range_for(
+            "v",
+            vec![Statement::If {
+                condition: Expression::Variable("cond".to_string()),
+                then_branch: vec![method_call("v", "push_back", 4)],
+                else_branch: None,
+                location: location(3),
+            }],
+        ));
+        let errors = check_range_for_mutation(&func, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].contains("Container 'v' is modified by 'push_back()' at line 4"));
+    }
+
+    #[test]
+    fn test_modification_directly_followed_by_break_or_return_ok() {
+        for exit in [Statement::Break, Statement::Return(None)] {
+            let func = function(range_for("v", vec![method_call("v", "erase", 3), exit]));
+            let errors = check_range_for_mutation(&func, SafetyMode::Safe);
+            assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        }
+
+        // Returning the call's result leaves the loop too
+        let erase = Expression::FunctionCall {
+            name: "std::vector<int>::erase".to_string(),
+            args: vec![Expression::Variable("v".to_string())],
+        };
+        let func = function(range_for("v", vec![Statement::Return(Some(erase))]));
+        assert!(check_range_for_mutation(&func, SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_break_out_of_inner_loop_or_switch_still_flagged() {
+        // The break leaves only the inner loop; the range-for goes on
+        let inner_loop = vec![
+            Statement::EnterLoop,
+            method_call("v", "push_back", 4),
+            Statement::Break,
+            Statement::ExitLoop,
+        ];
+        let func = function(range_for("v", inner_loop));
+        assert_eq!(check_range_for_mutation(&func, SafetyMode::Safe).len(), 1);
+
+        let switch = Statement::Switch {
+            condition: Expression::Variable("kind".to_string()),
+            cases: vec![crate::parser::ast_visitor::SwitchCase {
+                label: None,
+                statements: vec![method_call("v", "push_back", 4), Statement::Break],
+            }],
+            location: location(3),
+        };
+        let func = function(range_for("v", vec![switch]));
+        assert_eq!(check_range_for_mutation(&func, SafetyMode::Safe).len(), 1);
+    }
+
+    #[test]
+    fn test_other_container_or_after_loop_not_flagged() {
+        let mut body = range_for("v", vec![method_call("w", "push_back", 3)]);
+        body.push(method_call("v", "push_back", 5));
+        let func = function(body);
+        assert!(check_range_for_mutation(&func, SafetyMode::Safe).is_empty());
+
+        // A plain loop does not iterate over `v`
+        let func = function(vec![
+            Statement::EnterLoop,
+            method_call("v", "push_back", 3),
+            Statement::ExitLoop,
+        ]);
+        assert!(check_range_for_mutation(&func, SafetyMode::Safe).is_empty());
+    }
+}
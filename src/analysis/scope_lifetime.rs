@@ -190,7 +190,8 @@ impl ScopedLifetimeTracker {
                         source.starts_with("__") || // Compiler-generated temporaries
                         source.starts_with("temp_") || // Explicit temporaries
                         source.starts_with("_temp_call_") || // Function call result temporaries (ir/mod.rs)
-                        source.starts_with("_temp_expr_"); // Binary expression temporaries (ir/mod.rs)
+                        source.starts_with("_temp_expr_") || // Binary expression temporaries (ir/mod.rs)
+                        source.starts_with('*'); // Raw pointer targets (ir/mod.rs), not scoped here
 
                     // Check that the source is alive where the reference is used (skip for temporaries)
                     if !is_function_call_result {
@@ -320,7 +321,8 @@ fn analyze_block(
                     from.starts_with("__") || // Compiler-generated temporaries
                     from.starts_with("temp_") || // Explicit temporaries
                     from.starts_with("_temp_call_") || // Function call result temporaries (ir/mod.rs)
-                    from.starts_with("_temp_expr_"); // Binary expression temporaries (ir/mod.rs)
+                    from.starts_with("_temp_expr_") || // Binary expression temporaries (ir/mod.rs)
+                    from.starts_with('*'); // Raw pointer targets (ir/mod.rs), not scoped here
 
                 // Check that 'from' is alive in this scope (skip for temporaries)
                 if !is_function_call_result && !tracker.is_alive_in_scope(from, scope_id) {
@@ -361,7 +363,8 @@ fn analyze_block(
                                             // The reference inherits the lifetime of what it was bound to
                                             // We rely on dependency tracking for these cases
                                         }
-                                        crate::ir::VariableType::Owned(_) => {
+                                        crate::ir::VariableType::Owned(_)
+                                        | crate::ir::VariableType::SharedPtr(_) => {
                                             // Variable is an OWNED local object
                                             // Returning a reference to it is dangerous
                                             errors.push(format!(
@@ -457,51 +460,17 @@ fn is_parameter(var_name: &str, function: &IrFunction) -> bool {
 }
 
 /// Check lifetimes for the entire program with scope tracking
-/// Check if a file path is from a system header (not user code)
-fn is_system_header(file_path: &str) -> bool {
-    let system_paths = [
-        "/usr/include",
-        "/usr/local/include",
-        "/opt/homebrew/include",
-        "/Library/Developer",
-        "C:\\Program Files",
-        "/Applications/Xcode.app",
-    ];
-
-    for path in &system_paths {
-        if file_path.starts_with(path) {
-            return true;
-        }
-    }
-
-    // STL and system library patterns (works for relative paths too)
-    if file_path.contains("/include/c++/")
-        || file_path.contains("/bits/")
-        || file_path.contains("/ext/")
-        || file_path.contains("stl_")
-        || file_path.contains("/lib/gcc/")
-    {
-        return true;
-    }
-
-    // Also skip project include directory
-    if file_path.contains("/include/rusty/") || file_path.contains("/include/unified_") {
-        return true;
-    }
-
-    false
-}
-
 pub fn check_scoped_lifetimes(
     program: &IrProgram,
     header_cache: &HeaderCache,
     safety_context: &SafetyContext,
+    include_system: bool,
 ) -> Result<Vec<String>, String> {
     let mut all_errors = Vec::new();
 
     for function in &program.functions {
         // Skip system header functions
-        if is_system_header(&function.source_file) {
+        if !include_system && super::is_system_header(&function.source_file) {
             continue;
         }
 
@@ -547,4 +516,55 @@ mod tests {
         assert!(tracker.check_outlives("'a", "'b"));
         assert!(!tracker.check_outlives("'b", "'a"));
     }
+
+    #[test]
+    fn test_system_header_function_checked_with_include_system() {
+        use crate::analysis::test_fixtures::ir_function;
+        use crate::ir::{BorrowKind, OwnershipGraph};
+        use crate::parser::safety_annotations::SafetyMode;
+
+        // A borrow of an undeclared variable, in a header under /usr/include
+        let function = IrFunction {
+            source_file: "/usr/include/vendored.h".to_string(),
+            ..ir_function(
+                vec![],
+                vec![IrStatement::Borrow {
+                    from: "ghost".to_string(),
+                    to: "r".to_string(),
+                    kind: BorrowKind::Immutable,
+                    line: 2,
+                    is_pointer: false,
+                }],
+            )
+        };
+        let program = IrProgram {
+            functions: vec![function],
+            ownership_graph: OwnershipGraph::new(),
+            types_with_ref_members: HashSet::new(),
+            method_qualifiers: HashMap::new(),
+        };
+        let safety_context = SafetyContext {
+            file_default: SafetyMode::Safe,
+            ..SafetyContext::new()
+        };
+        let header_cache = HeaderCache::new();
+
+        let errors =
+            check_scoped_lifetimes(&program, &header_cache, &safety_context, false).unwrap();
+        assert!(
+            errors.is_empty(),
+            "system headers are skipped: {:?}",
+            errors
+        );
+
+        let errors =
+            check_scoped_lifetimes(&program, &header_cache, &safety_context, true).unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Cannot borrow from 'ghost'")),
+            "unexpected errors: {:?}",
+            errors
+        );
+    }
 }
@@ -0,0 +1,438 @@
+//! Self-Assignment in Assignment Operators
+//!
+//! An `operator=` that releases a member before copying the same member from
+//! its argument breaks on `x = x`: by the time it reads `other.data_`, that
+//! is the member it just freed.
+//!
+//! ```cpp
+//! Buffer& Buffer::operator=(const Buffer& other) {
+//!     delete data_;                 // frees other.data_ too when &other == this
+//!     data_ = new int(*other.data_); // ERROR: use after free on self-assignment
+//!     return *this;
+//! }
+//! ```
+//!
+//! A `this != &other` (or `this == &other`) comparison anywhere in the body
+//! counts as a guard. Releases are `delete`, `free()` and `.reset()` of a
+//! member; they are tracked inside `@unsafe` blocks too, since that is where
+//! `delete` lives in @safe code.
+
+use super::at_line;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::HashMap;
+
+/// Check an assignment operator for release-then-read of a member without a
+/// self-assignment guard
+pub fn check_self_assignment(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let method = function.name.rsplit("::").next().unwrap_or(&function.name);
+    if method != "operator=" || function.parameters.len() != 1 {
+        return errors;
+    }
+    let other = &function.parameters[0];
+    if !other.is_reference || has_self_guard(&function.body, &other.name) {
+        return errors;
+    }
+
+    let mut released: HashMap<String, u32> = HashMap::new();
+    let mut reported: Vec<String> = Vec::new();
+    let mut events = Vec::new();
+    collect_events(&function.body, &other.name, &mut events);
+    for event in events {
+        match event {
+            Event::Release { field, line } => {
+                released.entry(field).or_insert(line);
+            }
+            Event::Reassign { field } => {
+                released.remove(&field);
+            }
+            Event::Read { field, line } => {
+                let Some(released_line) = released.get(&field) else {
+                    continue;
+                };
+                if reported.contains(&field) {
+                    continue;
+                }
+                errors.push(format!(
+                    "In function '{}': self-assignment would use after free: member '{}' is released{} and then read from '{}.{}'{} without a `this != &{}` guard",
+                    function.name,
+                    field,
+                    at_line(*released_line),
+                    other.name,
+                    field,
+                    at_line(line),
+                    other.name
+                ));
+                reported.push(field);
+            }
+        }
+    }
+    errors
+}
+
+enum Event {
+    /// A member of `*this` is deleted or reset
+    Release { field: String, line: u32 },
+    /// The same-named member of the argument is read
+    Read { field: String, line: u32 },
+    /// The member is given a new value, so `other.field` no longer aliases
+    /// freed memory on self-assignment
+    Reassign { field: String },
+}
+
+/// Events in source order; within one statement the reads come first, since
+/// arguments are evaluated before the call that releases (`p_.reset(new
+/// T(*other.p_))` is fine) and the right-hand side before the store
+fn collect_events(stmts: &[Statement], other: &str, events: &mut Vec<Event>) {
+    for stmt in stmts {
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::Assignment { lhs, rhs, location } => (vec![lhs, rhs], location.line),
+            Statement::ReferenceBinding {
+                target, location, ..
+            } => (vec![target], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            Statement::Return(Some(expr)) => (vec![expr], 0),
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                for arg in args {
+                    collect_reads(arg, other, location.line, events);
+                }
+                if let Some(field) = released_by_call(name, args) {
+                    events.push(Event::Release {
+                        field: field.clone(),
+                        line: location.line,
+                    });
+                }
+                continue;
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                collect_reads(condition, other, location.line, events);
+                collect_events(then_branch, other, events);
+                if let Some(else_stmts) = else_branch {
+                    collect_events(else_stmts, other, events);
+                }
+                continue;
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                collect_reads(condition, other, location.line, events);
+                for case in cases {
+                    collect_events(&case.statements, other, events);
+                }
+                continue;
+            }
+            Statement::Block(stmts) => {
+                collect_events(stmts, other, events);
+                continue;
+            }
+            _ => continue,
+        };
+        for expr in &exprs {
+            collect_reads(expr, other, line, events);
+        }
+        for expr in exprs {
+            collect_releases(expr, line, events);
+        }
+        if let Statement::Assignment { lhs, .. } = stmt {
+            if let Some(field) = this_member(lhs) {
+                events.push(Event::Reassign {
+                    field: field.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn collect_reads(expr: &Expression, other: &str, line: u32, events: &mut Vec<Event>) {
+    match expr {
+        Expression::MemberAccess { object, field } => {
+            if matches!(object.as_ref(), Expression::Variable(name) if name == other) {
+                events.push(Event::Read {
+                    field: field.clone(),
+                    line,
+                });
+            } else {
+                collect_reads(object, other, line, events);
+            }
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::AddressOf(inner)
+        | Expression::Cast { inner, .. }
+        | Expression::New(inner)
+        | Expression::Delete(inner) => collect_reads(inner, other, line, events),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_reads(arg, other, line, events);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            collect_reads(left, other, line, events);
+            collect_reads(right, other, line, events);
+        }
+        Expression::ArraySubscript { array, index } => {
+            collect_reads(array, other, line, events);
+            collect_reads(index, other, line, events);
+        }
+        Expression::PointerArithmetic {
+            pointer, offset, ..
+        } => {
+            collect_reads(pointer, other, line, events);
+            if let Some(offset) = offset {
+                collect_reads(offset, other, line, events);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_releases(expr: &Expression, line: u32, events: &mut Vec<Event>) {
+    match expr {
+        Expression::Delete(inner) => {
+            if let Some(field) = this_member(inner) {
+                events.push(Event::Release {
+                    field: field.clone(),
+                    line,
+                });
+            }
+        }
+        Expression::FunctionCall { name, args } => {
+            if let Some(field) = released_by_call(name, args) {
+                events.push(Event::Release {
+                    field: field.clone(),
+                    line,
+                });
+            }
+        }
+        Expression::Cast { inner, .. } => collect_releases(inner, line, events),
+        Expression::BinaryOp { left, op, right } if op == "," => {
+            collect_releases(left, line, events);
+            collect_releases(right, line, events);
+        }
+        _ => {}
+    }
+}
+
+/// The member a `free(m)` or `m.reset(...)` call releases
+fn released_by_call<'a>(name: &str, args: &'a [Expression]) -> Option<&'a String> {
+    let method = name.rsplit("::").next().unwrap_or(name);
+    match method {
+        "free" | "reset" => this_member(args.first()?),
+        _ => None,
+    }
+}
+
+/// `field` for `this->field` (implicit member access included)
+fn this_member(expr: &Expression) -> Option<&String> {
+    match expr {
+        Expression::MemberAccess { object, field } if matches!(object.as_ref(), Expression::Variable(name) if name == "this") => {
+            Some(field)
+        }
+        Expression::Cast { inner, .. } => this_member(inner),
+        _ => None,
+    }
+}
+
+/// Whether the body compares `this` against `&other` anywhere
+fn has_self_guard(stmts: &[Statement], other: &str) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            compares_this_with(condition, other)
+                || has_self_guard(then_branch, other)
+                || else_branch
+                    .as_ref()
+                    .is_some_and(|stmts| has_self_guard(stmts, other))
+        }
+        Statement::Block(stmts) => has_self_guard(stmts, other),
+        _ => false,
+    })
+}
+
+fn compares_this_with(expr: &Expression, other: &str) -> bool {
+    match expr {
+        Expression::BinaryOp { left, op, right } if op == "!=" || op == "==" => {
+            (is_this(left) && is_address_of(right, other))
+                || (is_this(right) && is_address_of(left, other))
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            compares_this_with(left, other) || compares_this_with(right, other)
+        }
+        Expression::Cast { inner, .. } => compares_this_with(inner, other),
+        _ => false,
+    }
+}
+
+fn is_this(expr: &Expression) -> bool {
+    match expr {
+        Expression::Variable(name) => name == "this",
+        Expression::Cast { inner, .. } => is_this(inner),
+        _ => false,
+    }
+}
+
+/// `&other` or `std::addressof(other)`
+fn is_address_of(expr: &Expression, other: &str) -> bool {
+    match expr {
+        Expression::AddressOf(inner) => {
+            matches!(inner.as_ref(), Expression::Variable(name) if name == other)
+        }
+        Expression::FunctionCall { name, args } if name.ends_with("addressof") => {
+            matches!(args.first(), Some(Expression::Variable(name)) if name == other)
+        }
+        Expression::Cast { inner, .. } => is_address_of(inner, other),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{location, method, variable};
+    use crate::parser::Variable;
+
+    fn var(name: &str) -> Expression {
+        Expression::Variable(name.to_string())
+    }
+
+    fn member(object: &str, field: &str) -> Expression {
+        Expression::MemberAccess {
+            object: Box::new(var(object)),
+            field: field.to_string(),
+        }
+    }
+
+    fn operator_assign(body: Vec<Statement>) -> Function {
+        let other = Variable {
+            is_reference: true,
+            is_const: true,
+            ..variable("other", "const Buffer &")
+        };
+        method("Buffer::operator=", vec![other], body)
+    }
+
+    /// `delete data_; data_ = new int(*other.data_);`
+    fn delete_then_copy() -> Vec<Statement> {
+        vec![
+            Statement::EnterUnsafe {
+                location: location(2),
+            },
+            Statement::ExpressionStatement {
+                expr: Expression::Delete(Box::new(member("this", "data_"))),
+                location: location(3),
+            },
+            Statement::Assignment {
+                lhs: member("this", "data_"),
+                rhs: Expression::New(Box::new(Expression::FunctionCall {
+                    name: "int".to_string(),
+                    args: vec![Expression::Dereference(Box::new(member("other", "data_")))],
+                })),
+                location: location(4),
+            },
+            Statement::ExitUnsafe,
+        ]
+    }
+
+    #[test]
+    fn test_unguarded_delete_then_copy() {
+        let errors = check_self_assignment(&operator_assign(delete_then_copy()), SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains(
+            "member 'data_' is released at line 3 and then read from 'other.data_' at line 4"
+        ));
+    }
+
+    #[test]
+    fn test_guarded_operator_ok() {
+        let guarded = vec![Statement::If {
+            condition: Expression::BinaryOp {
+                left: Box::new(var("this")),
+                op: "!=".to_string(),
+                right: Box::new(Expression::AddressOf(Box::new(var("other")))),
+            },
+            then_branch: delete_then_copy(),
+            else_branch: None,
+            location: location(2),
+        }];
+        assert!(check_self_assignment(&operator_assign(guarded), SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_reset_with_copy_in_argument_ok() {
+        // data_.reset(new int(*other.data_)); copies before releasing
+        let body = vec![Statement::FunctionCall {
+            name: "std::unique_ptr<int>::reset".to_string(),
+            args: vec![
+                member("this", "data_"),
+                Expression::New(Box::new(Expression::Dereference(Box::new(member(
+                    "other", "data_",
+                ))))),
+            ],
+            location: location(3),
+        }];
+        assert!(check_self_assignment(&operator_assign(body), SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_reset_then_copy() {
+        let body = vec![
+            Statement::FunctionCall {
+                name: "std::unique_ptr<int>::reset".to_string(),
+                args: vec![member("this", "data_")],
+                location: location(3),
+            },
+            Statement::Assignment {
+                lhs: member("this", "data_"),
+                rhs: Expression::FunctionCall {
+                    name: "std::make_unique".to_string(),
+                    args: vec![Expression::Dereference(Box::new(member("other", "data_")))],
+                },
+                location: location(4),
+            },
+        ];
+        let errors = check_self_assignment(&operator_assign(body), SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_read_in_return_names_only_the_release_line() {
+        let body = vec![
+            Statement::ExpressionStatement {
+                expr: Expression::Delete(Box::new(member("this", "data_"))),
+                location: location(3),
+            },
+            Statement::Return(Some(Expression::Dereference(Box::new(member(
+                "other", "data_",
+            ))))),
+        ];
+        let errors = check_self_assignment(&operator_assign(body), SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].contains("released at line 3 and then read from 'other.data_' without"),
+            "{}",
+            errors[0]
+        );
+    }
+}
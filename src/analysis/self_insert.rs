@@ -0,0 +1,253 @@
+//! Inserting a Container's Own Element
+//!
+//! `push_back` and `emplace_back` take their argument by reference. When that
+//! reference points into the same vector and the call has to grow it, the
+//! element is moved to the new buffer while the argument still refers to the
+//! old one:
+//!
+//! ```cpp
+//! // @safe
+//! void duplicate_last(std::vector<std::string>& v) {
+//!     v.push_back(v.back()); // WARNING: v.back() may be invalidated mid-call
+//! }
+//! ```
+//!
+//! Conforming implementations copy the argument before reallocating, but
+//! hand-written containers and `emplace_back` with constructor arguments
+//! often don't, so any element of the receiver — `v[i]`, `v.front()`,
+//! `v.back()`, or a `std::move` of one — is flagged. Calls inside `@unsafe`
+//! blocks are skipped.
+
+use super::at_line;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+
+/// Check a function for `push_back`/`emplace_back` calls passed an element
+/// of their own receiver
+pub fn check_self_insert(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return warnings;
+    }
+
+    check_statements(&function.body, &function.name, &mut 0, &mut warnings);
+    warnings
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    unsafe_depth: &mut usize,
+    warnings: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        let (exprs, line): (Vec<&Expression>, u32) = match stmt {
+            Statement::EnterUnsafe { .. } => {
+                *unsafe_depth += 1;
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                *unsafe_depth = unsafe_depth.saturating_sub(1);
+                continue;
+            }
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                if *unsafe_depth == 0 {
+                    check_call(name, args, func_name, location.line, warnings);
+                    for arg in args {
+                        check_expression(arg, func_name, location.line, warnings);
+                    }
+                }
+                continue;
+            }
+            Statement::Assignment { lhs, rhs, location } => (vec![lhs, rhs], location.line),
+            Statement::ExpressionStatement { expr, location } => (vec![expr], location.line),
+            Statement::Return(Some(expr)) => (vec![expr], 0),
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, unsafe_depth, warnings);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, unsafe_depth, warnings);
+                }
+                continue;
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_statements(&case.statements, func_name, unsafe_depth, warnings);
+                }
+                continue;
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, unsafe_depth, warnings);
+                continue;
+            }
+            _ => continue,
+        };
+        if *unsafe_depth == 0 {
+            for expr in exprs {
+                check_expression(expr, func_name, line, warnings);
+            }
+        }
+    }
+}
+
+fn check_expression(expr: &Expression, func_name: &str, line: u32, warnings: &mut Vec<String>) {
+    match expr {
+        Expression::FunctionCall { name, args } => {
+            check_call(name, args, func_name, line, warnings);
+            for arg in args {
+                check_expression(arg, func_name, line, warnings);
+            }
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::Cast { inner, .. } => check_expression(inner, func_name, line, warnings),
+        Expression::BinaryOp { left, right, .. } => {
+            check_expression(left, func_name, line, warnings);
+            check_expression(right, func_name, line, warnings);
+        }
+        _ => {}
+    }
+}
+
+fn check_call(
+    name: &str,
+    args: &[Expression],
+    func_name: &str,
+    line: u32,
+    warnings: &mut Vec<String>,
+) {
+    let method = name.rsplit("::").next().unwrap_or(name);
+    if method != "push_back" && method != "emplace_back" {
+        return;
+    }
+    let Some((Expression::Variable(container), inserted)) = args.split_first() else {
+        return;
+    };
+    if let Some(element) = inserted.iter().find_map(|arg| element_of(arg, container)) {
+        warnings.push(format!(
+            "In function '{}': warning: '{}.{}()'{} is passed {}, an element of '{}' itself; if the call reallocates, the argument may be invalidated mid-call",
+            func_name,
+            container,
+            method,
+            at_line(line),
+            element,
+            container
+        ));
+    }
+}
+
+/// Description of `expr` if it names an element of `container`
+fn element_of(expr: &Expression, container: &str) -> Option<String> {
+    match expr {
+        Expression::Move { inner, .. } | Expression::Cast { inner, .. } => {
+            element_of(inner, container)
+        }
+        Expression::ArraySubscript { array, .. } if is_variable(array, container) => {
+            Some(format!("'{}[...]'", container))
+        }
+        Expression::FunctionCall { name, args } => {
+            let method = name.rsplit("::").next().unwrap_or(name);
+            let accessor = matches!(method, "operator[]" | "at" | "front" | "back");
+            (accessor && args.first().is_some_and(|arg| is_variable(arg, container))).then(|| {
+                match method {
+                    "operator[]" => format!("'{}[...]'", container),
+                    "at" => format!("'{}.at(...)'", container),
+                    _ => format!("'{}.{}()'", container, method),
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+fn is_variable(expr: &Expression, name: &str) -> bool {
+    matches!(expr, Expression::Variable(var) if var == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location};
+    use crate::parser::MoveKind;
+
+    fn method_call(container: &str, method: &str, mut args: Vec<Expression>) -> Expression {
+        args.insert(0, Expression::Variable(container.to_string()));
+        Expression::FunctionCall {
+            name: format!("std::vector<std::string>::{}", method),
+            args,
+        }
+    }
+
+    fn push_back(container: &str, arg: Expression, line: u32) -> Statement {
+        Statement::FunctionCall {
+            name: "std::vector<std::string>::push_back".to_string(),
+            args: vec![Expression::Variable(container.to_string()), arg],
+            location: location(line),
+        }
+    }
+
+    fn function(body: Vec<Statement>) -> Function {
+        test_fixtures::function("duplicate_last", vec![], body)
+    }
+
+    #[test]
+    fn test_push_back_of_own_element() {
+        let func = function(vec![push_back("v", method_call("v", "back", vec![]), 2)]);
+        let warnings = check_self_insert(&func, SafetyMode::Safe);
+        assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+        assert!(warnings[0].contains("'v.push_back()' at line 2 is passed 'v.back()'"));
+
+        // A moved-from subscript is the same element
+        let index = Expression::Literal("0".to_string());
+        let element = method_call("v", "operator[]", vec![index]);
+        let func = function(vec![push_back(
+            "v",
+            Expression::Move {
+                inner: Box::new(element),
+                kind: MoveKind::StdMove,
+            },
+            2,
+        )]);
+        let warnings = check_self_insert(&func, SafetyMode::Safe);
+        assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+        assert!(warnings[0].contains("is passed 'v[...]'"));
+    }
+
+    #[test]
+    fn test_push_back_in_return_names_no_line() {
+        let call = method_call("v", "push_back", vec![method_call("v", "back", vec![])]);
+        let func = function(vec![Statement::Return(Some(call))]);
+        let warnings = check_self_insert(&func, SafetyMode::Safe);
+        assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+        assert!(
+            warnings[0].contains("'v.push_back()' is passed 'v.back()'"),
+            "{}",
+            warnings[0]
+        );
+    }
+
+    #[test]
+    fn test_element_of_other_container_not_flagged() {
+        let func = function(vec![push_back("v", method_call("w", "back", vec![]), 2)]);
+        assert!(check_self_insert(&func, SafetyMode::Safe).is_empty());
+
+        // Inside an @unsafe block
+        let func = function(vec![
+            Statement::EnterUnsafe {
+                location: location(2),
+            },
+            push_back("v", method_call("v", "front", vec![]), 3),
+            Statement::ExitUnsafe,
+        ]);
+        assert!(check_self_insert(&func, SafetyMode::Safe).is_empty());
+    }
+}
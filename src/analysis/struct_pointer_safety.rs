@@ -313,7 +313,7 @@ fn check_constructor_for_nullptr(ctor: &crate::parser::Function, class: &Class)
     let mut unsafe_depth = 0;
     for stmt in &ctor.body {
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
             }
             Statement::ExitUnsafe => {
@@ -0,0 +1,147 @@
+//! Parsed-AST and IR Builders for Unit Tests
+//!
+//! The per-function passes take the parser's `Function`s and `Class`es, or
+//! the IR's `IrFunction`s; their unit tests build them by hand. These
+//! builders fill every field a test doesn't care about with the value of a
+//! plain declaration in `test.cpp`, so a test spells out only what it
+//! exercises and overrides the rest with struct update syntax, e.g.
+//! `Variable { is_reference: true, ..variable("v", "std::vector<int> &") }`.
+
+use crate::ir::{
+    BasicBlock, ControlFlowGraph, IrFunction, IrStatement, OwnershipState, VariableInfo,
+    VariableType,
+};
+use crate::parser::ast_visitor::Class;
+use crate::parser::{Function, SourceLocation, Statement, Variable};
+use std::collections::HashMap;
+
+/// Column 5 of `line` in `test.cpp`
+pub fn location(line: u32) -> SourceLocation {
+    SourceLocation {
+        file: "test.cpp".to_string(),
+        line,
+        column: 5,
+        end_line: 0,
+        end_column: 0,
+    }
+}
+
+/// A non-reference, non-const `type_name name;` on line 1, without an
+/// initializer
+pub fn variable(name: &str, type_name: &str) -> Variable {
+    Variable {
+        name: name.to_string(),
+        type_name: type_name.to_string(),
+        is_reference: false,
+        is_rvalue_reference: false,
+        is_pointer: false,
+        is_const: false,
+        is_unique_ptr: false,
+        is_shared_ptr: false,
+        is_static: false,
+        is_mutable: false,
+        is_volatile: false,
+        location: location(1),
+        is_pack: false,
+        pack_element_type: None,
+        has_initializer: false,
+    }
+}
+
+/// An unannotated free function `void name(parameters)` on line 1
+pub fn function(name: &str, parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+    Function {
+        name: name.to_string(),
+        parameters,
+        return_type: "void".to_string(),
+        body,
+        location: location(1),
+        is_method: false,
+        method_qualifier: None,
+        template_parameters: vec![],
+        safety_annotation: None,
+        has_explicit_safety_annotation: false,
+        is_deleted: false,
+        member_initializers: vec![],
+    }
+}
+
+/// [`function`], as a non-const method `name` (`Class::method`)
+pub fn method(name: &str, parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+    Function {
+        is_method: true,
+        ..function(name, parameters, body)
+    }
+}
+
+/// An unannotated `struct name {};` on line 1 with the implicit special
+/// members
+pub fn class(name: &str) -> Class {
+    Class {
+        name: name.to_string(),
+        members: vec![],
+        methods: vec![],
+        base_classes: vec![],
+        location: location(1),
+        is_union: false,
+        has_destructor: false,
+        is_interface: false,
+        has_virtual_destructor: false,
+        destructor_is_defaulted: false,
+        all_methods_pure_virtual: false,
+        has_non_virtual_methods: false,
+        safety_annotation: None,
+        has_copy_constructor: false,
+        has_copy_assignment: false,
+        copy_constructor_deleted: false,
+        copy_assignment_deleted: false,
+        is_move_only: false,
+        has_user_defined_constructor: false,
+        has_default_constructor: true,
+        default_constructor_deleted: false,
+    }
+}
+
+/// An owned local `name` of type `ty` without a destructor, declared
+/// `declaration_index`-th in the function body
+pub fn ir_variable(name: &str, ty: VariableType, declaration_index: usize) -> VariableInfo {
+    VariableInfo {
+        name: name.to_string(),
+        ty,
+        ownership: OwnershipState::Owned,
+        lifetime: None,
+        is_parameter: false,
+        is_static: false,
+        scope_level: 1,
+        has_destructor: false,
+        declaration_index,
+    }
+}
+
+/// A free function `void test()` in `test.cpp` with `variables` as its
+/// locals and `statements` as its single basic block
+pub fn ir_function(variables: Vec<VariableInfo>, statements: Vec<IrStatement>) -> IrFunction {
+    let mut cfg = ControlFlowGraph::new();
+    cfg.add_node(BasicBlock {
+        id: 0,
+        statements,
+        terminator: None,
+    });
+    IrFunction {
+        name: "test".to_string(),
+        cfg,
+        variables: variables
+            .into_iter()
+            .map(|info| (info.name.clone(), info))
+            .collect(),
+        return_type: "void".to_string(),
+        source_file: "test.cpp".to_string(),
+        source_line: 1,
+        is_method: false,
+        method_qualifier: None,
+        lifetime_params: HashMap::new(),
+        param_lifetimes: vec![],
+        return_lifetime: None,
+        lifetime_constraints: vec![],
+    }
+}
@@ -1,5 +1,6 @@
-use crate::ir::BorrowKind;
-use crate::parser::MethodQualifier;
+use crate::ir::{BorrowKind, IrFunction, IrProgram, IrStatement, VariableType};
+use crate::parser::annotations::LifetimeAnnotation;
+use crate::parser::{HeaderCache, MethodQualifier};
 use std::collections::{HashMap, HashSet};
 
 /// Tracks the state of member fields within a method based on 'this' pointer semantics
@@ -125,6 +126,36 @@ impl ThisPointerTracker {
         }
     }
 
+    /// Check if we can move the whole object (`std::move(*this)`)
+    ///
+    /// Rules:
+    /// - Const methods (&self): CANNOT move
+    /// - Non-const methods (&mut self): CANNOT move
+    /// - Rvalue methods (self): CAN move
+    /// - Cannot move while any field is borrowed
+    pub fn can_move_self(&self) -> Result<(), String> {
+        let mut borrowed: Vec<&String> = self.borrowed_fields.keys().collect();
+        borrowed.sort();
+        if let Some(field) = borrowed.first() {
+            return Err(format!(
+                "Cannot move '*this': field '{}' is currently borrowed",
+                field
+            ));
+        }
+
+        match self.method_qualifier {
+            Some(MethodQualifier::Const) => Err(
+                "Cannot move '*this' from const method (requires && method for self ownership)"
+                    .to_string(),
+            ),
+            Some(MethodQualifier::NonConst) => Err(
+                "Cannot move '*this' from &mut self method (use && qualified method for self ownership)"
+                    .to_string(),
+            ),
+            Some(MethodQualifier::RvalueRef) | None => Ok(()),
+        }
+    }
+
     /// Check if we can borrow a member field
     ///
     /// Rules:
@@ -176,6 +207,27 @@ impl ThisPointerTracker {
         }
     }
 
+    /// Check if a member field (or `*this` when `field` is None) can be bound
+    /// to a non-const reference parameter of `callee`
+    ///
+    /// Rules:
+    /// - Const methods (&self): CANNOT - &self cannot yield &mut to its members
+    /// - Non-const methods (&mut self): CAN
+    /// - Rvalue methods (self): CAN
+    pub fn can_pass_mutably(&self, field: Option<&str>, callee: &str) -> Result<(), String> {
+        if let Some(MethodQualifier::Const) = self.method_qualifier {
+            let what = match field {
+                Some(field) => format!("field '{}'", field),
+                None => "'*this'".to_string(),
+            };
+            return Err(format!(
+                "Cannot pass {} to non-const reference parameter of '{}' in const method",
+                what, callee
+            ));
+        }
+        Ok(())
+    }
+
     /// Mark a field as moved
     pub fn mark_field_moved(&mut self, field: String) {
         self.moved_fields.insert(field.clone());
@@ -187,3 +239,146 @@ impl ThisPointerTracker {
         self.borrowed_fields.insert(field, kind);
     }
 }
+
+/// Whether each parameter (in declaration order) is a non-const lvalue
+/// reference `T&`, keyed by qualified name and parameter count so overloads
+/// stay apart. Functions the program doesn't define fall back to their
+/// header signature, where `&'a mut` marks a `T&` parameter.
+pub fn mutable_reference_params(
+    program: &IrProgram,
+    header_cache: &HeaderCache,
+) -> HashMap<(String, usize), Vec<bool>> {
+    let mut mutable_params: HashMap<(String, usize), Vec<bool>> = header_cache
+        .signatures()
+        .filter(|(_, sig)| !sig.param_lifetimes.is_empty())
+        .map(|(name, sig)| {
+            // A leading 'self entry annotates the receiver
+            let receiver = sig.param_lifetimes[0]
+                .as_ref()
+                .and_then(|annotation| annotation.lifetime_name())
+                == Some("self");
+            let is_mutable: Vec<bool> = sig.param_lifetimes[usize::from(receiver)..]
+                .iter()
+                .map(|annotation| matches!(annotation, Some(LifetimeAnnotation::MutRef(_))))
+                .collect();
+            ((name.clone(), is_mutable.len()), is_mutable)
+        })
+        .collect();
+    for function in &program.functions {
+        let mut params: Vec<_> = function
+            .variables
+            .values()
+            .filter(|var| var.is_parameter)
+            .collect();
+        params.sort_by_key(|var| var.declaration_index);
+        let is_mutable: Vec<bool> = params
+            .iter()
+            .map(|var| matches!(var.ty, VariableType::MutableReference(_)))
+            .collect();
+        mutable_params.insert((function.name.clone(), is_mutable.len()), is_mutable);
+    }
+    mutable_params
+}
+
+/// Report members and `*this` passed to `T&` parameters from a method whose
+/// qualifier does not allow it, e.g. `bump(this->count_)` in a const method
+pub fn check_mutable_arguments(
+    function: &IrFunction,
+    mutable_params: &HashMap<(String, usize), Vec<bool>>,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    if !function.is_method {
+        return errors;
+    }
+    let tracker = ThisPointerTracker::new(function.method_qualifier.clone());
+    let mut unsafe_depth = 0;
+    for node in function.cfg.node_indices() {
+        check_call_arguments(
+            &function.cfg[node].statements,
+            &tracker,
+            mutable_params,
+            &mut unsafe_depth,
+            &mut errors,
+        );
+    }
+    errors
+}
+
+fn check_call_arguments(
+    statements: &[IrStatement],
+    tracker: &ThisPointerTracker,
+    mutable_params: &HashMap<(String, usize), Vec<bool>>,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for statement in statements {
+        match statement {
+            IrStatement::EnterUnsafe { .. } => *unsafe_depth += 1,
+            IrStatement::ExitUnsafe => *unsafe_depth = unsafe_depth.saturating_sub(1),
+            IrStatement::CallExpr { func, args, .. } if *unsafe_depth == 0 => {
+                let Some((offset, params)) = lookup_params(func, args.len(), mutable_params) else {
+                    continue;
+                };
+                for (arg, is_mutable) in args[offset..].iter().zip(params) {
+                    if !is_mutable {
+                        continue;
+                    }
+                    let field = match arg.strip_prefix("this.") {
+                        Some(path) => Some(path.split('.').next().unwrap_or(path)),
+                        None if arg == "this" => None,
+                        None => continue,
+                    };
+                    if let Err(err) = tracker.can_pass_mutably(field, func) {
+                        errors.push(err);
+                    }
+                }
+            }
+            IrStatement::If {
+                then_branch,
+                else_branch,
+            } => {
+                check_call_arguments(then_branch, tracker, mutable_params, unsafe_depth, errors);
+                if let Some(else_branch) = else_branch {
+                    check_call_arguments(
+                        else_branch,
+                        tracker,
+                        mutable_params,
+                        unsafe_depth,
+                        errors,
+                    );
+                }
+            }
+            IrStatement::Switch { cases } => {
+                for case in cases {
+                    check_call_arguments(case, tracker, mutable_params, unsafe_depth, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The overload of `func` a call with `arg_count` arguments resolves to, and
+/// where its parameters start among the arguments: method calls carry the
+/// receiver first. Calls name functions as written (`bump`), definitions may
+/// be qualified (`util::bump`). An ambiguous call resolves to nothing.
+fn lookup_params<'a>(
+    func: &str,
+    arg_count: usize,
+    mutable_params: &'a HashMap<(String, usize), Vec<bool>>,
+) -> Option<(usize, &'a Vec<bool>)> {
+    let candidates = |matches_name: &dyn Fn(&str) -> bool| {
+        let mut found = mutable_params.iter().filter_map(|((name, count), params)| {
+            let offset = arg_count
+                .checked_sub(*count)
+                .filter(|offset| *offset <= 1)?;
+            matches_name(name.as_str()).then_some((offset, params))
+        });
+        match (found.next(), found.next()) {
+            (Some(overload), None) => Some(overload),
+            _ => None,
+        }
+    };
+    candidates(&|name| name == func)
+        .or_else(|| candidates(&|name| name.rsplit("::").next() == Some(func)))
+}
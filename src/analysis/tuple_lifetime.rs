@@ -0,0 +1,390 @@
+//! Returned pairs and tuples of references
+//!
+//! A `std::pair` or `std::tuple` with reference elements does not own what
+//! those elements refer to. Returning one whose reference element points at
+//! a local or a by-value parameter leaves the caller holding a dangling
+//! reference, exactly like returning `T&` to a local:
+//!
+//! ```cpp
+//! std::pair<int&, int&> f(int& a) {
+//!     int local = 0;
+//!     int& r = local;
+//!     return std::make_pair(std::ref(r), std::ref(a));  // ERROR: element 0
+//! }
+//! ```
+//!
+//! The returned value is recognized when it is built by `std::make_pair`,
+//! `std::make_tuple`, `std::tie`, `std::forward_as_tuple`, a `pair`/`tuple`
+//! constructor, a braced initializer list, or a local pair/tuple variable
+//! built by one of those.
+
+use crate::ir::unqualified_type_name;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct TupleState {
+    /// Locals and by-value parameters, with how to describe them
+    owners: HashMap<String, &'static str>,
+    /// Reference variable -> the variable it is bound to
+    aliases: HashMap<String, String>,
+    /// Local pair/tuple variable -> the variable each element was built from
+    tuples: HashMap<String, Vec<Option<String>>>,
+    unsafe_depth: usize,
+}
+
+/// Check a function for returned pairs/tuples whose reference elements
+/// refer to locals or by-value parameters
+pub fn check_tuple_lifetime(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let Some(kind) = tuple_kind(&function.return_type) else {
+        return errors;
+    };
+    let references = reference_elements(&function.return_type);
+    if !references.contains(&true) {
+        return errors;
+    }
+
+    let mut state = TupleState::default();
+    for param in &function.parameters {
+        if !param.is_reference && !param.is_pointer {
+            state
+                .owners
+                .insert(param.name.clone(), "by-value parameter");
+        }
+    }
+    check_statements(
+        &function.body,
+        &function.name,
+        kind,
+        &references,
+        &mut state,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    kind: &str,
+    references: &[bool],
+    state: &mut TupleState,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => state.unsafe_depth += 1,
+            Statement::ExitUnsafe => state.unsafe_depth = state.unsafe_depth.saturating_sub(1),
+            Statement::VariableDecl(var) if !var.is_reference && !var.is_static => {
+                state.owners.insert(var.name.clone(), "local");
+            }
+            Statement::ReferenceBinding { name, target, .. } => match element_source(target) {
+                Some(source) => {
+                    let root = state.aliases.get(source).unwrap_or(source).clone();
+                    state.aliases.insert(name.clone(), root);
+                }
+                None => {
+                    state.aliases.remove(name);
+                }
+            },
+            Statement::Assignment {
+                lhs: Expression::Variable(name),
+                rhs,
+                ..
+            } => match tuple_elements(rhs) {
+                Some(elements) => {
+                    let sources = elements
+                        .iter()
+                        .map(|element| element_source(element).cloned())
+                        .collect();
+                    state.tuples.insert(name.clone(), sources);
+                }
+                None => {
+                    state.tuples.remove(name);
+                }
+            },
+            Statement::Return(Some(expr)) if state.unsafe_depth == 0 => {
+                let sources: Vec<Option<String>> = match (tuple_elements(expr), expr) {
+                    (Some(elements), _) => elements
+                        .iter()
+                        .map(|element| element_source(element).cloned())
+                        .collect(),
+                    (None, Expression::Variable(name)) => match state.tuples.get(name) {
+                        Some(sources) => sources.clone(),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                for (index, source) in sources.iter().enumerate() {
+                    if !references.get(index).copied().unwrap_or(false) {
+                        continue;
+                    }
+                    let Some(source) = source else {
+                        continue;
+                    };
+                    let root = state.aliases.get(source).unwrap_or(source);
+                    if let Some(owner) = state.owners.get(root) {
+                        let through = if root != source {
+                            format!(" (through '{}')", source)
+                        } else {
+                            String::new()
+                        };
+                        errors.push(format!(
+                            "In function '{}': Dangling reference: element {} of the returned {} refers to {} '{}'{}, which is destroyed when the function returns",
+                            func_name, index, kind, owner, root, through
+                        ));
+                    }
+                }
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, kind, references, state, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, kind, references, state, errors);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_statements(&case.statements, func_name, kind, references, state, errors);
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, kind, references, state, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `"pair"` or `"tuple"` for a by-value `std::pair`/`std::tuple` type
+fn tuple_kind(type_name: &str) -> Option<&'static str> {
+    let trimmed = type_name.trim();
+    if trimmed.ends_with('&') || trimmed.ends_with('*') {
+        return None;
+    }
+    match unqualified_type_name(trimmed).as_str() {
+        "pair" => Some("pair"),
+        "tuple" => Some("tuple"),
+        _ => None,
+    }
+}
+
+/// For each template argument of a pair/tuple type, whether it is an
+/// lvalue or rvalue reference
+fn reference_elements(type_name: &str) -> Vec<bool> {
+    let Some(open) = type_name.find('<') else {
+        return Vec::new();
+    };
+    let Some(close) = type_name.rfind('>') else {
+        return Vec::new();
+    };
+    if close <= open {
+        return Vec::new();
+    }
+
+    let mut elements = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in type_name[open + 1..close].chars() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                elements.push(current.trim().ends_with('&'));
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    elements.push(current.trim().ends_with('&'));
+    elements
+}
+
+/// The element expressions of a pair/tuple built in place
+fn tuple_elements(expr: &Expression) -> Option<Vec<&Expression>> {
+    match expr {
+        Expression::FunctionCall { name, args } => {
+            let callee = name.split('<').next().unwrap_or(name);
+            let callee = callee.rsplit("::").next().unwrap_or(callee);
+            let builds_tuple = matches!(
+                callee,
+                "make_pair" | "make_tuple" | "tie" | "forward_as_tuple"
+            ) || tuple_kind(name).is_some();
+            builds_tuple.then(|| args.iter().collect())
+        }
+        // `return {a, b};` arrives as a comma-joined initializer list
+        Expression::BinaryOp { op, .. } if op == "," => {
+            let mut elements = Vec::new();
+            flatten_initializer_list(expr, &mut elements);
+            Some(elements)
+        }
+        Expression::Cast { inner, .. } => tuple_elements(inner),
+        _ => None,
+    }
+}
+
+fn flatten_initializer_list<'a>(expr: &'a Expression, elements: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::BinaryOp { left, op, right } if op == "," => {
+            flatten_initializer_list(left, elements);
+            flatten_initializer_list(right, elements);
+        }
+        _ => elements.push(expr),
+    }
+}
+
+/// The variable an element refers to, looking through `std::ref`/`std::cref`
+/// and member access
+fn element_source(expr: &Expression) -> Option<&String> {
+    match expr {
+        Expression::Variable(name) => Some(name),
+        Expression::FunctionCall { name, args } => {
+            let callee = name.rsplit("::").next().unwrap_or(name);
+            if matches!(callee, "ref" | "cref") {
+                element_source(args.first()?)
+            } else {
+                None
+            }
+        }
+        Expression::MemberAccess { object, .. } => element_source(object),
+        Expression::Cast { inner, .. } => element_source(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location, variable};
+    use crate::parser::Variable;
+
+    fn reference(name: &str, type_name: &str) -> Variable {
+        Variable {
+            is_reference: true,
+            ..variable(name, type_name)
+        }
+    }
+
+    fn var(name: &str) -> Expression {
+        Expression::Variable(name.to_string())
+    }
+
+    fn returning(return_type: &str, parameters: Vec<Variable>, body: Vec<Statement>) -> Function {
+        Function {
+            return_type: return_type.to_string(),
+            ..test_fixtures::function("f", parameters, body)
+        }
+    }
+
+    #[test]
+    fn test_make_pair_of_reference_to_local() {
+        // int local; int& ref_to_local = local; return std::make_pair(ref_to_local, x);
+        let body = vec![
+            Statement::VariableDecl(variable("local", "int")),
+            Statement::VariableDecl(reference("ref_to_local", "int &")),
+            Statement::ReferenceBinding {
+                name: "ref_to_local".to_string(),
+                target: var("local"),
+                is_mutable: true,
+                location: location(1),
+            },
+            Statement::Return(Some(Expression::FunctionCall {
+                name: "std::make_pair".to_string(),
+                args: vec![var("ref_to_local"), var("x")],
+            })),
+        ];
+        let f = returning(
+            "std::pair<const int &, const int &>",
+            vec![reference("x", "const int &")],
+            body,
+        );
+        let errors = check_tuple_lifetime(&f, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains(
+            "element 0 of the returned pair refers to local 'local' (through 'ref_to_local')"
+        ));
+    }
+
+    #[test]
+    fn test_braced_tuple_of_by_value_parameter() {
+        // return {a, b}; with `a` by value and `b` by reference
+        let returned = Expression::BinaryOp {
+            left: Box::new(var("a")),
+            op: ",".to_string(),
+            right: Box::new(var("b")),
+        };
+        let f = returning(
+            "std::tuple<int &, int &>",
+            vec![variable("a", "int"), reference("b", "int &")],
+            vec![Statement::Return(Some(returned))],
+        );
+        let errors = check_tuple_lifetime(&f, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].contains("element 0 of the returned tuple refers to by-value parameter 'a'")
+        );
+    }
+
+    #[test]
+    fn test_value_elements_and_reference_parameters_ok() {
+        // std::pair<int, int&> f(int& a) { int local; return std::make_pair(local, std::ref(a)); }
+        let body = vec![
+            Statement::VariableDecl(variable("local", "int")),
+            Statement::Return(Some(Expression::FunctionCall {
+                name: "std::make_pair".to_string(),
+                args: vec![
+                    var("local"),
+                    Expression::FunctionCall {
+                        name: "std::ref".to_string(),
+                        args: vec![var("a")],
+                    },
+                ],
+            })),
+        ];
+        let f = returning("std::pair<int, int &>", vec![reference("a", "int &")], body);
+        assert!(check_tuple_lifetime(&f, SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_returned_local_tuple_built_by_tie() {
+        let body = vec![
+            Statement::VariableDecl(variable("local", "int")),
+            Statement::VariableDecl(variable("t", "std::tuple<int &>")),
+            Statement::Assignment {
+                lhs: var("t"),
+                rhs: Expression::FunctionCall {
+                    name: "std::tie".to_string(),
+                    args: vec![var("local")],
+                },
+                location: location(1),
+            },
+            Statement::Return(Some(var("t"))),
+        ];
+        let f = returning("std::tuple<int &>", Vec::new(), body);
+        let errors = check_tuple_lifetime(&f, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("refers to local 'local'"));
+    }
+
+    #[test]
+    fn test_reference_elements() {
+        assert_eq!(
+            reference_elements("std::pair<std::map<int, int> &, int>"),
+            vec![true, false]
+        );
+        assert_eq!(tuple_kind("std::pair<int &, int &> &"), None);
+    }
+}
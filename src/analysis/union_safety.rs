@@ -0,0 +1,438 @@
+//! Inactive Union Member Reads
+//!
+//! Only the member of a union that was written last is active; reading any
+//! other member is undefined behavior (type punning without a cast):
+//!
+//! ```cpp
+//! union Value { int i; float f; };
+//!
+//! Value v;
+//! v.i = 42;
+//! float f = v.f;  // ERROR: 'i' is the active member
+//! ```
+//!
+//! The active member of each local union is tracked through writes of the
+//! form `u.m = ...`. Anything that could write the union behind our back
+//! (assigning the whole union, taking an address, passing it to a call,
+//! binding a reference into it) forgets what is known, as do loop
+//! boundaries and branches that disagree.
+
+use super::at_line;
+use crate::parser::ast_visitor::Class;
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement, Variable};
+use std::collections::{HashMap, HashSet};
+
+/// Union-typed variables in scope and their last-written member, if known
+struct UnionState<'a> {
+    union_types: &'a HashSet<&'a str>,
+    variables: HashSet<String>,
+    active: HashMap<String, String>,
+}
+
+impl UnionState<'_> {
+    fn declare(&mut self, var: &Variable) {
+        self.active.remove(&var.name);
+        if !var.is_pointer && is_union_type(&var.type_name, self.union_types) {
+            self.variables.insert(var.name.clone());
+        } else {
+            self.variables.remove(&var.name);
+        }
+    }
+
+    /// The union variable `expr` names, if any
+    fn union_variable<'e>(&self, expr: &'e Expression) -> Option<&'e String> {
+        match expr {
+            Expression::Variable(name) if self.variables.contains(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Keep only what every branch agrees on
+    fn merge(&mut self, branches: &[HashMap<String, String>]) {
+        self.active.retain(|name, member| {
+            branches
+                .iter()
+                .all(|branch| branch.get(name) == Some(member))
+        });
+    }
+}
+
+fn is_union_type(type_name: &str, union_types: &HashSet<&str>) -> bool {
+    let base = type_name
+        .trim()
+        .trim_start_matches("const ")
+        .trim_start_matches("union ")
+        .trim_end_matches(['&', ' '])
+        .trim_end_matches(" const");
+    union_types.iter().any(|union_name| {
+        *union_name == base
+            || union_name.ends_with(&format!("::{}", base))
+            || base.ends_with(&format!("::{}", union_name))
+    })
+}
+
+/// Check a function for reads of a union member other than the one last written
+pub fn check_union_access(
+    function: &Function,
+    classes: &[Class],
+    function_safety: SafetyMode,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let union_types: HashSet<&str> = classes
+        .iter()
+        .filter(|class| class.is_union)
+        .map(|class| class.name.as_str())
+        .collect();
+    if union_types.is_empty() {
+        return errors;
+    }
+
+    let mut state = UnionState {
+        union_types: &union_types,
+        variables: HashSet::new(),
+        active: HashMap::new(),
+    };
+    for param in &function.parameters {
+        state.declare(param);
+    }
+
+    let mut unsafe_depth = 0;
+    check_statements(
+        &function.body,
+        &function.name,
+        &mut state,
+        &mut unsafe_depth,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    state: &mut UnionState,
+    unsafe_depth: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => *unsafe_depth += 1,
+            Statement::ExitUnsafe => *unsafe_depth = unsafe_depth.saturating_sub(1),
+            Statement::EnterLoop | Statement::EnterDoLoop | Statement::ExitLoop => {
+                state.active.clear();
+            }
+            Statement::VariableDecl(var) => state.declare(var),
+            Statement::Assignment { lhs, rhs, location } => {
+                report(rhs, func_name, location.line, state, *unsafe_depth, errors);
+                forget_escaped(rhs, state);
+                match lhs {
+                    Expression::MemberAccess { object, field } => {
+                        if let Some(name) = state.union_variable(object) {
+                            state.active.insert(name.clone(), field.clone());
+                        } else {
+                            report(
+                                object,
+                                func_name,
+                                location.line,
+                                state,
+                                *unsafe_depth,
+                                errors,
+                            );
+                        }
+                    }
+                    Expression::Variable(name) => {
+                        state.active.remove(name);
+                    }
+                    _ => {
+                        report(lhs, func_name, location.line, state, *unsafe_depth, errors);
+                        forget_escaped(lhs, state);
+                    }
+                }
+            }
+            Statement::ReferenceBinding { target, .. } => {
+                if let Some(name) = root_variable(target) {
+                    state.active.remove(name);
+                }
+            }
+            Statement::Return(Some(expr)) => {
+                report(expr, func_name, 0, state, *unsafe_depth, errors)
+            }
+            Statement::FunctionCall {
+                name,
+                args,
+                location,
+            } => {
+                let call = Expression::FunctionCall {
+                    name: name.clone(),
+                    args: args.clone(),
+                };
+                report(
+                    &call,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+                forget_escaped(&call, state);
+            }
+            Statement::ExpressionStatement { expr, location } => {
+                report(expr, func_name, location.line, state, *unsafe_depth, errors);
+                forget_escaped(expr, state);
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+                location,
+            } => {
+                report(
+                    condition,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+                let before = state.active.clone();
+                check_statements(then_branch, func_name, state, unsafe_depth, errors);
+                let after_then = std::mem::replace(&mut state.active, before);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, state, unsafe_depth, errors);
+                }
+                state.merge(&[after_then]);
+            }
+            Statement::Switch {
+                condition,
+                cases,
+                location,
+            } => {
+                report(
+                    condition,
+                    func_name,
+                    location.line,
+                    state,
+                    *unsafe_depth,
+                    errors,
+                );
+                let before = state.active.clone();
+                let mut after_cases = Vec::new();
+                for case in cases {
+                    state.active = before.clone();
+                    check_statements(&case.statements, func_name, state, unsafe_depth, errors);
+                    after_cases.push(std::mem::take(&mut state.active));
+                }
+                state.active = before;
+                state.merge(&after_cases);
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, state, unsafe_depth, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads in unsafe blocks are not reported, but their writes still count
+fn report(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    state: &UnionState,
+    unsafe_depth: usize,
+    errors: &mut Vec<String>,
+) {
+    if unsafe_depth == 0 {
+        check_expr(expr, func_name, line, state, errors);
+    }
+}
+
+/// Report reads of inactive members anywhere inside an expression
+fn check_expr(
+    expr: &Expression,
+    func_name: &str,
+    line: u32,
+    state: &UnionState,
+    errors: &mut Vec<String>,
+) {
+    match expr {
+        Expression::MemberAccess { object, field } => {
+            if let Some(name) = state.union_variable(object) {
+                if let Some(active) = state.active.get(name).filter(|active| *active != field) {
+                    errors.push(format!(
+                        "In function '{}': reading inactive union member '{}' of '{}'{} (last written: '{}')",
+                        func_name,
+                        field,
+                        name,
+                        at_line(line),
+                        active
+                    ));
+                }
+            }
+            check_expr(object, func_name, line, state, errors);
+        }
+        // Taking an address is not a read
+        Expression::AddressOf(_) => {}
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::Cast { inner, .. } => check_expr(inner, func_name, line, state, errors),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                check_expr(arg, func_name, line, state, errors);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            check_expr(left, func_name, line, state, errors);
+            check_expr(right, func_name, line, state, errors);
+        }
+        Expression::ArraySubscript { array, index } => {
+            check_expr(array, func_name, line, state, errors);
+            check_expr(index, func_name, line, state, errors);
+        }
+        _ => {}
+    }
+}
+
+/// Forget the active member of unions that escape: their address is taken
+/// or the whole union is passed to a call
+fn forget_escaped(expr: &Expression, state: &mut UnionState) {
+    match expr {
+        Expression::AddressOf(inner) => {
+            if let Some(name) = root_variable(inner) {
+                state.active.remove(name);
+            }
+        }
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                if let Expression::Variable(name) = arg {
+                    state.active.remove(name);
+                }
+                forget_escaped(arg, state);
+            }
+        }
+        Expression::Move { inner, .. }
+        | Expression::Dereference(inner)
+        | Expression::Cast { inner, .. } => forget_escaped(inner, state),
+        Expression::BinaryOp { left, right, .. } => {
+            forget_escaped(left, state);
+            forget_escaped(right, state);
+        }
+        Expression::MemberAccess { object, .. } => forget_escaped(object, state),
+        _ => {}
+    }
+}
+
+/// `u` for `u`, `u.m`, `u.m.n`
+fn root_variable(expr: &Expression) -> Option<&String> {
+    match expr {
+        Expression::Variable(name) => Some(name),
+        Expression::MemberAccess { object, .. } => root_variable(object),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::location;
+
+    fn member(object: &str, field: &str) -> Expression {
+        Expression::MemberAccess {
+            object: Box::new(Expression::Variable(object.to_string())),
+            field: field.to_string(),
+        }
+    }
+
+    fn write(object: &str, field: &str, line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: member(object, field),
+            rhs: Expression::Literal("1".to_string()),
+            location: location(line),
+        }
+    }
+
+    fn read(object: &str, field: &str, line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: Expression::Variable("out".to_string()),
+            rhs: member(object, field),
+            location: location(line),
+        }
+    }
+
+    fn check(body: Vec<Statement>) -> Vec<String> {
+        let union_types: HashSet<&str> = ["Value"].into_iter().collect();
+        let mut state = UnionState {
+            union_types: &union_types,
+            variables: ["v".to_string()].into_iter().collect(),
+            active: HashMap::new(),
+        };
+        let mut errors = Vec::new();
+        check_statements(&body, "f", &mut state, &mut 0, &mut errors);
+        errors
+    }
+
+    #[test]
+    fn test_read_of_inactive_member() {
+        let errors = check(vec![write("v", "i", 3), read("v", "f", 4)]);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].contains("reading inactive union member 'f' of 'v' at line 4"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_read_in_return_names_no_line() {
+        let errors = check(vec![
+            write("v", "i", 3),
+            Statement::Return(Some(member("v", "f"))),
+        ]);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(
+            errors[0].ends_with("member 'f' of 'v' (last written: 'i')"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_read_of_active_member_ok() {
+        let errors = check(vec![
+            write("v", "i", 3),
+            read("v", "i", 4),
+            write("v", "f", 5),
+            read("v", "f", 6),
+        ]);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_branches_that_disagree_forget_active_member() {
+        let errors = check(vec![
+            Statement::If {
+                condition: Expression::Variable("c".to_string()),
+                then_branch: vec![write("v", "i", 4)],
+                else_branch: Some(vec![write("v", "f", 6)]),
+                location: location(3),
+            },
+            read("v", "f", 8),
+        ]);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_is_union_type() {
+        let union_types: HashSet<&str> = ["ns::Value"].into_iter().collect();
+        assert!(is_union_type("Value", &union_types));
+        assert!(is_union_type("const union ns::Value &", &union_types));
+        assert!(!is_union_type("OtherValue", &union_types));
+    }
+}
@@ -0,0 +1,272 @@
+//! Unnecessary `@unsafe` Blocks
+//!
+//! An `@unsafe` block should wrap only the operations that need it. One that
+//! hides no diagnostic is noise, and it lets later edits slip unchecked code
+//! in unnoticed:
+//!
+//! ```cpp
+//! // @safe
+//! void update(int& total, int delta) {
+//!     // @unsafe
+//!     {
+//!         total += delta; // WARNING (--pedantic): nothing here is unsafe
+//!     }
+//! }
+//! ```
+//!
+//! Each block is checked by running the given passes again with that block
+//! (and the blocks nested in it) treated as safe code: if they report nothing
+//! more, the block is unnecessary. The borrow checker probes its blocks
+//! itself (`analysis::unsafe_blocks_suppressing`), so each warning carries the
+//! line of its block's opening brace for the caller to match against.
+
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Function, Statement};
+
+/// Check a function for `@unsafe` blocks that suppress none of the
+/// diagnostics `count_violations` counts. Each warning comes with the line of
+/// its block's `EnterUnsafe`.
+pub fn check_unnecessary_unsafe(
+    function: &Function,
+    function_safety: SafetyMode,
+    count_violations: impl Fn(&Function) -> usize,
+) -> Vec<(u32, String)> {
+    let mut warnings = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return warnings;
+    }
+
+    let mut lines = Vec::new();
+    block_lines(&function.body, &mut Vec::new(), &mut Vec::new(), &mut lines);
+    if lines.is_empty() {
+        return warnings;
+    }
+
+    let baseline = count_violations(function);
+    for (index, (block_line, line)) in lines.into_iter().enumerate() {
+        let mut variant = function.clone();
+        strip_block(&mut variant.body, index, &mut 0, false);
+        if count_violations(&variant) > baseline {
+            continue;
+        }
+        let warning = match line {
+            Some(line) => format!(
+                "In function '{}': warning: unnecessary @unsafe block at line {}: nothing inside it needs unsafe",
+                function.name, line
+            ),
+            None => format!(
+                "In function '{}': warning: unnecessary @unsafe block: it is empty",
+                function.name
+            ),
+        };
+        warnings.push((block_line, warning));
+    }
+    warnings
+}
+
+/// Line of each `@unsafe` block's `EnterUnsafe` and of its first statement,
+/// in the order the markers appear; `None` for an empty block
+fn block_lines(
+    stmts: &[Statement],
+    open: &mut Vec<usize>,
+    pending: &mut Vec<usize>,
+    lines: &mut Vec<(u32, Option<u32>)>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { location } => {
+                open.push(lines.len());
+                pending.push(lines.len());
+                lines.push((location.line, None));
+                continue;
+            }
+            Statement::ExitUnsafe => {
+                if let Some(block) = open.pop() {
+                    pending.retain(|pending| *pending != block);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(line) = statement_line(stmt) {
+            for block in pending.drain(..) {
+                lines[block].1 = Some(line);
+            }
+        }
+        match stmt {
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                block_lines(then_branch, open, pending, lines);
+                if let Some(else_stmts) = else_branch {
+                    block_lines(else_stmts, open, pending, lines);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    block_lines(&case.statements, open, pending, lines);
+                }
+            }
+            Statement::Block(stmts) => block_lines(stmts, open, pending, lines),
+            _ => {}
+        }
+    }
+}
+
+fn statement_line(stmt: &Statement) -> Option<u32> {
+    match stmt {
+        Statement::VariableDecl(var) => Some(var.location.line),
+        Statement::Assignment { location, .. }
+        | Statement::ReferenceBinding { location, .. }
+        | Statement::FunctionCall { location, .. }
+        | Statement::If { location, .. }
+        | Statement::Switch { location, .. }
+        | Statement::ExpressionStatement { location, .. }
+        | Statement::PackExpansion { location, .. }
+        | Statement::LambdaExpr { location, .. }
+        | Statement::Suspend { location } => Some(location.line),
+        _ => None,
+    }
+}
+
+/// Remove the markers of the `target`-th `@unsafe` block and of every block
+/// nested in it. `seen` counts the blocks met so far; `inside` is set within
+/// the target block.
+fn strip_block(stmts: &mut Vec<Statement>, target: usize, seen: &mut usize, inside: bool) {
+    // Depth of the markers being removed that were opened in this list
+    let mut depth = 0;
+    let mut kept = Vec::with_capacity(stmts.len());
+    for mut stmt in stmts.drain(..) {
+        match &mut stmt {
+            Statement::EnterUnsafe { .. } => {
+                let index = *seen;
+                *seen += 1;
+                if inside || depth > 0 || index == target {
+                    depth += 1;
+                    continue;
+                }
+            }
+            Statement::ExitUnsafe if depth > 0 => {
+                depth -= 1;
+                continue;
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                strip_block(then_branch, target, seen, inside || depth > 0);
+                if let Some(else_stmts) = else_branch {
+                    strip_block(else_stmts, target, seen, inside || depth > 0);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    strip_block(&mut case.statements, target, seen, inside || depth > 0);
+                }
+            }
+            Statement::Block(stmts) => strip_block(stmts, target, seen, inside || depth > 0),
+            _ => {}
+        }
+        kept.push(stmt);
+    }
+    *stmts = kept;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures::{self, location};
+    use crate::parser::Expression;
+
+    fn deref(line: u32) -> Statement {
+        Statement::ExpressionStatement {
+            expr: Expression::Dereference(Box::new(Expression::Variable("p".to_string()))),
+            location: location(line),
+        }
+    }
+
+    fn assign(line: u32) -> Statement {
+        Statement::Assignment {
+            lhs: Expression::Variable("x".to_string()),
+            rhs: Expression::Literal("0".to_string()),
+            location: location(line),
+        }
+    }
+
+    fn function(body: Vec<Statement>) -> Function {
+        test_fixtures::function("update", vec![], body)
+    }
+
+    /// Stand-in for the real passes: every dereference outside an `@unsafe`
+    /// block is a violation
+    fn count_derefs(function: &Function) -> usize {
+        fn count(stmts: &[Statement], depth: &mut usize) -> usize {
+            let mut total = 0;
+            for stmt in stmts {
+                match stmt {
+                    Statement::EnterUnsafe { .. } => *depth += 1,
+                    Statement::ExitUnsafe => *depth -= 1,
+                    Statement::ExpressionStatement {
+                        expr: Expression::Dereference(_),
+                        ..
+                    } if *depth == 0 => total += 1,
+                    Statement::If { then_branch, .. } => total += count(then_branch, depth),
+                    _ => {}
+                }
+            }
+            total
+        }
+        count(&function.body, &mut 0)
+    }
+
+    /// An `@unsafe` block whose opening brace is on `line`
+    fn unsafe_block(line: u32, body: Vec<Statement>) -> Vec<Statement> {
+        let mut stmts = vec![
+            Statement::EnterScope,
+            Statement::EnterUnsafe {
+                location: location(line),
+            },
+        ];
+        stmts.extend(body);
+        stmts.extend([Statement::ExitUnsafe, Statement::ExitScope]);
+        stmts
+    }
+
+    #[test]
+    fn test_block_of_safe_operations() {
+        let mut body = unsafe_block(2, vec![assign(3)]);
+        body.extend(unsafe_block(5, vec![deref(6)]));
+        let func = function(body);
+
+        let warnings = check_unnecessary_unsafe(&func, SafetyMode::Safe, count_derefs);
+        assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+        let (block_line, warning) = &warnings[0];
+        assert_eq!(*block_line, 2);
+        assert!(warning.contains("unnecessary @unsafe block at line 3"));
+    }
+
+    #[test]
+    fn test_outer_block_needed_by_nested_block() {
+        // The inner block is redundant, but the outer one isn't: removing it
+        // drops the inner one's coverage too
+        let mut inner = unsafe_block(4, vec![deref(5)]);
+        inner.insert(0, assign(3));
+        let func = function(vec![Statement::If {
+            condition: Expression::Variable("ready".to_string()),
+            then_branch: unsafe_block(2, inner),
+            else_branch: None,
+            location: location(2),
+        }]);
+
+        let warnings = check_unnecessary_unsafe(&func, SafetyMode::Safe, count_derefs);
+        assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+        let (block_line, warning) = &warnings[0];
+        assert_eq!(*block_line, 4);
+        assert!(warning.contains("unnecessary @unsafe block at line 5"));
+    }
+}
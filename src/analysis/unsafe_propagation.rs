@@ -478,7 +478,7 @@ fn check_statements_with_unsafe_tracking(
     for stmt in statements {
         // Track unsafe scope depth
         match stmt {
-            Statement::EnterUnsafe => {
+            Statement::EnterUnsafe { .. } => {
                 unsafe_depth += 1;
                 continue;
             }
@@ -1130,6 +1130,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1161,6 +1163,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1194,6 +1198,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 10,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
@@ -1225,6 +1231,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 15,
                 column: 5,
+                end_line: 0,
+                end_column: 0,
             },
         };
 
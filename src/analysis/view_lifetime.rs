@@ -0,0 +1,277 @@
+//! View-into-Temporary Detection
+//!
+//! `std::string_view` and `std::span` do not own their storage. Creating one
+//! from a temporary owning container leaves it dangling as soon as the full
+//! expression ends:
+//!
+//! ```cpp
+//! std::string_view sv = std::string("hello");  // ERROR: temporary destroyed
+//! std::span<int> s = std::vector<int>{1, 2};   // ERROR
+//! std::string_view ok = name;                   // OK: borrows `name`
+//! ```
+//!
+//! Views created from named variables are modeled as borrows in the IR and
+//! checked by the borrow/scope analyses. Returning a view is checked here:
+//! a view of a local or a by-value parameter dangles once the function
+//! returns, and no lifetime annotation can tie a by-value parameter to the
+//! caller.
+//!
+//! ```cpp
+//! std::string_view f(std::string s) { return s; }  // ERROR: `s` destroyed at return
+//! ```
+
+use crate::ir::{is_view_type, unqualified_type_name};
+use crate::parser::safety_annotations::SafetyMode;
+use crate::parser::{Expression, Function, Statement};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Default)]
+struct ViewState {
+    /// Variables declared with a view type
+    views: HashSet<String>,
+    /// View variable -> the variable it was last created from
+    sources: HashMap<String, String>,
+    /// Owning locals and by-value parameters, with how to describe them
+    owners: HashMap<String, &'static str>,
+    unsafe_depth: usize,
+}
+
+/// Check a function for views initialized from temporaries
+pub fn check_view_lifetime(function: &Function, function_safety: SafetyMode) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // Only check @safe functions
+    if function_safety != SafetyMode::Safe {
+        return errors;
+    }
+
+    let mut state = ViewState::default();
+    let returns_view = is_view_type(&function.return_type);
+    if returns_view {
+        for param in &function.parameters {
+            if !param.is_reference && is_owning_container(&param.type_name) {
+                state
+                    .owners
+                    .insert(param.name.clone(), "by-value parameter");
+            }
+        }
+    }
+    check_statements(
+        &function.body,
+        &function.name,
+        returns_view,
+        &mut state,
+        &mut errors,
+    );
+    errors
+}
+
+fn check_statements(
+    stmts: &[Statement],
+    func_name: &str,
+    returns_view: bool,
+    state: &mut ViewState,
+    errors: &mut Vec<String>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Statement::EnterUnsafe { .. } => state.unsafe_depth += 1,
+            Statement::ExitUnsafe => state.unsafe_depth = state.unsafe_depth.saturating_sub(1),
+            Statement::VariableDecl(var) if is_view_type(&var.type_name) => {
+                state.views.insert(var.name.clone());
+            }
+            Statement::VariableDecl(var)
+                if returns_view
+                    && !var.is_reference
+                    && !var.is_static
+                    && is_owning_container(&var.type_name) =>
+            {
+                state.owners.insert(var.name.clone(), "local");
+            }
+            Statement::Assignment {
+                lhs: Expression::Variable(name),
+                rhs,
+                location,
+            } if state.views.contains(name) => {
+                match named_source(rhs) {
+                    Some(source) => {
+                        state.sources.insert(name.clone(), source.clone());
+                    }
+                    None => {
+                        state.sources.remove(name);
+                    }
+                }
+                if state.unsafe_depth > 0 {
+                    continue;
+                }
+                if let Some(temporary) = owning_temporary(rhs) {
+                    errors.push(format!(
+                        "In function '{}': Dangling reference: '{}' views a temporary '{}' that is destroyed at the end of the statement (line {})",
+                        func_name, name, temporary, location.line
+                    ));
+                }
+            }
+            Statement::Return(Some(expr)) if returns_view && state.unsafe_depth == 0 => {
+                let Some(mut source) = named_source(expr) else {
+                    continue;
+                };
+                if state.views.contains(source) {
+                    match state.sources.get(source) {
+                        Some(viewed) => source = viewed,
+                        None => continue,
+                    }
+                }
+                if let Some(kind) = state.owners.get(source) {
+                    errors.push(format!(
+                        "In function '{}': Dangling reference: returned view borrows {} '{}', which is destroyed when the function returns",
+                        func_name, kind, source
+                    ));
+                }
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                check_statements(then_branch, func_name, returns_view, state, errors);
+                if let Some(else_stmts) = else_branch {
+                    check_statements(else_stmts, func_name, returns_view, state, errors);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    check_statements(&case.statements, func_name, returns_view, state, errors);
+                }
+            }
+            Statement::Block(stmts) => {
+                check_statements(stmts, func_name, returns_view, state, errors)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The named variable a view expression is built from, looking through the
+/// same conversions as [`owning_temporary`]
+fn named_source(expr: &Expression) -> Option<&String> {
+    match expr {
+        Expression::Variable(name) => Some(name),
+        Expression::FunctionCall { name, args }
+            if name.contains("operator basic_string_view")
+                || name.contains("operator std::basic_string_view")
+                || is_view_type(name) =>
+        {
+            named_source(args.first()?)
+        }
+        Expression::Cast { inner, .. } => named_source(inner),
+        _ => None,
+    }
+}
+
+/// Type name of the owning temporary a view initializer is built from, looking
+/// through `operator basic_string_view` conversions and view constructors
+fn owning_temporary(expr: &Expression) -> Option<&str> {
+    match expr {
+        Expression::FunctionCall { name, .. } if is_owning_container(name) => Some(name),
+        Expression::FunctionCall { name, args }
+            if name.contains("operator basic_string_view")
+                || name.contains("operator std::basic_string_view")
+                || is_view_type(name) =>
+        {
+            owning_temporary(args.first()?)
+        }
+        Expression::Cast { inner, .. } => owning_temporary(inner),
+        _ => None,
+    }
+}
+
+/// Owning string/vector types whose storage a view can point into
+fn is_owning_container(type_name: &str) -> bool {
+    matches!(
+        unqualified_type_name(type_name).as_str(),
+        "string" | "wstring" | "u8string" | "u16string" | "u32string" | "basic_string" | "vector"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_fixtures;
+
+    fn call(name: &str, args: Vec<Expression>) -> Expression {
+        Expression::FunctionCall {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn test_view_of_temporary_string() {
+        let temporary = call(
+            "std::string",
+            vec![Expression::Literal("\"hello\"".to_string())],
+        );
+        assert_eq!(owning_temporary(&temporary), Some("std::string"));
+
+        let converted = call(
+            "std::basic_string<char>::operator basic_string_view",
+            vec![temporary],
+        );
+        assert_eq!(owning_temporary(&converted), Some("std::string"));
+    }
+
+    fn function_returning_view(param_is_reference: bool, body: Vec<Statement>) -> Function {
+        let param = crate::parser::Variable {
+            is_reference: param_is_reference,
+            is_const: param_is_reference,
+            ..test_fixtures::variable("s", "std::string")
+        };
+        Function {
+            return_type: "std::string_view".to_string(),
+            ..test_fixtures::function("f", vec![param], body)
+        }
+    }
+
+    #[test]
+    fn test_returning_view_of_by_value_parameter() {
+        let conversion = call(
+            "std::basic_string<char>::operator basic_string_view",
+            vec![Expression::Variable("s".to_string())],
+        );
+        let function =
+            function_returning_view(false, vec![Statement::Return(Some(conversion.clone()))]);
+        let errors = check_view_lifetime(&function, SafetyMode::Safe);
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("returned view borrows by-value parameter 's'"));
+
+        // A reference parameter outlives the call, so the view is fine
+        let function = function_returning_view(true, vec![Statement::Return(Some(conversion))]);
+        assert!(check_view_lifetime(&function, SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_returning_view_of_static_local_ok() {
+        // static const std::string table = "abc"; return table;
+        let table = crate::parser::Variable {
+            is_static: true,
+            ..test_fixtures::variable("table", "std::string")
+        };
+        let function = function_returning_view(
+            true,
+            vec![
+                Statement::VariableDecl(table),
+                Statement::Return(Some(Expression::Variable("table".to_string()))),
+            ],
+        );
+        assert!(check_view_lifetime(&function, SafetyMode::Safe).is_empty());
+    }
+
+    #[test]
+    fn test_view_of_named_string_ok() {
+        let converted = call(
+            "std::basic_string<char>::operator basic_string_view",
+            vec![Expression::Variable("name".to_string())],
+        );
+        assert_eq!(owning_temporary(&converted), None);
+    }
+}
@@ -0,0 +1,347 @@
+//! Persistent Analysis Daemon
+//!
+//! `--daemon` keeps one process running for an editor: instead of spawning
+//! the checker per keystroke, the editor writes analysis requests to its
+//! stdin and reads the results from its stdout. Headers are parsed once and
+//! their signatures reused by later requests.
+//!
+//! Each message, in both directions, is a JSON object framed like the
+//! Language Server Protocol:
+//!
+//! ```text
+//! Content-Length: 52\r\n
+//! \r\n
+//! {"id":1,"file":"src/a.cpp","contents":"int main() {}"}
+//! ```
+//!
+//! A request names the `file` to analyze and may carry its unsaved
+//! `contents`; without them the file is read from disk. The `id`, any JSON
+//! value, is echoed back. The response holds either the `violations`, each
+//! shaped like an entry of the `--format json` report, or an `error`:
+//!
+//! ```text
+//! {"id":1,"file":"src/a.cpp","violations":[{"file":...,"kind":...,"severity":...,"message":...}]}
+//! {"id":2,"file":"src/b.cpp","error":"Failed to read src/b.cpp: ..."}
+//! ```
+//!
+//! Under `--max-violations`, a response cut down to the limit also carries
+//! `"truncated": true`.
+//!
+//! A message that can't be read, such as one with a malformed header, a
+//! body over 64 MiB or a body that isn't UTF-8, gets an `error` response
+//! with a null `id`; reading resumes at the next `Content-Length` header.
+//!
+//! The daemon exits when stdin is closed. A request for a header it has
+//! already parsed, or any parsed header changing on disk, drops the cached
+//! headers, so edits to headers are picked up on the next request.
+
+use crate::check::{self, CheckOptions};
+use crate::parser::HeaderCache;
+use crate::report::{self, Policy};
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Largest message body read into memory
+const MAX_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
+/// Longest header line read at once; longer lines are read in pieces
+const MAX_HEADER_LINE: u64 = 4096;
+
+/// Answers requests, keeping the annotations of the headers seen so far
+pub struct Daemon {
+    options: CheckOptions,
+    policy: Policy,
+    /// `--max-violations`, applied to what `policy` reports
+    max_violations: Option<usize>,
+    headers: HeaderCache,
+    /// When each header in `headers` was last modified as it was parsed
+    header_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Daemon {
+    pub fn new(options: CheckOptions, policy: Policy, max_violations: Option<usize>) -> Self {
+        Self {
+            options,
+            policy,
+            max_violations,
+            headers: HeaderCache::new(),
+            header_mtimes: Vec::new(),
+        }
+    }
+
+    /// Serve requests from `input` until it is closed
+    pub fn serve(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+        let mut messages = MessageReader::new(input);
+        while let Some(message) = messages.next_message()? {
+            let request = message.and_then(|body| {
+                serde_json::from_str::<Value>(&body).map_err(|e| format!("Invalid request: {}", e))
+            });
+            let response = match request {
+                Ok(request) => self.handle(&request),
+                Err(e) => json!({ "id": Value::Null, "error": e }),
+            };
+            write_message(output, &response)?;
+        }
+        Ok(())
+    }
+
+    /// The response to one request
+    pub fn handle(&mut self, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let Some(file) = request.get("file").and_then(Value::as_str) else {
+            return json!({ "id": id, "error": "Request has no \"file\"" });
+        };
+        let result = match request.get("contents").and_then(Value::as_str) {
+            Some(contents) => self.analyze(file, contents),
+            None => std::fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read {}: {}", file, e))
+                .and_then(|contents| self.analyze(file, &contents)),
+        };
+        match result {
+            Ok(analyzed) => {
+                let violations: Vec<Value> = analyzed
+                    .violations
+                    .iter()
+                    .map(|message| {
+                        report::violation_json(
+                            Path::new(file),
+                            message,
+                            analyzed.spans.get(message),
+                        )
+                    })
+                    .collect();
+                let mut response = json!({ "id": id, "file": file, "violations": violations });
+                if analyzed.stopped_early {
+                    response["truncated"] = json!(true);
+                }
+                response
+            }
+            Err(e) => json!({ "id": id, "file": file, "error": e }),
+        }
+    }
+
+    fn analyze(&mut self, file: &str, contents: &str) -> Result<check::AnalysisReport, String> {
+        let path = Path::new(file);
+        // The file being edited is one of the cached headers, or a cached
+        // header was saved since it was parsed
+        let stale = self
+            .header_mtimes
+            .iter()
+            .any(|(header, mtime)| modified(header) != *mtime);
+        if stale || self.headers.has_processed(path) {
+            self.headers = HeaderCache::new();
+            self.header_mtimes.clear();
+        }
+
+        self.headers
+            .set_include_paths(self.options.include_paths.clone());
+        self.headers.parse_includes_from_content(contents, path)?;
+        if !self.headers.annotation_errors.is_empty() {
+            // Read the headers again once they are fixed
+            let errors = self.headers.annotation_errors.join("\n");
+            self.headers = HeaderCache::new();
+            self.header_mtimes.clear();
+            return Err(errors);
+        }
+        // Headers are only ever added to the cache
+        let parsed = &self.headers.processed_headers()[self.header_mtimes.len()..];
+        self.header_mtimes.extend(
+            parsed
+                .iter()
+                .map(|header| (header.clone(), modified(header))),
+        );
+
+        let mut analyzed =
+            check::check_source_with_headers(contents, file, &self.options, self.headers.clone())?;
+        analyzed.violations = self.policy.filter(path, analyzed.violations);
+        let count = analyzed.violations.len();
+        if let Some(max) = self.max_violations.filter(|&max| count > max) {
+            analyzed.violations.truncate(max);
+            analyzed.stopped_early = true;
+        }
+        Ok(analyzed)
+    }
+}
+
+/// When `path` was last modified, if it can be told
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Reads framed messages, skipping past the ones that can't be read
+pub struct MessageReader<R> {
+    input: R,
+    /// The header that starts the next message, found while skipping past
+    /// a malformed one
+    pending: Option<String>,
+}
+
+impl<R: BufRead> MessageReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            pending: None,
+        }
+    }
+
+    /// The body of the next message, or why it couldn't be read; `None`
+    /// once the input is closed
+    pub fn next_message(&mut self) -> io::Result<Option<Result<String, String>>> {
+        let mut length = None;
+        loop {
+            let Some(line) = self.read_line()? else {
+                return Ok(length.map(|_| Err("Input closed inside a message header".to_string())));
+            };
+            let header = line.trim_end_matches(['\r', '\n']);
+            if header.is_empty() {
+                if length.is_some() {
+                    break;
+                }
+                continue;
+            }
+            match parse_header(header) {
+                Ok(Some(value)) => length = Some(value),
+                Ok(None) => {}
+                Err(e) => {
+                    self.skip_to_next_header()?;
+                    return Ok(Some(Err(e)));
+                }
+            }
+        }
+
+        let length = length.unwrap_or_default();
+        let mut body = (&mut self.input).take(length as u64);
+        if length > MAX_MESSAGE_LENGTH {
+            io::copy(&mut body, &mut io::sink())?;
+            return Ok(Some(Err(format!(
+                "Message of {} bytes exceeds the limit of {} bytes",
+                length, MAX_MESSAGE_LENGTH
+            ))));
+        }
+        let mut bytes = Vec::with_capacity(length);
+        if body.read_to_end(&mut bytes)? < length {
+            return Ok(Some(Err("Input closed inside a message body".to_string())));
+        }
+        Ok(Some(
+            String::from_utf8(bytes).map_err(|_| "Message body is not UTF-8".to_string()),
+        ))
+    }
+
+    /// The next line, or a piece of a long one, with invalid UTF-8 replaced
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        if let Some(line) = self.pending.take() {
+            return Ok(Some(line));
+        }
+        let mut bytes = Vec::new();
+        if (&mut self.input)
+            .take(MAX_HEADER_LINE)
+            .read_until(b'\n', &mut bytes)?
+            == 0
+        {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    /// Drop the input up to the next `Content-Length` header. Bodies aren't
+    /// newline-terminated, so it may follow the rest of a body on one line.
+    fn skip_to_next_header(&mut self) -> io::Result<()> {
+        while let Some(line) = self.read_line()? {
+            // Lowercasing only changes ASCII bytes, so offsets are kept
+            if let Some(start) = line.to_ascii_lowercase().find("content-length:") {
+                self.pending = Some(line[start..].to_string());
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The length given by a `Content-Length` header; other headers are ignored
+fn parse_header(header: &str) -> Result<Option<usize>, String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("Malformed header '{}'", header))?;
+    if !name.trim().eq_ignore_ascii_case("Content-Length") {
+        return Ok(None);
+    }
+    value
+        .trim()
+        .parse::<usize>()
+        .map(Some)
+        .map_err(|_| format!("Invalid Content-Length '{}'", value.trim()))
+}
+
+/// Frame `message` and write it to `output`
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next_request(messages: &mut MessageReader<impl BufRead>) -> Value {
+        serde_json::from_str(&messages.next_message().unwrap().unwrap().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_message_round_trip() {
+        let mut framed = Vec::new();
+        write_message(&mut framed, &json!({ "id": 1, "file": "a.cpp" })).unwrap();
+        write_message(&mut framed, &json!({ "id": 2, "contents": "int x = 1;\n" })).unwrap();
+
+        let mut messages = MessageReader::new(io::Cursor::new(framed));
+        assert_eq!(next_request(&mut messages)["file"], "a.cpp");
+        assert_eq!(next_request(&mut messages)["contents"], "int x = 1;\n");
+        assert!(messages.next_message().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_missing_content_length_rejected() {
+        let input = io::Cursor::new(b"Content-Type: json\r\n\r\n{}".to_vec());
+        let mut messages = MessageReader::new(input);
+        assert!(matches!(messages.next_message(), Ok(Some(Err(_)))));
+        assert!(messages.next_message().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unreadable_messages_answered_and_skipped() {
+        let mut framed = Vec::new();
+        framed.extend_from_slice(b"Content-Length: nine\r\n\r\n{\"id\":0}");
+        write_message(&mut framed, &json!({ "id": 1, "file": "a.cpp" })).unwrap();
+        framed.extend_from_slice(b"Content-Length: 99999999999\r\n\r\n{}");
+
+        let mut messages = MessageReader::new(io::Cursor::new(framed));
+        let error = |message: io::Result<Option<Result<String, String>>>| {
+            message.unwrap().unwrap().unwrap_err()
+        };
+        assert_eq!(
+            error(messages.next_message()),
+            "Invalid Content-Length 'nine'"
+        );
+        // Reading resumes at the header after the malformed one
+        assert_eq!(next_request(&mut messages)["id"], 1);
+        // The oversized body is skipped without being allocated
+        assert!(error(messages.next_message()).contains("exceeds the limit"));
+        assert!(messages.next_message().unwrap().is_none());
+
+        let mut framed = b"Content-Length: 2\r\n\r\n\xff\xfe".to_vec();
+        write_message(&mut framed, &json!({ "id": 1, "file": "a.cpp" })).unwrap();
+        framed.extend_from_slice(b"Content-Length: 10\r\n\r\n{}");
+
+        let mut messages = MessageReader::new(io::Cursor::new(framed));
+        assert_eq!(error(messages.next_message()), "Message body is not UTF-8");
+        assert_eq!(next_request(&mut messages)["id"], 1);
+        assert_eq!(
+            error(messages.next_message()),
+            "Input closed inside a message body"
+        );
+        assert!(messages.next_message().unwrap().is_none());
+    }
+}
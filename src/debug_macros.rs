@@ -1,8 +1,37 @@
-// Macro for debug logging - enabled via RUSTY_CPP_DEBUG env var
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Runtime log level set from the `-v` flag count.
+/// 1 = progress info, 2 = include resolution, 3 = full debug output.
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Verbosity level at which `debug_println!` output is enabled.
+pub const DEBUG_VERBOSITY: u8 = 3;
+
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+// Macro for debug logging - enabled via -vvv or the RUSTY_CPP_DEBUG env var
 #[macro_export]
 macro_rules! debug_println {
     ($($arg:tt)*) => {
-        if std::env::var("RUSTY_CPP_DEBUG").is_ok() {
+        if $crate::debug_macros::verbosity() >= $crate::debug_macros::DEBUG_VERBOSITY
+            || std::env::var("RUSTY_CPP_DEBUG").is_ok()
+        {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Macro for leveled diagnostics - printed when `-v` was given at least `$level` times
+#[macro_export]
+macro_rules! verbose_println {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::debug_macros::verbosity() >= $level {
             eprintln!($($arg)*);
         }
     };
@@ -0,0 +1,157 @@
+//! `--fix`: apply the fix-it suggestions carried by violations
+//!
+//! Only suggestions a checker knows to be correct are attached to messages
+//! (see [`Suggestion`]), so every one is applied. Each inserts a line above
+//! a given line; edits are applied bottom-up per file so earlier insertions
+//! don't shift the lines later ones point at. The file is checked again
+//! afterwards, and the edits are reverted if that finds anything besides
+//! what was there before minus what the edits fixed (see [`new_violations`]).
+
+use crate::analysis::Suggestion;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// The files `apply` rewrote, with their previous contents
+pub struct Applied {
+    pub edits: usize,
+    originals: Vec<(PathBuf, String)>,
+}
+
+impl Applied {
+    /// Put every rewritten file back as it was
+    pub fn revert(&self) -> Result<(), String> {
+        for (path, contents) in &self.originals {
+            fs::write(path, contents)
+                .map_err(|e| format!("Failed to restore '{}': {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn apply(suggestions: &[Suggestion]) -> Result<Applied, String> {
+    let mut by_file: BTreeMap<&str, Vec<(u32, &str)>> = BTreeMap::new();
+    for suggestion in suggestions {
+        let edits = by_file.entry(suggestion.file.as_str()).or_default();
+        if !edits.iter().any(|(line, _)| *line == suggestion.line) {
+            edits.push((suggestion.line, suggestion.text.as_str()));
+        }
+    }
+
+    let mut applied = Applied {
+        edits: 0,
+        originals: Vec::new(),
+    };
+    for (file, edits) in by_file {
+        let path = PathBuf::from(file);
+        let source = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        let fixed = insert_lines(&source, &edits);
+        if let Err(e) = fs::write(&path, &fixed) {
+            applied.revert()?;
+            return Err(format!("Failed to write '{}': {}", path.display(), e));
+        }
+        applied.edits += edits.len();
+        applied.originals.push((path, source));
+    }
+    Ok(applied)
+}
+
+/// The violations in `after` that are not among `before` minus the ones
+/// carrying a suggestion, which `apply` fixed. Line numbers are ignored, as
+/// the inserted lines shift everything below them.
+pub fn new_violations<'a>(before: &[String], after: &'a [String]) -> Vec<&'a String> {
+    let line = Regex::new(r"\bline \d+").unwrap();
+    let mut expected: HashMap<String, usize> = HashMap::new();
+    for violation in before {
+        if Suggestion::from_message(violation).is_none() {
+            *expected
+                .entry(line.replace_all(violation, "line _").into_owned())
+                .or_default() += 1;
+        }
+    }
+    after
+        .iter()
+        .filter(|violation| {
+            match expected.get_mut(line.replace_all(violation, "line _").as_ref()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        })
+        .collect()
+}
+
+/// Insert each `(line, text)` above its 1-based line, indented like it.
+/// Lines past the end of the file are ignored.
+fn insert_lines(source: &str, edits: &[(u32, &str)]) -> String {
+    let mut lines: Vec<String> = source.split_inclusive('\n').map(String::from).collect();
+    let mut edits = edits.to_vec();
+    edits.sort_by_key(|(line, _)| std::cmp::Reverse(*line));
+    for (line, text) in edits {
+        let Some(index) = (line as usize).checked_sub(1).filter(|i| *i < lines.len()) else {
+            continue;
+        };
+        let target = &lines[index];
+        let indent: String = target
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        let newline = if target.ends_with("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        lines.insert(index, format!("{}{}{}", indent, text, newline));
+    }
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_violations_ignore_shifted_lines() {
+        let fixed = "Safe function 'get' returns a reference but has no @lifetime annotation; add `// @lifetime: (&'a) -> &'a` with parameter 'x' (insert above line 3 of a.cpp)".to_string();
+        let moved = |line: u32| {
+            format!(
+                "Use after move: variable 'v' has already been moved at line {}",
+                line
+            )
+        };
+        let before = vec![fixed.clone(), moved(7)];
+
+        // The annotation went in above line 3, so the move is now on line 8
+        assert!(new_violations(&before, &[moved(8)]).is_empty());
+        // The same count, but a different violation than the one fixed
+        let null = "In function 'f': dereference of null pointer 'p'".to_string();
+        assert_eq!(new_violations(&before, &[moved(8), null.clone()]), [&null]);
+        // The fixed violation is still reported
+        assert_eq!(
+            new_violations(&before, std::slice::from_ref(&fixed)),
+            [&fixed]
+        );
+        // A second copy of a violation reported once before is new
+        assert_eq!(new_violations(&before, &[moved(8), moved(9)]), [&moved(9)]);
+    }
+
+    #[test]
+    fn test_insert_lines_bottom_up() {
+        let source = "// @safe\nconst int& a(const int& x) { return x; }\n\n  int& b(int& y) { return y; }\n";
+        let fixed = insert_lines(
+            source,
+            &[
+                (2, "// @lifetime: (&'a) -> &'a"),
+                (4, "// @lifetime: (&'a mut) -> &'a mut"),
+            ],
+        );
+        assert_eq!(
+            fixed,
+            "// @safe\n// @lifetime: (&'a) -> &'a\nconst int& a(const int& x) { return x; }\n\n  // @lifetime: (&'a mut) -> &'a mut\n  int& b(int& y) { return y; }\n"
+        );
+    }
+}
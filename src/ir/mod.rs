@@ -43,6 +43,22 @@ fn is_member_access_operator(func_name: &str) -> bool {
     }
 }
 
+/// Root variable of an `operator->` argument. The parser gives `a->b->f()` the
+/// receiver `operator->(operator->(a).b)`, so the root is found through nested
+/// arrow calls and member accesses.
+fn arrow_chain_root(expr: &crate::parser::Expression) -> Option<&String> {
+    match expr {
+        crate::parser::Expression::Variable(var) => Some(var),
+        crate::parser::Expression::FunctionCall { name, args }
+            if is_member_access_operator(name) =>
+        {
+            arrow_chain_root(args.first()?)
+        }
+        crate::parser::Expression::MemberAccess { object, .. } => arrow_chain_root(object),
+        _ => None,
+    }
+}
+
 /// Determine if a pointer type should be treated as immutable (Ptr<T>) or mutable (MutPtr<T>)
 /// This is used for borrow checking - Ptr<T> creates an immutable borrow, MutPtr<T> creates a mutable borrow
 fn is_immutable_pointer_type(type_name: &str) -> bool {
@@ -67,6 +83,19 @@ fn is_immutable_pointer_type(type_name: &str) -> bool {
     type_name.starts_with("const ")
 }
 
+/// Borrow kind for `p = &x`, from the declared type of the pointer `p`
+/// - Ptr<T> (const T*) -> Immutable borrow
+/// - MutPtr<T> (T*) -> Mutable borrow
+fn pointer_borrow_kind(lhs_var: &str, variables: &HashMap<String, VariableInfo>) -> BorrowKind {
+    match variables.get(lhs_var).map(|info| &info.ty) {
+        Some(VariableType::Owned(type_name)) if is_immutable_pointer_type(type_name) => {
+            BorrowKind::Immutable
+        }
+        // Non-const pointer, MutPtr or unknown type defaults to mutable borrow
+        _ => BorrowKind::Mutable,
+    }
+}
+
 /// If `name` looks like a constructor call (just `ClassName` or `ClassName::ClassName`),
 /// return the class name. Otherwise return None.
 fn normalize_constructor_name(name: &str) -> Option<String> {
@@ -92,50 +121,78 @@ fn normalize_constructor_name(name: &str) -> Option<String> {
     }
 }
 
+/// `std::tie(a, b) = rhs`: every tied variable is assigned from `rhs`, which
+/// is consumed when it is `std::move(p)`. `std::ignore` slots are skipped.
+fn tie_assignment(
+    targets: &[crate::parser::Expression],
+    rhs: &crate::parser::Expression,
+    line: usize,
+) -> Vec<IrStatement> {
+    let source = match rhs {
+        crate::parser::Expression::Move { inner, .. } => match inner.as_ref() {
+            crate::parser::Expression::Variable(var) => Some((var, true)),
+            _ => None,
+        },
+        crate::parser::Expression::Variable(var) => Some((var, false)),
+        _ => None,
+    };
+    let value = match source {
+        Some((var, true)) => IrExpression::Move(var.clone()),
+        Some((var, false)) => IrExpression::Variable(var.clone()),
+        None => IrExpression::Literal("std::tie".to_string()),
+    };
+
+    let mut statements: Vec<IrStatement> = targets
+        .iter()
+        .filter_map(|target| match target {
+            crate::parser::Expression::Variable(var)
+                if !matches!(var.as_str(), "ignore" | "std::ignore") =>
+            {
+                Some(IrStatement::Assign {
+                    lhs: var.clone(),
+                    rhs: value.clone(),
+                    line,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    if let Some((var, true)) = source {
+        statements.push(IrStatement::Move {
+            from: var.clone(),
+            to: format!("_moved_{}", var),
+            line,
+        });
+    }
+    statements
+}
+
 /// Check if an expression chain originates from a temporary (constructor call).
 /// This handles chained method calls like Builder().set(42).get_value().
 /// Returns true if the ultimate receiver is a constructor call (creating a temporary).
-fn is_receiver_temporary(expr: &crate::parser::Expression) -> bool {
+fn is_receiver_temporary(
+    expr: &crate::parser::Expression,
+    class_names: &std::collections::HashSet<String>,
+) -> bool {
     match expr {
-        // A function call where the name looks like a constructor (ClassName or ClassName::ClassName)
         crate::parser::Expression::FunctionCall { name, args } => {
-            // Check if this is a constructor call (name is just a type name, no :: or matches X::X pattern)
-            let is_constructor = if name.contains("::") {
-                // Check for explicit constructor like Builder::Builder
-                let parts: Vec<&str> = name.split("::").collect();
-                if parts.len() >= 2 {
-                    let last = parts[parts.len() - 1];
-                    let second_last = parts[parts.len() - 2];
-                    last == second_last // X::X pattern
-                } else {
-                    false
-                }
-            } else {
-                // A standalone name like "Builder" is a constructor call
-                // if it starts with uppercase (convention for type names)
-                name.chars()
-                    .next()
-                    .map(|c| c.is_uppercase())
-                    .unwrap_or(false)
-            };
-
-            if is_constructor {
+            if is_constructor_call(name, class_names) {
                 return true;
             }
 
             // For method calls, check if the receiver (first arg) is a temporary
-            // Method call pattern: the function name is Class::method and first arg is receiver
-            if name.contains("::") && !args.is_empty() {
-                // The first argument is the receiver for method calls
-                return is_receiver_temporary(&args[0]);
+            if is_receiver_arg(name, 0, class_names) && !args.is_empty() {
+                return is_receiver_temporary(&args[0], class_names);
             }
 
             false
         }
         // Member access on a temporary propagates the temporary status
-        crate::parser::Expression::MemberAccess { object, .. } => is_receiver_temporary(object),
+        crate::parser::Expression::MemberAccess { object, .. } => {
+            is_receiver_temporary(object, class_names)
+        }
         // Dereference of a temporary propagates the temporary status
-        crate::parser::Expression::Dereference(inner) => is_receiver_temporary(inner),
+        crate::parser::Expression::Dereference(inner) => is_receiver_temporary(inner, class_names),
         // Variable references are NOT temporaries
         crate::parser::Expression::Variable(_) => false,
         // Literals are temporaries (but they're value types, so less important)
@@ -146,6 +203,22 @@ fn is_receiver_temporary(expr: &crate::parser::Expression) -> bool {
     }
 }
 
+/// Whether a call to `name` constructs one of `class_names`: `Builder()`,
+/// `ns::Builder()` or `Builder::Builder()`. A function that merely has a
+/// type-like name, such as `GetConfig()`, constructs nothing.
+fn is_constructor_call(name: &str, class_names: &std::collections::HashSet<String>) -> bool {
+    let name = name.split('<').next().unwrap_or(name);
+    let class = match name.rsplit_once("::") {
+        Some((class, constructor)) if class.rsplit("::").next() == Some(constructor) => class,
+        _ => name,
+    };
+    class_names.contains(class)
+        || (!class.contains("::")
+            && class_names
+                .iter()
+                .any(|known| known.rsplit("::").next() == Some(class)))
+}
+
 /// Extract the full object path and final field from a nested MemberAccess expression
 /// For `o.inner.data`, returns Some(("o.inner", "data"))
 /// For `o.field`, returns Some(("o", "field"))
@@ -184,6 +257,272 @@ fn extract_full_member_path(expr: &crate::parser::Expression) -> Option<String>
     }
 }
 
+/// Name under which the target of raw pointer `ptr` is borrowed and
+/// written, so `*p = v` conflicts with `auto& r = *p` but not with `p = q`
+fn pointee_name(ptr: &str) -> String {
+    format!("*{}", ptr)
+}
+
+/// Whether `expr` is `*this`, i.e. the whole object in a member function.
+/// `std::move(*this)` is lowered to a Move from `this` so the method
+/// qualifier can be checked against it.
+fn is_deref_this(expr: &crate::parser::Expression) -> bool {
+    matches!(
+        expr,
+        crate::parser::Expression::Dereference(inner)
+            if matches!(inner.as_ref(), crate::parser::Expression::Variable(name) if name == "this")
+    )
+}
+
+/// Emit Move/MoveField statements for every `std::move` inside nested call arguments.
+/// For `v.push_back(Item(wrap(std::move(x))))` this moves `x` even though the
+/// move sits two calls deep.
+fn push_nested_call_moves(
+    args: &[crate::parser::Expression],
+    line: usize,
+    statements: &mut Vec<IrStatement>,
+) {
+    for arg in args {
+        match arg {
+            crate::parser::Expression::Move { inner, .. } => match inner.as_ref() {
+                crate::parser::Expression::Variable(var) => {
+                    debug_println!("DEBUG IR: Found Move(Variable) in nested call: {}", var);
+                    statements.push(IrStatement::Move {
+                        from: var.clone(),
+                        to: format!("_moved_{}", var),
+                        line,
+                    });
+                }
+                crate::parser::Expression::MemberAccess { .. } => {
+                    if let Some((obj_path, field_name)) = extract_member_path(inner.as_ref()) {
+                        debug_println!(
+                            "DEBUG IR: Found Move(MemberAccess) in nested call: {}.{}",
+                            obj_path,
+                            field_name
+                        );
+                        statements.push(IrStatement::MoveField {
+                            object: obj_path,
+                            field: field_name.clone(),
+                            to: format!("_moved_{}", field_name),
+                            line,
+                        });
+                    }
+                }
+                inner_expr if is_deref_this(inner_expr) => {
+                    statements.push(IrStatement::Move {
+                        from: "this".to_string(),
+                        to: "_moved_this".to_string(),
+                        line,
+                    });
+                }
+                _ => {}
+            },
+            crate::parser::Expression::FunctionCall {
+                args: inner_args, ..
+            } => {
+                push_nested_call_moves(inner_args, line, statements);
+            }
+            crate::parser::Expression::Cast { inner, .. } => {
+                push_nested_call_moves(std::slice::from_ref(inner.as_ref()), line, statements);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A method call nested in an argument, such as `p.get()` in `f(p.get())`,
+/// still reads its receiver, which must not have been moved from.
+fn nested_receiver_use(name: &str, args: &[crate::parser::Expression]) -> Option<IrStatement> {
+    if !(name.contains("::") || name.starts_with("operator")) {
+        return None;
+    }
+    match args.first() {
+        Some(crate::parser::Expression::Variable(var)) => Some(IrStatement::UseVariable {
+            var: var.clone(),
+            operation: format!("call method '{}'", name),
+        }),
+        _ => None,
+    }
+}
+
+/// Lower a constructor member initializer so moves out of, and reads from,
+/// the source object are tracked before the body runs. In a move constructor
+/// `: a(std::move(other.a)), n(other.a.size())` the second initializer reads
+/// a field the first one already moved.
+fn member_initializer_statements(
+    init: &crate::parser::ast_visitor::MemberInitializer,
+) -> Vec<IrStatement> {
+    let line = init.location.line as usize;
+    let to = format!("_init_{}", init.member_name);
+    let mut statements = Vec::new();
+    match &init.initializer {
+        crate::parser::Expression::Move { inner, .. } => match inner.as_ref() {
+            crate::parser::Expression::Variable(var) => {
+                statements.push(IrStatement::Move {
+                    from: var.clone(),
+                    to,
+                    line,
+                });
+            }
+            member @ crate::parser::Expression::MemberAccess { .. } => {
+                if let Some((obj_path, field_name)) = extract_member_path(member) {
+                    statements.push(IrStatement::MoveField {
+                        object: obj_path,
+                        field: field_name,
+                        to,
+                        line,
+                    });
+                }
+            }
+            _ => {}
+        },
+        expr => {
+            push_field_reads(expr, &mut statements);
+            if let crate::parser::Expression::FunctionCall { args, .. } = expr {
+                push_nested_call_moves(args, line, &mut statements);
+            }
+        }
+    }
+    statements
+}
+
+/// Emit a `UseField` read for every `obj.field` that `expr` evaluates
+fn push_field_reads(expr: &crate::parser::Expression, statements: &mut Vec<IrStatement>) {
+    match expr {
+        crate::parser::Expression::MemberAccess { .. } => {
+            if let Some((obj_path, field_name)) = extract_member_path(expr) {
+                statements.push(IrStatement::UseField {
+                    object: obj_path,
+                    field: field_name,
+                    operation: "read".to_string(),
+                });
+            }
+        }
+        crate::parser::Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                push_field_reads(arg, statements);
+            }
+        }
+        crate::parser::Expression::BinaryOp { left, right, .. } => {
+            push_field_reads(left, statements);
+            push_field_reads(right, statements);
+        }
+        crate::parser::Expression::Cast { inner, .. } => push_field_reads(inner, statements),
+        _ => {}
+    }
+}
+
+/// Collect the outer variables a lambda's move init-captures consume.
+/// For `[q = std::move(p)]` the source is taken from the capture initializer
+/// (`p`); when libclang didn't expose the initializer, fall back to the
+/// capture name itself, which covers the common `[p = std::move(p)]` form.
+fn moved_lambda_capture_sources(
+    captures: &[crate::parser::ast_visitor::LambdaCaptureKind],
+    capture_initializers: &[crate::parser::Expression],
+) -> Vec<String> {
+    use crate::parser::ast_visitor::LambdaCaptureKind;
+
+    let move_inits: Vec<&String> = captures
+        .iter()
+        .filter_map(|c| match c {
+            LambdaCaptureKind::Init {
+                name,
+                is_move: true,
+            } => Some(name),
+            _ => None,
+        })
+        .collect();
+    if move_inits.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sources: Vec<String> = Vec::new();
+    for init in capture_initializers {
+        if let crate::parser::Expression::Move { inner, .. } = init {
+            if let crate::parser::Expression::Variable(var) = inner.as_ref() {
+                if !sources.contains(var) {
+                    sources.push(var.clone());
+                }
+            }
+        }
+    }
+
+    if sources.is_empty() {
+        sources = move_inits.into_iter().cloned().collect();
+    }
+    sources
+}
+
+/// The captures of a lambda as the analyses see them. A `[&]` default
+/// capture is expanded to the outer variables the body uses, each captured
+/// by reference.
+fn lambda_capture_infos(
+    captures: &[crate::parser::ast_visitor::LambdaCaptureKind],
+    body: &[crate::parser::Statement],
+    variables: &HashMap<String, VariableInfo>,
+) -> Vec<LambdaCaptureInfo> {
+    use crate::parser::ast_visitor::LambdaCaptureKind;
+
+    let mut infos: Vec<LambdaCaptureInfo> = Vec::new();
+    for capture in captures {
+        match capture {
+            LambdaCaptureKind::DefaultRef => {
+                let explicit: Vec<&String> = captures
+                    .iter()
+                    .filter_map(|c| match c {
+                        LambdaCaptureKind::ByRef(name)
+                        | LambdaCaptureKind::ByCopy(name)
+                        | LambdaCaptureKind::Init { name, .. } => Some(name),
+                        _ => None,
+                    })
+                    .collect();
+                let mut used = Vec::new();
+                let mut declared = std::collections::HashSet::new();
+                crate::analysis::lambda_capture_safety::collect_used_variables(
+                    body,
+                    &mut used,
+                    &mut declared,
+                );
+                for name in used {
+                    if !variables.contains_key(&name)
+                        || declared.contains(&name)
+                        || explicit.contains(&&name)
+                        || infos.iter().any(|info| info.name == name)
+                    {
+                        continue;
+                    }
+                    infos.push(LambdaCaptureInfo { name, is_ref: true });
+                }
+            }
+            LambdaCaptureKind::DefaultCopy => infos.push(LambdaCaptureInfo {
+                name: "<default>".to_string(),
+                is_ref: false,
+            }),
+            LambdaCaptureKind::ByRef(name) => infos.push(LambdaCaptureInfo {
+                name: name.clone(),
+                is_ref: true,
+            }),
+            LambdaCaptureKind::ByCopy(name) => infos.push(LambdaCaptureInfo {
+                name: name.clone(),
+                is_ref: false,
+            }),
+            LambdaCaptureKind::Init { name, .. } => infos.push(LambdaCaptureInfo {
+                name: name.clone(),
+                is_ref: false, // Init captures are by value
+            }),
+            LambdaCaptureKind::This => infos.push(LambdaCaptureInfo {
+                name: "this".to_string(),
+                is_ref: true, // 'this' capture is a pointer, essentially by-ref
+            }),
+            LambdaCaptureKind::ThisCopy => infos.push(LambdaCaptureInfo {
+                name: "this".to_string(),
+                is_ref: false, // *this capture is by value
+            }),
+        }
+    }
+    infos
+}
+
 #[derive(Debug, Clone)]
 pub struct IrProgram {
     pub functions: Vec<IrFunction>,
@@ -192,6 +531,9 @@ pub struct IrProgram {
     /// Struct lifetime tracking: Classes that have reference members
     /// These types implicitly "borrow" from the variables passed to their constructors
     pub types_with_ref_members: std::collections::HashSet<String>,
+    /// Qualifier of each method declared in a class or defined in the file,
+    /// by qualified name, so calls can be told apart as reads or mutations
+    pub method_qualifiers: HashMap<String, MethodQualifier>,
 }
 
 #[derive(Debug, Clone)]
@@ -202,6 +544,7 @@ pub struct IrFunction {
     pub variables: HashMap<String, VariableInfo>,
     pub return_type: String, // Return type from AST
     pub source_file: String, // Source file path for distinguishing user code from system headers
+    pub source_line: u32,    // Line of the declaration, where fix-its insert annotations
     // Method information for tracking 'this' pointer
     pub is_method: bool,
     pub method_qualifier: Option<MethodQualifier>,
@@ -331,6 +674,7 @@ pub enum IrStatement {
         /// True if this is a method call where the receiver is a temporary expression
         /// (e.g., `Builder().method()` where `Builder()` is a temporary)
         receiver_is_temporary: bool,
+        line: usize,
     },
     Return {
         value: Option<String>,
@@ -351,8 +695,10 @@ pub enum IrStatement {
     Switch {
         cases: Vec<Vec<IrStatement>>,
     },
-    // Safety markers
-    EnterUnsafe,
+    // Safety markers; `EnterUnsafe` is at the opening brace of the block
+    EnterUnsafe {
+        line: usize,
+    },
     ExitUnsafe,
     // Phase 4: Pack expansion tracking
     PackExpansion {
@@ -391,6 +737,7 @@ pub enum IrStatement {
     },
     // Lambda expression with captures (for safety checking)
     LambdaCapture {
+        lambda_var: String, // The variable the closure is stored in
         captures: Vec<LambdaCaptureInfo>,
     },
     // Variable declaration (for loop-local tracking)
@@ -445,6 +792,155 @@ pub enum OwnershipEdge {
     MutBorrows,
 }
 
+/// Unqualified class name of a type or member name, without const or
+/// template arguments: "const std::vector<int>" -> "vector",
+/// "std::basic_string<char>::operator basic_string_view" -> "operator basic_string_view"
+pub(crate) fn unqualified_type_name(type_name: &str) -> String {
+    let mut base = String::new();
+    let mut depth = 0usize;
+    for c in type_name.trim().trim_start_matches("const ").chars() {
+        match c {
+            '<' => depth += 1,
+            '>' if depth > 0 => depth -= 1,
+            _ if depth == 0 => base.push(c),
+            _ => {}
+        }
+    }
+    base.rsplit("::").next().unwrap_or("").trim().to_string()
+}
+
+/// Non-owning views (`std::string_view`, `std::span`) borrow the storage they
+/// are created from rather than owning it
+pub fn is_view_type(type_name: &str) -> bool {
+    let trimmed = type_name.trim();
+    if trimmed.ends_with('&') || trimmed.ends_with('*') {
+        return false;
+    }
+    matches!(
+        unqualified_type_name(trimmed).as_str(),
+        "string_view"
+            | "wstring_view"
+            | "u8string_view"
+            | "u16string_view"
+            | "u32string_view"
+            | "basic_string_view"
+            | "span"
+    )
+}
+
+/// `std::reference_wrapper<T>` holds a reference that can be rebound, so it
+/// borrows whatever it is bound to
+pub fn is_reference_wrapper_type(type_name: &str) -> bool {
+    let trimmed = type_name.trim();
+    if trimmed.ends_with('&') || trimmed.ends_with('*') {
+        return false;
+    }
+    unqualified_type_name(trimmed) == "reference_wrapper"
+}
+
+/// The variable a reference_wrapper of `wrapper_type` is bound to by
+/// `w = std::ref(x)`, `w = std::cref(x)`, `w = std::reference_wrapper<T>(x)`
+/// or `w = x`, and whether the binding allows mutation
+fn reference_wrapper_source<'a>(
+    expr: &'a crate::parser::Expression,
+    wrapper_type: &str,
+) -> Option<(&'a String, BorrowKind)> {
+    let kind = if wrapper_type.contains("<const ") {
+        BorrowKind::Immutable
+    } else {
+        BorrowKind::Mutable
+    };
+    match expr {
+        crate::parser::Expression::Variable(name) => Some((name, kind)),
+        crate::parser::Expression::FunctionCall { name, args } => {
+            let callee = unqualified_type_name(name);
+            let kind = match callee.as_str() {
+                "cref" => BorrowKind::Immutable,
+                "ref" | "reference_wrapper" => kind,
+                _ => return None,
+            };
+            match args.first()? {
+                crate::parser::Expression::Variable(source) => Some((source, kind)),
+                _ => None,
+            }
+        }
+        crate::parser::Expression::Cast { inner, .. } => {
+            reference_wrapper_source(inner, wrapper_type)
+        }
+        _ => None,
+    }
+}
+
+/// `w = w2` copies `w2`'s binding, so `w` borrows what `w2` is bound to rather
+/// than `w2` itself. `bindings` maps each wrapper to its current referent.
+fn follow_reference_wrappers(
+    statements: &mut [IrStatement],
+    variables: &HashMap<String, VariableInfo>,
+    bindings: &mut HashMap<String, (String, BorrowKind)>,
+) {
+    let is_wrapper = |name: &str| {
+        matches!(
+            variables.get(name).map(|info| &info.ty),
+            Some(VariableType::Owned(type_name)) if is_reference_wrapper_type(type_name)
+        )
+    };
+    for stmt in statements {
+        match stmt {
+            IrStatement::Borrow { from, to, kind, .. } if is_wrapper(to.as_str()) => {
+                if let Some((referent, referent_kind)) = bindings.get(from.as_str()) {
+                    *from = referent.clone();
+                    if *referent_kind == BorrowKind::Immutable {
+                        *kind = BorrowKind::Immutable;
+                    }
+                }
+                bindings.insert(to.clone(), (from.clone(), kind.clone()));
+            }
+            IrStatement::If {
+                then_branch,
+                else_branch,
+            } => {
+                follow_reference_wrappers(then_branch, variables, &mut bindings.clone());
+                if let Some(else_branch) = else_branch {
+                    follow_reference_wrappers(else_branch, variables, &mut bindings.clone());
+                }
+            }
+            IrStatement::Switch { cases } => {
+                for case in cases {
+                    follow_reference_wrappers(case, variables, &mut bindings.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The variable a view is created from: `sv = s`, `sv = std::string_view(s)`,
+/// or the implicit `s.operator basic_string_view()` conversion
+fn view_source<'a>(
+    expr: &'a crate::parser::Expression,
+    variables: &HashMap<String, VariableInfo>,
+) -> Option<&'a String> {
+    match expr {
+        crate::parser::Expression::Variable(name) => {
+            // A view built from a raw pointer borrows the pointee, not the pointer
+            match variables.get(name).map(|info| &info.ty) {
+                Some(VariableType::Owned(t)) if t.trim_end().ends_with('*') => None,
+                Some(VariableType::Raw(_)) => None,
+                _ => Some(name),
+            }
+        }
+        crate::parser::Expression::FunctionCall { name, args }
+            if name.contains("operator basic_string_view")
+                || name.contains("operator std::basic_string_view")
+                || is_view_type(name) =>
+        {
+            view_source(args.first()?, variables)
+        }
+        crate::parser::Expression::Cast { inner, .. } => view_source(inner, variables),
+        _ => None,
+    }
+}
+
 /// Detect if a type has a non-trivial destructor (RAII type)
 /// These types need implicit drop tracking at scope end
 fn is_raii_type(type_name: &str) -> bool {
@@ -469,6 +965,12 @@ pub fn is_raii_type_with_user_defined(
         return false;
     }
 
+    // Views only borrow their storage (and "std::string_view" would otherwise
+    // match the "std::string" prefix below)
+    if is_view_type(type_name) {
+        return false;
+    }
+
     // NOTE: rusty:: RAII types (Box, Rc, Arc, RefCell, Cell) are NOT hard-coded here.
     // They are detected dynamically via user_defined_raii_types when the rusty headers
     // are parsed and their destructors are found. This avoids unnecessary hard-coding.
@@ -533,6 +1035,56 @@ pub fn is_raii_type_with_user_defined(
     false
 }
 
+/// Whether argument `index` of a call to `func_name` is the object a method
+/// is called on: the parser passes it first and qualifies the call with its
+/// class. Qualified free functions (`util::consume(x)`, `std::swap(a, b)`)
+/// have no receiver.
+fn is_receiver_arg(
+    func_name: &str,
+    index: usize,
+    class_names: &std::collections::HashSet<String>,
+) -> bool {
+    index == 0
+        && func_name.rsplit_once("::").is_some_and(|(class, _)| {
+            // `std::vector<int>::push_back` is a method of `std::vector`
+            let class = class.split('<').next().unwrap_or(class);
+            class_names.contains(class)
+        })
+}
+
+/// Whether `func_name` is a method of the class `receiver` is declared as,
+/// e.g. `std::vector<int>::at` called on a `std::vector<int>`, unlike
+/// `std::max(a, b)` whose first argument is not a receiver
+fn is_method_of(
+    func_name: &str,
+    receiver: &str,
+    variables: &HashMap<String, VariableInfo>,
+) -> bool {
+    let Some((class, _)) = func_name.rsplit_once("::") else {
+        return false;
+    };
+    let type_name = match variables.get(receiver).map(|info| &info.ty) {
+        Some(
+            VariableType::Owned(t) | VariableType::Reference(t) | VariableType::MutableReference(t),
+        ) => t,
+        _ => return false,
+    };
+    unqualified_type_name(type_name.trim_end_matches(['&', ' '])) == unqualified_type_name(class)
+}
+
+/// Whether `type_name` names a class annotated `@move_only`
+fn is_move_only_type(type_name: &str, move_only_types: &std::collections::HashSet<String>) -> bool {
+    let base_type = type_name
+        .trim_start_matches("const ")
+        .trim_end_matches(['&', ' '])
+        .trim();
+    let base_type = base_type.strip_prefix("struct ").unwrap_or(base_type);
+    let base_type = base_type.strip_prefix("class ").unwrap_or(base_type);
+    move_only_types
+        .iter()
+        .any(|move_only| move_only == base_type || move_only.rsplit("::").next() == Some(base_type))
+}
+
 #[allow(dead_code)]
 pub fn build_ir(ast: CppAst) -> Result<IrProgram, String> {
     let mut functions = Vec::new();
@@ -542,11 +1094,16 @@ pub fn build_ir(ast: CppAst) -> Result<IrProgram, String> {
     let mut user_defined_raii_types = std::collections::HashSet::new();
     // Struct lifetime tracking: Collect types with reference members
     let mut types_with_ref_members = std::collections::HashSet::new();
+    // @move_only types: copying one moves it, like unique_ptr
+    let mut move_only_types = std::collections::HashSet::new();
     for class in &ast.classes {
         if class.has_destructor {
             user_defined_raii_types.insert(class.name.clone());
             debug_println!("RAII: Registered user-defined RAII type '{}'", class.name);
         }
+        if class.is_move_only {
+            move_only_types.insert(class.name.clone());
+        }
         // Check if class has any reference members
         if class.members.iter().any(|m| m.is_reference) {
             types_with_ref_members.insert(class.name.clone());
@@ -557,18 +1114,58 @@ pub fn build_ir(ast: CppAst) -> Result<IrProgram, String> {
         }
     }
 
+    let method_qualifiers = collect_method_qualifiers(&ast);
+    let class_names = ast.classes.iter().map(|class| class.name.clone()).collect();
+
     for func in ast.functions {
-        let ir_func = convert_function(&func, &user_defined_raii_types, &types_with_ref_members)?;
+        let ir_func = convert_function(
+            &func,
+            &user_defined_raii_types,
+            &types_with_ref_members,
+            &move_only_types,
+            &class_names,
+        )?;
         functions.push(ir_func);
+        functions.extend(convert_lambda_bodies(
+            &func,
+            &user_defined_raii_types,
+            &types_with_ref_members,
+            &move_only_types,
+            &class_names,
+        )?);
     }
 
     Ok(IrProgram {
         functions,
         ownership_graph,
         types_with_ref_members,
+        method_qualifiers,
     })
 }
 
+/// Method qualifiers by qualified name. An overload set counts as const if
+/// any overload is, since the call may pick it (`T& get()` / `const T& get() const`).
+fn collect_method_qualifiers(ast: &CppAst) -> HashMap<String, MethodQualifier> {
+    let mut qualifiers = HashMap::new();
+    let methods = ast
+        .classes
+        .iter()
+        .flat_map(|class| class.methods.iter())
+        .chain(ast.functions.iter());
+    for method in methods {
+        let Some(qualifier) = &method.method_qualifier else {
+            continue;
+        };
+        match qualifiers.get(&method.name) {
+            Some(MethodQualifier::Const) => {}
+            _ => {
+                qualifiers.insert(method.name.clone(), qualifier.clone());
+            }
+        }
+    }
+    qualifiers
+}
+
 pub fn build_ir_with_safety_context(
     ast: CppAst,
     _safety_context: crate::parser::safety_annotations::SafetyContext,
@@ -580,11 +1177,16 @@ pub fn build_ir_with_safety_context(
     let mut user_defined_raii_types = std::collections::HashSet::new();
     // Struct lifetime tracking: Collect types with reference members
     let mut types_with_ref_members = std::collections::HashSet::new();
+    // @move_only types: copying one moves it, like unique_ptr
+    let mut move_only_types = std::collections::HashSet::new();
     for class in &ast.classes {
         if class.has_destructor {
             user_defined_raii_types.insert(class.name.clone());
             debug_println!("RAII: Registered user-defined RAII type '{}'", class.name);
         }
+        if class.is_move_only {
+            move_only_types.insert(class.name.clone());
+        }
         // Check if class has any reference members
         if class.members.iter().any(|m| m.is_reference) {
             types_with_ref_members.insert(class.name.clone());
@@ -595,15 +1197,32 @@ pub fn build_ir_with_safety_context(
         }
     }
 
+    let method_qualifiers = collect_method_qualifiers(&ast);
+    let class_names = ast.classes.iter().map(|class| class.name.clone()).collect();
+
     for func in ast.functions {
-        let ir_func = convert_function(&func, &user_defined_raii_types, &types_with_ref_members)?;
+        let ir_func = convert_function(
+            &func,
+            &user_defined_raii_types,
+            &types_with_ref_members,
+            &move_only_types,
+            &class_names,
+        )?;
         functions.push(ir_func);
+        functions.extend(convert_lambda_bodies(
+            &func,
+            &user_defined_raii_types,
+            &types_with_ref_members,
+            &move_only_types,
+            &class_names,
+        )?);
     }
 
     Ok(IrProgram {
         functions,
         ownership_graph,
         types_with_ref_members,
+        method_qualifiers,
     })
 }
 
@@ -611,6 +1230,8 @@ fn convert_function(
     func: &crate::parser::Function,
     user_defined_raii_types: &std::collections::HashSet<String>,
     types_with_ref_members: &std::collections::HashSet<String>,
+    move_only_types: &std::collections::HashSet<String>,
+    class_names: &std::collections::HashSet<String>,
 ) -> Result<IrFunction, String> {
     let mut cfg = DiGraph::new();
     let mut variables = HashMap::new();
@@ -619,6 +1240,10 @@ fn convert_function(
     // Create entry block and convert statements
     let mut statements = Vec::new();
 
+    for init in &func.member_initializers {
+        statements.extend(member_initializer_statements(init));
+    }
+
     for stmt in &func.body {
         // Convert the statement
         if let Some(ir_stmts) = convert_statement(
@@ -627,10 +1252,13 @@ fn convert_function(
             &mut current_scope_level,
             user_defined_raii_types,
             types_with_ref_members,
+            move_only_types,
+            class_names,
         )? {
             statements.extend(ir_stmts);
         }
     }
+    follow_reference_wrappers(&mut statements, &variables, &mut HashMap::new());
 
     let entry_block = BasicBlock {
         id: 0,
@@ -659,6 +1287,11 @@ fn convert_function(
                     OwnershipState::Borrowed(BorrowKind::Mutable),
                 )
             }
+        } else if param.is_shared_ptr && !param.is_pointer {
+            (
+                VariableType::SharedPtr(param.type_name.clone()),
+                OwnershipState::Owned,
+            )
         } else {
             (
                 VariableType::Owned(param.type_name.clone()),
@@ -692,6 +1325,7 @@ fn convert_function(
         variables,
         return_type: func.return_type.clone(),
         source_file: func.location.file.clone(),
+        source_line: func.location.line,
         is_method: func.is_method,
         method_qualifier: func.method_qualifier.clone(),
         // Phase 1: Initialize lifetime fields (will be populated from annotations)
@@ -702,8 +1336,125 @@ fn convert_function(
     })
 }
 
+/// Lambda bodies are checked as nested functions named `{outer}::<lambda#N>`,
+/// so they inherit the safety of the enclosing function. By-copy and init
+/// captures start out owned inside the body.
+fn convert_lambda_bodies(
+    func: &crate::parser::Function,
+    user_defined_raii_types: &std::collections::HashSet<String>,
+    types_with_ref_members: &std::collections::HashSet<String>,
+    move_only_types: &std::collections::HashSet<String>,
+    class_names: &std::collections::HashSet<String>,
+) -> Result<Vec<IrFunction>, String> {
+    use crate::parser::ast_visitor::LambdaCaptureKind;
+
+    let mut lambdas = Vec::new();
+    collect_lambdas(&func.body, &mut lambdas);
+
+    let mut functions = Vec::new();
+    for (i, (captures, body)) in lambdas.into_iter().enumerate() {
+        let nested = crate::parser::Function {
+            name: format!("{}::<lambda#{}>", func.name, i + 1),
+            parameters: Vec::new(),
+            return_type: "auto".to_string(),
+            body: body.to_vec(),
+            is_method: false,
+            method_qualifier: None,
+            member_initializers: Vec::new(),
+            ..func.clone()
+        };
+        let mut ir_func = convert_function(
+            &nested,
+            user_defined_raii_types,
+            types_with_ref_members,
+            move_only_types,
+            class_names,
+        )?;
+
+        for capture in captures {
+            let name = match capture {
+                LambdaCaptureKind::ByCopy(name) | LambdaCaptureKind::Init { name, .. } => name,
+                _ => continue,
+            };
+            let declaration_index = ir_func.variables.len();
+            ir_func
+                .variables
+                .entry(name.clone())
+                .or_insert_with(|| VariableInfo {
+                    name: name.clone(),
+                    ty: VariableType::Owned("auto".to_string()),
+                    ownership: OwnershipState::Owned,
+                    lifetime: None,
+                    is_parameter: false,
+                    is_static: false,
+                    scope_level: 0,
+                    has_destructor: false,
+                    declaration_index,
+                });
+        }
+        functions.push(ir_func);
+    }
+    Ok(functions)
+}
+
+type LambdaParts<'a> = (
+    &'a [crate::parser::ast_visitor::LambdaCaptureKind],
+    &'a [crate::parser::Statement],
+);
+
+/// Lambdas written directly in `stmts` (not inside another lambda's body)
+fn collect_lambdas<'a>(stmts: &'a [crate::parser::Statement], out: &mut Vec<LambdaParts<'a>>) {
+    use crate::parser::Statement;
+
+    for stmt in stmts {
+        match stmt {
+            Statement::Assignment { rhs, .. } => collect_expr_lambdas(rhs, out),
+            Statement::ReferenceBinding { target, .. } => collect_expr_lambdas(target, out),
+            Statement::Return(Some(expr)) | Statement::ExpressionStatement { expr, .. } => {
+                collect_expr_lambdas(expr, out)
+            }
+            Statement::FunctionCall { args, .. } => {
+                for arg in args {
+                    collect_expr_lambdas(arg, out);
+                }
+            }
+            Statement::Block(body) => collect_lambdas(body, out),
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                collect_lambdas(then_branch, out);
+                if let Some(else_branch) = else_branch {
+                    collect_lambdas(else_branch, out);
+                }
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    collect_lambdas(&case.statements, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_expr_lambdas<'a>(expr: &'a crate::parser::Expression, out: &mut Vec<LambdaParts<'a>>) {
+    use crate::parser::Expression;
+
+    match expr {
+        Expression::Lambda { captures, body, .. } => out.push((captures, body)),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_expr_lambdas(arg, out);
+            }
+        }
+        Expression::Move { inner, .. } => collect_expr_lambdas(inner, out),
+        _ => {}
+    }
+}
+
 // Helper function to get line number from a statement
-#[allow(dead_code)]
 fn get_statement_line(stmt: &crate::parser::Statement) -> Option<u32> {
     use crate::parser::Statement;
     match stmt {
@@ -812,6 +1563,15 @@ fn extract_return_source(
                     }
                     None
                 }
+                inner_expr if is_deref_this(inner_expr) => {
+                    debug_println!("DEBUG IR: Return Move(*this)");
+                    statements.push(IrStatement::Move {
+                        from: "this".to_string(),
+                        to: "_returned_this".to_string(),
+                        line: 0,
+                    });
+                    None
+                }
                 _ => {
                     // Move of complex expression - process inner for its
                     // side-effects but do not surface a source string.
@@ -946,13 +1706,98 @@ fn convert_statement(
     current_scope_level: &mut usize,
     user_defined_raii_types: &std::collections::HashSet<String>,
     types_with_ref_members: &std::collections::HashSet<String>,
+    move_only_types: &std::collections::HashSet<String>,
+    class_names: &std::collections::HashSet<String>,
 ) -> Result<Option<Vec<IrStatement>>, String> {
-    use crate::parser::Statement;
+    // `run([p = std::move(p)] { ... });` moves `p` out before the statement runs
+    let mut statements = statement_lambda_moves(stmt);
+    let converted = lower_statement(
+        stmt,
+        variables,
+        current_scope_level,
+        user_defined_raii_types,
+        types_with_ref_members,
+        move_only_types,
+        class_names,
+    )?;
+    if statements.is_empty() {
+        return Ok(converted);
+    }
+    statements.extend(converted.into_iter().flatten());
+    Ok(Some(statements))
+}
 
-    debug_println!(
-        "DEBUG IR: Converting statement: {:?}",
-        match stmt {
-            Statement::VariableDecl(_) => "VariableDecl",
+/// A `Move` out of each outer variable consumed by a move init-capture of a
+/// lambda written in `stmt`. `auto f = [p = std::move(p)] { ... };` is left
+/// to [`lower_statement`], which moves `p` into `f` itself; lambdas in nested
+/// statements are handled when those are converted.
+fn statement_lambda_moves(stmt: &crate::parser::Statement) -> Vec<IrStatement> {
+    use crate::parser::{Expression, Statement};
+
+    let exprs: Vec<&Expression> = match stmt {
+        Statement::Assignment {
+            lhs: Expression::Variable(_),
+            rhs: Expression::Lambda { .. },
+            ..
+        } => Vec::new(),
+        Statement::Assignment { rhs, .. } => vec![rhs],
+        Statement::ReferenceBinding { target, .. } => vec![target],
+        Statement::Return(Some(expr)) | Statement::ExpressionStatement { expr, .. } => vec![expr],
+        Statement::FunctionCall { args, .. } => args.iter().collect(),
+        Statement::If { condition, .. } | Statement::Switch { condition, .. } => vec![condition],
+        _ => Vec::new(),
+    };
+    let mut sources = Vec::new();
+    for expr in exprs {
+        collect_lambda_move_sources(expr, &mut sources);
+    }
+    let line = get_statement_line(stmt).unwrap_or(0) as usize;
+    sources
+        .into_iter()
+        .map(|source| IrStatement::Move {
+            to: format!("_moved_{}", source),
+            from: source,
+            line,
+        })
+        .collect()
+}
+
+fn collect_lambda_move_sources(expr: &crate::parser::Expression, out: &mut Vec<String>) {
+    use crate::parser::Expression;
+
+    match expr {
+        Expression::Lambda {
+            captures,
+            capture_initializers,
+            ..
+        } => out.extend(moved_lambda_capture_sources(captures, capture_initializers)),
+        Expression::FunctionCall { args, .. } => {
+            for arg in args {
+                collect_lambda_move_sources(arg, out);
+            }
+        }
+        Expression::Move { inner, .. } | Expression::Cast { inner, .. } => {
+            collect_lambda_move_sources(inner, out)
+        }
+        _ => {}
+    }
+}
+
+fn lower_statement(
+    stmt: &crate::parser::Statement,
+    variables: &mut HashMap<String, VariableInfo>,
+    current_scope_level: &mut usize,
+    user_defined_raii_types: &std::collections::HashSet<String>,
+    types_with_ref_members: &std::collections::HashSet<String>,
+    move_only_types: &std::collections::HashSet<String>,
+    class_names: &std::collections::HashSet<String>,
+) -> Result<Option<Vec<IrStatement>>, String> {
+    use crate::parser::Statement;
+
+    debug_println!(
+        "DEBUG IR: Converting statement: {:?}",
+        match stmt {
+            Statement::VariableDecl(_) => "VariableDecl",
             Statement::Assignment { .. } => "Assignment",
             Statement::ReferenceBinding { .. } => "ReferenceBinding",
             Statement::Return(_) => "Return",
@@ -992,6 +1837,11 @@ fn convert_statement(
                         OwnershipState::Uninitialized,
                     )
                 }
+            } else if var.is_shared_ptr && !var.is_pointer {
+                (
+                    VariableType::SharedPtr(var.type_name.clone()),
+                    OwnershipState::Owned,
+                )
             } else {
                 (
                     VariableType::Owned(var.type_name.clone()),
@@ -1072,6 +1922,36 @@ fn convert_statement(
                     });
                 }
 
+                // Reference to a raw pointer's target: `auto& r = *p;` borrows
+                // the pointee, not `p` itself, so re-seating `p` stays legal
+                crate::parser::Expression::Dereference(inner) => {
+                    let crate::parser::Expression::Variable(ptr_var) = inner.as_ref() else {
+                        return Ok(None);
+                    };
+                    let kind = if *is_mutable {
+                        BorrowKind::Mutable
+                    } else {
+                        BorrowKind::Immutable
+                    };
+                    if let Some(var_info) = variables.get_mut(name) {
+                        var_info.ownership = OwnershipState::Borrowed(kind.clone());
+                        if let VariableType::Owned(type_name) = &var_info.ty {
+                            var_info.ty = if *is_mutable {
+                                VariableType::MutableReference(type_name.clone())
+                            } else {
+                                VariableType::Reference(type_name.clone())
+                            };
+                        }
+                    }
+                    statements.push(IrStatement::Borrow {
+                        from: pointee_name(ptr_var),
+                        to: name.clone(),
+                        kind,
+                        line,
+                        is_pointer: false,
+                    });
+                }
+
                 // Reference to function call result: create CallExpr with result
                 crate::parser::Expression::FunctionCall {
                     name: func_name,
@@ -1085,17 +1965,24 @@ fn convert_statement(
 
                     // Check if the receiver (first arg for method calls) is a temporary
                     // This detects patterns like Builder().set(42).get_value()
-                    let receiver_is_temp = if func_name.contains("::") && !args.is_empty() {
-                        // For method calls, check if the receiver (first arg) originates from a temporary
-                        is_receiver_temporary(&args[0])
-                    } else {
-                        false
-                    };
+                    let receiver_is_temp =
+                        if is_receiver_arg(func_name, 0, class_names) && !args.is_empty() {
+                            // For method calls, check if the receiver (first arg) originates from a temporary
+                            is_receiver_temporary(&args[0], class_names)
+                        } else {
+                            false
+                        };
 
                     // Process arguments
                     for (i, arg) in args.iter().enumerate() {
                         match arg {
                             crate::parser::Expression::Variable(var) => {
+                                if !is_receiver_arg(func_name, i, class_names) {
+                                    statements.push(IrStatement::UseVariable {
+                                        var: var.clone(),
+                                        operation: format!("pass to function '{}'", func_name),
+                                    });
+                                }
                                 arg_names.push(var.clone());
                             }
                             crate::parser::Expression::Move { inner, .. } => {
@@ -1106,6 +1993,13 @@ fn convert_statement(
                                         line,
                                     });
                                     arg_names.push(var.clone());
+                                } else if is_deref_this(inner) {
+                                    statements.push(IrStatement::Move {
+                                        from: "this".to_string(),
+                                        to: "_moved_this".to_string(),
+                                        line,
+                                    });
+                                    arg_names.push("_moved_this".to_string());
                                 }
                             }
                             // Track literals as temporaries for lifetime analysis
@@ -1139,7 +2033,7 @@ fn convert_statement(
                             crate::parser::Expression::MemberAccess { .. } => {
                                 if let Some((obj_path, field_name)) = extract_member_path(arg) {
                                     // If this is the first arg (method receiver), track it for field borrowing
-                                    if i == 0 && func_name.contains("::") {
+                                    if is_receiver_arg(func_name, i, class_names) {
                                         debug_println!(
                                             "DEBUG IR: Method receiver is field access: {}.{}",
                                             obj_path,
@@ -1191,6 +2085,27 @@ fn convert_statement(
                             debug_println!("DEBUG IR: operator* with no arguments");
                         }
                     } else {
+                        // `const T& r = v.at(i);` borrows from the receiver. (The parser
+                        // already turned bindings of by-value results into locals.)
+                        if let Some(crate::parser::Expression::Variable(receiver)) = args.first() {
+                            let is_method_call = is_receiver_arg(func_name, 0, class_names)
+                                || is_method_of(func_name, receiver, variables);
+                            if is_method_call && receiver != "this" {
+                                let kind = if *is_mutable {
+                                    BorrowKind::Mutable
+                                } else {
+                                    BorrowKind::Immutable
+                                };
+                                statements.push(IrStatement::Borrow {
+                                    from: receiver.clone(),
+                                    to: name.clone(),
+                                    kind,
+                                    line,
+                                    is_pointer: false,
+                                });
+                            }
+                        }
+
                         // For other function calls, create CallExpr
                         debug_println!(
                             "DEBUG IR: Creating CallExpr for '{}' with receiver_is_temporary={}",
@@ -1202,6 +2117,7 @@ fn convert_statement(
                             args: arg_names,
                             result: Some(name.clone()),
                             receiver_is_temporary: receiver_is_temp,
+                            line,
                         });
 
                         // Phase 3: If method was called on a field and result is assigned to reference,
@@ -1335,17 +2251,24 @@ fn convert_statement(
             if let crate::parser::Expression::Dereference(ptr_expr) = lhs {
                 // Dereference assignment: *ptr = value
                 if let crate::parser::Expression::Variable(ptr_var) = ptr_expr.as_ref() {
-                    // Extract the RHS variable
-                    let _value_var = match rhs {
-                        crate::parser::Expression::Variable(v) => v.clone(),
-                        _ => return Ok(None), // For now, only handle simple cases
+                    let value = match rhs {
+                        crate::parser::Expression::Variable(v) => IrExpression::Variable(v.clone()),
+                        _ => IrExpression::Literal("<expr>".to_string()),
                     };
 
-                    // Create a UseVariable statement to check that ptr is valid
-                    return Ok(Some(vec![IrStatement::UseVariable {
-                        var: ptr_var.clone(),
-                        operation: "dereference_write".to_string(),
-                    }]));
+                    // Check that ptr is valid, then write the pointee: the
+                    // write conflicts with live references bound to `*ptr`
+                    return Ok(Some(vec![
+                        IrStatement::UseVariable {
+                            var: ptr_var.clone(),
+                            operation: "dereference_write".to_string(),
+                        },
+                        IrStatement::Assign {
+                            lhs: pointee_name(ptr_var),
+                            rhs: value,
+                            line,
+                        },
+                    ]));
                 }
                 return Ok(None);
             }
@@ -1388,12 +2311,41 @@ fn convert_statement(
                 );
 
                 if let crate::parser::Expression::Variable(obj_name) = object.as_ref() {
+                    let mut statements = Vec::new();
+                    push_field_reads(rhs, &mut statements);
+                    // this->a = std::move(other.a): the source field is consumed
+                    if let crate::parser::Expression::Move { inner, .. } = rhs {
+                        if let Some((obj_path, field_name)) = extract_member_path(inner) {
+                            statements.push(IrStatement::MoveField {
+                                object: obj_path,
+                                field: field_name,
+                                to: format!("{}.{}", obj_name, field),
+                                line,
+                            });
+                        }
+                    }
                     // Generate UseField statement for write operation
-                    return Ok(Some(vec![IrStatement::UseField {
+                    statements.push(IrStatement::UseField {
                         object: obj_name.clone(),
                         field: field.clone(),
                         operation: "write".to_string(),
-                    }]));
+                    });
+                    // obj.field = &x: the object now holds the address of x
+                    if let crate::parser::Expression::AddressOf(inner) = rhs {
+                        if let crate::parser::Expression::Variable(target_var) = inner.as_ref() {
+                            let struct_type = match variables.get(obj_name).map(|v| &v.ty) {
+                                Some(VariableType::Owned(type_name)) => type_name.clone(),
+                                _ => String::new(),
+                            };
+                            statements.push(IrStatement::StructBorrow {
+                                struct_var: obj_name.clone(),
+                                borrowed_from: target_var.clone(),
+                                struct_type,
+                                line,
+                            });
+                        }
+                    }
+                    return Ok(Some(statements));
                 } else {
                     return Ok(None);
                 }
@@ -1405,6 +2357,41 @@ fn convert_statement(
                 _ => return Ok(None), // Skip complex lhs for now
             };
 
+            // w = std::ref(x): a reference_wrapper borrows what it is bound to
+            if let Some(VariableType::Owned(type_name)) =
+                variables.get(lhs_var).map(|info| &info.ty)
+            {
+                if is_reference_wrapper_type(type_name) {
+                    if let Some((source, kind)) = reference_wrapper_source(rhs, type_name) {
+                        return Ok(Some(vec![IrStatement::Borrow {
+                            from: source.clone(),
+                            to: lhs_var.clone(),
+                            kind,
+                            line,
+                            is_pointer: false,
+                        }]));
+                    }
+                }
+            }
+
+            // A string_view/span borrows its backing storage like a const reference
+            let lhs_is_view = matches!(
+                variables.get(lhs_var).map(|info| &info.ty),
+                Some(VariableType::Owned(type_name)) if is_view_type(type_name)
+            );
+            if lhs_is_view {
+                if let Some(source) = view_source(rhs, variables) {
+                    debug_println!("DEBUG IR: View '{}' borrows from '{}'", lhs_var, source);
+                    return Ok(Some(vec![IrStatement::Borrow {
+                        from: source.clone(),
+                        to: lhs_var.clone(),
+                        kind: BorrowKind::Immutable,
+                        line,
+                        is_pointer: false,
+                    }]));
+                }
+            }
+
             // SPECIAL CASE: Check if LHS is an RAII type (Box, Rc, Arc, etc.)
             // For RAII types, assignment is operator= which:
             // 1. Drops the old value (checked if borrowed)
@@ -1412,7 +2399,9 @@ fn convert_statement(
             // This applies when RHS is Move or creates a new object
             let lhs_is_raii = if let Some(lhs_info) = variables.get(lhs_var) {
                 match &lhs_info.ty {
-                    VariableType::Owned(type_name) => is_raii_type(type_name),
+                    VariableType::Owned(type_name) | VariableType::SharedPtr(type_name) => {
+                        is_raii_type(type_name)
+                    }
                     _ => false,
                 }
             } else {
@@ -1446,7 +2435,7 @@ fn convert_statement(
                             return Ok(Some(vec![IrStatement::Move {
                                 from: from_var.clone(),
                                 to: lhs_var.clone(),
-                                line: 0,
+                                line,
                             }]));
                         }
                         _ => {
@@ -1485,7 +2474,26 @@ fn convert_statement(
                                 Ok(Some(vec![IrStatement::Move {
                                     from: rhs_var.clone(),
                                     to: lhs_var.clone(),
-                                    line: 0,
+                                    line,
+                                }]))
+                            }
+                            VariableType::Owned(type_name)
+                                if is_move_only_type(type_name, move_only_types) =>
+                            {
+                                // Copying a @move_only type is treated as a move
+                                Ok(Some(vec![IrStatement::Move {
+                                    from: rhs_var.clone(),
+                                    to: lhs_var.clone(),
+                                    line,
+                                }]))
+                            }
+                            VariableType::SharedPtr(_) => {
+                                // Copying a shared_ptr only adds an owner;
+                                // `q = std::move(p)` is what empties `p`
+                                Ok(Some(vec![IrStatement::Assign {
+                                    lhs: lhs_var.clone(),
+                                    rhs: IrExpression::Variable(rhs_var.clone()),
+                                    line,
                                 }]))
                             }
                             _ => {
@@ -1547,7 +2555,7 @@ fn convert_statement(
                             Ok(Some(vec![IrStatement::Move {
                                 from: var.clone(),
                                 to: lhs_var.clone(),
-                                line: 0,
+                                line,
                             }]))
                         }
                         // NEW: Handle std::move(obj.field) including nested fields
@@ -1572,6 +2580,13 @@ fn convert_statement(
                                 Ok(None)
                             }
                         }
+                        inner_expr if is_deref_this(inner_expr) => {
+                            Ok(Some(vec![IrStatement::Move {
+                                from: "this".to_string(),
+                                to: lhs_var.clone(),
+                                line,
+                            }]))
+                        }
                         _ => {
                             debug_println!(
                                 "DEBUG IR: Move expression doesn't contain a variable or member access"
@@ -1627,6 +2642,13 @@ fn convert_statement(
                                             operation: format!("call method '{}'", name),
                                         });
                                     }
+                                } else {
+                                    // Passing by value copies the argument, so it must not
+                                    // have been moved from
+                                    statements.push(IrStatement::UseVariable {
+                                        var: var.clone(),
+                                        operation: format!("pass to function '{}'", name),
+                                    });
                                 }
                                 arg_names.push(var.clone());
                             }
@@ -1649,7 +2671,7 @@ fn convert_statement(
                                         statements.push(IrStatement::Move {
                                             from: var.clone(),
                                             to: temp_name.clone(),
-                                            line: 0,
+                                            line,
                                         });
 
                                         if is_method_call && i == 0 {
@@ -1684,6 +2706,14 @@ fn convert_statement(
                                             arg_names.push(format!("{}.{}", obj_path, field_name));
                                         }
                                     }
+                                    inner_expr if is_deref_this(inner_expr) => {
+                                        statements.push(IrStatement::Move {
+                                            from: "this".to_string(),
+                                            to: "_moved_this".to_string(),
+                                            line,
+                                        });
+                                        arg_names.push("_moved_this".to_string());
+                                    }
                                     _ => {}
                                 }
                             }
@@ -1696,25 +2726,33 @@ fn convert_statement(
 
                                 // Check if this is operator-> (pointer dereference for method call)
                                 if is_member_access_operator(&recv_name) {
-                                    // Extract the actual pointer variable from operator-> args
-                                    for recv_arg in recv_args {
-                                        if let crate::parser::Expression::Variable(var) = recv_arg {
-                                            debug_println!(
-                                                "DEBUG IR: Found pointer variable in operator->: {}",
-                                                var
-                                            );
-                                            statements.push(IrStatement::UseVariable {
-                                                var: var.clone(),
-                                                operation: format!(
-                                                    "call method '{}' via operator->",
-                                                    name
-                                                ),
-                                            });
-                                        }
+                                    // Extract the root pointer variable, through a->b->c chains
+                                    for var in recv_args.iter().filter_map(arrow_chain_root) {
+                                        debug_println!(
+                                            "DEBUG IR: Found pointer variable in operator->: {}",
+                                            var
+                                        );
+                                        statements.push(IrStatement::UseVariable {
+                                            var: var.clone(),
+                                            operation: format!(
+                                                "call method '{}' via operator->",
+                                                name
+                                            ),
+                                        });
                                     }
                                 }
                                 arg_names.push(format!("_result_of_{}", recv_name));
                             }
+                            crate::parser::Expression::FunctionCall {
+                                name: inner_name,
+                                args: inner_args,
+                            } => {
+                                if let Some(use_receiver) =
+                                    nested_receiver_use(inner_name, inner_args)
+                                {
+                                    statements.push(use_receiver);
+                                }
+                            }
                             // NOTE: Expression::Move is handled above at the start of this match
                             // NEW: Handle field access as function argument (including nested)
                             crate::parser::Expression::MemberAccess { .. } => {
@@ -1743,6 +2781,7 @@ fn convert_statement(
                         args: arg_names,
                         result: Some(lhs_var.clone()),
                         receiver_is_temporary: false, // TODO: detect temporaries
+                        line,
                     });
 
                     // Struct-with-ref-members: if this is a constructor for a type
@@ -1800,48 +2839,36 @@ fn convert_statement(
                     }]))
                 }
                 // Lambda expression: generate LambdaCapture statement for safety checking
-                crate::parser::Expression::Lambda { captures, .. } => {
+                crate::parser::Expression::Lambda {
+                    captures,
+                    capture_initializers,
+                    body,
+                } => {
                     debug_println!("DEBUG IR: Lambda assignment: {} = [captures]", lhs_var);
-                    let capture_infos: Vec<LambdaCaptureInfo> = captures
-                        .iter()
-                        .map(|c| {
-                            use crate::parser::ast_visitor::LambdaCaptureKind;
-                            match c {
-                                LambdaCaptureKind::DefaultRef => LambdaCaptureInfo {
-                                    name: "<default>".to_string(),
-                                    is_ref: true,
-                                },
-                                LambdaCaptureKind::DefaultCopy => LambdaCaptureInfo {
-                                    name: "<default>".to_string(),
-                                    is_ref: false,
-                                },
-                                LambdaCaptureKind::ByRef(name) => LambdaCaptureInfo {
-                                    name: name.clone(),
-                                    is_ref: true,
-                                },
-                                LambdaCaptureKind::ByCopy(name) => LambdaCaptureInfo {
-                                    name: name.clone(),
-                                    is_ref: false,
-                                },
-                                LambdaCaptureKind::Init { name, .. } => LambdaCaptureInfo {
-                                    name: name.clone(),
-                                    is_ref: false, // Init captures are by value
-                                },
-                                LambdaCaptureKind::This => LambdaCaptureInfo {
-                                    name: "this".to_string(),
-                                    is_ref: true, // 'this' capture is a pointer, essentially by-ref
-                                },
-                                LambdaCaptureKind::ThisCopy => LambdaCaptureInfo {
-                                    name: "this".to_string(),
-                                    is_ref: false, // *this capture is by value
-                                },
-                            }
-                        })
-                        .collect();
+                    let capture_infos = lambda_capture_infos(captures, body, variables);
 
-                    Ok(Some(vec![IrStatement::LambdaCapture {
+                    let mut statements = vec![IrStatement::LambdaCapture {
+                        lambda_var: lhs_var.clone(),
                         captures: capture_infos,
-                    }]))
+                    }];
+
+                    // Move init-captures ([p = std::move(p)]) transfer ownership of
+                    // the outer variable into the closure, so later uses of it are
+                    // use-after-move.
+                    for source in moved_lambda_capture_sources(captures, capture_initializers) {
+                        debug_println!(
+                            "DEBUG IR: Lambda init-capture moves '{}' into '{}'",
+                            source,
+                            lhs_var
+                        );
+                        statements.push(IrStatement::Move {
+                            from: source,
+                            to: lhs_var.clone(),
+                            line,
+                        });
+                    }
+
+                    Ok(Some(statements))
                 }
                 // NEW: Handle pointer initialization from address-of: T* p = &x
                 // This creates a borrow from x to p (pointer borrows the address of x)
@@ -1856,24 +2883,7 @@ fn convert_statement(
                             );
 
                             // Determine mutability from LHS pointer type
-                            // - Ptr<T> (const T*) -> Immutable borrow
-                            // - MutPtr<T> (T*) -> Mutable borrow
-                            let kind = if let Some(var_info) = variables.get(lhs_var) {
-                                match &var_info.ty {
-                                    VariableType::Owned(type_name)
-                                        if is_immutable_pointer_type(type_name) =>
-                                    {
-                                        BorrowKind::Immutable
-                                    }
-                                    _ => {
-                                        // Non-const pointer or MutPtr defaults to mutable borrow
-                                        BorrowKind::Mutable
-                                    }
-                                }
-                            } else {
-                                // Default to mutable for unknown types
-                                BorrowKind::Mutable
-                            };
+                            let kind = pointer_borrow_kind(lhs_var, variables);
 
                             Ok(Some(vec![IrStatement::Borrow {
                                 from: target_var.clone(),
@@ -1894,18 +2904,7 @@ fn convert_statement(
                                 );
 
                                 // Same mutability logic as above
-                                let kind = if let Some(var_info) = variables.get(lhs_var) {
-                                    match &var_info.ty {
-                                        VariableType::Owned(type_name)
-                                            if is_immutable_pointer_type(type_name) =>
-                                        {
-                                            BorrowKind::Immutable
-                                        }
-                                        _ => BorrowKind::Mutable,
-                                    }
-                                } else {
-                                    BorrowKind::Mutable
-                                };
+                                let kind = pointer_borrow_kind(lhs_var, variables);
 
                                 // For field borrows, we track against the whole object
                                 // (partial borrow tracking is handled separately)
@@ -1923,6 +2922,38 @@ fn convert_statement(
                         _ => Ok(None),
                     }
                 }
+                // Compound assignment `x += y` (parsed as `x = x + y`): lower the
+                // read of `y` as for `x = y`, and make sure the write of `x` is
+                // an Assign so it is checked against outstanding borrows
+                crate::parser::Expression::BinaryOp { left, op, right } if matches!(left.as_ref(), crate::parser::Expression::Variable(v) if v == lhs_var) =>
+                {
+                    let operand = Statement::Assignment {
+                        lhs: lhs.clone(),
+                        rhs: right.as_ref().clone(),
+                        location: location.clone(),
+                    };
+                    let mut statements = convert_statement(
+                        &operand,
+                        variables,
+                        current_scope_level,
+                        user_defined_raii_types,
+                        types_with_ref_members,
+                        move_only_types,
+                        class_names,
+                    )?
+                    .unwrap_or_default();
+                    let writes_lhs = statements
+                        .iter()
+                        .any(|s| matches!(s, IrStatement::Assign { lhs, .. } if lhs == lhs_var));
+                    if !writes_lhs {
+                        statements.push(IrStatement::Assign {
+                            lhs: lhs_var.clone(),
+                            rhs: IrExpression::Literal(op.clone()),
+                            line,
+                        });
+                    }
+                    Ok(Some(statements))
+                }
                 _ => Ok(None),
             };
 
@@ -1971,18 +3002,67 @@ fn convert_statement(
             if is_assignment_operator(&name) {
                 debug_println!("DEBUG IR: Detected operator= call");
                 if args.len() == 2 {
+                    // std::tie(a, b) = std::move(p): p's components are moved into
+                    // a and b, which hold values again afterwards
+                    if let crate::parser::Expression::FunctionCall {
+                        name: tie,
+                        args: targets,
+                    } = &args[0]
+                    {
+                        if tie.rsplit("::").next() == Some("tie") {
+                            return Ok(Some(tie_assignment(targets, &args[1], line)));
+                        }
+                    }
+
                     // First arg is LHS (destination), second is RHS (source)
                     if let crate::parser::Expression::Variable(lhs) = &args[0] {
                         // Check if LHS is an RAII type
                         let lhs_is_raii = if let Some(lhs_info) = variables.get(lhs) {
                             match &lhs_info.ty {
-                                VariableType::Owned(type_name) => is_raii_type(type_name),
+                                VariableType::Owned(type_name)
+                                | VariableType::SharedPtr(type_name) => is_raii_type(type_name),
                                 _ => false,
                             }
                         } else {
                             false
                         };
 
+                        // w = std::ref(x): the wrapper rebinds to borrow x
+                        if let Some(VariableType::Owned(type_name)) =
+                            variables.get(lhs).map(|info| &info.ty)
+                        {
+                            if is_reference_wrapper_type(type_name) {
+                                if let Some((source, kind)) =
+                                    reference_wrapper_source(&args[1], type_name)
+                                {
+                                    return Ok(Some(vec![IrStatement::Borrow {
+                                        from: source.clone(),
+                                        to: lhs.clone(),
+                                        kind,
+                                        line,
+                                        is_pointer: false,
+                                    }]));
+                                }
+                            }
+                        }
+
+                        // sv = s: a string_view/span rebinds to borrow s
+                        let lhs_is_view = matches!(
+                            variables.get(lhs).map(|info| &info.ty),
+                            Some(VariableType::Owned(type_name)) if is_view_type(type_name)
+                        );
+                        if lhs_is_view {
+                            if let Some(source) = view_source(&args[1], variables) {
+                                return Ok(Some(vec![IrStatement::Borrow {
+                                    from: source.clone(),
+                                    to: lhs.clone(),
+                                    kind: BorrowKind::Immutable,
+                                    line,
+                                    is_pointer: false,
+                                }]));
+                            }
+                        }
+
                         // Handle Move RHS
                         if let crate::parser::Expression::Move {
                             inner: rhs_inner, ..
@@ -1998,7 +3078,7 @@ fn convert_statement(
                                 return Ok(Some(vec![IrStatement::Move {
                                     from: rhs.clone(),
                                     to: lhs.clone(),
-                                    line: 0,
+                                    line,
                                 }]));
                             }
                         }
@@ -2012,6 +3092,53 @@ fn convert_statement(
                             // Generate Drop check - the FunctionCall itself will be processed below
                             statements.push(IrStatement::Drop(lhs.clone()));
                         }
+
+                        // p = std::make_unique<T>() / p = make(): the call result is a
+                        // fresh value, so p holds a value again whatever its old state
+                        let lhs_is_value = matches!(
+                            variables.get(lhs).map(|info| &info.ty),
+                            Some(ty) if !matches!(
+                                ty,
+                                VariableType::Reference(_) | VariableType::MutableReference(_)
+                            )
+                        );
+                        if let crate::parser::Expression::FunctionCall {
+                            name: rhs_name,
+                            args: rhs_args,
+                        } = &args[1]
+                        {
+                            if lhs_is_value {
+                                if let Some(use_receiver) = nested_receiver_use(rhs_name, rhs_args)
+                                {
+                                    statements.push(use_receiver);
+                                }
+                                push_nested_call_moves(rhs_args, line, &mut statements);
+                                let rhs_arg_names = rhs_args
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, arg)| match arg {
+                                        crate::parser::Expression::Variable(var) => var.clone(),
+                                        crate::parser::Expression::Move { inner, .. } => {
+                                            match inner.as_ref() {
+                                                crate::parser::Expression::Variable(var) => {
+                                                    var.clone()
+                                                }
+                                                _ => format!("_temp_expr_{}", i),
+                                            }
+                                        }
+                                        _ => format!("_temp_expr_{}", i),
+                                    })
+                                    .collect();
+                                statements.push(IrStatement::CallExpr {
+                                    func: rhs_name.clone(),
+                                    args: rhs_arg_names,
+                                    result: Some(lhs.clone()),
+                                    receiver_is_temporary: false,
+                                    line,
+                                });
+                                return Ok(Some(statements));
+                            }
+                        }
                     }
                 }
             }
@@ -2056,7 +3183,7 @@ fn convert_statement(
                                 statements.push(IrStatement::Move {
                                     from: var.clone(),
                                     to: temp_name.clone(),
-                                    line: 0,
+                                    line,
                                 });
 
                                 if is_method_call && i == 0 {
@@ -2091,6 +3218,14 @@ fn convert_statement(
                                     arg_names.push(format!("{}.{}", obj_path, field_name));
                                 }
                             }
+                            inner_expr if is_deref_this(inner_expr) => {
+                                statements.push(IrStatement::Move {
+                                    from: "this".to_string(),
+                                    to: "_moved_this".to_string(),
+                                    line,
+                                });
+                                arg_names.push("_moved_this".to_string());
+                            }
                             _ => {}
                         }
                     }
@@ -2104,65 +3239,26 @@ fn convert_statement(
                         if is_method_call && i == 0 {
                             // Check if this is operator-> (pointer dereference for method call)
                             if is_member_access_operator(&inner_name) {
-                                // Extract the actual pointer variable from operator-> args
-                                for inner_arg in inner_args {
-                                    if let crate::parser::Expression::Variable(var) = inner_arg {
-                                        debug_println!(
-                                            "DEBUG IR: Found pointer variable in operator->: {}",
-                                            var
-                                        );
-                                        statements.push(IrStatement::UseVariable {
-                                            var: var.clone(),
-                                            operation: format!(
-                                                "call method '{}' via operator->",
-                                                name
-                                            ),
-                                        });
-                                    }
+                                // Extract the root pointer variable, through a->b->c chains
+                                for var in inner_args.iter().filter_map(arrow_chain_root) {
+                                    debug_println!(
+                                        "DEBUG IR: Found pointer variable in operator->: {}",
+                                        var
+                                    );
+                                    statements.push(IrStatement::UseVariable {
+                                        var: var.clone(),
+                                        operation: format!("call method '{}' via operator->", name),
+                                    });
                                 }
                             }
+                        } else if let Some(use_receiver) =
+                            nested_receiver_use(inner_name, inner_args)
+                        {
+                            statements.push(use_receiver);
                         }
 
-                        // Recursively check for moves in nested function call
-                        for inner_arg in inner_args {
-                            if let crate::parser::Expression::Move {
-                                inner: move_inner, ..
-                            } = inner_arg
-                            {
-                                match move_inner.as_ref() {
-                                    crate::parser::Expression::Variable(var) => {
-                                        debug_println!(
-                                            "DEBUG IR: Found Move(Variable) in nested call: {}",
-                                            var
-                                        );
-                                        statements.push(IrStatement::Move {
-                                            from: var.clone(),
-                                            to: format!("_moved_{}", var),
-                                            line,
-                                        });
-                                    }
-                                    crate::parser::Expression::MemberAccess { .. } => {
-                                        // Use helper to extract full path for nested member access
-                                        if let Some((obj_path, field_name)) =
-                                            extract_member_path(move_inner.as_ref())
-                                        {
-                                            debug_println!(
-                                                "DEBUG IR: Found Move(MemberAccess) in nested call: {}.{}",
-                                                obj_path,
-                                                field_name
-                                            );
-                                            statements.push(IrStatement::MoveField {
-                                                object: obj_path,
-                                                field: field_name.clone(),
-                                                to: format!("_moved_{}", field_name),
-                                                line,
-                                            });
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
+                        // Recursively check for moves in nested function calls
+                        push_nested_call_moves(inner_args, line, &mut statements);
                         // Use placeholder for nested call result
                         arg_names.push(format!("_result_of_{}", inner_name));
                     }
@@ -2216,6 +3312,10 @@ fn convert_statement(
                         temp_counter += 1;
                         arg_names.push(temp_name);
                     }
+                    // `f(*this)` hands out the whole object
+                    expr if is_deref_this(expr) => {
+                        arg_names.push("this".to_string());
+                    }
                     _ => {}
                 }
             }
@@ -2225,6 +3325,7 @@ fn convert_statement(
                 args: arg_names,
                 result: None,
                 receiver_is_temporary: false, // TODO: detect temporaries
+                line,
             });
 
             Ok(Some(statements))
@@ -2313,9 +3414,11 @@ fn convert_statement(
             *current_scope_level = current_scope_level.saturating_sub(1);
             Ok(Some(statements))
         }
-        Statement::EnterLoop => Ok(Some(vec![IrStatement::EnterLoop])),
+        Statement::EnterLoop | Statement::EnterDoLoop => Ok(Some(vec![IrStatement::EnterLoop])),
         Statement::ExitLoop => Ok(Some(vec![IrStatement::ExitLoop])),
-        Statement::EnterUnsafe => Ok(Some(vec![IrStatement::EnterUnsafe])),
+        Statement::EnterUnsafe { location } => Ok(Some(vec![IrStatement::EnterUnsafe {
+            line: location.line as usize,
+        }])),
         Statement::ExitUnsafe => Ok(Some(vec![IrStatement::ExitUnsafe])),
         Statement::If {
             condition,
@@ -2371,6 +3474,8 @@ fn convert_statement(
                     current_scope_level,
                     user_defined_raii_types,
                     types_with_ref_members,
+                    move_only_types,
+                    class_names,
                 )? {
                     then_ir.extend(ir_stmts);
                 }
@@ -2386,6 +3491,8 @@ fn convert_statement(
                         current_scope_level,
                         user_defined_raii_types,
                         types_with_ref_members,
+                        move_only_types,
+                        class_names,
                     )? {
                         else_ir.extend(ir_stmts);
                     }
@@ -2442,6 +3549,8 @@ fn convert_statement(
                         current_scope_level,
                         user_defined_raii_types,
                         types_with_ref_members,
+                        move_only_types,
+                        class_names,
                     )? {
                         statements.extend(ir_stmts);
                     }
@@ -2453,7 +3562,7 @@ fn convert_statement(
             result.push(IrStatement::Switch { cases: case_ir });
             Ok(Some(result))
         }
-        Statement::ExpressionStatement { expr, .. } => {
+        Statement::ExpressionStatement { expr, location } => {
             // Handle expression statements (dereference, method calls, assignments, etc.)
             match expr {
                 crate::parser::Expression::Dereference(inner) => {
@@ -2471,10 +3580,41 @@ fn convert_statement(
                     // Address-of doesn't use the value, so no moved-state check needed
                     Ok(None)
                 }
+                // `delete p;` frees what p owns - the RAII pass tracks double frees
+                crate::parser::Expression::Delete(inner) => {
+                    if let crate::parser::Expression::Variable(var) = inner.as_ref() {
+                        Ok(Some(vec![IrStatement::CallExpr {
+                            func: "operator delete".to_string(),
+                            args: vec![var.clone()],
+                            result: None,
+                            receiver_is_temporary: false,
+                            line: location.line as usize,
+                        }]))
+                    } else {
+                        Ok(None)
+                    }
+                }
                 // Handle assignment expressions (e.g., value = 42;)
-                crate::parser::Expression::BinaryOp { left, op, .. } if op == "=" => {
+                crate::parser::Expression::BinaryOp { left, op, right } if op == "=" => {
                     debug_println!("DEBUG IR: ExpressionStatement assignment: op={}", op);
 
+                    // p = &local: the pointer borrows local, so it must not outlive it
+                    if let (
+                        crate::parser::Expression::Variable(lhs_var),
+                        crate::parser::Expression::AddressOf(inner),
+                    ) = (left.as_ref(), right.as_ref())
+                    {
+                        if let crate::parser::Expression::Variable(target_var) = inner.as_ref() {
+                            return Ok(Some(vec![IrStatement::Borrow {
+                                from: target_var.clone(),
+                                to: lhs_var.clone(),
+                                kind: pointer_borrow_kind(lhs_var, variables),
+                                line: location.line as usize,
+                                is_pointer: true,
+                            }]));
+                        }
+                    }
+
                     // Check if LHS is a field access (e.g., this.value = 42)
                     if let crate::parser::Expression::MemberAccess { object, field } = left.as_ref()
                     {
@@ -2539,6 +3679,8 @@ mod tests {
                 file: "test.cpp".to_string(),
                 line: 1,
                 column: 1,
+                end_line: 0,
+                end_column: 0,
             },
             is_method: false,
             method_qualifier: None,
@@ -2562,10 +3704,13 @@ mod tests {
             is_shared_ptr: false,
             is_static: false,
             is_mutable: false,
+            is_volatile: false,
             location: SourceLocation {
                 file: "test.cpp".to_string(),
                 line: 1,
                 column: 1,
+                end_line: 0,
+                end_column: 0,
             },
             is_pack: false,
             pack_element_type: None,
@@ -2628,6 +3773,1273 @@ mod tests {
         assert_eq!(var_info.ownership, OwnershipState::Owned);
     }
 
+    #[test]
+    fn test_lambda_move_init_capture_moves_outer_variable() {
+        use crate::parser::ast_visitor::{LambdaCaptureKind, MoveKind};
+        use crate::parser::{Expression, Statement};
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("p", "std::unique_ptr<int>", true)),
+            Statement::Assignment {
+                lhs: Expression::Variable("f".to_string()),
+                rhs: Expression::Lambda {
+                    captures: vec![LambdaCaptureKind::Init {
+                        name: "p".to_string(),
+                        is_move: true,
+                    }],
+                    capture_initializers: vec![Expression::Move {
+                        inner: Box::new(Expression::Variable("p".to_string())),
+                        kind: MoveKind::StdMove,
+                    }],
+                    body: vec![],
+                },
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 3,
+                    column: 5,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let moves_p = ir.functions[0].cfg.node_weights().any(|block| {
+            block.statements.iter().any(|stmt| {
+                matches!(stmt, IrStatement::Move { from, to, .. } if from == "p" && to == "f")
+            })
+        });
+        assert!(moves_p, "Move init-capture should move 'p' into the lambda");
+    }
+
+    #[test]
+    fn test_lambda_move_init_capture_in_call_argument_moves_outer_variable() {
+        use crate::parser::ast_visitor::{LambdaCaptureKind, MoveKind};
+        use crate::parser::{Expression, Statement};
+
+        // run([q = std::move(p)] {});
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("p", "std::unique_ptr<int>", true)),
+            Statement::FunctionCall {
+                name: "run".to_string(),
+                args: vec![Expression::Lambda {
+                    captures: vec![LambdaCaptureKind::Init {
+                        name: "q".to_string(),
+                        is_move: true,
+                    }],
+                    capture_initializers: vec![Expression::Move {
+                        inner: Box::new(Expression::Variable("p".to_string())),
+                        kind: MoveKind::StdMove,
+                    }],
+                    body: vec![],
+                }],
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 3,
+                    column: 5,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let moves_p = ir.functions[0].cfg.node_weights().any(|block| {
+            block.statements.iter().any(|stmt| {
+                matches!(stmt, IrStatement::Move { from, line, .. } if from == "p" && *line == 3)
+            })
+        });
+        assert!(moves_p, "Move init-capture should move 'p' before the call");
+    }
+
+    #[test]
+    fn test_shared_ptr_copy_is_assign_and_move_is_move() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let shared = |name: &str| Variable {
+            is_shared_ptr: true,
+            ..create_test_variable(name, "std::shared_ptr<int>", false)
+        };
+        let assign = |lhs: &str, rhs: Expression| Statement::Assignment {
+            lhs: Expression::Variable(lhs.to_string()),
+            rhs,
+            location: SourceLocation {
+                file: "test.cpp".to_string(),
+                line: 3,
+                column: 5,
+                end_line: 0,
+                end_column: 0,
+            },
+        };
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(shared("p")),
+            Statement::VariableDecl(shared("q")),
+            assign("q", Expression::Variable("p".to_string())),
+            Statement::VariableDecl(shared("r")),
+            assign(
+                "r",
+                Expression::Move {
+                    inner: Box::new(Expression::Variable("p".to_string())),
+                    kind: MoveKind::StdMove,
+                },
+            ),
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let function = &ir.functions[0];
+        assert!(matches!(
+            function.variables["p"].ty,
+            VariableType::SharedPtr(_)
+        ));
+        let statements: Vec<&IrStatement> = function
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .collect();
+        assert!(statements.iter().any(|stmt| matches!(
+            stmt,
+            IrStatement::Assign { lhs, rhs: IrExpression::Variable(rhs), .. } if lhs == "q" && rhs == "p"
+        )));
+        assert!(!statements.iter().any(|stmt| matches!(
+            stmt,
+            IrStatement::Move { from, to, .. } if from == "p" && to == "q"
+        )));
+        assert!(statements.iter().any(|stmt| matches!(
+            stmt,
+            IrStatement::Move { from, to, .. } if from == "p" && to == "r"
+        )));
+    }
+
+    #[test]
+    fn test_lambda_body_checked_as_nested_function() {
+        use crate::parser::ast_visitor::{LambdaCaptureKind, MoveKind};
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 4,
+            column: 9,
+            end_line: 0,
+            end_column: 0,
+        };
+        let move_x_into = |lhs: &str| Statement::Assignment {
+            lhs: Expression::Variable(lhs.to_string()),
+            rhs: Expression::Move {
+                inner: Box::new(Expression::Variable("x".to_string())),
+                kind: MoveKind::StdMove,
+            },
+            location: location.clone(),
+        };
+
+        let mut func = create_test_function("test");
+        func.body = vec![Statement::Assignment {
+            lhs: Expression::Variable("f".to_string()),
+            rhs: Expression::Lambda {
+                captures: vec![LambdaCaptureKind::ByCopy("x".to_string())],
+                capture_initializers: vec![],
+                body: vec![move_x_into("y"), move_x_into("z")],
+            },
+            location: location.clone(),
+        }];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        assert_eq!(ir.functions.len(), 2);
+        let lambda = &ir.functions[1];
+        assert_eq!(lambda.name, "test::<lambda#1>");
+        assert_eq!(
+            lambda.variables.get("x").map(|info| &info.ownership),
+            Some(&OwnershipState::Owned),
+            "by-copy capture starts out owned inside the body"
+        );
+
+        let errors = crate::analysis::check_borrows(ir).unwrap();
+        assert!(
+            errors.iter().any(|e| e.contains("Use after move")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_while_borrowed() {
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 4,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let var = |name: &str| Expression::Variable(name.to_string());
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("sum", "int", false)),
+            Statement::VariableDecl(create_test_variable("p", "int *", false)),
+            // const int& r = sum;
+            Statement::ReferenceBinding {
+                name: "r".to_string(),
+                target: var("sum"),
+                is_mutable: false,
+                location: location.clone(),
+            },
+            // sum += *p;
+            Statement::Assignment {
+                lhs: var("sum"),
+                rhs: Expression::BinaryOp {
+                    left: Box::new(var("sum")),
+                    op: "+".to_string(),
+                    right: Box::new(Expression::Dereference(Box::new(var("p")))),
+                },
+                location: location.clone(),
+            },
+            Statement::FunctionCall {
+                name: "use".to_string(),
+                args: vec![var("r")],
+                location,
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let errors = crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Cannot assign to 'sum' because it is borrowed by: r")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_write_through_pointer_while_pointee_borrowed() {
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 4,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let var = |name: &str| Expression::Variable(name.to_string());
+        let deref = |name: &str| Expression::Dereference(Box::new(var(name)));
+        let body = |reseat: bool| {
+            let mut body = vec![
+                Statement::VariableDecl(create_test_variable("p", "int *", false)),
+                Statement::VariableDecl(create_test_variable("q", "int *", false)),
+                Statement::VariableDecl(create_test_variable("r", "const int &", false)),
+                // const int& r = *p;
+                Statement::ReferenceBinding {
+                    name: "r".to_string(),
+                    target: deref("p"),
+                    is_mutable: false,
+                    location: location.clone(),
+                },
+            ];
+            if reseat {
+                // p = q;
+                body.push(Statement::Assignment {
+                    lhs: var("p"),
+                    rhs: var("q"),
+                    location: location.clone(),
+                });
+            }
+            // *p = 5;
+            body.push(Statement::Assignment {
+                lhs: deref("p"),
+                rhs: Expression::Literal("5".to_string()),
+                location: location.clone(),
+            });
+            body.push(Statement::FunctionCall {
+                name: "use".to_string(),
+                args: vec![var("r")],
+                location: location.clone(),
+            });
+            body
+        };
+        let errors_for = |reseat: bool| {
+            let mut func = create_test_function("test");
+            func.body = body(reseat);
+            let mut ast = crate::parser::CppAst::new();
+            ast.functions.push(func);
+            crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap()
+        };
+
+        let errors = errors_for(false);
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Cannot assign to '*p' because it is borrowed by: r")),
+            "{:?}",
+            errors
+        );
+        let errors = errors_for(true);
+        assert!(
+            !errors.iter().any(|e| e.contains("Cannot assign to")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_get_on_moved_unique_ptr_is_use_after_move() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 5,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let get = Expression::FunctionCall {
+            name: "std::unique_ptr<int>::get".to_string(),
+            args: vec![Expression::Variable("p".to_string())],
+        };
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("p", "std::unique_ptr<int>", true)),
+            // consume(std::move(p));
+            Statement::FunctionCall {
+                name: "consume".to_string(),
+                args: vec![Expression::Move {
+                    inner: Box::new(Expression::Variable("p".to_string())),
+                    kind: MoveKind::StdMove,
+                }],
+                location: location.clone(),
+            },
+            // use_raw(p.get());
+            Statement::FunctionCall {
+                name: "use_raw".to_string(),
+                args: vec![get.clone()],
+                location: location.clone(),
+            },
+            // int* raw = use_raw(p.get());
+            Statement::Assignment {
+                lhs: Expression::Variable("raw".to_string()),
+                rhs: Expression::FunctionCall {
+                    name: "use_raw".to_string(),
+                    args: vec![get],
+                },
+                location,
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let errors = crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap();
+
+        let get_uses = errors
+            .iter()
+            .filter(|e| e.contains("cannot call method 'std::unique_ptr<int>::get' variable 'p'"))
+            .count();
+        assert_eq!(get_uses, 2, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_factory_reassignment_after_move_is_fresh() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 5,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let p = || Expression::Variable("p".to_string());
+        let call = |name: &str, args: Vec<Expression>| Statement::FunctionCall {
+            name: name.to_string(),
+            args,
+            location: location.clone(),
+        };
+        let make = || Expression::FunctionCall {
+            name: "std::make_unique<int>".to_string(),
+            args: vec![],
+        };
+        let moved_p = || Expression::Move {
+            inner: Box::new(p()),
+            kind: MoveKind::StdMove,
+        };
+        let errors_for = |reassign: bool| {
+            let mut func = create_test_function("test");
+            func.body = vec![
+                Statement::VariableDecl(create_test_variable("p", "std::unique_ptr<int>", true)),
+                // auto p = std::make_unique<int>();
+                Statement::Assignment {
+                    lhs: p(),
+                    rhs: make(),
+                    location: location.clone(),
+                },
+                call("consume", vec![moved_p()]),
+            ];
+            if reassign {
+                // p = std::make_unique<int>();
+                func.body
+                    .push(call("std::unique_ptr<int>::operator=", vec![p(), make()]));
+            }
+            func.body.push(call("use", vec![p()]));
+            let mut ast = crate::parser::CppAst::new();
+            ast.functions.push(func);
+            crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap()
+        };
+
+        let errors = errors_for(true);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let errors = errors_for(false);
+        assert!(
+            errors.iter().any(|e| e.contains("Use after move")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_move_only_type_assignment_consumes_source() {
+        // Token b = a; use(a); with and without `// @move_only` on Token
+        use crate::parser::ast_visitor::Class;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 3,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let errors_for = |is_move_only: bool| {
+            let mut func = create_test_function("test");
+            func.body = vec![
+                Statement::VariableDecl(create_test_variable("a", "Token", false)),
+                Statement::VariableDecl(create_test_variable("b", "Token", false)),
+                Statement::Assignment {
+                    lhs: Expression::Variable("b".to_string()),
+                    rhs: Expression::Variable("a".to_string()),
+                    location: location.clone(),
+                },
+                Statement::FunctionCall {
+                    name: "use".to_string(),
+                    args: vec![Expression::Variable("a".to_string())],
+                    location: location.clone(),
+                },
+            ];
+            let mut ast = crate::parser::CppAst::new();
+            ast.functions.push(func);
+            ast.classes.push(Class {
+                name: "Token".to_string(),
+                members: Vec::new(),
+                methods: Vec::new(),
+                base_classes: Vec::new(),
+                location: location.clone(),
+                is_union: false,
+                has_destructor: false,
+                is_interface: false,
+                has_virtual_destructor: false,
+                destructor_is_defaulted: false,
+                all_methods_pure_virtual: false,
+                has_non_virtual_methods: false,
+                safety_annotation: None,
+                has_copy_constructor: false,
+                has_copy_assignment: false,
+                copy_constructor_deleted: false,
+                copy_assignment_deleted: false,
+                is_move_only,
+                has_user_defined_constructor: false,
+                has_default_constructor: true,
+                default_constructor_deleted: false,
+            });
+            crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap()
+        };
+
+        let errors = errors_for(true);
+        assert!(
+            errors.iter().any(|e| e.contains("Use after move")),
+            "{:?}",
+            errors
+        );
+        let errors = errors_for(false);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_tie_assignment_from_move_consumes_source() {
+        // consume(std::move(a)); std::tie(a, b) = std::move(p); use(...);
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 5,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let var = |name: &str| Expression::Variable(name.to_string());
+        let call = |name: &str, args: Vec<Expression>| Statement::FunctionCall {
+            name: name.to_string(),
+            args,
+            location: location.clone(),
+        };
+        let errors_for = |used: &str| {
+            let mut func = create_test_function("test");
+            func.body = vec![
+                Statement::VariableDecl(create_test_variable(
+                    "p",
+                    "std::pair<std::string, std::string>",
+                    false,
+                )),
+                Statement::VariableDecl(create_test_variable("a", "std::string", false)),
+                Statement::VariableDecl(create_test_variable("b", "std::string", false)),
+                call(
+                    "consume",
+                    vec![Expression::Move {
+                        inner: Box::new(var("a")),
+                        kind: MoveKind::StdMove,
+                    }],
+                ),
+                // std::tie(a, b) = std::move(p);
+                call(
+                    "std::tuple<std::string &, std::string &>::operator=",
+                    vec![
+                        Expression::FunctionCall {
+                            name: "std::tie".to_string(),
+                            args: vec![var("a"), var("b")],
+                        },
+                        Expression::Move {
+                            inner: Box::new(var("p")),
+                            kind: MoveKind::StdMove,
+                        },
+                    ],
+                ),
+                call("use", vec![var(used)]),
+            ];
+            let mut ast = crate::parser::CppAst::new();
+            ast.functions.push(func);
+            crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap()
+        };
+
+        let errors = errors_for("p");
+        assert!(
+            errors.iter().any(|e| e.contains("Use after move")),
+            "{:?}",
+            errors
+        );
+        let errors = errors_for("a");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_tie_skips_only_ignore_slots() {
+        use crate::parser::Expression;
+
+        // std::tie(dont_ignore, std::ignore, ignore) = p;
+        let targets = ["dont_ignore", "std::ignore", "ignore"]
+            .map(|name| Expression::Variable(name.to_string()));
+        let statements = tie_assignment(&targets, &Expression::Variable("p".to_string()), 3);
+        let assigned: Vec<&str> = statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                IrStatement::Assign { lhs, .. } => Some(lhs.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(assigned, ["dont_ignore"]);
+    }
+
+    #[test]
+    fn test_move_in_for_increment_is_use_after_move() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 4,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        // for (;; x = std::move(y)) {}
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("x", "std::string", false)),
+            Statement::VariableDecl(create_test_variable("y", "std::string", false)),
+            Statement::EnterLoop,
+            Statement::Assignment {
+                lhs: Expression::Variable("x".to_string()),
+                rhs: Expression::Move {
+                    inner: Box::new(Expression::Variable("y".to_string())),
+                    kind: MoveKind::StdMove,
+                },
+                location,
+            },
+            Statement::ExitLoop,
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let errors = crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("'y'") && e.contains("moved")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_arrow_chain_on_moved_root_is_use_after_move() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 6,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let arrow = |ptr_type: &str, target: Expression| Expression::FunctionCall {
+            name: format!("{}::operator->", ptr_type),
+            args: vec![target],
+        };
+        // a->b->method()
+        let receiver = arrow(
+            "std::unique_ptr<B>",
+            Expression::MemberAccess {
+                object: Box::new(arrow(
+                    "std::unique_ptr<A>",
+                    Expression::Variable("a".to_string()),
+                )),
+                field: "b".to_string(),
+            },
+        );
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("a", "std::unique_ptr<A>", true)),
+            Statement::FunctionCall {
+                name: "consume".to_string(),
+                args: vec![Expression::Move {
+                    inner: Box::new(Expression::Variable("a".to_string())),
+                    kind: MoveKind::StdMove,
+                }],
+                location: location.clone(),
+            },
+            Statement::FunctionCall {
+                name: "B::method".to_string(),
+                args: vec![receiver],
+                location,
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let errors = crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap();
+
+        assert!(
+            errors.iter().any(|e| e.contains(
+                "cannot call method 'B::method' via operator-> variable 'a' because it has been moved"
+            )),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_container_insert_moves_argument() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let moved = |name: &str| Expression::Move {
+            inner: Box::new(Expression::Variable(name.to_string())),
+            kind: MoveKind::StdMove,
+        };
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 4,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            // v.push_back(std::move(x));
+            Statement::FunctionCall {
+                name: "std::vector<std::string>::push_back".to_string(),
+                args: vec![Expression::Variable("v".to_string()), moved("x")],
+                location: location.clone(),
+            },
+            // v.emplace_back(Item(wrap(std::move(y))));
+            Statement::FunctionCall {
+                name: "std::vector<Item>::emplace_back".to_string(),
+                args: vec![
+                    Expression::Variable("v".to_string()),
+                    Expression::FunctionCall {
+                        name: "Item".to_string(),
+                        args: vec![Expression::FunctionCall {
+                            name: "wrap".to_string(),
+                            args: vec![moved("y")],
+                        }],
+                    },
+                ],
+                location,
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let moves: Vec<(String, usize)> = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .filter_map(|stmt| match stmt {
+                IrStatement::Move { from, line, .. } => Some((from.clone(), *line)),
+                _ => None,
+            })
+            .collect();
+        assert!(moves.contains(&("x".to_string(), 4)));
+        assert!(moves.contains(&("y".to_string(), 4)));
+    }
+
+    #[test]
+    fn test_move_constructor_initializers_track_source_fields() {
+        use crate::parser::Expression;
+        use crate::parser::ast_visitor::{MemberInitializer, MoveKind};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 5,
+            column: 9,
+            end_line: 0,
+            end_column: 0,
+        };
+        let other_a = || Expression::MemberAccess {
+            object: Box::new(Expression::Variable("other".to_string())),
+            field: "a".to_string(),
+        };
+
+        // Widget(Widget&& other) : a(std::move(other.a)), n(other.a.size()) {}
+        let mut func = create_test_function("Widget::Widget");
+        func.parameters = vec![create_test_variable("other", "Widget &&", false)];
+        func.member_initializers = vec![
+            MemberInitializer {
+                member_name: "a".to_string(),
+                initializer: Expression::Move {
+                    inner: Box::new(other_a()),
+                    kind: MoveKind::StdMove,
+                },
+                is_nullptr: false,
+                location: location.clone(),
+            },
+            MemberInitializer {
+                member_name: "n".to_string(),
+                initializer: Expression::FunctionCall {
+                    name: "std::string::size".to_string(),
+                    args: vec![other_a()],
+                },
+                is_nullptr: false,
+                location,
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let statements: Vec<&IrStatement> = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .collect();
+        assert!(matches!(
+            statements[0],
+            IrStatement::MoveField { object, field, line: 5, .. } if object == "other" && field == "a"
+        ));
+        assert!(matches!(
+            statements[1],
+            IrStatement::UseField { object, field, operation }
+                if object == "other" && field == "a" && operation == "read"
+        ));
+    }
+
+    #[test]
+    fn test_call_result_arguments_checked_for_moves() {
+        use crate::parser::{Expression, Statement};
+
+        let var = |name: &str| Expression::Variable(name.to_string());
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("n", "size_t", false)),
+            // n = w.measure(x);
+            Statement::Assignment {
+                lhs: var("n"),
+                rhs: Expression::FunctionCall {
+                    name: "Widget::measure".to_string(),
+                    args: vec![var("w"), var("x")],
+                },
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 3,
+                    column: 5,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let uses: Vec<(String, String)> = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .filter_map(|stmt| match stmt {
+                IrStatement::UseVariable { var, operation } => {
+                    Some((var.clone(), operation.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                ("w".to_string(), "call method 'Widget::measure'".to_string()),
+                (
+                    "x".to_string(),
+                    "pass to function 'Widget::measure'".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_qualified_free_function_arguments_checked_for_moves() {
+        use crate::analysis::test_fixtures::class;
+        use crate::parser::{Expression, Statement};
+
+        let var = |name: &str| Expression::Variable(name.to_string());
+        let bind = |callee: &str, args: Vec<Expression>| Statement::ReferenceBinding {
+            name: "r".to_string(),
+            target: Expression::FunctionCall {
+                name: callee.to_string(),
+                args,
+            },
+            is_mutable: false,
+            location: SourceLocation {
+                file: "test.cpp".to_string(),
+                line: 3,
+                column: 5,
+                end_line: 0,
+                end_column: 0,
+            },
+        };
+        let mut func = create_test_function("test");
+        func.body = vec![
+            // const std::string& r = util::pick(x);
+            bind("util::pick", vec![var("x")]),
+            // const std::string& r = w.label(k);
+            bind("Widget::label", vec![var("w"), var("k")]),
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.classes.push(class("Widget"));
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let uses: Vec<(String, String)> = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .filter_map(|stmt| match stmt {
+                IrStatement::UseVariable { var, operation } => {
+                    Some((var.clone(), operation.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                ("x".to_string(), "pass to function 'util::pick'".to_string()),
+                (
+                    "k".to_string(),
+                    "pass to function 'Widget::label'".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_address_of_assignment_expression_borrows() {
+        use crate::parser::{Expression, Statement};
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("p", "int *", false)),
+            Statement::EnterScope,
+            Statement::VariableDecl(create_test_variable("local", "int", false)),
+            // p = &local;
+            Statement::ExpressionStatement {
+                expr: Expression::BinaryOp {
+                    left: Box::new(Expression::Variable("p".to_string())),
+                    op: "=".to_string(),
+                    right: Box::new(Expression::AddressOf(Box::new(Expression::Variable(
+                        "local".to_string(),
+                    )))),
+                },
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 6,
+                    column: 9,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+            Statement::ExitScope,
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let borrow = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .find_map(|stmt| match stmt {
+                IrStatement::Borrow { from, to, line, .. } => {
+                    Some((from.clone(), to.clone(), *line))
+                }
+                _ => None,
+            });
+        assert_eq!(borrow, Some(("local".to_string(), "p".to_string(), 6)));
+        assert_eq!(ir.functions[0].variables["local"].scope_level, 1);
+        assert_eq!(ir.functions[0].variables["p"].scope_level, 0);
+    }
+
+    #[test]
+    fn test_string_view_borrows_backing_string() {
+        use crate::parser::{Expression, Statement};
+
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("s", "std::string", false)),
+            Statement::VariableDecl(create_test_variable("sv", "std::string_view", false)),
+            // std::string_view sv = s;  (via the implicit conversion operator)
+            Statement::Assignment {
+                lhs: Expression::Variable("sv".to_string()),
+                rhs: Expression::FunctionCall {
+                    name: "std::basic_string<char>::operator basic_string_view".to_string(),
+                    args: vec![Expression::Variable("s".to_string())],
+                },
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 3,
+                    column: 5,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+
+        let ir = build_ir(ast).unwrap();
+        let has_borrow = ir.functions[0]
+            .cfg
+            .node_weights()
+            .flat_map(|block| block.statements.iter())
+            .any(|stmt| {
+                matches!(stmt, IrStatement::Borrow { from, to, kind: BorrowKind::Immutable, .. }
+                    if from == "s" && to == "sv")
+            });
+        assert!(has_borrow);
+        assert!(!ir.functions[0].variables["sv"].has_destructor);
+    }
+
+    #[test]
+    fn test_view_type_detection() {
+        assert!(is_view_type("std::string_view"));
+        assert!(is_view_type("const std::basic_string_view<char>"));
+        assert!(is_view_type("std::span<int>"));
+        assert!(!is_view_type("std::string"));
+        assert!(!is_view_type("const std::string_view &"));
+        assert!(!is_view_type(
+            "std::basic_string<char>::operator basic_string_view"
+        ));
+    }
+
+    #[test]
+    fn test_reference_wrapper_outliving_referent_dangles() {
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 5,
+            column: 9,
+            end_line: 0,
+            end_column: 0,
+        };
+        // std::reference_wrapper<int> w = outer;
+        // { int local = 2; w = std::ref(local); }
+        let rebind = |name: &str, func: &str| Statement::FunctionCall {
+            name: "std::reference_wrapper<int>::operator=".to_string(),
+            args: vec![
+                Expression::Variable("w".to_string()),
+                Expression::FunctionCall {
+                    name: func.to_string(),
+                    args: vec![Expression::Variable(name.to_string())],
+                },
+            ],
+            location: location.clone(),
+        };
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("outer", "int", false)),
+            Statement::VariableDecl(create_test_variable(
+                "w",
+                "std::reference_wrapper<int>",
+                false,
+            )),
+            rebind("outer", "std::ref"),
+            Statement::EnterScope,
+            Statement::VariableDecl(create_test_variable("local", "int", false)),
+            rebind("local", "std::ref"),
+            Statement::ExitScope,
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let ir = build_ir(ast).unwrap();
+        let errors = crate::analysis::raii_tracking::check_raii_issues(
+            &ir.functions[0],
+            &crate::parser::HeaderCache::new(),
+        )
+        .unwrap();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("'w'") && e.contains("'local'")),
+            "{:?}",
+            errors
+        );
+        assert!(
+            !errors.iter().any(|e| e.contains("'outer'")),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_reference_wrapper_detection() {
+        assert!(is_reference_wrapper_type("std::reference_wrapper<int>"));
+        assert!(is_reference_wrapper_type(
+            "const std::reference_wrapper<const std::string>"
+        ));
+        assert!(!is_reference_wrapper_type("std::reference_wrapper<int> &"));
+        assert!(!is_reference_wrapper_type(
+            "std::vector<std::reference_wrapper<int>>"
+        ));
+    }
+
+    #[test]
+    fn test_constructor_call_detection() {
+        let class_names = ["Builder".to_string(), "cfg::Config".to_string()]
+            .into_iter()
+            .collect();
+        assert!(is_constructor_call("Builder", &class_names));
+        assert!(is_constructor_call("Builder::Builder", &class_names));
+        assert!(is_constructor_call("Config", &class_names));
+        assert!(is_constructor_call("cfg::Config", &class_names));
+        // Functions with type-like names construct nothing
+        assert!(!is_constructor_call("GetConfig", &class_names));
+        assert!(!is_constructor_call("util::Builder", &class_names));
+    }
+
+    #[test]
+    fn test_reference_to_free_function_result_has_no_receiver() {
+        use crate::parser::{Expression, Statement};
+
+        // const int& m = std::max(a, b);
+        let mut m = create_test_variable("m", "const int &", false);
+        m.is_reference = true;
+        m.is_const = true;
+        let mut func = create_test_function("test");
+        func.body = vec![
+            Statement::VariableDecl(create_test_variable("a", "int", false)),
+            Statement::VariableDecl(create_test_variable("b", "int", false)),
+            Statement::VariableDecl(m),
+            Statement::ReferenceBinding {
+                name: "m".to_string(),
+                target: Expression::FunctionCall {
+                    name: "std::max".to_string(),
+                    args: vec![
+                        Expression::Variable("a".to_string()),
+                        Expression::Variable("b".to_string()),
+                    ],
+                },
+                is_mutable: false,
+                location: SourceLocation {
+                    file: "test.cpp".to_string(),
+                    line: 3,
+                    column: 5,
+                    end_line: 0,
+                    end_column: 0,
+                },
+            },
+        ];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let ir = build_ir(ast).unwrap();
+        let borrows_a = ir.functions[0].cfg.node_weights().any(|block| {
+            block
+                .statements
+                .iter()
+                .any(|stmt| matches!(stmt, IrStatement::Borrow { from, .. } if from == "a"))
+        });
+        assert!(!borrows_a);
+    }
+
+    #[test]
+    fn test_reference_to_method_result_borrows_receiver() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let location = SourceLocation {
+            file: "test.cpp".to_string(),
+            line: 3,
+            column: 5,
+            end_line: 0,
+            end_column: 0,
+        };
+        let at = Expression::FunctionCall {
+            name: "std::vector<std::string>::at".to_string(),
+            args: vec![
+                Expression::Variable("v".to_string()),
+                Expression::Literal("0".to_string()),
+            ],
+        };
+        let body_after_binding = |binding: Statement| {
+            vec![
+                Statement::VariableDecl(create_test_variable(
+                    "v",
+                    "std::vector<std::string>",
+                    false,
+                )),
+                binding,
+                Statement::VariableDecl(create_test_variable(
+                    "w",
+                    "std::vector<std::string>",
+                    false,
+                )),
+                Statement::Assignment {
+                    lhs: Expression::Variable("w".to_string()),
+                    rhs: Expression::Move {
+                        inner: Box::new(Expression::Variable("v".to_string())),
+                        kind: MoveKind::StdMove,
+                    },
+                    location: location.clone(),
+                },
+                Statement::FunctionCall {
+                    name: "consume".to_string(),
+                    args: vec![Expression::Variable("r".to_string())],
+                    location: location.clone(),
+                },
+            ]
+        };
+        let check = |mut body: Vec<Statement>, r: Variable| {
+            body.insert(1, Statement::VariableDecl(r));
+            let mut func = create_test_function("test");
+            func.body = body;
+            let mut ast = crate::parser::CppAst::new();
+            ast.functions.push(func);
+            crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap()
+        };
+
+        // const std::string& r = v.at(0);  (at returns a reference)
+        let mut r = create_test_variable("r", "const std::string &", false);
+        r.is_reference = true;
+        r.is_const = true;
+        let errors = check(
+            body_after_binding(Statement::ReferenceBinding {
+                name: "r".to_string(),
+                target: at.clone(),
+                is_mutable: false,
+                location: location.clone(),
+            }),
+            r,
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("Cannot move 'v' because it is borrowed by")),
+            "{:?}",
+            errors
+        );
+
+        // const std::string& r = v.name();  (name returns by value: the parser
+        // declares r as the lifetime-extended temporary it binds)
+        let mut r = create_test_variable("r", "const std::string", false);
+        r.is_const = true;
+        let errors = check(
+            body_after_binding(Statement::Assignment {
+                lhs: Expression::Variable("r".to_string()),
+                rhs: Expression::FunctionCall {
+                    name: "Widget::name".to_string(),
+                    args: vec![Expression::Variable("v".to_string())],
+                },
+                location: location.clone(),
+            }),
+            r,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_recursive_call_does_not_reset_moved_parameter() {
+        use crate::parser::ast_visitor::MoveKind;
+        use crate::parser::{Expression, Statement};
+
+        let call = |callee: &str, line: u32| Statement::FunctionCall {
+            name: callee.to_string(),
+            args: vec![Expression::Move {
+                inner: Box::new(Expression::Variable("b".to_string())),
+                kind: MoveKind::StdMove,
+            }],
+            location: SourceLocation {
+                file: "test.cpp".to_string(),
+                line,
+                column: 5,
+                end_line: 0,
+                end_column: 0,
+            },
+        };
+        // void f(Box b) { consume(std::move(b)); f(std::move(b)); }
+        let mut func = create_test_function("f");
+        func.parameters = vec![create_test_variable("b", "Box", false)];
+        func.body = vec![call("consume", 2), call("f", 3)];
+        let mut ast = crate::parser::CppAst::new();
+        ast.functions.push(func);
+        let errors = crate::analysis::check_borrows(build_ir(ast).unwrap()).unwrap();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("'b'"), "{}", errors[0]);
+        assert!(errors[0].contains("already been moved"), "{}", errors[0]);
+    }
+
     #[test]
     fn test_lifetime_creation() {
         let lifetime = Lifetime {
@@ -5,7 +5,9 @@
 pub mod debug_macros;
 
 pub mod analysis;
+pub mod check;
 pub mod diagnostics;
 pub mod ir;
 pub mod parser;
 pub mod solver;
+pub mod timings;
@@ -10,10 +10,15 @@ use std::path::{Path, PathBuf};
 mod debug_macros;
 
 mod analysis;
+mod check;
+mod daemon;
 mod diagnostics;
+mod fix;
 mod ir;
 mod parser;
+mod report;
 mod solver;
+mod timings;
 
 #[derive(clap::Parser, Debug)]
 #[command(name = "rusty-cpp-checker")]
@@ -25,12 +30,21 @@ Environment variables:\n  \
 CPLUS_INCLUDE_PATH  : Colon-separated list of C++ include directories\n  \
 C_INCLUDE_PATH      : Colon-separated list of C include directories\n  \
 CPATH               : Colon-separated list of C/C++ include directories\n  \
-CPP_INCLUDE_PATH    : Custom include paths for this tool"
+CPP_INCLUDE_PATH    : Custom include paths for this tool\n\n\
+Exit codes:\n  \
+0  : No violations found\n  \
+1  : Violations found (warnings only count with --deny-warnings)\n  \
+2  : The checker could not run: bad arguments, or a file failed to parse"
 )]
 struct Args {
     /// C++ source file to analyze
-    #[arg(value_name = "FILE")]
-    input: PathBuf,
+    #[arg(value_name = "FILE", required_unless_present_any = ["project", "list_checks", "print_schema", "daemon"])]
+    input: Option<PathBuf>,
+
+    /// Check every translation unit in a compile_commands.json, sharing
+    /// function signatures and safety annotations across them
+    #[arg(long, value_name = "COMPILE_COMMANDS", conflicts_with = "input")]
+    project: Option<PathBuf>,
 
     /// Include paths for header files (can be specified multiple times)
     #[arg(short = 'I', value_name = "DIR")]
@@ -45,13 +59,109 @@ struct Args {
     #[arg(long, value_name = "FILE")]
     compile_commands: Option<PathBuf>,
 
-    /// Verbosity level
+    /// File listing include directories, one per line; relative entries
+    /// are resolved against the directory of the list file
+    #[arg(long, value_name = "PATH")]
+    include_file: Option<PathBuf>,
+
+    /// Verbosity level (-v, -vv, -vvv)
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 
-    /// Output format (text, json)
+    /// Output format (text, json, junit)
     #[arg(long, default_value = "text")]
     format: String,
+
+    /// Write the report in the selected --format to this file; the
+    /// human-readable listing is still printed to stdout
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// Don't print the per-kind violation summary after the list
+    #[arg(long)]
+    no_summary: bool,
+
+    /// List every analysis pass with its name, error kinds and default state
+    #[arg(long)]
+    list_checks: bool,
+
+    /// Print the JSON Schema of the --format json report and exit
+    #[arg(long)]
+    print_schema: bool,
+
+    /// Turn off an analysis pass by name (repeatable or comma-separated)
+    #[arg(long, value_name = "CHECK", value_delimiter = ',')]
+    disable: Vec<String>,
+
+    /// Turn on an analysis pass by name; wins over --disable
+    #[arg(long, value_name = "CHECK", value_delimiter = ',')]
+    enable: Vec<String>,
+
+    /// Also run the heuristic passes that are off by default (see
+    /// --list-checks) and warn about skipped @safe functions as with
+    /// --warn-unchecked-safe
+    #[arg(long)]
+    pedantic: bool,
+
+    /// Exit non-zero when any warning is reported, not just errors
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// Don't report violations of this kind, e.g. `--allow use-after-move`
+    /// (repeatable or comma-separated; see the kinds in --list-checks)
+    #[arg(long, value_name = "KIND", value_delimiter = ',')]
+    allow: Vec<String>,
+
+    /// Also analyze functions from system and library headers (/usr/include,
+    /// /usr/local/include, the STL, ...), which are skipped by default
+    #[arg(long)]
+    include_system: bool,
+
+    /// Warn about functions in the checked file annotated @safe that are
+    /// skipped anyway (system-header path, operator overload, or overridden
+    /// by another annotation), with the reason
+    #[arg(long)]
+    warn_unchecked_safe: bool,
+
+    /// Print how long parsing, IR building and each analysis pass took
+    /// (summed over all files) to stderr
+    #[arg(long)]
+    timings: bool,
+
+    /// Only report violations in functions matching this name; a suffix on
+    /// a `::` boundary is enough (`MyClass::foo` matches `ns::MyClass::foo`)
+    #[arg(long, value_name = "QUALIFIED_NAME")]
+    function: Option<String>,
+
+    /// Print nothing but the violations themselves (no banner, headers,
+    /// summary or success message); the exit code tells the result
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Only report violations on these lines, e.g.
+    /// `--changed-lines src/a.cpp:10-20,src/b.cpp:7` (repeatable or
+    /// comma-separated); the whole file is still analyzed
+    #[arg(long, value_name = "FILE:START-END", value_delimiter = ',')]
+    changed_lines: Vec<String>,
+
+    /// Stop analyzing once N violations are found and report only those
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    max_violations: Option<usize>,
+
+    /// Apply fix-it suggestions (currently: insert the elided @lifetime
+    /// annotation of single-reference functions) in place, then re-check
+    #[arg(long, conflicts_with = "project")]
+    fix: bool,
+
+    /// Keep running and answer analysis requests read from stdin (JSON with
+    /// a Content-Length header, as in LSP), reusing parsed headers across
+    /// requests; for editor integration
+    #[arg(long, conflicts_with_all = ["input", "project", "fix"])]
+    daemon: bool,
 }
 
 #[derive(Debug, Default)]
@@ -65,51 +175,499 @@ struct CompileCommandConfig {
 }
 
 fn main() {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    debug_macros::set_verbosity(args.verbose);
+    timings::set_enabled(args.timings);
+    if args.include_system && !args.quiet {
+        eprintln!(
+            "Warning: --include-system analyzes system and library headers; expect noisy results"
+        );
+    }
 
-    println!("{}", "Rusty C++ Checker".bold().blue());
-    println!("Analyzing: {}", args.input.display());
+    if args.list_checks {
+        print!("{}", analysis::checks::format_check_list());
+        return;
+    }
 
-    match analyze_file(
-        &args.input,
-        &args.include_paths,
-        &args.defines,
-        args.compile_commands.as_ref(),
+    if args.print_schema {
+        print!("{}", report::json_schema());
+        return;
+    }
+
+    let checks = match analysis::checks::EnabledChecks::from_flags(
+        &args.enable,
+        &args.disable,
+        args.pedantic,
     ) {
-        Ok(results) => {
-            if results.is_empty() {
-                println!("{}", "✓ rusty-cpp: no violations found!".green());
-            } else {
-                println!(
-                    "{}",
-                    format!(
-                        "✗ Found {} violation(s) in {}:",
-                        results.len(),
-                        args.input.display()
-                    )
-                    .red()
-                );
-                for error in results {
+        Ok(checks) => checks,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+
+    let policy = match report::Policy::from_flags(&args.allow, args.deny_warnings)
+        .and_then(|policy| policy.with_changed_lines(&args.changed_lines))
+    {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+
+    // `--include-file` entries join the -I paths of every file analyzed
+    if let Some(list) = &args.include_file {
+        match read_include_file(list) {
+            Ok(paths) => args.include_paths.extend(paths),
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red().bold(), e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let options = check::CheckOptions {
+        include_paths: args.include_paths.clone(),
+        defines: args.defines.clone(),
+        checks,
+        function_filter: args.function.clone(),
+        include_system: args.include_system,
+        warn_unchecked_safe: args.warn_unchecked_safe,
+        // `--allow` and `--changed-lines` drop violations after analysis, so
+        // stopping early could leave fewer than N to report; the limit is
+        // then applied to what they keep instead
+        max_violations: args.max_violations.filter(|_| !policy.drops_violations()),
+    };
+
+    if args.daemon {
+        run_daemon(options, policy, args.max_violations);
+        return;
+    }
+
+    let format = match report::OutputFormat::parse(&args.format) {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+    // `--format json` without `--output` owns stdout, so the human listing
+    // is dropped to keep it parseable
+    let human = format == report::OutputFormat::Text || args.output.is_some();
+
+    if human && !args.quiet {
+        println!("{}", "Rusty C++ Checker".bold().blue());
+    }
+
+    if let Some(project) = &args.project {
+        run_project(&args, project, &options, &policy, format, human);
+        return;
+    }
+
+    let input = args
+        .input
+        .as_ref()
+        .expect("FILE is required unless --project is given");
+    if human && !args.quiet {
+        println!("Analyzing: {}", input.display());
+    }
+
+    let outcome = analyze_file(input, args.compile_commands.as_ref(), None, &options);
+    let outcome = match outcome {
+        Ok(analyzed) if args.fix => Ok(apply_fixes(
+            &args, input, &options, &policy, analyzed, human,
+        )),
+        outcome => outcome,
+    };
+    print_timings(&args);
+    match outcome {
+        Ok(analyzed) => {
+            let mut results = policy.filter(input, analyzed.violations);
+            let truncated =
+                truncate_violations(&mut results, args.max_violations, analyzed.stopped_early);
+            if human && args.quiet {
+                for error in &results {
                     println!("{}", error);
                 }
+            } else if human {
+                if results.is_empty() {
+                    println!("{}", "✓ rusty-cpp: no violations found!".green());
+                } else {
+                    println!(
+                        "{}",
+                        format!(
+                            "✗ Found {} violation(s) in {}:",
+                            results.len(),
+                            input.display()
+                        )
+                        .red()
+                    );
+                    for error in &results {
+                        println!("{}", error);
+                    }
+                    if !args.no_summary {
+                        println!("{}", format_violation_summary(&results, 1));
+                    }
+                }
+            }
+            if let Some(max) = truncated {
+                print_truncation_notice(max, human);
+            }
+            let failed = policy.fails(&results);
+            emit_report(
+                &args,
+                format,
+                &[report::FileReport {
+                    file: input.clone(),
+                    violations: results,
+                    functions: analyzed.functions,
+                    spans: analyzed.spans,
+                }],
+            );
+            if failed {
                 std::process::exit(1);
             }
         }
         Err(e) => {
             eprintln!("{}: {}", "Error".red().bold(), e);
-            std::process::exit(1);
+            std::process::exit(2);
         }
     }
 }
 
+/// `--daemon`: serve analysis requests on stdin/stdout until stdin closes
+fn run_daemon(
+    mut options: check::CheckOptions,
+    policy: report::Policy,
+    max_violations: Option<usize>,
+) {
+    options
+        .include_paths
+        .extend(extract_include_paths_from_env());
+    options
+        .include_paths
+        .extend(extract_include_paths_from_clang());
+
+    let mut daemon = daemon::Daemon::new(options, policy, max_violations);
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    if let Err(e) = daemon.serve(&mut stdin.lock(), &mut stdout.lock()) {
+        eprintln!("{}: {}", "Error".red().bold(), e);
+        std::process::exit(2);
+    }
+}
+
+/// `--fix`: apply the suggestions attached to the `results` that `policy`
+/// reports and re-check the file. The edits are rolled back if the re-check
+/// reports anything they didn't account for; returns the violations that
+/// remain.
+fn apply_fixes(
+    args: &Args,
+    input: &PathBuf,
+    options: &check::CheckOptions,
+    policy: &report::Policy,
+    results: check::AnalysisReport,
+    human: bool,
+) -> check::AnalysisReport {
+    // Stopping at `--max-violations` hides violations past the limit from
+    // the fixes, and the re-check would then report them as new. Both runs
+    // go over the whole file; the limit applies to what is printed.
+    let options = &check::CheckOptions {
+        max_violations: None,
+        ..options.clone()
+    };
+    let results = if results.stopped_early {
+        match analyze_file(input, args.compile_commands.as_ref(), None, options) {
+            Ok(full) => full,
+            Err(_) => return results,
+        }
+    } else {
+        results
+    };
+
+    // `--allow`ed kinds and violations off the `--changed-lines` aren't fixed
+    let suggestions: Vec<analysis::Suggestion> = policy
+        .filter(input, results.violations.clone())
+        .iter()
+        .filter_map(|message| analysis::Suggestion::from_message(message))
+        .collect();
+    if suggestions.is_empty() {
+        return results;
+    }
+    let applied = match fix::apply(&suggestions) {
+        Ok(applied) => applied,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+
+    let recheck = analyze_file(input, args.compile_commands.as_ref(), None, options);
+    match recheck {
+        Ok(remaining)
+            if fix::new_violations(&results.violations, &remaining.violations).is_empty() =>
+        {
+            if human && !args.quiet {
+                println!("Applied {} fix(es)", applied.edits);
+            }
+            remaining
+        }
+        _ => {
+            if let Err(e) = applied.revert() {
+                eprintln!("{}: {}", "Error".red().bold(), e);
+                std::process::exit(2);
+            }
+            eprintln!(
+                "Warning: fixes were not applied: re-checking the edited file reported new violations"
+            );
+            results
+        }
+    }
+}
+
+/// `--max-violations`: cut `results` down to the limit. Returns the limit
+/// when anything was dropped, or when analysis stopped early because of it.
+fn truncate_violations(
+    results: &mut Vec<String>,
+    max: Option<usize>,
+    stopped_early: bool,
+) -> Option<usize> {
+    let max = max?;
+    if results.len() <= max && !stopped_early {
+        return None;
+    }
+    results.truncate(max);
+    Some(max)
+}
+
+fn print_truncation_notice(max: usize, human: bool) {
+    let notice = format!("output truncated at {} violations", max);
+    if human {
+        println!("{}", notice);
+    } else {
+        eprintln!("{}", notice);
+    }
+}
+
+fn print_timings(args: &Args) {
+    if args.timings {
+        eprint!("{}", timings::report());
+    }
+}
+
+/// Print the `--format` rendering to stdout, or write it to `--output`.
+/// Plain text without `--output` was already printed as files were analyzed.
+fn emit_report(args: &Args, format: report::OutputFormat, files: &[report::FileReport]) {
+    let Some(path) = &args.output else {
+        if format != report::OutputFormat::Text {
+            print!(
+                "{}",
+                report::render(format, files, !args.no_summary, args.deny_warnings)
+            );
+        }
+        return;
+    };
+    let contents = report::render(format, files, !args.no_summary, args.deny_warnings);
+    if let Err(e) = report::write_atomically(path, &contents) {
+        eprintln!("{}: {}", "Error".red().bold(), e);
+        std::process::exit(2);
+    }
+}
+
+/// Whole-program mode: every translation unit listed in compile_commands.json
+/// is checked against a shared map of function signatures and safety
+/// annotations collected from all of them, so calls into functions defined
+/// in another TU see that TU's annotations.
+fn run_project(
+    args: &Args,
+    compile_commands: &PathBuf,
+    options: &check::CheckOptions,
+    policy: &report::Policy,
+    format: report::OutputFormat,
+    human: bool,
+) {
+    let sources = match project_source_files(compile_commands) {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+    if human && !args.quiet {
+        println!(
+            "Analyzing project: {} ({} translation unit(s))",
+            compile_commands.display(),
+            sources.len()
+        );
+    }
+
+    let project_cache = build_project_cache(compile_commands, &sources, &args.include_paths);
+
+    let mut all_results = Vec::new();
+    let mut files_affected = 0;
+    let mut had_error = false;
+    let mut file_reports = Vec::new();
+    let mut truncated = None;
+    for source in &sources {
+        if truncated.is_some() {
+            break;
+        }
+        match analyze_file(
+            source,
+            Some(compile_commands),
+            Some(&project_cache),
+            options,
+        ) {
+            Ok(analyzed) => {
+                let mut results = policy.filter(source, analyzed.violations);
+                let remaining = args
+                    .max_violations
+                    .map(|max| max.saturating_sub(all_results.len()));
+                if truncate_violations(&mut results, remaining, analyzed.stopped_early).is_some() {
+                    truncated = args.max_violations;
+                }
+                if !results.is_empty() {
+                    if human && !args.quiet {
+                        println!(
+                            "{}",
+                            format!(
+                                "✗ Found {} violation(s) in {}:",
+                                results.len(),
+                                source.display()
+                            )
+                            .red()
+                        );
+                    }
+                    if human {
+                        for error in &results {
+                            println!("{}", error);
+                        }
+                    }
+                    files_affected += 1;
+                    all_results.extend(results.iter().cloned());
+                }
+                file_reports.push(report::FileReport {
+                    file: source.clone(),
+                    violations: results,
+                    functions: analyzed.functions,
+                    spans: analyzed.spans,
+                });
+            }
+            Err(e) => {
+                eprintln!("{}: {}: {}", "Error".red().bold(), source.display(), e);
+                had_error = true;
+            }
+        }
+    }
+
+    print_timings(args);
+
+    if human && !args.quiet {
+        if all_results.is_empty() {
+            if !had_error {
+                println!("{}", "✓ rusty-cpp: no violations found!".green());
+            }
+        } else if !args.no_summary {
+            println!("{}", format_violation_summary(&all_results, files_affected));
+        }
+    }
+    if let Some(max) = truncated {
+        print_truncation_notice(max, human);
+    }
+    emit_report(args, format, &file_reports);
+    if had_error {
+        std::process::exit(2);
+    }
+    if policy.fails(&all_results) {
+        std::process::exit(1);
+    }
+}
+
+/// Source files listed in compile_commands.json, absolutized against each
+/// entry's directory, in manifest order without duplicates
+fn project_source_files(cc_path: &PathBuf) -> Result<Vec<PathBuf>, String> {
+    let content = fs::read_to_string(cc_path)
+        .map_err(|e| format!("Failed to read compile_commands.json: {}", e))?;
+    let commands: Vec<serde_json::Value> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse compile_commands.json: {}", e))?;
+
+    let mut sources = Vec::new();
+    for entry in commands {
+        let Some(file) = entry.get("file").and_then(|f| f.as_str()) else {
+            continue;
+        };
+        let directory = entry
+            .get("directory")
+            .and_then(|d| d.as_str())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let source = absolutize_if_needed(file, &directory);
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    Ok(sources)
+}
+
+/// Collect signatures and safety annotations from every translation unit
+fn build_project_cache(
+    cc_path: &PathBuf,
+    sources: &[PathBuf],
+    include_paths: &[PathBuf],
+) -> parser::HeaderCache {
+    let mut cache = parser::HeaderCache::new();
+    for source in sources {
+        let mut paths = include_paths.to_vec();
+        if let Ok(config) = extract_compile_config_from_compile_commands(cc_path, source) {
+            paths.extend(config.include_paths);
+        }
+        cache.set_include_paths(paths);
+        if let Err(e) = cache.parse_header(source) {
+            verbose_println!(
+                1,
+                "Skipping {} while collecting project signatures: {}",
+                source.display(),
+                e
+            );
+        }
+    }
+    cache
+}
+
+/// Summary line with violation counts by kind, most frequent first,
+/// e.g. `Summary: 3 use-after-move, 1 dangling-reference, 1 file affected`
+fn format_violation_summary(violations: &[String], files_affected: usize) -> String {
+    let mut counts: std::collections::BTreeMap<analysis::ErrorKind, usize> =
+        std::collections::BTreeMap::new();
+    for violation in violations {
+        *counts
+            .entry(analysis::ErrorKind::from_message(violation))
+            .or_insert(0) += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut parts: Vec<String> = counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}", count, kind.name()))
+        .collect();
+    parts.push(format!(
+        "{} file{} affected",
+        files_affected,
+        if files_affected == 1 { "" } else { "s" }
+    ));
+    format!("Summary: {}", parts.join(", "))
+}
+
 fn analyze_file(
     path: &PathBuf,
-    include_paths: &[PathBuf],
-    defines: &[String],
     compile_commands: Option<&PathBuf>,
-) -> Result<Vec<String>, String> {
+    project_cache: Option<&parser::HeaderCache>,
+    options: &check::CheckOptions,
+) -> Result<check::AnalysisReport, String> {
     // Start with CLI-provided include paths
-    let mut all_include_paths = include_paths.to_vec();
+    let mut all_include_paths = options.include_paths.clone();
     let mut extra_clang_args: Vec<String> = Vec::new();
     let mut should_auto_detect_clang_includes = true;
 
@@ -166,11 +724,13 @@ fn analyze_file(
     // Parse included headers for lifetime annotations
     let mut header_cache = parser::HeaderCache::new();
     header_cache.set_include_paths(all_include_paths.clone());
-    header_cache.parse_includes_from_source(path)?;
+    timings::time("headers", || -> Result<(), String> {
+        header_cache.parse_includes_from_source(path)?;
 
-    // IMPORTANT: Also parse the source file itself for lifetime annotations
-    // Without this, lifetime annotations in .cc/.cpp files are not recognized
-    header_cache.parse_header(path)?;
+        // IMPORTANT: Also parse the source file itself for lifetime annotations
+        // Without this, lifetime annotations in .cc/.cpp files are not recognized
+        header_cache.parse_header(path)
+    })?;
 
     // Also parse external annotations from the source file itself (not just headers)
     // This allows annotations like @external: { function: [unsafe, ...] } in .cc/.cpp files
@@ -188,282 +748,38 @@ fn analyze_file(
         }
     }
 
-    // Parse the C++ file with include paths and defines
-    let mut ast = parser::parse_cpp_file_with_includes_defines_and_args(
-        path,
-        &all_include_paths,
-        defines,
-        &extra_clang_args,
-    )?;
-
-    // Parse safety annotations using the unified rule
-    let mut safety_context = parser::safety_annotations::parse_safety_annotations(path)?;
-
-    // Merge safety annotations from headers into the context
-    safety_context.merge_header_annotations(&header_cache);
-
-    // Build a set of known safe functions from the safety context
-    let mut known_safe_functions = std::collections::HashSet::new();
-    for (func_sig, mode) in &safety_context.function_overrides {
-        if *mode == parser::safety_annotations::SafetyMode::Safe {
-            known_safe_functions.insert(func_sig.name.clone());
-        }
-    }
-
-    // Helper function to check if a file or function is from a system header
-    fn is_system_header_or_std(file_path: &str, _function_name: &str) -> bool {
-        // Common system header paths (absolute)
-        let system_paths = [
-            "/usr/include",
-            "/usr/local/include",
-            "/opt/homebrew/include",
-            "/Library/Developer",
-            "C:\\Program Files",
-            "/Applications/Xcode.app",
-        ];
-
-        for path in &system_paths {
-            if file_path.starts_with(path) {
-                return true;
-            }
-        }
-
-        // STL and system library patterns (works for relative paths too)
-        if file_path.contains("/include/c++/")
-            || file_path.contains("/bits/")
-            || file_path.contains("/ext/")
-            || file_path.contains("stl_")
-            || file_path.contains("/lib/gcc/")
-        {
-            return true;
-        }
-
-        // Also skip the project's include/ directory (third-party headers like rusty::Box)
-        if file_path.contains("/include/rusty/")
-            || file_path.starts_with("include/rusty/")
-            || file_path.contains("/include/unified_")
-            || file_path.starts_with("include/unified_")
-        {
-            return true;
-        }
-
-        false
+    if !header_cache.annotation_errors.is_empty() {
+        return Err(header_cache.annotation_errors.join("\n"));
     }
 
-    fn is_header_file(file_path: &str) -> bool {
-        matches!(
-            Path::new(file_path)
-                .extension()
-                .and_then(|ext| ext.to_str()),
-            Some("h" | "hh" | "hpp" | "hxx")
-        )
+    // In --project mode, functions defined in other translation units are
+    // known through the shared project map. This TU's own entries win.
+    if let Some(project_cache) = project_cache {
+        header_cache.merge_from(project_cache);
     }
 
-    // Check for unsafe pointer operations and unsafe propagation in safe functions
-    let mut violations = Vec::new();
-    let parsed_functions: Vec<_> = ast
-        .functions
-        .iter()
-        .chain(ast.classes.iter().flat_map(|class| class.methods.iter()))
-        .collect();
-
-    debug_println!(
-        "DEBUG: Found {} functions/methods in AST",
-        parsed_functions.len()
-    );
-
-    // Canonical path of the file being checked. Used to filter out function
-    // bodies that libclang surfaced via imports/headers — those should be
-    // analyzed when THEIR own file is the check target, not re-analyzed
-    // from every consumer. Without this filter, large module-import graphs
-    // produce massive duplicate findings (e.g. each consumer of rrr.reactor
-    // re-flags every @safe→@unsafe call in Reactor's methods).
-    let main_file_canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
-
-    for function in parsed_functions {
-        // Skip system header functions - they shouldn't be analyzed internally
-        if is_system_header_or_std(&function.location.file, &function.name) {
-            debug_println!(
-                "DEBUG: Skipping system header function '{}' from {}",
-                function.name,
-                function.location.file
-            );
-            continue;
-        }
-        let fn_file = std::fs::canonicalize(&function.location.file)
-            .unwrap_or_else(|_| PathBuf::from(&function.location.file));
-
-        // TEMPORARY WORKAROUND: Treat all operator overloads as unsafe
-        // This bypasses annotation matching issues with template operators
-        let is_operator = function.name.contains("operator");
-
-        // Get the function's safety mode to pass to the pointer checker
-        let mut function_safety = safety_context.get_function_safety(&function.name);
-
-        // Override safety mode for operators - treat them as unsafe
-        if is_operator {
-            function_safety = parser::safety_annotations::SafetyMode::Unsafe;
-            debug_println!(
-                "DEBUG: Function '{}' is an operator overload, automatically treating as unsafe",
-                function.name
-            );
-        }
-
-        // Only analyze bodies whose source file matches the TU being checked,
-        // except for safe inline/header functions. Header bodies are part of
-        // the included API contract and must be validated when a TU includes
-        // them. Non-header cross-file bodies (modules/other .cpp files) have
-        // their own check pass; analyzing them here creates duplicate findings.
-        let is_current_tu = fn_file == main_file_canonical;
-        let should_check_header_body = is_header_file(&function.location.file)
-            && function_safety == parser::safety_annotations::SafetyMode::Safe;
-        if !is_current_tu && !should_check_header_body {
-            debug_println!(
-                "DEBUG: Skipping cross-file function '{}' from {} (current TU is {})",
-                function.name,
-                function.location.file,
-                main_file_canonical.display()
-            );
-            continue;
-        }
-
-        debug_println!(
-            "DEBUG: Processing function '{}' from '{}' with {} statements",
-            function.name,
-            function.location.file,
-            function.body.len()
-        );
-
-        if safety_context.should_check_function(&function.name) && !is_operator {
-            debug_println!(
-                "DEBUG: Function '{}' is marked safe, performing checks",
-                function.name
-            );
-            // Check for pointer operations (pass the function's safety mode)
-            let pointer_errors = analysis::pointer_safety::check_parsed_function_for_pointers(
-                function,
-                function_safety,
-            );
-            violations.extend(pointer_errors);
-
-            // Check for null safety (dereferencing potentially null pointers)
-            let null_errors = analysis::null_safety::check_null_safety(function, function_safety);
-            violations.extend(null_errors);
-
-            // Check for initialization safety (use of uninitialized variables)
-            let init_errors = analysis::initialization_tracking::check_initialization_safety(
-                function,
-                function_safety,
-            );
-            violations.extend(init_errors);
-
-            // Check for pointer provenance (pointer subtraction/comparison between different allocations)
-            let provenance_errors =
-                analysis::pointer_provenance::check_pointer_provenance(function, function_safety);
-            violations.extend(provenance_errors);
-
-            // Check for alignment safety (misaligned pointer access)
-            let alignment_errors =
-                analysis::alignment_safety::check_alignment_safety(function, function_safety);
-            violations.extend(alignment_errors);
-
-            // Check for array bounds safety (out-of-bounds access)
-            let bounds_errors =
-                analysis::array_bounds::check_array_bounds(function, function_safety);
-            violations.extend(bounds_errors);
-
-            // Check for std::move on references (forbidden in @safe code)
-            let std_move_errors =
-                analysis::pointer_safety::check_std_move_on_references(function, function_safety);
-            violations.extend(std_move_errors);
-
-            // Check for lambda capture safety (reference captures forbidden in @safe)
-            let lambda_errors = analysis::lambda_capture_safety::check_lambda_capture_safety(
-                function,
-                function_safety,
-            );
-            violations.extend(lambda_errors);
-
-            // Check for calls to unsafe functions with external annotations from headers
-            let propagation_errors =
-                analysis::unsafe_propagation::check_unsafe_propagation_with_external(
-                    function,
-                    &safety_context,
-                    &known_safe_functions,
-                    Some(&header_cache.external_annotations),
-                );
-            violations.extend(propagation_errors);
+    if debug_macros::verbosity() >= 3 {
+        let mut signatures: Vec<_> = header_cache.signatures().collect();
+        signatures.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, signature) in signatures {
+            eprintln!("Signature '{}': {:?}", name, signature);
         }
     }
 
-    // Check for mutable fields in safe classes (before building IR)
-    // Pass external annotations to skip STL internal types marked as unsafe_type
-    let mutable_violations = analysis::mutable_checker::check_mutable_fields(
-        &ast,
-        &safety_context,
-        Some(&header_cache.external_annotations),
-    )?;
-    violations.extend(mutable_violations);
-
-    // Check inheritance safety (@interface validation, safe inheritance rules)
-    let inheritance_violations =
-        analysis::inheritance_safety::check_inheritance_safety(&ast.classes);
-    violations.extend(inheritance_violations);
-
-    // Check struct pointer member safety (pointer members must be non-null)
-    let struct_pointer_violations =
-        analysis::struct_pointer_safety::check_struct_pointer_safety(&ast.classes);
-    violations.extend(struct_pointer_violations);
-
-    // Check const propagation through pointer members (in @safe code, const propagates)
-    let const_propagation_violations =
-        analysis::const_propagation::check_const_propagation(&ast.functions, &ast.classes);
-    violations.extend(const_propagation_violations);
-
-    // Scope the IR passes (borrow checking, lifetime inference, RAII
-    // tracking) to the code this TU is responsible for:
-    //  - the TU's own functions;
-    //  - USER-header bodies it includes. Header implementations are part of
-    //    the included API contract, and per-TU checking is the only chance
-    //    to borrow/lifetime-check them (headers are never a TU themselves) —
-    //    a use-after-move in a user's inline header function must be caught.
-    // System and rusty-library headers stay out: they are the trusted
-    // library tier (the same rule the safety loop above applies via
-    // is_system_header_or_std). Bodies from other .cpp files / imported
-    // modules stay out too — they are analyzed when their own file is the
-    // check target; re-analyzing them from every consumer only duplicates
-    // findings.
-    ast.functions.retain(|function| {
-        let function_file = std::fs::canonicalize(&function.location.file)
-            .unwrap_or_else(|_| PathBuf::from(&function.location.file));
-        if function_file == main_file_canonical {
-            return true;
-        }
-        is_header_file(&function.location.file)
-            && !is_system_header_or_std(&function.location.file, &function.name)
-    });
-
-    // Build intermediate representation with safety context
-    let mut ir = ir::build_ir_with_safety_context(ast, safety_context.clone())?;
-
-    // Phase 1: Populate lifetime information from annotations in HeaderCache
-    for ir_func in &mut ir.functions {
-        // Try to get the function signature from the header cache
-        if let Some(signature) = header_cache.get_signature(&ir_func.name) {
-            debug_println!(
-                "DEBUG MAIN: Found lifetime annotations for function '{}'",
-                ir_func.name
-            );
-            ir::populate_lifetime_info(ir_func, signature);
-        }
-    }
+    // Parse the C++ file with include paths and defines
+    let ast = timings::time("parse", || {
+        parser::parse_cpp_file_with_includes_defines_and_args(
+            path,
+            &all_include_paths,
+            &options.defines,
+            &extra_clang_args,
+        )
+    })?;
 
-    // Perform borrow checking analysis with header knowledge and safety context
-    let borrow_violations =
-        analysis::check_borrows_with_safety_context(ir, header_cache, safety_context)?;
-    violations.extend(borrow_violations);
+    // Parse safety annotations using the unified rule
+    let safety_context = parser::safety_annotations::parse_safety_annotations(path)?;
 
-    Ok(violations)
+    check::check_translation_unit(path, ast, header_cache, safety_context, options)
 }
 
 fn extract_compile_config_from_compile_commands(
@@ -609,14 +925,17 @@ fn extract_compile_config_from_tokens(
                 }
             }
             i += 1;
-        } else if token == "-std" && i + 1 < tokens.len() {
-            // -std gnu++23
-            config.clang_args.push("-std".to_string());
-            config.clang_args.push(tokens[i + 1].clone());
+        } else if (token == "-std" || token == "--std") && i + 1 < tokens.len() {
+            // -std gnu++23 (libclang only accepts the joined form)
+            config.clang_args.push(format!("-std={}", tokens[i + 1]));
             i += 2;
-        } else if token.starts_with("-std=") {
-            // -std=gnu++23
-            config.clang_args.push(token.to_string());
+        } else if let Some(standard) = token
+            .strip_prefix("-std=")
+            .or_else(|| token.strip_prefix("--std="))
+        {
+            // -std=gnu++23. This overrides the parser's default standard, so
+            // C++20/23 features parse exactly as the real build sees them.
+            config.clang_args.push(format!("-std={}", standard));
             i += 1;
         } else if token == "-stdlib" && i + 1 < tokens.len() {
             // -stdlib libc++
@@ -632,6 +951,11 @@ fn extract_compile_config_from_tokens(
             config.clang_args.push("-x".to_string());
             config.clang_args.push(tokens[i + 1].clone());
             i += 2;
+        } else if let Some(language) = token.strip_prefix("-x").filter(|l| !l.is_empty()) {
+            // -xc++
+            config.clang_args.push("-x".to_string());
+            config.clang_args.push(language.to_string());
+            i += 1;
         } else if token == "-fprebuilt-module-path" && i + 1 < tokens.len() {
             // -fprebuilt-module-path /path/to/pcms
             let raw = strip_outer_quotes(tokens[i + 1].as_str());
@@ -710,21 +1034,23 @@ fn extract_compile_config_from_tokens(
                 }
             }
             i += 1;
-        } else if token.starts_with("-D") || token.starts_with("-U") {
-            // Preprocessor define/undefine. Source code often guards on these
-            // (e.g. -DCONFIG_H="..."), so dropping them silently leads to
-            // hard-to-diagnose parse failures.
-            config.clang_args.push(token.to_string());
-            i += 1;
         } else if token == "-D" || token == "-U" {
-            // Two-token form: -D NAME, -U NAME.
+            // Two-token form: -D NAME, -U NAME. Joined into one argument so
+            // the value can't be mistaken for a separate flag.
             if i + 1 < tokens.len() {
-                config.clang_args.push(token.to_string());
-                config.clang_args.push(tokens[i + 1].clone());
+                config
+                    .clang_args
+                    .push(format!("{}{}", token, tokens[i + 1]));
                 i += 2;
             } else {
                 i += 1;
             }
+        } else if token.starts_with("-D") || token.starts_with("-U") {
+            // Preprocessor define/undefine. Source code often guards on these
+            // (e.g. -DCONFIG_H="..."), so dropping them silently leads to
+            // hard-to-diagnose parse failures.
+            config.clang_args.push(token.to_string());
+            i += 1;
         } else if token.starts_with("-m") || token.starts_with("-march") {
             // Target / codegen feature flags (-march=native, -mtune=skylake,
             // -mmmx, -msse4.2, -mavx2, etc.). Without these, clang's bundled
@@ -761,6 +1087,29 @@ fn extract_compile_config_from_command(
     extract_compile_config_from_tokens(&tokens, directory)
 }
 
+/// Include directories listed one per line in `--include-file`. Blank lines
+/// and `#` comments are skipped; relative entries are resolved against the
+/// directory of the list file.
+fn read_include_file(list: &Path) -> Result<Vec<PathBuf>, String> {
+    let content = fs::read_to_string(list)
+        .map_err(|e| format!("Failed to read include file '{}': {}", list.display(), e))?;
+    let base = list.parent().unwrap_or_else(|| Path::new("."));
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let path = PathBuf::from(line);
+            if path.is_absolute() {
+                path
+            } else {
+                base.join(path)
+            }
+        })
+        .collect())
+}
+
 fn extract_include_paths_from_env() -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
@@ -793,7 +1142,8 @@ fn extract_include_paths_from_env() -> Vec<PathBuf> {
 
     // Print info about environment paths if verbose mode is enabled
     if !paths.is_empty() {
-        eprintln!(
+        verbose_println!(
+            1,
             "Found {} include path(s) from environment variables",
             paths.len()
         );
@@ -927,7 +1277,7 @@ fn extract_include_paths_from_clang() -> Vec<PathBuf> {
             add_system_c_include_paths(&mut paths);
 
             if !paths.is_empty() {
-                eprintln!("Auto-detected {} C++ include path(s)", paths.len());
+                verbose_println!(1, "Auto-detected {} C++ include path(s)", paths.len());
             }
         }
         None => {
@@ -1046,6 +1396,60 @@ fn add_system_c_include_paths(paths: &mut Vec<PathBuf>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn violation_summary_counts_by_kind() {
+        let violations = vec![
+            "Use after move: variable 'a' has been moved".to_string(),
+            "Dangling reference: 'r' borrows from 'x' which goes out of scope".to_string(),
+            "Use after move: variable 'b' has already been moved".to_string(),
+        ];
+        assert_eq!(
+            format_violation_summary(&violations, 1),
+            "Summary: 2 use-after-move, 1 dangling-reference, 1 file affected"
+        );
+        assert_eq!(
+            format_violation_summary(&violations[..1], 2),
+            "Summary: 1 use-after-move, 2 files affected"
+        );
+    }
+
+    #[test]
+    fn max_violations_truncates_to_the_limit() {
+        let mut results: Vec<String> = (1..=5).map(|i| format!("violation {}", i)).collect();
+        assert_eq!(truncate_violations(&mut results, Some(3), false), Some(3));
+        assert_eq!(results, ["violation 1", "violation 2", "violation 3"]);
+
+        // Exactly the limit, with nothing left unchecked, is not truncated
+        assert_eq!(truncate_violations(&mut results, Some(3), false), None);
+        assert_eq!(truncate_violations(&mut results, Some(3), true), Some(3));
+
+        let mut results = vec!["violation 1".to_string()];
+        assert_eq!(truncate_violations(&mut results, Some(3), false), None);
+        assert_eq!(truncate_violations(&mut results, None, true), None);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn include_file_resolves_relative_entries() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let list = temp_dir.path().join("includes.txt");
+        fs::write(
+            &list,
+            "# vendored headers\nthird_party/include\n\n/opt/sdk/include\n",
+        )
+        .expect("write include file");
+
+        let paths = read_include_file(&list).expect("read include file");
+        assert_eq!(
+            paths,
+            vec![
+                temp_dir.path().join("third_party/include"),
+                PathBuf::from("/opt/sdk/include")
+            ]
+        );
+        assert!(read_include_file(&temp_dir.path().join("missing.txt")).is_err());
+    }
+
     #[test]
     fn extracts_module_flags_from_response_file() {
         let temp_dir = tempfile::tempdir().expect("create temp dir");
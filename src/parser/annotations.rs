@@ -21,6 +21,24 @@ impl LifetimeAnnotation {
     pub fn is_owned(&self) -> bool {
         matches!(self, LifetimeAnnotation::Owned)
     }
+
+    /// The lifetime name without its leading quote ("a" for both `'a` and `&'a`)
+    pub fn lifetime_name(&self) -> Option<&str> {
+        match self {
+            LifetimeAnnotation::Lifetime(name)
+            | LifetimeAnnotation::Ref(name)
+            | LifetimeAnnotation::MutRef(name)
+            | LifetimeAnnotation::Ptr(name)
+            | LifetimeAnnotation::ConstPtr(name) => Some(name.trim_start_matches('\'')),
+            LifetimeAnnotation::Owned => None,
+        }
+    }
+
+    /// `&'static` and friends: the value outlives everything, so it borrows
+    /// from no argument
+    pub fn is_static(&self) -> bool {
+        self.lifetime_name() == Some("static")
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -38,6 +56,12 @@ pub struct FunctionSignature {
     pub param_lifetimes: Vec<Option<LifetimeAnnotation>>,
     pub lifetime_bounds: Vec<LifetimeBound>, // e.g., 'a: 'b
     pub safety: Option<SafetyAnnotation>,    // @safe or @unsafe
+    /// Per parameter: whether the function stores the argument's address
+    /// beyond the call (`@escapes(p)`), so it must not point to a local
+    pub param_escapes: Vec<bool>,
+    /// Per parameter: whether it is an out-parameter (`out &'a`) the function
+    /// sets to borrow from the inputs with the same lifetime
+    pub param_outputs: Vec<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,12 +70,75 @@ pub struct LifetimeBound {
     pub shorter: String, // 'b in 'a: 'b
 }
 
+impl FunctionSignature {
+    /// Lifetime names used by the return type, an `out` parameter or a
+    /// `where` bound that no input parameter introduces. A bound `'a: 'c` on a declared `'a` introduces
+    /// the shorter `'c`. The return-only shorthand (`@lifetime: &'a`)
+    /// declares no parameters and is not checked.
+    pub fn undeclared_lifetimes(&self) -> Vec<String> {
+        if self.param_lifetimes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut declared: Vec<&str> = self
+            .param_lifetimes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.is_output_param(*i))
+            .filter_map(|(_, annotation)| annotation.as_ref()?.lifetime_name())
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for bound in &self.lifetime_bounds {
+                if declared.contains(&bound.longer.as_str())
+                    && !declared.contains(&bound.shorter.as_str())
+                {
+                    declared.push(bound.shorter.as_str());
+                    changed = true;
+                }
+            }
+        }
+        let outputs = self
+            .param_lifetimes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_output_param(*i))
+            .filter_map(|(_, annotation)| annotation.as_ref()?.lifetime_name());
+        let used = self
+            .return_lifetime
+            .iter()
+            .filter_map(|annotation| annotation.lifetime_name())
+            .chain(outputs)
+            .chain(
+                self.lifetime_bounds
+                    .iter()
+                    .flat_map(|bound| [bound.longer.as_str(), bound.shorter.as_str()]),
+            );
+
+        let mut undeclared: Vec<String> = Vec::new();
+        for name in used {
+            let special = matches!(name, "static" | "self" | "this");
+            if !special && !declared.contains(&name) && !undeclared.iter().any(|n| n == name) {
+                undeclared.push(name.to_string());
+            }
+        }
+        undeclared
+    }
+
+    /// Whether parameter `index` is an `out` parameter
+    pub fn is_output_param(&self, index: usize) -> bool {
+        self.param_outputs.get(index).copied().unwrap_or(false)
+    }
+}
+
 pub fn extract_annotations(entity: &Entity) -> Option<FunctionSignature> {
     let name = entity.get_name()?;
     // Try getting comment from LibClang first (doc comments like /// or /** */)
     if let Some(comment) = entity.get_comment() {
         if let Some(sig) = parse_lifetime_annotations(&comment, name.clone()) {
-            return Some(sig);
+            let sig = with_receiver_mutability(sig, &comment, &entity_return_type(entity));
+            return Some(with_param_escapes(sig, &comment, entity));
         }
         // Comment exists but no lifetime annotation found, fall through to source reading
     }
@@ -91,7 +178,9 @@ fn read_lifetime_from_source(entity: &Entity, name: &str) -> Option<FunctionSign
             // Parse accumulated annotations
             if !annotations.is_empty() {
                 if let Some(sig) = parse_lifetime_annotations(&annotations, name.to_string()) {
-                    return Some(sig);
+                    let sig =
+                        with_receiver_mutability(sig, &annotations, &entity_return_type(entity));
+                    return Some(with_param_escapes(sig, &annotations, entity));
                 }
             }
             return None;
@@ -111,10 +200,89 @@ fn read_lifetime_from_source(entity: &Entity, name: &str) -> Option<FunctionSign
     None
 }
 
+fn entity_return_type(entity: &Entity) -> String {
+    entity
+        .get_result_type()
+        .map(|ty| ty.get_display_name())
+        .unwrap_or_default()
+}
+
+/// `@lifetime: this` on a method returning a non-const reference lends the
+/// receiver mutably, like `(&'self mut) -> &'self mut`
+pub(crate) fn with_receiver_mutability(
+    mut sig: FunctionSignature,
+    comment: &str,
+    return_type: &str,
+) -> FunctionSignature {
+    let is_this_shorthand = Regex::new(r"@lifetime(?::\s*this\s*$|\(this\))")
+        .is_ok_and(|re| comment.lines().any(|line| re.is_match(line.trim_end())));
+    if is_this_shorthand && is_mutable_reference_type(return_type) {
+        let self_mut = || Some(LifetimeAnnotation::MutRef("self".to_string()));
+        sig.param_lifetimes = vec![self_mut()];
+        sig.return_lifetime = self_mut();
+    }
+    sig
+}
+
+/// Whether `type_name` is an lvalue reference to a non-const object
+fn is_mutable_reference_type(type_name: &str) -> bool {
+    let type_name = type_name.trim();
+    let Some(pointee) = type_name.strip_suffix('&') else {
+        return false;
+    };
+    !pointee.trim_end().ends_with('&') && !pointee.split_whitespace().any(|word| word == "const")
+}
+
+/// Resolve the parameter names listed by `@escapes(...)` against the
+/// function's parameters
+fn with_param_escapes(
+    mut sig: FunctionSignature,
+    comment: &str,
+    entity: &Entity,
+) -> FunctionSignature {
+    let escaping = escaping_param_names(comment);
+    if escaping.is_empty() {
+        return sig;
+    }
+    sig.param_escapes = entity
+        .get_children()
+        .iter()
+        .filter(|child| child.get_kind() == clang::EntityKind::ParmDecl)
+        .map(|param| {
+            param
+                .get_name()
+                .is_some_and(|name| escaping.contains(&name.as_str()))
+        })
+        .collect();
+    sig
+}
+
+/// Parameter names in `@escapes(p, q)`: the function keeps these arguments
+/// (pointers or references) after it returns
+fn escaping_param_names(comment: &str) -> Vec<&str> {
+    let Some(captures) = Regex::new(r"@escapes\(([^)]*)\)")
+        .ok()
+        .and_then(|re| re.captures(comment))
+    else {
+        return Vec::new();
+    };
+    captures
+        .get(1)
+        .map_or("", |names| names.as_str())
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
 // Parse annotations like:
 // @lifetime: 'a -> &'a T
 // @lifetime: ('a, 'b) -> &'a T where 'a: 'b
 // @lifetime: owned
+// @lifetime: this        (method returns a reference into *this, also @lifetime(this))
+// @lifetime: (&'a) -> 'a (on a constructor: the object borrows the argument)
+// @lifetime: (&'a, out &'a) -> void (the out-parameter is set to borrow from 'a)
+// @escapes(p)            (the function stores parameter p beyond the call)
 pub(crate) fn parse_lifetime_annotations(
     comment: &str,
     func_name: String,
@@ -135,11 +303,11 @@ pub(crate) fn parse_lifetime_annotations(
     };
 
     // Look for @lifetime annotation
-    let lifetime_re = Regex::new(r"@lifetime:\s*(.+)").ok()?;
+    let lifetime_re = Regex::new(r"@lifetime(?::\s*(.+)|\((this|static|&'static)\))").ok()?;
 
     // If we have either safety or lifetime annotations, create a signature
     if let Some(captures) = lifetime_re.captures(comment) {
-        let annotation_str = captures.get(1)?.as_str();
+        let annotation_str = captures.get(1).or_else(|| captures.get(2))?.as_str();
 
         // Parse the annotation string
         let mut signature = FunctionSignature {
@@ -148,6 +316,8 @@ pub(crate) fn parse_lifetime_annotations(
             param_lifetimes: Vec::new(),
             lifetime_bounds: Vec::new(),
             safety,
+            param_escapes: Vec::new(),
+            param_outputs: Vec::new(),
         };
 
         // Check for where clause
@@ -161,7 +331,16 @@ pub(crate) fn parse_lifetime_annotations(
         }
 
         // Parse main lifetime specification
-        if main_part.contains("->") {
+        if main_part == "this" {
+            // Shorthand for (&'self) -> &'self: the returned reference borrows
+            // from the receiver object (e.g., `int& get() { return value_; }`)
+            signature.param_lifetimes = vec![Some(LifetimeAnnotation::Ref("self".to_string()))];
+            signature.return_lifetime = Some(LifetimeAnnotation::Ref("self".to_string()));
+        } else if main_part == "static" {
+            // Shorthand for () -> &'static: a reference to a static local or
+            // global, tied to no parameter
+            signature.return_lifetime = Some(LifetimeAnnotation::Ref("static".to_string()));
+        } else if main_part.contains("->") {
             // Has parameters and return type
             let arrow_parts: Vec<&str> = main_part.split("->").collect();
             if arrow_parts.len() == 2 {
@@ -169,7 +348,8 @@ pub(crate) fn parse_lifetime_annotations(
                 let return_str = arrow_parts[1].trim();
 
                 // Parse parameters
-                signature.param_lifetimes = parse_param_lifetimes(params_str);
+                (signature.param_lifetimes, signature.param_outputs) =
+                    parse_param_lifetimes(params_str);
 
                 // Parse return type
                 signature.return_lifetime = parse_single_lifetime(return_str);
@@ -180,32 +360,43 @@ pub(crate) fn parse_lifetime_annotations(
         }
 
         Some(signature)
-    } else if safety.is_some() {
-        // Even if no lifetime annotation, return signature if we have safety annotation
+    } else if safety.is_some() || !escaping_param_names(comment).is_empty() {
+        // Even if no lifetime annotation, return signature if we have safety
+        // or escape annotations
         Some(FunctionSignature {
             name: func_name,
             return_lifetime: None,
             param_lifetimes: Vec::new(),
             lifetime_bounds: Vec::new(),
             safety,
+            param_escapes: Vec::new(),
+            param_outputs: Vec::new(),
         })
     } else {
         None
     }
 }
 
-fn parse_param_lifetimes(params_str: &str) -> Vec<Option<LifetimeAnnotation>> {
+/// Parameter lifetimes, and which parameters are marked `out`
+fn parse_param_lifetimes(params_str: &str) -> (Vec<Option<LifetimeAnnotation>>, Vec<bool>) {
     let mut result = Vec::new();
+    let mut outputs = Vec::new();
 
     // Remove parentheses if present
     let cleaned = params_str.trim_start_matches('(').trim_end_matches(')');
 
     // Split by comma
     for param in cleaned.split(',') {
-        result.push(parse_single_lifetime(param.trim()));
+        let param = param.trim();
+        let (param, is_output) = match param.strip_prefix("out ") {
+            Some(rest) => (rest, true),
+            None => (param, false),
+        };
+        result.push(parse_single_lifetime(param));
+        outputs.push(is_output);
     }
 
-    result
+    (result, outputs)
 }
 
 fn parse_single_lifetime(lifetime_str: &str) -> Option<LifetimeAnnotation> {
@@ -300,6 +491,125 @@ mod tests {
         assert!(sig.param_lifetimes.is_empty());
     }
 
+    #[test]
+    fn test_undeclared_return_lifetime() {
+        let comment = "// @lifetime: (&'a) -> &'b";
+        let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+        assert_eq!(sig.undeclared_lifetimes(), vec!["b".to_string()]);
+
+        let comment = "// @lifetime: (&'a, &'b) -> &'a where 'c: 'a";
+        let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+        assert_eq!(sig.undeclared_lifetimes(), vec!["c".to_string()]);
+
+        for comment in [
+            "// @lifetime: (&'a, &'b) -> &'a where 'a: 'b",
+            "// @lifetime: (&'a, &'b) -> &'c where 'a: 'c, 'b: 'c",
+            "// @lifetime: () -> &'static",
+            "// @lifetime: &'a",
+            "// @lifetime: this",
+        ] {
+            let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+            assert!(sig.undeclared_lifetimes().is_empty(), "{}", comment);
+        }
+    }
+
+    #[test]
+    fn test_parse_out_param_lifetime() {
+        let comment = "// @lifetime: (&'a, out &'a) -> void";
+        let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+        assert_eq!(
+            sig.param_lifetimes,
+            vec![
+                Some(LifetimeAnnotation::Ref("a".to_string())),
+                Some(LifetimeAnnotation::Ref("a".to_string()))
+            ]
+        );
+        assert_eq!(sig.param_outputs, vec![false, true]);
+        assert_eq!(sig.return_lifetime, None);
+        assert!(sig.undeclared_lifetimes().is_empty());
+
+        // An out-parameter doesn't introduce the lifetime it borrows for
+        let comment = "// @lifetime: (const int* 'a, out int* 'b) -> void";
+        let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+        assert_eq!(sig.param_outputs, vec![false, true]);
+        assert_eq!(sig.undeclared_lifetimes(), vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        assert_eq!(
+            escaping_param_names("// @escapes(handler, ctx)"),
+            vec!["handler", "ctx"]
+        );
+        assert!(escaping_param_names("// @lifetime: &'a").is_empty());
+
+        // @escapes alone is enough to record a signature
+        let sig = parse_lifetime_annotations("// @escapes(handler)", "register".to_string());
+        assert!(sig.is_some());
+    }
+
+    #[test]
+    fn test_parse_this_lifetime() {
+        for comment in ["// @lifetime: this", "// @lifetime(this)"] {
+            let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+
+            assert_eq!(
+                sig.param_lifetimes,
+                vec![Some(LifetimeAnnotation::Ref("self".to_string()))]
+            );
+            assert_eq!(
+                sig.return_lifetime,
+                Some(LifetimeAnnotation::Ref("self".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_this_lifetime_follows_return_constness() {
+        let mutable = || Some(LifetimeAnnotation::MutRef("self".to_string()));
+        let shared = || Some(LifetimeAnnotation::Ref("self".to_string()));
+        for (return_type, expected) in [
+            ("int &", mutable()),
+            ("std::string&", mutable()),
+            ("const int &", shared()),
+            ("int const&", shared()),
+            ("int", shared()),
+        ] {
+            let sig = parse_lifetime_annotations("// @lifetime: this", "get".to_string()).unwrap();
+            let sig = with_receiver_mutability(sig, "// @lifetime: this", return_type);
+            assert_eq!(sig.return_lifetime, expected, "{}", return_type);
+            assert_eq!(sig.param_lifetimes, vec![expected], "{}", return_type);
+        }
+
+        // Only the shorthand is rewritten; explicit annotations say what they mean
+        let comment = "// @lifetime: (&'self) -> &'self";
+        let sig = parse_lifetime_annotations(comment, "get".to_string()).unwrap();
+        let sig = with_receiver_mutability(sig, comment, "int&");
+        assert_eq!(sig.return_lifetime, shared());
+    }
+
+    #[test]
+    fn test_parse_static_lifetime() {
+        for comment in [
+            "// @lifetime(static)",
+            "// @lifetime(&'static)",
+            "// @lifetime: static",
+            "// @lifetime: &'static",
+        ] {
+            let sig = parse_lifetime_annotations(comment, "counter".to_string()).unwrap();
+
+            assert!(sig.param_lifetimes.is_empty(), "{}", comment);
+            assert_eq!(
+                sig.return_lifetime,
+                Some(LifetimeAnnotation::Ref("static".to_string())),
+                "{}",
+                comment
+            );
+            assert!(sig.return_lifetime.as_ref().unwrap().is_static());
+            assert!(sig.undeclared_lifetimes().is_empty());
+        }
+    }
+
     #[test]
     fn test_parse_owned() {
         let comment = "// @lifetime: owned";
@@ -409,6 +409,7 @@ pub struct Class {
     pub methods: Vec<Function>,    // Member methods
     pub base_classes: Vec<String>, // Base class names (may contain packs like "Bases...")
     pub location: SourceLocation,
+    pub is_union: bool, // Declared with `union`: only the last-written member may be read
     // RAII Phase 2: Track if class has a destructor
     pub has_destructor: bool, // True if class has ~ClassName()
     // Inheritance safety: Interface-related fields
@@ -423,6 +424,7 @@ pub struct Class {
     pub has_copy_assignment: bool,  // True if class has operator=(const ClassName&)
     pub copy_constructor_deleted: bool, // True if copy constructor is explicitly deleted
     pub copy_assignment_deleted: bool, // True if copy assignment is explicitly deleted
+    pub is_move_only: bool,         // Has @move_only annotation: copies are moves
     // Constructor tracking for pointer member safety
     pub has_user_defined_constructor: bool, // Any user-defined constructor exists
     pub has_default_constructor: bool,      // Default ctor exists (explicit or implicit)
@@ -492,14 +494,15 @@ pub struct Variable {
     pub type_name: String,
     pub is_reference: bool,
     pub is_rvalue_reference: bool,
-    #[allow(dead_code)]
     pub is_pointer: bool,
     pub is_const: bool,
     pub is_unique_ptr: bool,
-    #[allow(dead_code)]
     pub is_shared_ptr: bool,
     pub is_static: bool,
     pub is_mutable: bool, // C++ mutable keyword (for interior mutability)
+    /// Accesses through this variable are volatile: the object is
+    /// `volatile`, or for pointers and references, what they point to is
+    pub is_volatile: bool,
     #[allow(dead_code)]
     pub location: SourceLocation,
     // Variadic template support (Phase 1)
@@ -537,6 +540,8 @@ pub enum Statement {
         location: SourceLocation,
     },
     Return(Option<Expression>),
+    // Leaves the innermost loop or switch
+    Break,
     FunctionCall {
         name: String,
         args: Vec<Expression>,
@@ -546,11 +551,21 @@ pub enum Statement {
     // Scope markers
     EnterScope,
     ExitScope,
-    // Loop markers
+    // Loop markers; a do-while or infinite-loop body opens with
+    // `EnterDoLoop` since it always runs at least once
     EnterLoop,
+    EnterDoLoop,
     ExitLoop,
-    // Safety markers
-    EnterUnsafe,
+    // Precedes the `EnterLoop` of a range-for; `range` is the expression
+    // iterated over
+    RangeFor {
+        range: Expression,
+        location: SourceLocation,
+    },
+    // Safety markers; `EnterUnsafe` is at the opening brace of the block
+    EnterUnsafe {
+        location: SourceLocation,
+    },
     ExitUnsafe,
     // Conditional statements
     If {
@@ -580,6 +595,11 @@ pub enum Statement {
         captures: Vec<LambdaCaptureKind>,
         location: SourceLocation,
     },
+    // Coroutine suspension point, after a statement containing `co_await`
+    // or `co_yield`
+    Suspend {
+        location: SourceLocation,
+    },
 }
 
 /// Represents a lambda capture
@@ -593,8 +613,8 @@ pub enum LambdaCaptureKind {
     ByRef(String),
     /// [x] - explicit copy capture
     ByCopy(String),
-    /// [x = expr] - init capture (includes move captures)
-    Init { name: String },
+    /// [x = expr] - init capture; `is_move` is set for [x = std::move(y)]
+    Init { name: String, is_move: bool },
     /// [this] - captures this pointer
     This,
     /// [*this] - captures this by copy (C++17)
@@ -691,7 +711,7 @@ pub enum Expression {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceLocation {
     #[allow(dead_code)]
     pub file: String,
@@ -699,6 +719,10 @@ pub struct SourceLocation {
     pub line: u32,
     #[allow(dead_code)]
     pub column: u32,
+    /// End of the entity's source range; `end_column` is one past its last
+    /// character, as libclang reports it. 0 when the range is unknown.
+    pub end_line: u32,
+    pub end_column: u32,
 }
 
 /// Extract member initializer list from a constructor
@@ -981,13 +1005,14 @@ pub fn extract_function(entity: &Entity) -> Function {
 pub fn extract_class(entity: &Entity) -> Class {
     use crate::debug_println;
     use crate::parser::safety_annotations::{
-        check_class_interface_annotation, parse_entity_safety,
+        check_class_interface_annotation, check_class_move_only_annotation, parse_entity_safety,
     };
 
     // Bug #8 fix: Use qualified name for classes to prevent namespace collision
     // e.g., "yaml::Node" instead of just "Node"
     let name = get_qualified_name(entity);
     let location = extract_location(entity);
+    let is_union = entity.get_kind() == EntityKind::UnionDecl;
 
     debug_println!("DEBUG PARSE: Extracting class '{}'", name);
 
@@ -1000,6 +1025,8 @@ pub fn extract_class(entity: &Entity) -> Class {
     // Check for @safe/@unsafe annotation on the class
     let safety_annotation = parse_entity_safety(entity);
 
+    let is_move_only = check_class_move_only_annotation(entity);
+
     let mut members = Vec::new();
     let mut methods = Vec::new();
     let mut base_classes = Vec::new();
@@ -1253,6 +1280,7 @@ pub fn extract_class(entity: &Entity) -> Class {
         methods,
         base_classes,
         location,
+        is_union,
         has_destructor, // RAII Phase 2
         // Inheritance safety fields
         is_interface,
@@ -1266,6 +1294,7 @@ pub fn extract_class(entity: &Entity) -> Class {
         has_copy_assignment,
         copy_constructor_deleted,
         copy_assignment_deleted,
+        is_move_only,
         // Constructor tracking for pointer member safety
         has_user_defined_constructor,
         has_default_constructor,
@@ -1308,6 +1337,84 @@ fn detect_method_qualifier(entity: &Entity) -> MethodQualifier {
     }
 }
 
+/// The first child of a VarDecl that parses as an expression: its initializer
+fn initializer_expression<'tu>(decl: &Entity<'tu>) -> Option<(Entity<'tu>, Expression)> {
+    decl.get_children()
+        .into_iter()
+        .find_map(|child| extract_expression(&child).map(|expr| (child, expr)))
+}
+
+/// `const T& r = make();` where `make` returns by value (or `= T(...)`)
+/// binds `r` to a temporary whose lifetime is extended to `r`'s scope,
+/// unlike `const T& r = v.at(i);`, which borrows from `v`
+fn binds_lifetime_extended_temporary(init: &Entity) -> bool {
+    let mut expr = *init;
+    // Look through implicit conversions, MaterializeTemporaryExpr and
+    // ExprWithCleanups, which libclang exposes as single-child wrappers
+    while expr.get_kind() != EntityKind::CallExpr {
+        let children = expr.get_children();
+        match (expr.get_kind(), children.as_slice()) {
+            (EntityKind::UnexposedExpr | EntityKind::ParenExpr, [inner]) => expr = *inner,
+            _ => return false,
+        }
+    }
+    let Some(callee) = expr.get_reference() else {
+        return false;
+    };
+    if callee.get_kind() == EntityKind::Constructor {
+        return true;
+    }
+    callee.get_result_type().is_some_and(|result| {
+        !matches!(
+            result.get_canonical_type().get_kind(),
+            TypeKind::LValueReference | TypeKind::RValueReference
+        )
+    })
+}
+
+/// A reference bound to a lifetime-extended temporary is, for ownership
+/// purposes, a local value of the referred-to type
+fn extend_temporary(var: &mut Variable, decl: &Entity) {
+    let declared = var.type_name.trim_end();
+    var.type_name = if declared.ends_with('&') {
+        declared.trim_end_matches('&').trim_end().to_string()
+    } else {
+        decl.get_type()
+            .and_then(|ty| ty.get_canonical_type().get_pointee_type())
+            .map(|pointee| type_to_string(&pointee))
+            .unwrap_or_else(|| var.type_name.clone())
+    };
+    var.is_reference = false;
+    var.is_rvalue_reference = false;
+}
+
+/// Guidelines Support Library owning raw pointer: `gsl::owner<T*>`
+pub fn is_gsl_owner_type(type_name: &str) -> bool {
+    type_name.contains("gsl::owner<")
+}
+
+/// `std::shared_ptr<T>` itself, possibly cv-qualified or a reference to
+/// one, but not a type that merely has a shared_ptr template argument such
+/// as `std::vector<std::shared_ptr<T>>`
+pub fn is_shared_ptr_type(type_name: &str) -> bool {
+    let Some((outer, _)) = type_name.split_once('<') else {
+        return false;
+    };
+    let outer = outer
+        .trim()
+        .trim_start_matches("const ")
+        .trim_start_matches("volatile ")
+        .trim_start_matches("::");
+    matches!(outer, "std::shared_ptr" | "shared_ptr")
+        || (outer.starts_with("std::") && outer.ends_with("::shared_ptr"))
+}
+
+/// Guidelines Support Library pointer that can never hold nullptr:
+/// `gsl::not_null<T*>`
+pub fn is_gsl_not_null_type(type_name: &str) -> bool {
+    type_name.contains("gsl::not_null<")
+}
+
 pub fn extract_variable(entity: &Entity) -> Variable {
     let name = entity.get_name().unwrap_or_else(|| "anonymous".to_string());
     let location = extract_location(entity);
@@ -1315,7 +1422,11 @@ pub fn extract_variable(entity: &Entity) -> Variable {
     let type_info = entity.get_type().unwrap();
     let type_name = type_to_string(&type_info);
 
-    let type_kind = type_info.get_kind();
+    // Classify references on the canonical type: `auto x = obj.get_ref()`
+    // deduces a value (a copy) while `auto&`, `auto&&`, `const auto&`,
+    // `decltype(auto)` and reference typedefs all come out as references
+    let reference_type = type_info.get_canonical_type();
+    let type_kind = reference_type.get_kind();
     let is_rvalue_reference = matches!(type_kind, TypeKind::RValueReference);
     let is_reference = matches!(
         type_kind,
@@ -1325,7 +1436,7 @@ pub fn extract_variable(entity: &Entity) -> Variable {
 
     // For references, check if the pointee type is const
     let is_const = if is_reference {
-        if let Some(pointee) = type_info.get_pointee_type() {
+        if let Some(pointee) = reference_type.get_pointee_type() {
             pointee.is_const_qualified()
         } else {
             type_info.is_const_qualified()
@@ -1334,8 +1445,23 @@ pub fn extract_variable(entity: &Entity) -> Variable {
         type_info.is_const_qualified()
     };
 
-    let is_unique_ptr = type_name.contains("unique_ptr");
-    let is_shared_ptr = type_name.contains("shared_ptr");
+    // `gsl::owner<T*>` owns its pointee, so it moves and drops like a unique_ptr
+    let is_unique_ptr = type_name.contains("unique_ptr") || is_gsl_owner_type(&type_name);
+    let is_shared_ptr = is_shared_ptr_type(&type_name);
+
+    // `volatile int* reg` and `volatile int& reg` access their pointee
+    // volatilely; `int* volatile p` is just a volatile pointer value
+    let is_volatile = if is_pointer {
+        type_info
+            .get_pointee_type()
+            .is_some_and(|pointee| pointee.is_volatile_qualified())
+    } else if is_reference {
+        reference_type
+            .get_pointee_type()
+            .is_some_and(|pointee| pointee.is_volatile_qualified())
+    } else {
+        type_info.is_volatile_qualified()
+    };
 
     // Check if this is a static variable
     // In clang, static variables have StorageClass::Static
@@ -1362,6 +1488,7 @@ pub fn extract_variable(entity: &Entity) -> Variable {
         is_shared_ptr,
         is_static,
         is_mutable,
+        is_volatile,
         location,
         is_pack: false,          // Will be set properly for function parameters
         pack_element_type: None, // Will be set properly for function parameters
@@ -1662,37 +1789,52 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
             EntityKind::DeclStmt => {
                 for decl_child in child.get_children() {
                     if decl_child.get_kind() == EntityKind::VarDecl {
-                        let var = extract_variable(&decl_child);
+                        let mut var = extract_variable(&decl_child);
+                        let init = initializer_expression(&decl_child);
+                        if var.is_reference
+                            && init.as_ref().is_some_and(|(init_entity, _)| {
+                                binds_lifetime_extended_temporary(init_entity)
+                            })
+                        {
+                            extend_temporary(&mut var, &decl_child);
+                        }
 
                         // Always add the variable declaration first
                         statements.push(Statement::VariableDecl(var.clone()));
 
                         // Check if this variable has an initializer
-                        for init_child in decl_child.get_children() {
-                            if let Some(expr) = extract_expression(&init_child) {
-                                // Check if this is a reference binding
-                                if var.is_reference {
-                                    statements.push(Statement::ReferenceBinding {
-                                        name: var.name.clone(),
-                                        target: expr,
-                                        is_mutable: !var.is_const,
-                                        location: extract_location(&decl_child),
-                                    });
-                                } else {
-                                    // Regular assignment/initialization
-                                    statements.push(Statement::Assignment {
-                                        lhs: Expression::Variable(var.name.clone()),
-                                        rhs: expr,
-                                        location: extract_location(&decl_child),
-                                    });
-                                }
-                                break;
+                        if let Some((_, expr)) = init {
+                            // Check if this is a reference binding
+                            if var.is_reference {
+                                statements.push(Statement::ReferenceBinding {
+                                    name: var.name.clone(),
+                                    target: expr,
+                                    is_mutable: !var.is_const,
+                                    location: extract_location(&decl_child),
+                                });
+                            } else {
+                                // Regular assignment/initialization
+                                statements.push(Statement::Assignment {
+                                    lhs: Expression::Variable(var.name.clone()),
+                                    rhs: expr,
+                                    location: extract_location(&decl_child),
+                                });
                             }
                         }
                     }
                 }
             }
             EntityKind::BinaryOperator | EntityKind::CompoundAssignOperator => {
+                // `ready && consume(std::move(x));` only evaluates the call
+                // when `ready` holds
+                let short_circuit = (child.get_kind() == EntityKind::BinaryOperator)
+                    .then(|| extract_expression(&child))
+                    .flatten()
+                    .filter(is_short_circuit);
+                if let Some(expr) = short_circuit {
+                    statements.push(short_circuit_statement(expr, extract_location(&child)));
+                    continue;
+                }
                 // Handle assignments (incl. compound `+=`/`-=`: both sides
                 // still need pointer/unsafe scanning, and Assignment is the
                 // shape the analyses already understand)
@@ -1713,6 +1855,7 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
                     debug_println!("DEBUG STMT: BinaryOperator RHS: {:?}", rhs_expr);
                     if let (Some(lhs), Some(rhs)) = (lhs_expr, rhs_expr) {
                         debug_println!("DEBUG STMT: Creating Assignment statement");
+                        let rhs = compound_assignment_rhs(&child, &lhs, rhs);
                         statements.push(Statement::Assignment {
                             lhs, // Now supports dereference: *ptr = value
                             rhs,
@@ -2190,6 +2333,7 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
                     .find_map(|c| extract_expression(&c));
                 statements.push(Statement::Return(return_expr));
             }
+            EntityKind::BreakStmt => statements.push(Statement::Break),
             EntityKind::CompoundStmt => {
                 // Regular nested block scope - add scope markers
                 statements.push(Statement::EnterScope);
@@ -2198,7 +2342,9 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
                 let is_unsafe = check_for_unsafe_annotation(&child);
                 if is_unsafe {
                     debug_println!("DEBUG UNSAFE: Found @unsafe block");
-                    statements.push(Statement::EnterUnsafe);
+                    statements.push(Statement::EnterUnsafe {
+                        location: extract_location(&child),
+                    });
                 }
 
                 statements.extend(extract_compound_statement(&child));
@@ -2210,7 +2356,22 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
                 statements.push(Statement::ExitScope);
             }
             EntityKind::ForRangeStmt => {
-                statements.extend(extract_range_for_control_statements(&child));
+                let control = extract_range_for_control_statements(&child);
+                let range = control.last().and_then(|stmt| match stmt {
+                    Statement::ExpressionStatement { expr, .. } => Some(expr.clone()),
+                    Statement::FunctionCall { name, args, .. } => Some(Expression::FunctionCall {
+                        name: name.clone(),
+                        args: args.clone(),
+                    }),
+                    _ => None,
+                });
+                statements.extend(control);
+                if let Some(range) = range {
+                    statements.push(Statement::RangeFor {
+                        range,
+                        location: extract_location(&child),
+                    });
+                }
                 statements.push(Statement::EnterLoop);
 
                 let loop_children: Vec<Entity> = child.get_children().into_iter().collect();
@@ -2354,11 +2515,42 @@ fn extract_compound_statement(entity: &Entity) -> Vec<Statement> {
             }
             _ => {}
         }
+
+        if is_suspension_statement(&child) {
+            statements.push(Statement::Suspend {
+                location: extract_location(&child),
+            });
+        }
     }
 
     statements
 }
 
+/// Whether a simple statement suspends the enclosing coroutine. libclang
+/// exposes `co_await` and `co_yield` only as unexposed expressions, so look
+/// for the keywords; compound and control-flow statements are skipped since
+/// their bodies are extracted (and marked) separately.
+fn is_suspension_statement(entity: &Entity) -> bool {
+    if matches!(
+        entity.get_kind(),
+        EntityKind::CompoundStmt
+            | EntityKind::IfStmt
+            | EntityKind::SwitchStmt
+            | EntityKind::ForStmt
+            | EntityKind::ForRangeStmt
+            | EntityKind::WhileStmt
+            | EntityKind::DoStmt
+            | EntityKind::LambdaExpr
+    ) {
+        return false;
+    }
+    entity.get_range().is_some_and(|range| {
+        safe_tokenize(&range)
+            .iter()
+            .any(|token| matches!(token.get_spelling().as_str(), "co_await" | "co_yield"))
+    })
+}
+
 /// Extract an IfStmt cursor into a `Statement::If`. Shared by the braced
 /// path (`extract_compound_statement`) and the unbraced-substatement path
 /// (`extract_single_statement`), which is what makes `else if` chains work:
@@ -2375,7 +2567,7 @@ fn extract_if_statement(entity: &Entity) -> Statement {
         let child_kind = children[i].get_kind();
 
         if !condition_seen && child_kind != EntityKind::CompoundStmt {
-            if let Some(expr) = extract_expression(&children[i]) {
+            if let Some(expr) = extract_condition_expression(&children[i]) {
                 condition = expr;
                 condition_seen = true;
             }
@@ -2407,6 +2599,52 @@ fn extract_if_statement(entity: &Entity) -> Statement {
     }
 }
 
+/// Extract an `if` condition. `extract_expression` treats `!` as transparent,
+/// which would make `if (!p)` indistinguishable from `if (p)`; for pointer and
+/// smart-pointer operands the negation is kept as `p == nullptr` so null
+/// narrowing sees the inverted test.
+fn extract_condition_expression(entity: &Entity) -> Option<Expression> {
+    let expr = extract_expression(entity)?;
+
+    let mut current = *entity;
+    while current.get_kind() == EntityKind::UnexposedExpr {
+        let children = current.get_children();
+        if children.len() != 1 {
+            break;
+        }
+        current = children[0];
+    }
+    if current.get_kind() != EntityKind::UnaryOperator {
+        return Some(expr);
+    }
+    let is_logical_not = current
+        .get_range()
+        .map(|range| safe_tokenize(&range))
+        .and_then(|tokens| tokens.first().map(|t| t.get_spelling() == "!"))
+        .unwrap_or(false);
+    if !is_logical_not {
+        return Some(expr);
+    }
+
+    let operand_is_pointer = current
+        .get_children()
+        .first()
+        .and_then(|operand| operand.get_type())
+        .is_some_and(|t| canonical_type_to_string(&t).contains('*'));
+    let operand_is_bool_conversion = matches!(
+        &expr,
+        Expression::FunctionCall { name, .. } if name.ends_with("operator bool")
+    );
+    if operand_is_pointer || operand_is_bool_conversion {
+        return Some(Expression::BinaryOp {
+            left: Box::new(expr),
+            op: "==".to_string(),
+            right: Box::new(Expression::Nullptr),
+        });
+    }
+    Some(expr)
+}
+
 /// Extract a C++17 if-init statement (`if (auto x = f(); cond)`). The init
 /// statement is a sibling of the condition; `extract_if_statement` parses the
 /// condition and branches but drops the init, so it is emitted separately here
@@ -2475,6 +2713,33 @@ fn expression_to_statement(expr: Expression, location: SourceLocation) -> Statem
     }
 }
 
+fn is_short_circuit(expr: &Expression) -> bool {
+    matches!(expr, Expression::BinaryOp { op, .. } if op == "&&" || op == "||")
+}
+
+/// Lower an expression statement, turning the right-hand side of `&&` / `||`
+/// into the branch of an `if` it effectively is: `a && f()` runs `f()` only
+/// when `a` holds, `a || f()` only when it doesn't
+fn short_circuit_statement(expr: Expression, location: SourceLocation) -> Statement {
+    match expr {
+        Expression::BinaryOp { left, op, right } if op == "&&" || op == "||" => {
+            let rhs = vec![short_circuit_statement(*right, location.clone())];
+            let (then_branch, else_branch) = if op == "&&" {
+                (rhs, None)
+            } else {
+                (Vec::new(), Some(rhs))
+            };
+            Statement::If {
+                condition: *left,
+                then_branch,
+                else_branch,
+                location,
+            }
+        }
+        expr => expression_to_statement(expr, location),
+    }
+}
+
 fn extract_range_for_expression_from_tokens(entity: &Entity) -> Option<Statement> {
     let range = entity.get_range()?;
     let tokens = safe_tokenize(&range);
@@ -2757,7 +3022,8 @@ fn extract_switch_statement(entity: &Entity) -> Statement {
     }
 }
 
-/// Extract a For/While/Do cursor into `EnterLoop .. ExitLoop` statements.
+/// Extract a For/While/Do cursor into `EnterLoop .. ExitLoop` statements
+/// (`EnterDoLoop .. ExitLoop` for a do-while or an infinite loop).
 /// Shared by the braced and unbraced-substatement paths.
 ///
 /// Libclang exposes loop control pieces as siblings of the loop body:
@@ -2773,8 +3039,6 @@ fn extract_switch_statement(entity: &Entity) -> Statement {
 /// reach the safety analyses.
 fn extract_loop_statement(entity: &Entity) -> Vec<Statement> {
     let mut statements = Vec::new();
-    statements.push(Statement::EnterLoop);
-
     let loop_children: Vec<Entity> = entity.get_children().into_iter().collect();
 
     let body_index = if entity.get_kind() == EntityKind::DoStmt {
@@ -2806,12 +3070,60 @@ fn extract_loop_statement(entity: &Entity) -> Vec<Statement> {
         }
     };
 
-    // while/for: control pieces evaluate before the body each iteration.
+    // Split the `for (init; cond; inc)` header by where each piece starts:
+    // the init runs once before the loop and the increment after the body.
+    // Without the header's `;` positions every piece is treated as a
+    // condition.
+    let for_header = if entity.get_kind() == EntityKind::ForStmt {
+        for_header_semicolons(entity)
+    } else {
+        None
+    };
+    let mut init = Vec::new();
+    let mut conditions = Vec::new();
+    let mut increments = Vec::new();
+    for (idx, control) in loop_children.iter().enumerate() {
+        if Some(idx) == body_index {
+            continue;
+        }
+        let start = control
+            .get_range()
+            .map(|range| range.get_start().get_file_location().offset);
+        match (for_header, start) {
+            (Some((init_end, _)), Some(start)) if start < init_end => init.push(control),
+            (Some((_, cond_end)), Some(start)) if start > cond_end => increments.push(control),
+            _ => conditions.push(control),
+        }
+    }
+
+    // The init's declarations belong to the loop, not the enclosing scope
+    if !init.is_empty() {
+        statements.push(Statement::EnterScope);
+        for control in &init {
+            emit_control(control, &mut statements);
+        }
+    }
+
+    // `for (;;)` and `while (true)` leave only through the body, so like a
+    // do-while's it runs at least once
+    let runs_forever = match conditions.as_slice() {
+        [] => entity.get_kind() == EntityKind::ForStmt,
+        [condition] => matches!(
+            extract_expression(condition),
+            Some(Expression::Literal(ref value)) if value == "true" || value == "1"
+        ),
+        _ => false,
+    };
+    statements.push(if entity.get_kind() == EntityKind::DoStmt || runs_forever {
+        Statement::EnterDoLoop
+    } else {
+        Statement::EnterLoop
+    });
+
+    // while/for: the condition evaluates before the body each iteration.
     if entity.get_kind() != EntityKind::DoStmt {
-        for (idx, control) in loop_children.iter().enumerate() {
-            if Some(idx) != body_index {
-                emit_control(control, &mut statements);
-            }
+        for control in &conditions {
+            emit_control(control, &mut statements);
         }
     }
 
@@ -2828,19 +3140,66 @@ fn extract_loop_statement(entity: &Entity) -> Vec<Statement> {
         }
     }
 
+    // An assignment increment (`x = std::move(y)`, `i += 2`) keeps the
+    // Assignment form so moves in it are tracked like any other.
+    for control in &increments {
+        let is_assignment = control.get_kind() == EntityKind::CompoundAssignOperator
+            || (control.get_kind() == EntityKind::BinaryOperator
+                && matches!(
+                    extract_expression(control),
+                    Some(Expression::BinaryOp { ref op, .. }) if op == "="
+                ));
+        if is_assignment {
+            statements.extend(extract_single_statement(control));
+        } else {
+            emit_control(control, &mut statements);
+        }
+    }
+
     // do-while: the condition evaluates after the body each iteration.
     if entity.get_kind() == EntityKind::DoStmt {
-        for (idx, control) in loop_children.iter().enumerate() {
-            if Some(idx) != body_index {
-                emit_control(control, &mut statements);
-            }
+        for control in &conditions {
+            emit_control(control, &mut statements);
         }
     }
 
     statements.push(Statement::ExitLoop);
+    if !init.is_empty() {
+        statements.push(Statement::ExitScope);
+    }
     statements
 }
 
+/// File offsets of the two `;` separating a `for` header's init, condition
+/// and increment
+fn for_header_semicolons(entity: &Entity) -> Option<(u32, u32)> {
+    let range = entity.get_range()?;
+    let mut paren_depth = 0usize;
+    let mut brace_depth = 0usize;
+    let mut semicolons = Vec::new();
+    for token in safe_tokenize(&range) {
+        match token.get_spelling().as_str() {
+            "(" => paren_depth += 1,
+            ")" => {
+                paren_depth = paren_depth.saturating_sub(1);
+                if paren_depth == 0 {
+                    break;
+                }
+            }
+            "{" => brace_depth += 1,
+            "}" => brace_depth = brace_depth.saturating_sub(1),
+            ";" if paren_depth == 1 && brace_depth == 0 => {
+                semicolons.push(token.get_location().get_file_location().offset);
+            }
+            _ => {}
+        }
+    }
+    match semicolons[..] {
+        [init_end, cond_end] => Some((init_end, cond_end)),
+        _ => None,
+    }
+}
+
 fn extract_switch_body_statement(entity: &Entity) -> Vec<Statement> {
     match entity.get_kind() {
         EntityKind::BreakStmt => Vec::new(),
@@ -2992,26 +3351,31 @@ fn extract_single_statement(entity: &Entity) -> Vec<Statement> {
 
             for decl_child in entity.get_children() {
                 if decl_child.get_kind() == EntityKind::VarDecl {
-                    let var = extract_variable(&decl_child);
+                    let mut var = extract_variable(&decl_child);
+                    let init = initializer_expression(&decl_child);
+                    if var.is_reference
+                        && init.as_ref().is_some_and(|(init_entity, _)| {
+                            binds_lifetime_extended_temporary(init_entity)
+                        })
+                    {
+                        extend_temporary(&mut var, &decl_child);
+                    }
                     statements.push(Statement::VariableDecl(var.clone()));
 
-                    for init_child in decl_child.get_children() {
-                        if let Some(expr) = extract_expression(&init_child) {
-                            if var.is_reference {
-                                statements.push(Statement::ReferenceBinding {
-                                    name: var.name.clone(),
-                                    target: expr,
-                                    is_mutable: !var.is_const,
-                                    location: extract_location(&decl_child),
-                                });
-                            } else {
-                                statements.push(Statement::Assignment {
-                                    lhs: Expression::Variable(var.name.clone()),
-                                    rhs: expr,
-                                    location: extract_location(&decl_child),
-                                });
-                            }
-                            break;
+                    if let Some((_, expr)) = init {
+                        if var.is_reference {
+                            statements.push(Statement::ReferenceBinding {
+                                name: var.name.clone(),
+                                target: expr,
+                                is_mutable: !var.is_const,
+                                location: extract_location(&decl_child),
+                            });
+                        } else {
+                            statements.push(Statement::Assignment {
+                                lhs: Expression::Variable(var.name.clone()),
+                                rhs: expr,
+                                location: extract_location(&decl_child),
+                            });
                         }
                     }
                 }
@@ -3026,6 +3390,7 @@ fn extract_single_statement(entity: &Entity) -> Vec<Statement> {
                     extract_expression(&children[0]),
                     extract_expression(&children[1]),
                 ) {
+                    let rhs = compound_assignment_rhs(entity, &lhs, rhs);
                     return vec![Statement::Assignment { lhs, rhs, location }];
                 }
             }
@@ -3054,6 +3419,7 @@ fn extract_single_statement(entity: &Entity) -> Vec<Statement> {
                 .find_map(|c| extract_expression(&c));
             vec![Statement::Return(return_expr)]
         }
+        EntityKind::BreakStmt => vec![Statement::Break],
         EntityKind::UnexposedExpr
         | EntityKind::UnaryOperator
         | EntityKind::NewExpr
@@ -3152,6 +3518,29 @@ fn extract_operator_from_display(display: &str) -> Option<String> {
     None
 }
 
+/// `x op= y` reads `x` before writing it, so it is spelled out as
+/// `x = x op y` for the analyses. Pointer compound assignments (`p += n`)
+/// and anything whose operator cannot be recovered keep the plain `rhs`.
+fn compound_assignment_rhs(entity: &Entity, lhs: &Expression, rhs: Expression) -> Expression {
+    if entity.get_kind() != EntityKind::CompoundAssignOperator {
+        return rhs;
+    }
+    let op = match extract_expression(entity) {
+        Some(Expression::BinaryOp { op, .. }) => op,
+        _ => return rhs,
+    };
+    match op.strip_suffix('=') {
+        Some(binary @ ("+" | "-" | "*" | "/" | "%" | "&" | "|" | "^" | "<<" | ">>")) => {
+            Expression::BinaryOp {
+                left: Box::new(lhs.clone()),
+                op: binary.to_string(),
+                right: Box::new(rhs),
+            }
+        }
+        _ => rhs,
+    }
+}
+
 /// Extract a cast expression with the given cast kind
 fn extract_cast_expression(entity: &Entity, kind: CastKind) -> Option<Expression> {
     let children: Vec<Entity> = entity.get_children().into_iter().collect();
@@ -3218,6 +3607,33 @@ fn extract_lambda_capture_tokens(entity: &Entity) -> Vec<String> {
     capture_tokens
 }
 
+/// Check whether the init capture `name` in a capture list is initialized
+/// with a move, i.e. `[name = std::move(src)]` or `[name = rusty::move(src)]`.
+fn init_capture_is_move(capture_tokens: &[String], name: &str) -> bool {
+    let Some(start) = capture_tokens
+        .windows(2)
+        .position(|window| window[0] == name && window[1] == "=")
+    else {
+        return false;
+    };
+
+    let mut depth = 0usize;
+    for (idx, token) in capture_tokens.iter().enumerate().skip(start + 2) {
+        match token.as_str() {
+            "(" | "{" | "<" => depth += 1,
+            ")" | "}" | ">" => depth = depth.saturating_sub(1),
+            "," if depth == 0 => break,
+            "move" => {
+                if capture_tokens.get(idx + 1).is_some_and(|next| next == "(") {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
 fn extract_lambda_capture_initializer(
     entity: &Entity,
     capture_tokens: &[String],
@@ -4240,8 +4656,11 @@ fn extract_expression(entity: &Entity) -> Option<Expression> {
                                                 if let Some(eq_pos) = capture_list.find('=') {
                                                     let var_name =
                                                         capture_list[..eq_pos].trim().to_string();
+                                                    let is_move = capture_list[eq_pos + 1..]
+                                                        .contains("move(");
                                                     captures.push(LambdaCaptureKind::Init {
                                                         name: var_name,
+                                                        is_move,
                                                     });
                                                 }
                                             }
@@ -4281,14 +4700,28 @@ fn extract_expression(entity: &Entity) -> Option<Expression> {
                     debug_println!("DEBUG LAMBDA: Copy capture of '{}'", var_name);
                     captures.push(LambdaCaptureKind::ByCopy(var_name));
                 } else if has_move_call {
-                    // Has move() call = init move capture [y = std::move(x)]
-                    debug_println!("DEBUG LAMBDA: Init move capture '{}'", var_name);
-                    captures.push(LambdaCaptureKind::Init { name: var_name });
+                    // Has move() call = init capture, possibly [y = std::move(x)].
+                    // With mixed init captures only some of them move, so
+                    // consult the capture tokens when they are available.
+                    let is_move = capture_tokens.is_empty()
+                        || init_capture_is_move(&capture_tokens, &var_name);
+                    debug_println!(
+                        "DEBUG LAMBDA: Init capture '{}' (is_move={})",
+                        var_name,
+                        is_move
+                    );
+                    captures.push(LambdaCaptureKind::Init {
+                        name: var_name,
+                        is_move,
+                    });
                 } else if is_init_capture_pattern {
                     // Has DeclRefExpr with entirely DIFFERENT names = init capture [y = x]
                     // The VariableRef is the new capture name, DeclRefExpr is the source
                     debug_println!("DEBUG LAMBDA: Init copy capture '{}'", var_name);
-                    captures.push(LambdaCaptureKind::Init { name: var_name });
+                    captures.push(LambdaCaptureKind::Init {
+                        name: var_name,
+                        is_move: false,
+                    });
                 } else {
                     // No matching DeclRefExpr = reference capture
                     debug_println!("DEBUG LAMBDA: Reference capture of '{}'", var_name);
@@ -4407,9 +4840,17 @@ fn extract_location(entity: &Entity) -> SourceLocation {
             file: "unknown".to_string(),
             line: 0,
             column: 0,
+            end_line: 0,
+            end_column: 0,
         };
     };
     let file_location = location.get_file_location();
+    // Macro expansions and other odd ranges can end in a different file than
+    // they start; only keep an end that lies in the same file.
+    let end = entity
+        .get_range()
+        .map(|range| range.get_end().get_file_location())
+        .filter(|end| end.file == file_location.file && end.line >= file_location.line);
 
     SourceLocation {
         file: file_location
@@ -4418,6 +4859,8 @@ fn extract_location(entity: &Entity) -> SourceLocation {
             .unwrap_or_else(|| "unknown".to_string()),
         line: file_location.line,
         column: file_location.column,
+        end_line: end.as_ref().map_or(0, |end| end.line),
+        end_column: end.as_ref().map_or(0, |end| end.column),
     }
 }
 
@@ -6,6 +6,10 @@
 //           (e.g., std::string::length() is safe - no UB, no raw pointers exposed)
 // - [unsafe] - function may have unsafe behavior, must be called from @unsafe block
 //
+// A whole namespace can be annotated at once with
+// `@external: { namespace: [unsafe, "boost::"] }`, which applies to every
+// function whose qualified name starts with the given prefix.
+//
 // NOTE: The distinction is about programmer audit, not tool verification.
 // [safe] external functions can be called directly from @safe code.
 
@@ -57,6 +61,9 @@ pub struct ExternalAnnotations {
     // Unsafe scopes (classes/namespaces marked as entirely unsafe)
     pub unsafe_scopes: Vec<String>,
 
+    // Namespace-wide annotations: (qualified name prefix, safety)
+    pub namespace_annotations: Vec<(String, ExternalSafety)>,
+
     // Unsafe types - types whose internal structure should not be analyzed
     // A @safe class can have unsafe_type fields without triggering internal analysis
     pub unsafe_types: Vec<String>,
@@ -71,6 +78,7 @@ impl ExternalAnnotations {
             profiles: HashMap::new(),
             active_profile: None,
             unsafe_scopes: Vec::new(),
+            namespace_annotations: Vec::new(),
             unsafe_types: Vec::new(),
         };
 
@@ -222,6 +230,17 @@ impl ExternalAnnotations {
                             _ => continue,
                         };
 
+                        // namespace: [safety, "prefix::"] annotates a whole namespace
+                        if name == "namespace" {
+                            if let Some(prefix) = parts.get(1) {
+                                let prefix = prefix.trim_matches('"').to_string();
+                                if !prefix.is_empty() {
+                                    self.namespace_annotations.push((prefix, safety));
+                                }
+                            }
+                            continue;
+                        }
+
                         self.functions
                             .insert(name, ExternalFunctionAnnotation { safety });
                     }
@@ -538,6 +557,11 @@ impl ExternalAnnotations {
             }
         }
 
+        // Then check namespace-wide annotations (the longest matching prefix wins)
+        if let Some(safety) = self.namespace_safety(func_name) {
+            return Some(safety == ExternalSafety::Safe);
+        }
+
         // Then check active profile
         if let Some(profile_name) = &self.active_profile {
             if let Some(profile) = self.profiles.get(profile_name) {
@@ -572,6 +596,15 @@ impl ExternalAnnotations {
         None
     }
 
+    /// Find the safety of the most specific namespace annotation covering a qualified name
+    fn namespace_safety(&self, func_name: &str) -> Option<ExternalSafety> {
+        self.namespace_annotations
+            .iter()
+            .filter(|(prefix, _)| func_name.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, safety)| safety.clone())
+    }
+
     fn matches_any_pattern(name: &str, patterns: &[String]) -> bool {
         for pattern in patterns {
             #[cfg(test)]
@@ -907,4 +940,37 @@ mod tests {
         assert!(annotations.functions.contains_key("rusty::Option::is_none"));
         assert!(annotations.functions.contains_key("std::vector::push_back"));
     }
+
+    #[test]
+    fn test_namespace_annotation() {
+        let content = r#"
+        // @external: {
+        //   namespace: [unsafe, "boost::"]
+        //   namespace: [safe, "boost::algorithm::"]
+        //   boost::algorithm::split: [unsafe, (&mut self) -> void]
+        // }
+        "#;
+
+        let mut annotations = ExternalAnnotations::new();
+        annotations.parse_content(content).unwrap();
+
+        assert_eq!(annotations.namespace_annotations.len(), 2);
+        assert_eq!(
+            annotations.is_function_safe("boost::asio::connect"),
+            Some(false)
+        );
+        // The more specific namespace wins
+        assert_eq!(
+            annotations.is_function_safe("boost::algorithm::trim"),
+            Some(true)
+        );
+        // Explicit function annotations override namespace annotations
+        assert_eq!(
+            annotations.is_function_safe("boost::algorithm::split"),
+            Some(false)
+        );
+        // Prefix matching is on the qualified name only
+        assert_eq!(annotations.is_function_safe("boostlike::connect"), None);
+        assert_eq!(annotations.is_function_safe("connect"), None);
+    }
 }
@@ -1,16 +1,18 @@
 use crate::debug_println;
-use clang::{Clang, Index};
+use clang::{Clang, Index, Unsaved};
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::annotations::{FunctionSignature, extract_annotations, parse_lifetime_annotations};
+use super::annotations::{
+    FunctionSignature, extract_annotations, parse_lifetime_annotations, with_receiver_mutability,
+};
 use super::external_annotations::ExternalAnnotations;
 use super::safety_annotations::{SafetyMode, parse_entity_safety};
 
 /// Cache for storing function signatures from header files
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HeaderCache {
     /// Map from function name to its lifetime signature
     signatures: HashMap<String, FunctionSignature>,
@@ -22,6 +24,8 @@ pub struct HeaderCache {
     include_paths: Vec<PathBuf>,
     /// External annotations found in headers
     pub external_annotations: ExternalAnnotations,
+    /// Malformed @lifetime annotations, reported before analysis starts
+    pub annotation_errors: Vec<String>,
 }
 
 /// Strip template parameters from a name (e.g., "Option<T>" -> "Option")
@@ -101,6 +105,9 @@ fn extract_class_name_for_lifetime_scan(line: &str) -> Option<String> {
     Some(strip_template_params(simple_name))
 }
 
+/// Extract the declared function name from a declaration/definition line.
+/// A written qualifier is kept, so the out-of-line definition
+/// `const T& Foo<T>::get() const {` yields "Foo::get".
 fn extract_annotated_function_name(line: &str) -> Option<String> {
     if !line.contains('(') {
         return None;
@@ -112,16 +119,56 @@ fn extract_annotated_function_name(line: &str) -> Option<String> {
     }
 
     if let Some(operator_pos) = before_paren.rfind("operator") {
-        return Some(before_paren[operator_pos..].trim().to_string());
+        let operator_name = before_paren[operator_pos..].trim();
+        let qualifier = remove_template_args(&before_paren[..operator_pos])
+            .strip_suffix("::")
+            .and_then(|prefix| prefix.split_whitespace().last())
+            .and_then(join_name_parts);
+        return Some(match qualifier {
+            Some(qualifier) => format!("{}::{}", qualifier, operator_name),
+            None => operator_name.to_string(),
+        });
     }
 
-    let token = before_paren.split_whitespace().last()?;
-    let simple_name = token.rsplit("::").next().unwrap_or(token);
-    let name = strip_template_params(simple_name)
-        .trim_matches(|c: char| !c.is_alphanumeric() && c != '_' && c != '~')
-        .to_string();
+    let without_templates = remove_template_args(before_paren);
+    let token = without_templates.split_whitespace().last()?;
+    join_name_parts(token)
+}
 
-    if name.is_empty() { None } else { Some(name) }
+/// The text before the declarator name, e.g. "const int&" in "const int& get() const"
+fn declared_return_type(line: &str) -> &str {
+    let before_paren = line.split('(').next().unwrap_or_default().trim_end();
+    before_paren.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Normalize a possibly qualified declarator token, e.g. "&ns::Foo::get" -> "ns::Foo::get"
+fn join_name_parts(token: &str) -> Option<String> {
+    let parts: Vec<&str> = token
+        .split("::")
+        .map(|part| part.trim_matches(|c: char| !c.is_alphanumeric() && c != '_' && c != '~'))
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("::"))
+    }
+}
+
+/// Drop every `<...>` group, e.g. "Map<K, std::vector<V>>::find" -> "Map::find"
+fn remove_template_args(text: &str) -> String {
+    let mut result = String::new();
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' if depth > 0 => depth -= 1,
+            _ if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    result
 }
 
 impl HeaderCache {
@@ -132,6 +179,7 @@ impl HeaderCache {
             processed_headers: Vec::new(),
             include_paths: Vec::new(),
             external_annotations: ExternalAnnotations::new(),
+            annotation_errors: Vec::new(),
         }
     }
 
@@ -145,7 +193,61 @@ impl HeaderCache {
         self.signatures.get(func_name)
     }
 
+    /// Get the signature for a call site. Constructor calls spelled with the
+    /// bare class name (`Holder`) fall back to the `Holder::Holder` signature.
+    pub fn get_call_signature(&self, func_name: &str) -> Option<&FunctionSignature> {
+        self.get_signature(func_name).or_else(|| {
+            let class_name = func_name.rsplit("::").next()?;
+            self.signatures
+                .get(&format!("{}::{}", func_name, class_name))
+        })
+    }
+
+    /// Merge signatures and safety annotations collected elsewhere (e.g. from
+    /// other translation units). Entries already in this cache take precedence.
+    pub fn merge_from(&mut self, other: &HeaderCache) {
+        for (name, signature) in &other.signatures {
+            self.signatures
+                .entry(name.clone())
+                .or_insert_with(|| signature.clone());
+        }
+        for (name, safety) in &other.safety_annotations {
+            self.safety_annotations
+                .entry(name.clone())
+                .or_insert(*safety);
+        }
+    }
+
+    /// Whether `path` was parsed as a header (or module unit) already,
+    /// however either path spells it
+    pub fn has_processed(&self, path: &Path) -> bool {
+        let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let path = canonical(path);
+        self.processed_headers.iter().any(|p| canonical(p) == path)
+    }
+
+    /// Paths of the headers (and module units) parsed so far
+    pub fn processed_headers(&self) -> &[PathBuf] {
+        &self.processed_headers
+    }
+
+    /// Iterate over all cached function signatures
+    pub fn signatures(&self) -> impl Iterator<Item = (&String, &FunctionSignature)> {
+        self.signatures.iter()
+    }
+
     fn insert_signature(&mut self, qualified_name: String, sig: FunctionSignature) {
+        // An undeclared name would silently produce no borrow at call sites
+        for name in sig.undeclared_lifetimes() {
+            let error = format!(
+                "unknown lifetime '{} in signature of '{}'",
+                name, qualified_name
+            );
+            if !self.annotation_errors.contains(&error) {
+                self.annotation_errors.push(error);
+            }
+        }
+
         if sig.return_lifetime.is_none() {
             if let Some(existing) = self.signatures.get(&qualified_name) {
                 if existing.return_lifetime.is_some() {
@@ -163,6 +265,22 @@ impl HeaderCache {
 
     /// Parse a header file and extract all annotated function signatures
     pub fn parse_header(&mut self, header_path: &Path) -> Result<(), String> {
+        self.parse_header_contents(header_path, None)
+    }
+
+    /// Collect the annotations of a source file held in memory and of the
+    /// headers it includes; `path` names the file the source stands for
+    pub fn parse_source(&mut self, path: &Path, content: &str) -> Result<(), String> {
+        self.parse_includes_from_content(content, path)?;
+        self.parse_header_contents(path, Some(content))
+    }
+
+    /// [`Self::parse_header`], reading the header from `contents` when given
+    fn parse_header_contents(
+        &mut self,
+        header_path: &Path,
+        contents: Option<&str>,
+    ) -> Result<(), String> {
         debug_println!(
             "DEBUG HEADER: Parsing header file: {}",
             header_path.display()
@@ -174,13 +292,18 @@ impl HeaderCache {
             return Ok(());
         }
 
+        let content = match contents {
+            Some(content) => Some(content.to_string()),
+            None => fs::read_to_string(header_path).ok(),
+        };
+
         // Parse safety annotations directly from the header file (before libclang parsing)
         // This ensures we get regular C++ comments (// and /* */) not just Doxygen comments
         // Store temporarily - we'll qualify the names after LibClang parsing
         let mut unqualified_annotations = HashMap::new();
-        if let Ok(header_safety_context) =
-            super::safety_annotations::parse_safety_annotations(header_path)
-        {
+        if let Some(Ok(header_safety_context)) = content.as_deref().map(|content| {
+            super::safety_annotations::parse_safety_annotations_from_source(content, header_path)
+        }) {
             // Store unqualified annotations temporarily
             for (func_sig, safety_mode) in &header_safety_context.function_overrides {
                 debug_println!(
@@ -197,16 +320,16 @@ impl HeaderCache {
         }
 
         // Also parse external annotations from the header file
-        if let Ok(content) = fs::read_to_string(header_path) {
+        if let Some(content) = &content {
             // Parse external annotations from the file content
             // These might be in comments or in the file directly
-            if let Err(e) = self.external_annotations.parse_content(&content) {
+            if let Err(e) = self.external_annotations.parse_content(content) {
                 debug_println!("DEBUG HEADER: Failed to parse external annotations: {}", e);
             } else {
                 debug_println!("DEBUG HEADER: Parsed external annotations from header");
             }
 
-            self.parse_lifetime_annotations_from_text(&content);
+            self.parse_lifetime_annotations_from_text(content);
         }
 
         // Initialize Clang
@@ -224,9 +347,14 @@ impl HeaderCache {
         }
 
         // Parse the header file
+        let unsaved: Vec<Unsaved> = contents
+            .map(|contents| Unsaved::new(header_path, contents))
+            .into_iter()
+            .collect();
         let tu = index
             .parser(header_path)
             .arguments(&args.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+            .unsaved(&unsaved)
             .parse()
             .map_err(|e| format!("Failed to parse header {}: {:?}", header_path.display(), e))?;
 
@@ -297,8 +425,8 @@ impl HeaderCache {
         self.processed_headers.push(header_path.to_path_buf());
 
         // Recursively parse includes from this header
-        if let Ok(content) = fs::read_to_string(header_path) {
-            let (quoted_includes, angle_includes) = extract_includes(&content);
+        if let Some(content) = &content {
+            let (quoted_includes, angle_includes) = extract_includes(content);
 
             // Process quoted includes (search relative to header file first)
             for include_path in quoted_includes {
@@ -328,7 +456,9 @@ impl HeaderCache {
         for line in content.lines() {
             let trimmed = line.trim();
 
-            if trimmed.starts_with("//") && trimmed.contains("@lifetime:") {
+            if trimmed.starts_with("//")
+                && (trimmed.contains("@lifetime:") || trimmed.contains("@lifetime(this)"))
+            {
                 pending_lifetime = Some(trimmed.to_string());
                 continue;
             }
@@ -347,7 +477,9 @@ impl HeaderCache {
                         None => func_name.clone(),
                     };
 
-                    if let Some(mut sig) = parse_lifetime_annotations(&comment, func_name.clone()) {
+                    if let Some(sig) = parse_lifetime_annotations(&comment, func_name.clone()) {
+                        let mut sig =
+                            with_receiver_mutability(sig, &comment, declared_return_type(trimmed));
                         sig.name = qualified_name.clone();
                         debug_println!(
                             "DEBUG HEADER: Text lifetime annotation '{}' -> {:?}",
@@ -392,8 +524,16 @@ impl HeaderCache {
     pub fn parse_includes_from_source(&mut self, cpp_file: &Path) -> Result<(), String> {
         let content = fs::read_to_string(cpp_file)
             .map_err(|e| format!("Failed to read {}: {}", cpp_file.display(), e))?;
+        self.parse_includes_from_content(&content, cpp_file)
+    }
 
-        let (quoted_includes, angle_includes) = extract_includes(&content);
+    /// Parse the headers included by `content`, the source of `cpp_file`
+    pub fn parse_includes_from_content(
+        &mut self,
+        content: &str,
+        cpp_file: &Path,
+    ) -> Result<(), String> {
+        let (quoted_includes, angle_includes) = extract_includes(content);
 
         // Process quoted includes (search relative to source file first)
         for include_path in quoted_includes {
@@ -413,7 +553,7 @@ impl HeaderCache {
         // to the project source file that declares `export module X.Y.Z;`,
         // then parsed for safety annotations (text pre-pass only — LibClang
         // can't parse module units without the full module-graph setup).
-        for module_name in extract_module_imports(&content) {
+        for module_name in extract_module_imports(content) {
             if let Some(resolved) = self.resolve_module_import(&module_name) {
                 self.parse_module_source_for_annotations(&resolved)?;
             } else {
@@ -522,6 +662,31 @@ impl HeaderCache {
         include_path: &str,
         source_file: &Path,
         search_source_dir: bool,
+    ) -> Option<PathBuf> {
+        let resolved = self.find_include(include_path, source_file, search_source_dir);
+        match &resolved {
+            Some(path) => verbose_println!(
+                2,
+                "Resolved include '{}' from {} -> {}",
+                include_path,
+                source_file.display(),
+                path.display()
+            ),
+            None => verbose_println!(
+                2,
+                "Could not resolve include '{}' from {}",
+                include_path,
+                source_file.display()
+            ),
+        }
+        resolved
+    }
+
+    fn find_include(
+        &self,
+        include_path: &str,
+        source_file: &Path,
+        search_source_dir: bool,
     ) -> Option<PathBuf> {
         // For quoted includes, first try relative to the source file
         if search_source_dir {
@@ -855,4 +1020,82 @@ mod tests {
         // Note: This tests the function itself, not the full qualified name handling
         assert_eq!(strip_template_params("Option<T>::Option"), "Option");
     }
+
+    #[test]
+    fn test_extract_annotated_function_name_keeps_qualifier() {
+        assert_eq!(
+            extract_annotated_function_name("const int& identity(const int& x) {"),
+            Some("identity".to_string())
+        );
+        assert_eq!(
+            extract_annotated_function_name("const T& Box<T>::get() const {"),
+            Some("Box::get".to_string())
+        );
+        assert_eq!(
+            extract_annotated_function_name("std::map<int, int>& ns::Registry::entries() {"),
+            Some("ns::Registry::entries".to_string())
+        );
+        assert_eq!(
+            extract_annotated_function_name("int& Vec::operator[](size_t i) {"),
+            Some("Vec::operator[]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_text_lifetime_annotation_on_cpp_definitions() {
+        let content = r#"
+// @safe
+// @lifetime: (&'a) -> &'a
+const int& identity(const int& x) {
+    return x;
+}
+
+namespace ns {
+// @lifetime: (&'a) -> &'a
+const int&
+Holder::pick(const int& x) const {
+    return x;
+}
+}
+"#;
+
+        let mut cache = HeaderCache::new();
+        cache.parse_lifetime_annotations_from_text(content);
+
+        assert!(
+            cache
+                .get_signature("identity")
+                .is_some_and(|sig| sig.return_lifetime.is_some())
+        );
+        assert!(
+            cache
+                .get_signature("ns::Holder::pick")
+                .is_some_and(|sig| sig.return_lifetime.is_some())
+        );
+    }
+
+    #[test]
+    fn test_constructor_signature_found_from_class_name() {
+        let content = r#"
+class Observer {
+public:
+    // @lifetime: (&'a) -> 'a
+    explicit Observer(const int& target) : target_(&target) {}
+private:
+    const int* target_;
+};
+"#;
+
+        let mut cache = HeaderCache::new();
+        cache.parse_lifetime_annotations_from_text(content);
+
+        let sig = cache.get_call_signature("Observer").unwrap();
+        assert_eq!(sig.name, "Observer::Observer");
+        assert_eq!(
+            sig.return_lifetime
+                .as_ref()
+                .and_then(|lifetime| lifetime.lifetime_name()),
+            Some("a")
+        );
+    }
 }
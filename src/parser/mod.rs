@@ -1,4 +1,4 @@
-use clang::{Clang, Entity, EntityKind, Index};
+use clang::{Clang, Entity, EntityKind, Index, Unsaved};
 use std::path::Path;
 
 pub mod annotations;
@@ -122,6 +122,27 @@ pub fn parse_cpp_file_with_includes_defines_and_args(
     include_paths: &[std::path::PathBuf],
     defines: &[String],
     extra_clang_args: &[String],
+) -> Result<CppAst, String> {
+    parse_translation_unit(path, None, include_paths, defines, extra_clang_args)
+}
+
+/// Parse C++ source held in memory, without touching disk; `path` names the
+/// file it stands for (quoted includes are resolved next to it)
+pub fn parse_cpp_source(
+    path: &Path,
+    contents: &str,
+    include_paths: &[std::path::PathBuf],
+    defines: &[String],
+) -> Result<CppAst, String> {
+    parse_translation_unit(path, Some(contents), include_paths, defines, &[])
+}
+
+fn parse_translation_unit(
+    path: &Path,
+    contents: Option<&str>,
+    include_paths: &[std::path::PathBuf],
+    defines: &[String],
+    extra_clang_args: &[String],
 ) -> Result<CppAst, String> {
     // Initialize Clang
     let clang = Clang::new().map_err(|e| format!("Failed to initialize Clang: {:?}", e))?;
@@ -162,10 +183,15 @@ pub fn parse_cpp_file_with_includes_defines_and_args(
         args.push(format!("-D{}", define));
     }
 
+    let unsaved: Vec<Unsaved> = contents
+        .map(|contents| Unsaved::new(path, contents))
+        .into_iter()
+        .collect();
     let parse_with_args = |parse_args: &[String]| {
         index
             .parser(path)
             .arguments(&parse_args.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+            .unsaved(&unsaved)
             .detailed_preprocessing_record(true)
             .skip_function_bodies(false) // We need function bodies for analysis
             .incomplete(true) // Allow incomplete translation units
@@ -361,6 +387,10 @@ fn visit_entity(entity: &Entity, ast: &mut CppAst, main_file: &Path) {
                 ast.classes.push(class);
             }
         }
+        EntityKind::UnionDecl if entity.is_definition() && entity.get_name().is_some() => {
+            // Named unions, so the union-access pass can tell their members apart
+            ast.classes.push(ast_visitor::extract_class(entity));
+        }
         EntityKind::CallExpr => {
             // Note: We don't need to handle template instantiations here.
             // Template functions are analyzed via their declarations (with generic types).
@@ -1,7 +1,5 @@
 use crate::debug_println;
 use clang::Entity;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 /// Helper function to check if a string starts with a safety annotation
@@ -31,6 +29,23 @@ fn contains_annotation(text: &str, annotation: &str) -> bool {
     }
 }
 
+/// `@checker: off`, which turns checking off for the whole file
+fn is_checker_off(text: &str) -> bool {
+    if !contains_annotation(text, "@checker") {
+        return false;
+    }
+    let setting = text["@checker".len()..].trim_start();
+    let Some(setting) = setting.strip_prefix(':') else {
+        return false;
+    };
+    let setting = setting.trim_start();
+    setting.starts_with("off")
+        && !setting[3..]
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_alphanumeric())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SafetyMode {
     Safe,   // Enforce borrow checking, can only call other @safe functions
@@ -94,6 +109,32 @@ pub struct SafetyContext {
     pub file_default: SafetyMode,
     pub function_overrides: Vec<(FunctionSignature, SafetyMode)>, // Function signature -> safety mode
     pub source_file: Option<String>, // The source file where annotations were parsed from
+    /// The file opted out of checking with `// @checker: off`
+    pub checker_off: bool,
+}
+
+/// `foo<int>` -> `foo`, `ns::Box<std::pair<int, int>>::get` -> `ns::Box::get`.
+/// The `<` of operator names (`operator<<`, `operator<=>`) is kept.
+fn strip_template_args(name: &str) -> String {
+    let mut stripped = String::with_capacity(name.len());
+    let mut depth = 0usize;
+    for ch in name.chars() {
+        match ch {
+            '<' if depth > 0 => depth += 1,
+            '<' if !stripped
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .starts_with("operator") =>
+            {
+                depth = 1
+            }
+            '>' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            _ => stripped.push(ch),
+        }
+    }
+    stripped
 }
 
 impl SafetyContext {
@@ -102,6 +143,7 @@ impl SafetyContext {
             file_default: SafetyMode::Unsafe,
             function_overrides: Vec::new(),
             source_file: None,
+            checker_off: false,
         }
     }
 
@@ -159,14 +201,28 @@ impl SafetyContext {
         }
     }
 
-    /// Get the safety mode of a specific function
+    /// Get the safety mode of a specific function. An instantiation like
+    /// `foo<int>` or `Box<T>::get` falls back to the template's annotation.
     pub fn get_function_safety(&self, func_name: &str) -> SafetyMode {
+        self.annotated_function_safety(func_name)
+            .or_else(|| {
+                let template_name = strip_template_args(func_name);
+                (template_name != func_name)
+                    .then(|| self.annotated_function_safety(&template_name))
+                    .flatten()
+            })
+            .unwrap_or(self.file_default)
+    }
+
+    /// The annotation that applies to `func_name` itself or an enclosing
+    /// scope, if any
+    fn annotated_function_safety(&self, func_name: &str) -> Option<SafetyMode> {
         let query = FunctionSignature::from_name_only(func_name.to_string());
 
         // First check for exact match with function-specific override
         for (sig, mode) in &self.function_overrides {
             if sig.matches(&query) {
-                return *mode;
+                return Some(*mode);
             }
 
             let sig_is_qualified = sig.name.contains("::");
@@ -188,7 +244,7 @@ impl SafetyContext {
                 if sig.name.ends_with(&format!("::{}", func_name))
                     || func_name.ends_with(&format!("::{}", sig.name))
                 {
-                    return *mode;
+                    return Some(*mode);
                 }
             }
             // Note: if !sig_is_qualified && func_is_qualified, we DON'T match
@@ -205,7 +261,7 @@ impl SafetyContext {
                 let scope_query = FunctionSignature::from_name_only(scope_name.clone());
                 for (sig, mode) in &self.function_overrides {
                     if sig.matches(&scope_query) {
-                        return *mode;
+                        return Some(*mode);
                     }
 
                     // Bug #8 fix: Careful suffix matching
@@ -218,15 +274,14 @@ impl SafetyContext {
                         if sig.name.ends_with(&format!("::{}", scope_name))
                             || scope_name.ends_with(&format!("::{}", sig.name))
                         {
-                            return *mode;
+                            return Some(*mode);
                         }
                     }
                 }
             }
         }
 
-        // Fall back to file default
-        self.file_default
+        None
     }
 
     /// Get the safety mode of a class, considering its source file location
@@ -306,10 +361,17 @@ impl SafetyContext {
 /// Parse safety annotations from a C++ file using the unified rule:
 /// @safe/@unsafe attaches to the next statement/block/function/namespace
 pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
-    let file =
-        File::open(path).map_err(|e| format!("Failed to open file for safety parsing: {}", e))?;
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to open file for safety parsing: {}", e))?;
+    parse_safety_annotations_from_source(&source, path)
+}
 
-    let reader = BufReader::new(file);
+/// Like [`parse_safety_annotations`], for source held in memory; `path` names
+/// the file it stands for
+pub fn parse_safety_annotations_from_source(
+    source: &str,
+    path: &Path,
+) -> Result<SafetyContext, String> {
     let mut context = SafetyContext::new();
 
     // Store the source file path for later reference
@@ -337,10 +399,15 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
     // `parse_inet4_addr` instead of `rrr::parse_inet4_addr`).
     let mut class_context_stack: Vec<(String, i32)> = Vec::new();
     let mut current_depth: i32 = 0;
-
-    for line_result in reader.lines() {
+    // Depths of `extern "C" { ... }` blocks, which hold declarations but
+    // don't qualify names
+    let mut linkage_depths: Vec<i32> = Vec::new();
+    // Whether the annotated code starts inside a function body, where
+    // `Widget w(x);` is a statement rather than a declaration
+    let mut accumulating_in_body = false;
+
+    for line in source.lines() {
         _current_line += 1;
-        let line = line_result.map_err(|e| format!("Failed to read line: {}", e))?;
         let trimmed = line.trim();
 
         // Handle multi-line comments
@@ -353,6 +420,7 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
             // requires `starts_with` of the literal — they don't actually collide
             // (different prefixes), but ordering keeps intent explicit.
             let cleaned = trimmed.trim_start_matches('*').trim();
+            context.checker_off |= is_checker_off(cleaned);
             if contains_annotation(cleaned, "@bridge") {
                 pending_annotation = Some(SafetyMode::Bridge);
             } else if contains_annotation(cleaned, "@safe") {
@@ -369,6 +437,7 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
             // Check if it's a single-line /* @safe */ / /* @unsafe */ / /* @bridge */ comment.
             if let Some(end_pos) = trimmed.find("*/") {
                 let comment_content = trimmed[2..end_pos].trim();
+                context.checker_off |= is_checker_off(comment_content);
                 if contains_annotation(comment_content, "@bridge") {
                     pending_annotation = Some(SafetyMode::Bridge);
                 } else if contains_annotation(comment_content, "@safe") {
@@ -385,6 +454,7 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
         if trimmed.starts_with("//") {
             // Only look for annotations that are word boundaries (not part of other text)
             let comment_text = trimmed[2..].trim();
+            context.checker_off |= is_checker_off(comment_text);
             if contains_annotation(comment_text, "@bridge") {
                 pending_annotation = Some(SafetyMode::Bridge);
             } else if contains_annotation(comment_text, "@safe") {
@@ -405,6 +475,16 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
         // comments perfectly but works for typical C++ code with annotations.
         let opens = trimmed.matches('{').count() as i32;
         let closes = trimmed.matches('}').count() as i32;
+        // Deeper than the innermost class, namespace or linkage block means
+        // inside a function body
+        let declaration_depth = class_context_stack
+            .last()
+            .map(|&(_, depth)| depth)
+            .into_iter()
+            .chain(linkage_depths.last().copied())
+            .max()
+            .unwrap_or(0);
+        let line_in_body = current_depth > declaration_depth;
         // Apply the line's net brace delta. Scopes that closed on this line
         // (their `push_depth > current_depth` after the update) are popped.
         current_depth += opens - closes;
@@ -418,6 +498,10 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
                 break;
             }
         }
+        linkage_depths.retain(|&depth| depth <= current_depth);
+        if trimmed.starts_with("extern \"") && opens > closes {
+            linkage_depths.push(current_depth);
+        }
 
         // Bug #8 fix: Track class declarations even without annotations.
         // This ensures method annotations get qualified with class name.
@@ -466,6 +550,7 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
         if pending_annotation.is_some() && !accumulating_for_annotation {
             accumulated_line.clear();
             accumulating_for_annotation = true;
+            accumulating_in_body = line_in_body;
         }
 
         // Only accumulate if we're looking for annotation target
@@ -493,7 +578,10 @@ pub fn parse_safety_annotations(path: &Path) -> Result<SafetyContext, String> {
             // Forward declarations (class Foo;) should consume the annotation without applying it
             // This prevents the annotation from carrying over to the next declaration
             let is_forward_decl = is_forward_declaration(&accumulated_line);
-            let is_function_decl = is_function_declaration(&accumulated_line);
+            // A function body holds no function declarations, only
+            // statements such as `Widget w(x);`
+            let is_function_decl = is_function_declaration(&accumulated_line)
+                && !(accumulating_in_body && accumulated_line.trim_end().ends_with(';'));
 
             if is_forward_decl && pending_annotation.is_some() {
                 // Forward declarations should NOT have annotations (they have no body)
@@ -877,7 +965,73 @@ fn is_function_declaration(line: &str) -> bool {
         starts_with_template_param || (has_template_syntax && has_parens)
     };
 
-    has_parens && (has_type || line.contains("::") || is_template_function)
+    has_parens
+        && (has_type
+            || line.contains("::")
+            || is_template_function
+            || has_return_type_and_name(line))
+}
+
+/// `Widget make(...)` / `char* raw(...)`: a return type followed by the
+/// function name, where neither is a statement keyword
+fn has_return_type_and_name(line: &str) -> bool {
+    let Some(paren_pos) = line.find('(') else {
+        return false;
+    };
+    let tokens: Vec<&str> = line[..paren_pos].split_whitespace().collect();
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return false;
+    };
+    let name = last.trim_start_matches(['*', '&']);
+    let is_identifier = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '~');
+    let is_statement = |token: &str| {
+        matches!(
+            token,
+            "if" | "else"
+                | "for"
+                | "while"
+                | "switch"
+                | "return"
+                | "throw"
+                | "case"
+                | "new"
+                | "delete"
+                | "sizeof"
+                | "co_return"
+                | "co_yield"
+                | "co_await"
+        )
+    };
+    // `Widget w(x, y)` constructs `w`: declared parameters have a type, so
+    // a list of bare names is a call
+    let arguments = line[paren_pos + 1..].split(')').next().unwrap_or_default();
+    let is_builtin_type = |token: &str| {
+        matches!(
+            token,
+            "void"
+                | "bool"
+                | "char"
+                | "short"
+                | "int"
+                | "long"
+                | "float"
+                | "double"
+                | "signed"
+                | "unsigned"
+                | "auto"
+        )
+    };
+    let bare_names = !arguments.trim().is_empty()
+        && arguments.split(',').all(|argument| {
+            let argument = argument.trim();
+            !argument.is_empty()
+                && argument.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !is_builtin_type(argument)
+        });
+    tokens.len() >= 2 && is_identifier && !is_statement(first) && !is_statement(name) && !bare_names
 }
 
 /// Extract function name from a declaration line (including qualified names)
@@ -1107,6 +1261,17 @@ pub fn parse_class_annotation(entity: &Entity) -> Option<ClassAnnotation> {
 /// Check if a class is marked as @interface by reading source file comments
 /// This is needed when libclang's get_comment() doesn't capture the annotation
 pub fn check_class_interface_annotation(entity: &Entity) -> bool {
+    check_class_annotation(entity, "@interface")
+}
+
+/// Check if a class is marked as @move_only: copying it is treated as a move,
+/// even if the parser sees a usable copy constructor
+pub fn check_class_move_only_annotation(entity: &Entity) -> bool {
+    check_class_annotation(entity, "@move_only")
+}
+
+/// Check a class's comment, or the line above it, for a flag annotation
+fn check_class_annotation(entity: &Entity, annotation: &str) -> bool {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
@@ -1125,7 +1290,7 @@ pub fn check_class_interface_annotation(entity: &Entity) -> bool {
             } else {
                 trimmed
             };
-            if contains_annotation(content, "@interface") {
+            if contains_annotation(content, annotation) {
                 return true;
             }
         }
@@ -1163,11 +1328,11 @@ pub fn check_class_interface_annotation(entity: &Entity) -> bool {
         };
 
         if current_line == entity_line {
-            // Check if previous line has @interface
+            // Check if previous line has the annotation
             let trimmed = prev_line.trim();
             if trimmed.starts_with("//") {
                 let content = trimmed[2..].trim();
-                if contains_annotation(content, "@interface") {
+                if contains_annotation(content, annotation) {
                     return true;
                 }
             }
@@ -1349,6 +1514,45 @@ public:
         );
     }
 
+    #[test]
+    fn test_class_annotation_sets_method_defaults() {
+        let code = r#"
+// @safe
+class Buffer {
+public:
+    void fill(int value) {
+        int x = value;
+    }
+
+    // @unsafe
+    char* raw() {
+        return nullptr;
+    }
+};
+
+void outside() {}
+"#;
+
+        let mut file = NamedTempFile::with_suffix(".cpp").unwrap();
+        file.write_all(code.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let context = parse_safety_annotations(file.path()).unwrap();
+        assert!(
+            context.should_check_function("Buffer::fill"),
+            "unannotated methods of a @safe class default to safe"
+        );
+        assert_eq!(
+            context.get_function_safety("Buffer::raw"),
+            SafetyMode::Unsafe,
+            "a method-level @unsafe overrides the class default"
+        );
+        assert!(
+            !context.should_check_function("outside"),
+            "the class default does not leak to free functions"
+        );
+    }
+
     #[test]
     fn test_function_safe_annotation() {
         let code = r#"
@@ -1375,6 +1579,27 @@ void explicit_unsafe() {}
         assert!(!context.should_check_function("explicit_unsafe"));
     }
 
+    #[test]
+    fn test_template_instantiations_use_template_annotation() {
+        let mut context = SafetyContext::new();
+        for (name, mode) in [("process", SafetyMode::Safe), ("ns::Box", SafetyMode::Safe)] {
+            context
+                .function_overrides
+                .push((FunctionSignature::from_name_only(name.to_string()), mode));
+        }
+
+        assert!(context.should_check_function("process<int>"));
+        assert!(context.should_check_function("process<std::vector<int>>"));
+        assert!(context.should_check_function("ns::Box<std::pair<int, int>>::get"));
+        assert!(!context.should_check_function("other<int>"));
+
+        assert_eq!(strip_template_args("operator<<"), "operator<<");
+        assert_eq!(
+            strip_template_args("Box<int>::operator<=>"),
+            "Box::operator<=>"
+        );
+    }
+
     #[test]
     fn test_first_code_element_annotation() {
         let code = r#"
@@ -1723,4 +1948,66 @@ public:
              annotation"
         );
     }
+
+    #[test]
+    fn test_annotated_constructor_statement_is_not_a_function() {
+        let code = r#"
+class Widget;
+
+// @safe
+void run(int x, int y) {
+    // @unsafe
+    Widget w(x);
+    // @safe
+    Widget v(x, y);
+}
+
+extern "C" {
+// @unsafe
+Widget make(int size);
+}
+"#;
+
+        let mut file = NamedTempFile::with_suffix(".cpp").unwrap();
+        file.write_all(code.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let context = parse_safety_annotations(file.path()).unwrap();
+        let annotated: Vec<&str> = context
+            .function_overrides
+            .iter()
+            .map(|(signature, _)| signature.name.as_str())
+            .collect();
+        assert_eq!(annotated, vec!["run", "make"]);
+        assert!(!has_return_type_and_name("Widget w(x, y);"));
+        assert!(has_return_type_and_name("Widget make(int size)"));
+    }
+
+    #[test]
+    fn test_checker_off_comment() {
+        let code = r#"
+/* Generated by protoc. */
+// @checker: off
+
+// @safe
+void generated() {}
+"#;
+        let context =
+            parse_safety_annotations_from_source(code, Path::new("generated.pb.cc")).unwrap();
+        assert!(context.checker_off);
+
+        // Only the exact setting counts
+        for code in [
+            "// @checker: on\n",
+            "// @checkers: off\n",
+            "// @checker: offline\n",
+        ] {
+            let context = parse_safety_annotations_from_source(code, Path::new("a.cpp")).unwrap();
+            assert!(
+                !context.checker_off,
+                "{:?} should not turn checking off",
+                code
+            );
+        }
+    }
 }
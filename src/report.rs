@@ -0,0 +1,789 @@
+//! Rendering of the final violation report
+//!
+//! The terminal always gets the human-readable listing as files are
+//! analyzed. `--format` selects how the complete report is rendered once
+//! analysis finishes, and `--output` sends that rendering to a file
+//! instead of stdout. `--print-schema` prints the JSON Schema of the
+//! `--format json` report. `--format junit` renders JUnit XML for CI
+//! systems that collect test results: each checked function is a test case
+//! and each of its errors a failure. Warnings go to the test case's output
+//! unless `--deny-warnings` makes them failures too.
+
+use crate::analysis::{BorrowCheckError, ErrorKind, Severity, Suggestion};
+use crate::check::FunctionReport;
+use crate::parser::SourceLocation;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Junit,
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "junit" => Ok(OutputFormat::Junit),
+            _ => Err(format!(
+                "Unknown output format '{}' (expected text, json or junit)",
+                name
+            )),
+        }
+    }
+}
+
+/// Which violations are reported (`--allow`, `--changed-lines`) and which
+/// of them fail the run (`--deny-warnings`)
+#[derive(Debug, Default)]
+pub struct Policy {
+    allowed: HashSet<ErrorKind>,
+    deny_warnings: bool,
+    changed_lines: Option<ChangedLines>,
+}
+
+impl Policy {
+    pub fn from_flags(allow: &[String], deny_warnings: bool) -> Result<Self, String> {
+        let allowed = allow
+            .iter()
+            .map(|name| {
+                ErrorKind::from_name(name).ok_or_else(|| {
+                    let names: Vec<&str> = ErrorKind::ALL.iter().map(|kind| kind.name()).collect();
+                    format!(
+                        "Unknown violation kind '{}' (expected one of: {})",
+                        name,
+                        names.join(", ")
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Policy {
+            allowed,
+            deny_warnings,
+            changed_lines: None,
+        })
+    }
+
+    /// Only report violations on the `--changed-lines` ranges
+    pub fn with_changed_lines(mut self, specs: &[String]) -> Result<Self, String> {
+        if !specs.is_empty() {
+            self.changed_lines = Some(ChangedLines::parse(specs)?);
+        }
+        Ok(self)
+    }
+
+    /// Drop violations of every `--allow`ed kind, and those of `file` that
+    /// are not on a changed line
+    pub fn filter(&self, file: &Path, violations: Vec<String>) -> Vec<String> {
+        violations
+            .into_iter()
+            .filter(|violation| !self.allowed.contains(&ErrorKind::from_message(violation)))
+            .filter(|violation| {
+                self.changed_lines
+                    .as_ref()
+                    .is_none_or(|changed| changed.contains(file, violation))
+            })
+            .collect()
+    }
+
+    /// Whether `--allow` or `--changed-lines` can drop violations
+    pub fn drops_violations(&self) -> bool {
+        !self.allowed.is_empty() || self.changed_lines.is_some()
+    }
+
+    /// Whether the reported violations give a non-zero exit code
+    pub fn fails(&self, violations: &[String]) -> bool {
+        violations.iter().any(|violation| {
+            self.deny_warnings || Severity::from_message(violation) == Severity::Error
+        })
+    }
+}
+
+/// `--changed-lines FILE:START-END,...`: the line ranges of each file a
+/// change touched. The whole file is still analyzed for context, but only
+/// violations whose line falls in a range are reported, so CI can gate new
+/// code without first fixing everything already there. Violations that name
+/// no line (many use-after-move errors) cannot be placed, so they are kept
+/// for any file with a changed range.
+#[derive(Debug)]
+pub struct ChangedLines {
+    ranges: Vec<(PathBuf, u32, u32)>,
+}
+
+impl ChangedLines {
+    /// Parse `FILE:START-END` or `FILE:LINE` entries
+    pub fn parse(specs: &[String]) -> Result<Self, String> {
+        let ranges = specs
+            .iter()
+            .map(|spec| {
+                let invalid = || {
+                    format!(
+                        "Invalid changed-lines range '{}' (expected FILE:START-END or FILE:LINE)",
+                        spec
+                    )
+                };
+                let (file, lines) = spec.rsplit_once(':').ok_or_else(invalid)?;
+                let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+                let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+                let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+                if file.is_empty() || start == 0 || end < start {
+                    return Err(invalid());
+                }
+                Ok((PathBuf::from(file), start, end))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ChangedLines { ranges })
+    }
+
+    /// Whether `violation`, found while analyzing `file`, is on a changed line
+    pub fn contains(&self, file: &Path, violation: &str) -> bool {
+        let line = BorrowCheckError::from_message(violation)
+            .location
+            .parse::<u32>()
+            .ok();
+        self.ranges.iter().any(|(path, start, end)| {
+            same_file(file, path) && line.is_none_or(|line| (*start..=*end).contains(&line))
+        })
+    }
+}
+
+/// `range_path` as given on the command line (often relative to the
+/// repository root) names `file` as the checker was given it
+fn same_file(file: &Path, range_path: &Path) -> bool {
+    if file == range_path || file.ends_with(range_path) {
+        return true;
+    }
+    match (fs::canonicalize(file), fs::canonicalize(range_path)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Violations found in one translation unit
+#[derive(Debug)]
+pub struct FileReport {
+    pub file: PathBuf,
+    pub violations: Vec<String>,
+    /// The checked functions, with the violations each owns
+    pub functions: Vec<FunctionReport>,
+    /// The source range of each violation that has one, by message
+    pub spans: HashMap<String, SourceLocation>,
+}
+
+/// Render the whole report in `format`. `deny_warnings` makes warnings
+/// JUnit failures like errors.
+pub fn render(
+    format: OutputFormat,
+    files: &[FileReport],
+    with_summary: bool,
+    deny_warnings: bool,
+) -> String {
+    match format {
+        OutputFormat::Text => render_text(files, with_summary),
+        OutputFormat::Json => render_json(files),
+        OutputFormat::Junit => render_junit(files, deny_warnings),
+    }
+}
+
+/// The terminal listing without colors
+fn render_text(files: &[FileReport], with_summary: bool) -> String {
+    let mut out = String::new();
+    let mut all: Vec<String> = Vec::new();
+    let mut files_affected = 0;
+    for report in files.iter().filter(|r| !r.violations.is_empty()) {
+        out.push_str(&format!(
+            "✗ Found {} violation(s) in {}:\n",
+            report.violations.len(),
+            report.file.display()
+        ));
+        for violation in &report.violations {
+            out.push_str(violation);
+            out.push('\n');
+        }
+        files_affected += 1;
+        all.extend(report.violations.iter().cloned());
+    }
+
+    if all.is_empty() {
+        out.push_str("✓ rusty-cpp: no violations found!\n");
+    } else if with_summary {
+        out.push_str(&crate::format_violation_summary(&all, files_affected));
+        out.push('\n');
+    }
+    out
+}
+
+/// `{"violations": [{"file", "kind", "severity", "message"}], "summary": {...}}`
+fn render_json(files: &[FileReport]) -> String {
+    let mut violations = Vec::new();
+    let mut by_kind: std::collections::BTreeMap<&'static str, usize> =
+        std::collections::BTreeMap::new();
+    for report in files {
+        for message in &report.violations {
+            let kind = ErrorKind::from_message(message).name();
+            *by_kind.entry(kind).or_insert(0) += 1;
+            violations.push(violation_json(
+                &report.file,
+                message,
+                report.spans.get(message),
+            ));
+        }
+    }
+
+    let report = serde_json::json!({
+        "summary": {
+            "total": violations.len(),
+            "files_analyzed": files.len(),
+            "files_affected": files.iter().filter(|r| !r.violations.is_empty()).count(),
+            "by_kind": by_kind,
+        },
+        "violations": violations,
+    });
+    let mut out = serde_json::to_string_pretty(&report).expect("report is valid JSON");
+    out.push('\n');
+    out
+}
+
+/// One entry of the report's `violations` array
+pub fn violation_json(
+    file: &Path,
+    message: &str,
+    span: Option<&SourceLocation>,
+) -> serde_json::Value {
+    let mut violation = serde_json::json!({
+        "file": file.display().to_string(),
+        "kind": ErrorKind::from_message(message).name(),
+        "severity": Severity::from_message(message).name(),
+        "message": message,
+    });
+    if let Some(suggestion) = Suggestion::from_message(message) {
+        violation["suggestion"] = serde_json::json!({
+            "text": suggestion.text,
+            "file": suggestion.file,
+            "line": suggestion.line,
+        });
+    }
+    if let Some(span) = span {
+        violation["span"] = serde_json::json!({
+            "file": span.file,
+            "line": span.line,
+            "column": span.column,
+            "end_line": span.end_line,
+            "end_column": span.end_column,
+        });
+    }
+    violation
+}
+
+/// `<testsuite>` with a `<testcase>` per checked function and a `<failure>`
+/// per reported error. Warnings are listed in the test case's `<system-out>`
+/// without failing it, unless `deny_warnings`. Violations no function owns
+/// (class-level checks) go to a test case named after the file.
+fn render_junit(files: &[FileReport], deny_warnings: bool) -> String {
+    let mut cases = Vec::new();
+    for report in files {
+        let file = report.file.display().to_string();
+        // Only what survived `--allow` / `--changed-lines` is reported; each
+        // reported violation is taken once, by the function recorded as its
+        // owner, so a message repeated in two functions fails each once
+        let mut unowned: Vec<&String> = report.violations.iter().collect();
+        for function in &report.functions {
+            let violations: Vec<&String> = function
+                .violations
+                .iter()
+                .filter_map(|owned| {
+                    let index = unowned.iter().position(|reported| *reported == owned)?;
+                    Some(unowned.remove(index))
+                })
+                .collect();
+            cases.push((file.clone(), function.name.clone(), violations));
+        }
+        if !unowned.is_empty() {
+            cases.push((file.clone(), file.clone(), unowned));
+        }
+    }
+
+    let fails =
+        |message: &&String| deny_warnings || Severity::from_message(message) == Severity::Error;
+    let failed = cases
+        .iter()
+        .filter(|(_, _, violations)| violations.iter().any(fails))
+        .count();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuite name=\"rusty-cpp\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+        cases.len(),
+        failed
+    ));
+    for (file, name, violations) in &cases {
+        let open = format!(
+            "  <testcase classname=\"{}\" name=\"{}\"",
+            xml_escape(file),
+            xml_escape(name)
+        );
+        if violations.is_empty() {
+            out.push_str(&format!("{}/>\n", open));
+            continue;
+        }
+        out.push_str(&format!("{}>\n", open));
+        let (failures, warnings): (Vec<&String>, Vec<&String>) =
+            violations.iter().copied().partition(fails);
+        let located = |message: &str| {
+            let violation = BorrowCheckError::from_message(message);
+            let location = if violation.location.is_empty() {
+                file.clone()
+            } else {
+                format!("{}:{}", file, violation.location)
+            };
+            (violation, location)
+        };
+        for message in failures {
+            let (violation, location) = located(message);
+            out.push_str(&format!(
+                "    <failure type=\"{}\" message=\"{}\">{}: {}</failure>\n",
+                violation.kind.name(),
+                xml_escape(&violation.message),
+                xml_escape(&location),
+                xml_escape(message)
+            ));
+        }
+        if !warnings.is_empty() {
+            out.push_str("    <system-out>");
+            for message in warnings {
+                let (_, location) = located(message);
+                out.push_str(&format!(
+                    "{}: {}\n",
+                    xml_escape(&location),
+                    xml_escape(message)
+                ));
+            }
+            out.push_str("</system-out>\n");
+        }
+        out.push_str("  </testcase>\n");
+    }
+    out.push_str("</testsuite>\n");
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// JSON Schema (draft 2020-12) of the report `--format json` renders. The
+/// `kind` and `severity` enums come from [`ErrorKind::ALL`] and
+/// [`Severity::ALL`], so new kinds show up here without further edits.
+pub fn json_schema() -> String {
+    let kinds: Vec<&str> = ErrorKind::ALL.iter().map(|kind| kind.name()).collect();
+    let severities: Vec<&str> = Severity::ALL
+        .iter()
+        .map(|severity| severity.name())
+        .collect();
+    let count = serde_json::json!({ "type": "integer", "minimum": 0 });
+
+    let schema = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "rusty-cpp-checker report",
+        "type": "object",
+        "required": ["summary", "violations"],
+        "additionalProperties": false,
+        "properties": {
+            "summary": {
+                "type": "object",
+                "required": ["total", "files_analyzed", "files_affected", "by_kind"],
+                "additionalProperties": false,
+                "properties": {
+                    "total": count,
+                    "files_analyzed": count,
+                    "files_affected": count,
+                    "by_kind": {
+                        "type": "object",
+                        "propertyNames": { "enum": kinds },
+                        "additionalProperties": count,
+                    },
+                },
+            },
+            "violations": {
+                "type": "array",
+                "items": { "$ref": "#/$defs/violation" },
+            },
+        },
+        "$defs": {
+            "violation": {
+                "type": "object",
+                "required": ["file", "kind", "severity", "message"],
+                "additionalProperties": false,
+                "properties": {
+                    "file": {
+                        "type": "string",
+                        "description": "Translation unit the violation was found in",
+                    },
+                    "kind": { "enum": kinds },
+                    "severity": { "enum": severities },
+                    "message": {
+                        "type": "string",
+                        "description": "Human-readable diagnostic, as printed in text mode",
+                    },
+                    "suggestion": {
+                        "type": "object",
+                        "description": "Fix-it: insert `text` as a new line above `line` of `file`",
+                        "required": ["text", "file", "line"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "text": { "type": "string" },
+                            "file": { "type": "string" },
+                            "line": { "type": "integer", "minimum": 1 },
+                        },
+                    },
+                    "span": {
+                        "type": "object",
+                        "description": "Source range the violation points at; `end_column` is one past its last character",
+                        "required": ["file", "line", "column", "end_line", "end_column"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "file": { "type": "string" },
+                            "line": { "type": "integer", "minimum": 1 },
+                            "column": { "type": "integer", "minimum": 1 },
+                            "end_line": { "type": "integer", "minimum": 1 },
+                            "end_column": { "type": "integer", "minimum": 1 },
+                        },
+                    },
+                },
+            },
+        },
+    });
+    let mut out = serde_json::to_string_pretty(&schema).expect("schema is valid JSON");
+    out.push('\n');
+    out
+}
+
+/// Write `contents` to `path` via a temporary file in the same directory and
+/// a rename, so a CI job never picks up a half-written artifact
+pub fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid output path '{}'", path.display()))?;
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    fs::write(&tmp_path, contents)
+        .map_err(|e| format!("Failed to write '{}': {}", tmp_path.display(), e))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write '{}': {}", path.display(), e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<FileReport> {
+        vec![
+            FileReport {
+                file: PathBuf::from("a.cpp"),
+                violations: vec!["Use after move: variable 'x' has been moved".to_string()],
+                functions: vec![],
+                spans: HashMap::new(),
+            },
+            FileReport {
+                file: PathBuf::from("b.cpp"),
+                violations: vec![],
+                functions: vec![],
+                spans: HashMap::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json_report_shape() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &sample(), true, false)).unwrap();
+        assert_eq!(json["summary"]["total"], 1);
+        assert_eq!(json["summary"]["files_analyzed"], 2);
+        assert_eq!(json["summary"]["files_affected"], 1);
+        assert_eq!(json["violations"][0]["file"], "a.cpp");
+        assert_eq!(json["violations"][0]["kind"], "use-after-move");
+        assert_eq!(json["violations"][0]["severity"], "error");
+        assert!(json["violations"][0].get("suggestion").is_none());
+    }
+
+    #[test]
+    fn test_json_report_suggestion() {
+        let files = vec![FileReport {
+            file: PathBuf::from("a.cpp"),
+            violations: vec![
+                "Safe function 'get' returns a reference but has no @lifetime annotation; add `// @lifetime: (&'a) -> &'a` with parameter 'x' (insert above line 3 of a.cpp)".to_string(),
+            ],
+            functions: vec![],
+            spans: HashMap::new(),
+        }];
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &files, true, false)).unwrap();
+        let suggestion = &json["violations"][0]["suggestion"];
+        assert_eq!(suggestion["text"], "// @lifetime: (&'a) -> &'a");
+        assert_eq!(suggestion["file"], "a.cpp");
+        assert_eq!(suggestion["line"], 3);
+    }
+
+    #[test]
+    fn test_json_report_span() {
+        let message =
+            "In function 'f': warning: std::move of const 's' at line 7 copies instead of moving";
+        let span = SourceLocation {
+            file: "/tmp/a.cpp".to_string(),
+            line: 7,
+            column: 5,
+            end_line: 7,
+            end_column: 17,
+        };
+        let files = vec![FileReport {
+            file: PathBuf::from("a.cpp"),
+            violations: vec![
+                message.to_string(),
+                "Use after move: variable 'x'".to_string(),
+            ],
+            functions: vec![],
+            spans: HashMap::from([(message.to_string(), span)]),
+        }];
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &files, true, false)).unwrap();
+        let span = &json["violations"][0]["span"];
+        assert_eq!(span["file"], "/tmp/a.cpp");
+        assert_eq!(span["line"], 7);
+        assert_eq!(span["column"], 5);
+        assert_eq!(span["end_line"], 7);
+        assert_eq!(span["end_column"], 17);
+        assert_eq!(json["violations"][0]["message"], message);
+        assert!(json["violations"][0].get("suggestion").is_none());
+        assert!(json["violations"][1].get("span").is_none());
+    }
+
+    #[test]
+    fn test_junit_report_failures_per_function() {
+        let moved = "Use after move: variable 'x' has been moved at line 4".to_string();
+        let pointer = "In function 'g': Unsafe pointer dereference at line 9".to_string();
+        let class_level = "Class 'Widget' has a mutable field 'cache'".to_string();
+        let files = vec![FileReport {
+            file: PathBuf::from("a.cpp"),
+            violations: vec![moved.clone(), pointer.clone(), class_level],
+            functions: vec![
+                FunctionReport {
+                    name: "f".to_string(),
+                    violations: vec![moved],
+                },
+                FunctionReport {
+                    name: "g".to_string(),
+                    violations: vec![pointer],
+                },
+                FunctionReport {
+                    name: "clean<int>".to_string(),
+                    violations: vec![],
+                },
+            ],
+            spans: HashMap::new(),
+        }];
+        let xml = render(OutputFormat::Junit, &files, true, false);
+
+        let suite = regex::Regex::new(r#"<testsuite [^>]*tests="(\d+)" failures="(\d+)""#).unwrap();
+        let counts = suite.captures(&xml).expect("a testsuite element");
+        assert_eq!(&counts[1], "4", "{}", xml);
+        assert_eq!(&counts[2], "3", "{}", xml);
+
+        let case = regex::Regex::new(
+            r#"(?s)<testcase classname="([^"]*)" name="([^"]*)"(/>|>(.*?)</testcase>)"#,
+        )
+        .unwrap();
+        let failures: Vec<(String, usize)> = case
+            .captures_iter(&xml)
+            .map(|case| {
+                let body = case.get(4).map_or("", |body| body.as_str());
+                (case[2].to_string(), body.matches("<failure ").count())
+            })
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                ("f".to_string(), 1),
+                ("g".to_string(), 1),
+                ("clean&lt;int&gt;".to_string(), 0),
+                ("a.cpp".to_string(), 1),
+            ]
+        );
+        assert!(xml.contains(r#"<failure type="use-after-move" message="Use after move: variable &apos;x&apos; has been moved at line 4">a.cpp:4: "#));
+    }
+
+    #[test]
+    fn test_junit_repeated_message_fails_each_owner_once() {
+        // Two overloads of 'f' each report the same message
+        let moved = "In function 'f': Use after move: variable 'x' has been moved".to_string();
+        let owned = vec![moved.clone()];
+        let files = vec![FileReport {
+            file: PathBuf::from("a.cpp"),
+            violations: vec![moved.clone(), moved],
+            functions: vec![
+                FunctionReport {
+                    name: "f".to_string(),
+                    violations: owned.clone(),
+                },
+                FunctionReport {
+                    name: "f".to_string(),
+                    violations: owned,
+                },
+            ],
+            spans: HashMap::new(),
+        }];
+        let xml = render(OutputFormat::Junit, &files, true, false);
+        assert_eq!(xml.matches("<failure ").count(), 2, "{}", xml);
+        assert!(xml.contains(r#"tests="2" failures="2""#), "{}", xml);
+    }
+
+    #[test]
+    fn test_junit_warnings_fail_only_when_denied() {
+        let warning = "In function 'f': warning: std::move of const 'v' copies".to_string();
+        let files = vec![FileReport {
+            file: PathBuf::from("a.cpp"),
+            violations: vec![warning.clone()],
+            functions: vec![FunctionReport {
+                name: "f".to_string(),
+                violations: vec![warning],
+            }],
+            spans: HashMap::new(),
+        }];
+
+        let xml = render(OutputFormat::Junit, &files, true, false);
+        assert!(xml.contains(r#"tests="1" failures="0""#), "{}", xml);
+        assert!(!xml.contains("<failure "), "{}", xml);
+        assert!(
+            xml.contains("<system-out>a.cpp: In function &apos;f&apos;: warning:"),
+            "{}",
+            xml
+        );
+
+        let xml = render(OutputFormat::Junit, &files, true, true);
+        assert!(xml.contains(r#"tests="1" failures="1""#), "{}", xml);
+        assert_eq!(xml.matches("<failure ").count(), 1, "{}", xml);
+        assert!(!xml.contains("<system-out>"), "{}", xml);
+    }
+
+    #[test]
+    fn test_policy_changed_lines() {
+        let violations = vec![
+            "In function 'old': Use after move: variable 'x' has been moved at line 4".to_string(),
+            "In function 'new': Use after move: variable 'y' has been moved at line 12".to_string(),
+            "Use after move: variable 'z' has been moved".to_string(),
+        ];
+        let policy = Policy::default()
+            .with_changed_lines(&["src/a.cpp:10-15".to_string()])
+            .unwrap();
+
+        // The line-less violation may be on a changed line, so it is kept
+        let reported = policy.filter(Path::new("/repo/src/a.cpp"), violations.clone());
+        assert_eq!(reported, vec![violations[1].clone(), violations[2].clone()]);
+        assert!(
+            policy
+                .filter(Path::new("/repo/src/b.cpp"), violations)
+                .is_empty()
+        );
+
+        for spec in ["a.cpp", "a.cpp:x", "a.cpp:9-3", ":4"] {
+            assert!(
+                ChangedLines::parse(&[spec.to_string()]).is_err(),
+                "{}",
+                spec
+            );
+        }
+        assert!(ChangedLines::parse(&["a.cpp:7".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn test_policy_allow_and_deny_warnings() {
+        let violations = vec![
+            "Use after move: variable 'x' has been moved".to_string(),
+            "In function 'f': warning: std::move of const 'v' copies".to_string(),
+        ];
+
+        let policy = Policy::from_flags(&["use-after-move".to_string()], false).unwrap();
+        let reported = policy.filter(Path::new("a.cpp"), violations.clone());
+        assert_eq!(reported.len(), 1);
+        assert!(!policy.fails(&reported), "warnings alone must not fail");
+
+        let strict = Policy::from_flags(&["use-after-move".to_string()], true).unwrap();
+        assert!(strict.fails(&strict.filter(Path::new("a.cpp"), violations.clone())));
+
+        assert!(Policy::default().fails(&violations));
+        assert!(
+            Policy::from_flags(&["no-such-kind".to_string()], false)
+                .unwrap_err()
+                .contains("Unknown violation kind 'no-such-kind'")
+        );
+    }
+
+    #[test]
+    fn test_json_schema_matches_report() {
+        let schema: serde_json::Value = serde_json::from_str(&json_schema()).unwrap();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+
+        let violation = &schema["$defs"]["violation"];
+        let kinds = violation["properties"]["kind"]["enum"].as_array().unwrap();
+        for kind in ErrorKind::ALL {
+            assert!(
+                kinds.contains(&kind.name().into()),
+                "missing {}",
+                kind.name()
+            );
+        }
+        assert_eq!(kinds.len(), ErrorKind::ALL.len());
+
+        // Every key the renderer emits is declared, and every required key
+        // is emitted
+        let report: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &sample(), true, false)).unwrap();
+        let keys = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let required = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|key| key.as_str().unwrap().to_string())
+                .collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(&report), required(&schema));
+        assert_eq!(
+            keys(&report["summary"]),
+            required(&schema["properties"]["summary"])
+        );
+        assert_eq!(keys(&report["violations"][0]), required(violation));
+    }
+
+    #[test]
+    fn test_write_atomically_replaces_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("report.json");
+        fs::write(&path, "old").unwrap();
+
+        write_atomically(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,73 @@
+//! Per-phase durations for `--timings`
+//!
+//! Phases are accumulated by name across every file analyzed, so a
+//! `--project` run reports totals. Nothing is measured unless enabled.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `(phase, total time, number of runs)` in first-run order
+static TOTALS: Mutex<Vec<(&'static str, Duration, usize)>> = Mutex::new(Vec::new());
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Run `f`, adding its duration to `phase` when timings are enabled
+pub fn time<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    record(phase, start.elapsed());
+    result
+}
+
+fn record(phase: &'static str, elapsed: Duration) {
+    let mut totals = TOTALS.lock().unwrap_or_else(|e| e.into_inner());
+    match totals.iter_mut().find(|(name, _, _)| *name == phase) {
+        Some((_, total, runs)) => {
+            *total += elapsed;
+            *runs += 1;
+        }
+        None => totals.push((phase, elapsed, 1)),
+    }
+}
+
+/// Table printed to stderr at the end of a `--timings` run
+pub fn report() -> String {
+    let totals = TOTALS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::from("Timings:\n");
+    for (phase, total, runs) in totals.iter() {
+        out.push_str(&format!(
+            "  {:<24} {:>10.3} ms  ({} run{})\n",
+            phase,
+            total.as_secs_f64() * 1000.0,
+            runs,
+            if *runs == 1 { "" } else { "s" }
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_accumulate() {
+        set_enabled(true);
+        assert_eq!(time("test-phase", || 1 + 1), 2);
+        time("test-phase", || ());
+        let report = report();
+        let line = report
+            .lines()
+            .find(|line| line.trim_start().starts_with("test-phase"))
+            .expect("phase line");
+        assert!(line.ends_with("(2 runs)"), "{}", line);
+    }
+}
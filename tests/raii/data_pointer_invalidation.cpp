@@ -0,0 +1,23 @@
+// Test: pointers from vector::data() dangle after a reallocation
+//
+// push_back may move the elements to a new buffer, leaving `d` pointing
+// into the freed one.
+
+#include <vector>
+
+void use(const int* p);
+void use_value(int v);
+
+// @safe
+void bad_data_after_push_back(std::vector<int>& v, int x) {
+    const int* d = v.data();
+    v.push_back(x);
+    use(d);  // ERROR: d was invalidated by push_back()
+}
+
+// @safe
+void good_copy_survives_push_back(std::vector<int>& v, int x) {
+    int first = v.front();
+    v.push_back(x);
+    use_value(first);  // OK: first is a copy
+}
@@ -0,0 +1,25 @@
+// Test: gsl::owner<T*> is an owning pointer
+//
+// Deleting an owner twice frees the same allocation twice.
+
+namespace gsl {
+template <class T>
+using owner = T;
+}
+
+// @safe
+void bad_owner_double_delete(gsl::owner<int*> p) {
+    // @unsafe
+    {
+        delete p;
+        delete p;  // ERROR: double free
+    }
+}
+
+// @safe
+void good_owner_single_delete(gsl::owner<int*> q) {
+    // @unsafe
+    {
+        delete q;  // OK
+    }
+}
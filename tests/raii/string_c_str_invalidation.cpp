@@ -0,0 +1,23 @@
+// Test: pointers from std::string::c_str() dangle after the string grows
+//
+// Appending may move the characters to a new buffer, leaving `c` pointing
+// into the freed one.
+
+#include <string>
+
+void use(const char* p);
+void use_index(unsigned long i);
+
+// @safe
+void bad_c_str_after_append(std::string& s) {
+    const char* c = s.c_str();
+    s += "x";
+    use(c);  // ERROR: c was invalidated by operator+=()
+}
+
+// @safe
+void good_index_survives_append(std::string& s) {
+    unsigned long pos = s.find('x');
+    s.append("y");
+    use_index(pos);  // OK: pos is an index, not an iterator
+}
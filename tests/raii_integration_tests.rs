@@ -71,6 +71,20 @@ fn test_iterator_basic_detection() {
     assert!(output.contains("Analyzing:") || output.contains("violation"));
 }
 
+#[test]
+fn test_data_pointer_invalidated_by_push_back() {
+    let (_, output) = run_checker("data_pointer_invalidation.cpp");
+    assert_contains_error(&output, "invalidated element reference 'd'");
+    assert_no_error(&output, "'first'");
+}
+
+#[test]
+fn test_c_str_pointer_invalidated_by_append() {
+    let (_, output) = run_checker("string_c_str_invalidation.cpp");
+    assert_contains_error(&output, "invalidated element reference 'c'");
+    assert_no_error(&output, "'pos'");
+}
+
 // =============================================================================
 // Phase 5: User-Defined RAII Types
 // =============================================================================
@@ -93,6 +107,13 @@ fn test_double_free_detection() {
     assert!(output.contains("Analyzing:") || output.contains("violation"));
 }
 
+#[test]
+fn test_gsl_owner_double_delete() {
+    let (_, output) = run_checker("gsl_owner_double_free.cpp");
+    assert_contains_error(&output, "Double free: 'p'");
+    assert_no_error(&output, "Double free: 'q'");
+}
+
 // =============================================================================
 // Phase 7: Lambda Capture Escape
 // =============================================================================
@@ -545,3 +545,41 @@ fn test_cannot_borrow_after_move() {
         output
     );
 }
+
+/// Test 16: Moving while a direct reference is still used afterwards names
+/// the reference, even when the only later use is inside a branch
+#[test]
+fn test_cannot_move_while_direct_reference_used_later() {
+    let code = r#"
+    #include <memory>
+
+    // @safe
+    void take_ownership(std::unique_ptr<int> ptr) {
+        int x = *ptr;
+    }
+
+    // @safe
+    void test(bool verbose) {
+        std::unique_ptr<int> x(new int(42));
+        const std::unique_ptr<int>& r = x;
+        int before = 0;
+
+        // ERROR: r still refers to x below
+        take_ownership(std::move(x));
+
+        if (verbose) {
+            int after = 0;
+            bool held = static_cast<bool>(r);
+        }
+    }
+    "#;
+
+    let temp_file = create_temp_cpp_file(code);
+    let (_success, output) = run_analyzer(temp_file.path());
+
+    assert!(
+        output.contains("Cannot move 'x' because it is borrowed by: r"),
+        "Should detect that x is borrowed by r. Output: {}",
+        output
+    );
+}
@@ -639,3 +639,52 @@ public:
         output
     );
 }
+
+// ============================================================================
+// CLASS DEFAULT TESTS (no enclosing namespace)
+// ============================================================================
+
+#[test]
+fn test_safe_class_checks_unannotated_methods() {
+    // Methods of a @safe class are checked without their own annotation
+    let code = r#"
+#include <utility>
+
+struct Payload {
+    int value;
+};
+
+// @safe
+class Holder {
+public:
+    void consume_twice() {
+        Payload p;
+        Payload a = std::move(p);
+        Payload b = std::move(p);  // ERROR - use after move
+    }
+
+    // @unsafe
+    char* raw() {
+        char* ptr = nullptr;
+        *ptr = 'x';  // OK - method opts out of the class default
+        return ptr;
+    }
+};
+"#;
+
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(temp_file, "{}", code).unwrap();
+
+    let (success, output) = run_analyzer(temp_file.path());
+
+    assert!(
+        !success && output.contains("Use after move"),
+        "Unannotated method of a @safe class should be checked. Output: {}",
+        output
+    );
+    assert!(
+        !output.contains("raw"),
+        "@unsafe method should override the class default. Output: {}",
+        output
+    );
+}
@@ -0,0 +1,80 @@
+//! Moving a base-class subobject out of a derived object partially moves it;
+//! using the (now sliced) object afterwards is a use after move.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+const CLASSES: &str = r#"
+#include <string>
+#include <utility>
+
+// @safe
+class Base {
+public:
+    std::string name;
+};
+
+// @safe
+class Derived : public Base {
+public:
+    std::string extra;
+    int size() const { return 0; }
+};
+"#;
+
+#[test]
+fn test_use_after_moving_base_subobject() {
+    let source = format!(
+        "{}{}",
+        CLASSES,
+        r#"
+// @safe
+int sliced() {
+    Derived d;
+    Base b = std::move(static_cast<Base&>(d));
+    return d.size();
+}
+"#
+    );
+    let (success, output) = check(&source);
+    assert!(!success, "{}", output);
+    assert!(
+        output.contains("use of partially moved 'd'")
+            && output.contains("base subobject 'Base' has been moved"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_derived_member_after_moving_base_subobject_ok() {
+    let source = format!(
+        "{}{}",
+        CLASSES,
+        r#"
+// @safe
+std::string rest() {
+    Derived d;
+    Base b = std::move(static_cast<Base&>(d));
+    return std::move(d.extra);
+}
+"#
+    );
+    let (success, output) = check(&source);
+    assert!(!output.contains("base subobject"), "{}", output);
+    assert!(success, "{}", output);
+}
@@ -0,0 +1,80 @@
+//! `--changed-lines FILE:START-END` reports only violations on the given
+//! lines, so CI can gate new code in a file with pre-existing findings.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+// `old_p` is on line 5, `new_p` on line 6
+const SOURCE: &str = r#"
+// @safe
+void legacy() {
+    int a = 1;
+    int* old_p = &a;
+    int* new_p = &a;
+}
+"#;
+
+// `s` is used after the move on line 8
+const MOVE_SOURCE: &str = r#"
+#include <string>
+#include <utility>
+
+// @safe
+void legacy(std::string s) {
+    std::string t = std::move(s);
+    std::string u = s;
+}
+"#;
+
+fn run(extra_args: &[&str]) -> (Option<i32>, String) {
+    run_source(SOURCE, extra_args)
+}
+
+fn run_source(source: &str, extra_args: &[&str]) -> (Option<i32>, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("legacy.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(extra_args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_only_changed_lines_are_reported() {
+    let (code, output) = run(&["--changed-lines", "legacy.cpp:6-6"]);
+    assert_eq!(code, Some(1), "the new violation fails the run: {}", output);
+    assert!(output.contains("'new_p'"), "{}", output);
+    assert!(
+        !output.contains("'old_p'"),
+        "pre-existing violation must be suppressed: {}",
+        output
+    );
+}
+
+#[test]
+fn test_use_after_move_on_changed_line_is_reported() {
+    let (code, output) = run_source(MOVE_SOURCE, &["--changed-lines", "legacy.cpp:8"]);
+    assert_eq!(code, Some(1), "{}", output);
+    assert!(output.contains("Use after move"), "{}", output);
+}
+
+#[test]
+fn test_untouched_lines_pass() {
+    let (code, output) = run(&["--changed-lines", "legacy.cpp:1-3,other.cpp:5"]);
+    assert_eq!(code, Some(0), "{}", output);
+    assert!(output.contains("no violations found"), "{}", output);
+}
+
+#[test]
+fn test_invalid_range_is_rejected() {
+    let (code, _) = run(&["--changed-lines", "legacy.cpp:9-3"]);
+    assert_eq!(code, Some(2));
+}
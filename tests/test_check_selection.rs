@@ -0,0 +1,119 @@
+//! Tests for `--list-checks` and turning individual passes off/on with
+//! `--disable` / `--enable`.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer(args: &[&str]) -> (bool, String) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--"]).args(args);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("Z3_SYS_Z3_HEADER", "/opt/homebrew/include/z3.h");
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("Z3_SYS_Z3_HEADER", "/usr/include/z3.h");
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let full_output = format!("{}{}", stdout, stderr);
+
+    (output.status.success(), full_output)
+}
+
+const RAW_POINTER_CODE: &str = r#"
+// @safe
+void take_address() {
+    int x = 1;
+    int* p = &x;
+}
+"#;
+
+#[test]
+fn test_list_checks() {
+    let (success, output) = run_analyzer(&["--list-checks"]);
+
+    assert!(
+        success,
+        "--list-checks should succeed.\nOutput:\n{}",
+        output
+    );
+    for name in [
+        "pointer-safety",
+        "unsafe-propagation",
+        "borrows",
+        "lifetimes",
+    ] {
+        assert!(
+            output.contains(name),
+            "'{}' missing from --list-checks.\nOutput:\n{}",
+            name,
+            output
+        );
+    }
+    assert!(
+        output.contains("unsafe-pointer"),
+        "error kinds should be listed.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_disable_pointer_safety_suppresses_raw_pointer_violation() {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_path = temp_dir.path().join("raw_pointer.cpp");
+    fs::write(&cpp_path, RAW_POINTER_CODE).unwrap();
+    let cpp = cpp_path.to_str().unwrap();
+
+    let (success, output) = run_analyzer(&[cpp]);
+    assert!(
+        !success && output.contains("address-of"),
+        "raw pointer should be reported by default.\nOutput:\n{}",
+        output
+    );
+
+    let (success, output) = run_analyzer(&["--disable", "pointer-safety", cpp]);
+    assert!(
+        success && !output.contains("address-of"),
+        "--disable pointer-safety should suppress the violation.\nOutput:\n{}",
+        output
+    );
+
+    let (success, output) = run_analyzer(&[
+        "--disable",
+        "pointer-safety",
+        "--enable",
+        "pointer-safety",
+        cpp,
+    ]);
+    assert!(
+        !success && output.contains("address-of"),
+        "--enable should win over --disable.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_unknown_check_name_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_path = temp_dir.path().join("raw_pointer.cpp");
+    fs::write(&cpp_path, RAW_POINTER_CODE).unwrap();
+
+    let (success, output) =
+        run_analyzer(&["--disable", "no-such-check", cpp_path.to_str().unwrap()]);
+
+    assert!(
+        !success,
+        "unknown check must be an error.\nOutput:\n{}",
+        output
+    );
+    assert!(
+        output.contains("Unknown check 'no-such-check'"),
+        "expected an unknown-check error.\nOutput:\n{}",
+        output
+    );
+}
@@ -0,0 +1,81 @@
+//! `check_source` analyzes source held in memory, so these tests need no
+//! temporary files.
+
+use rusty_cpp::analysis::ErrorKind;
+use rusty_cpp::check::{CheckOptions, check_source};
+
+#[test]
+fn test_use_after_move_in_memory() {
+    let source = r#"
+namespace std {
+template <typename T>
+T&& move(T& value) { return static_cast<T&&>(value); }
+}
+
+struct Buffer {
+    Buffer() {}
+    Buffer(Buffer&& other) {}
+};
+
+// @safe
+void test() {
+    Buffer a;
+    Buffer b = std::move(a);
+    Buffer c = std::move(a);
+}
+"#;
+
+    let report = check_source(source, "snippet.cpp", &CheckOptions::default())
+        .expect("in-memory source should parse");
+    assert!(
+        report
+            .violations
+            .iter()
+            .any(|violation| violation.contains("Use after move")),
+        "Expected a use-after-move error, got: {:?}",
+        report.violations
+    );
+    assert!(report.has_errors());
+}
+
+#[test]
+fn test_clean_source_reports_nothing() {
+    let source = r#"
+// @safe
+int add(int a, int b) {
+    int sum = a + b;
+    return sum;
+}
+"#;
+
+    let report = check_source(source, "clean.cpp", &CheckOptions::default())
+        .expect("in-memory source should parse");
+    assert!(
+        report.violations.is_empty(),
+        "Expected no violations, got: {:?}",
+        report.violations
+    );
+}
+
+#[test]
+fn test_file_is_never_read_from_disk() {
+    // No file by this name exists; the in-memory source is all there is
+    let source = r#"
+// @safe
+void test() {
+    int x = 1;
+    int* p = &x;
+}
+"#;
+
+    let report = check_source(source, "does/not/exist.cpp", &CheckOptions::default())
+        .expect("in-memory source should parse");
+    assert!(
+        report
+            .diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.kind == ErrorKind::UnsafePointer),
+        "Expected an unsafe pointer error in @safe code, got: {:?}",
+        report.violations
+    );
+}
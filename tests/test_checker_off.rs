@@ -0,0 +1,70 @@
+//! `// @checker: off` anywhere in a file skips every check for that file,
+//! even in its `@safe` functions.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    (
+        output.status.success(),
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    )
+}
+
+const USE_AFTER_MOVE: &str = r#"
+namespace std {
+template <typename T>
+T&& move(T& value) { return static_cast<T&&>(value); }
+}
+
+struct Buffer {
+    Buffer() {}
+    Buffer(Buffer&& other) {}
+};
+
+// @safe
+void test() {
+    Buffer a;
+    Buffer b = std::move(a);
+    Buffer c = std::move(a);
+}
+"#;
+
+#[test]
+fn test_checker_off_skips_file() {
+    let source = format!(
+        "// Generated file, do not edit.\n// @checker: off\n{}",
+        USE_AFTER_MOVE
+    );
+
+    let (success, output) = check(&source);
+    assert!(
+        success && !output.contains("Use after move"),
+        "Expected no violations with @checker: off, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_file_without_checker_off_is_checked() {
+    let (success, output) = check(USE_AFTER_MOVE);
+    assert!(
+        !success && output.contains("Use after move"),
+        "Expected a use-after-move error, got: {}",
+        output
+    );
+}
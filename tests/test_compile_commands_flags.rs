@@ -0,0 +1,118 @@
+/// Tests that language flags from compile_commands.json (`-std=`, `-x`, `-D`)
+/// reach libclang, so the file is parsed the way the real build sees it
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_with_compile_commands(source: &Path, compile_commands: &Path) -> (bool, String) {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "run",
+        "--quiet",
+        "--",
+        "--compile-commands",
+        compile_commands.to_str().unwrap(),
+        source.to_str().unwrap(),
+    ])
+    .env("Z3_SYS_Z3_HEADER", z3_header);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    (output.status.success(), format!("{}{}", stdout, stderr))
+}
+
+/// Write `source` as `main.cpp` with a compile_commands.json entry using `arguments`
+fn write_tu(dir: &Path, source: &str, arguments: &[&str]) -> (PathBuf, PathBuf) {
+    let source_path = dir.join("main.cpp");
+    fs::write(&source_path, source).unwrap();
+
+    let arguments: Vec<String> = arguments.iter().map(|a| format!("\"{}\"", a)).collect();
+    let compile_commands = dir.join("compile_commands.json");
+    fs::write(
+        &compile_commands,
+        format!(
+            r#"[{{"directory": "{}", "file": "main.cpp", "arguments": [{}]}}]"#,
+            dir.to_str().unwrap(),
+            arguments.join(", ")
+        ),
+    )
+    .unwrap();
+    (source_path, compile_commands)
+}
+
+#[test]
+fn test_std_flag_forwarded() {
+    // The @safe function only exists when the TU is parsed as C++20
+    let dir = TempDir::new().unwrap();
+    let (source, compile_commands) = write_tu(
+        dir.path(),
+        r#"
+#if __cplusplus == 202002L
+// @safe
+void only_in_cpp20() {
+    int x = 1;
+    int* p = &x;
+}
+#endif
+"#,
+        &["clang++", "-std=c++20", "-c", "main.cpp"],
+    );
+
+    let (success, output) = run_with_compile_commands(&source, &compile_commands);
+
+    assert!(
+        !success && output.contains("address-of"),
+        "C++20-only code should be analyzed when -std=c++20 is forwarded: {}",
+        output
+    );
+}
+
+#[test]
+fn test_separate_define_and_language_flags_forwarded() {
+    // `-D NAME` (two tokens) and `-x c++` must both survive extraction
+    let dir = TempDir::new().unwrap();
+    let (source, compile_commands) = write_tu(
+        dir.path(),
+        r#"
+#ifdef ENABLE_CHECKED
+// @safe
+void checked() {
+    int x = 1;
+    int* p = &x;
+}
+#endif
+"#,
+        &[
+            "clang++",
+            "-x",
+            "c++",
+            "-D",
+            "ENABLE_CHECKED",
+            "-c",
+            "main.cpp",
+        ],
+    );
+
+    let (success, output) = run_with_compile_commands(&source, &compile_commands);
+
+    assert!(
+        !success && output.contains("address-of"),
+        "code behind -D ENABLE_CHECKED should be analyzed: {}",
+        output
+    );
+}
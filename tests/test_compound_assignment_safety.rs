@@ -39,3 +39,37 @@ void f() {
         stdout
     );
 }
+
+#[test]
+fn compound_assignment_to_borrowed_variable_is_rejected() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("compound_assignment_borrow.cpp");
+
+    fs::write(
+        &file_path,
+        r#"
+void use(const int& x);
+
+// @safe
+void f(const int& delta) {
+    int sum = 0;
+    const int& r = sum;
+    sum += delta;
+    use(r);
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Cannot assign to 'sum' because it is borrowed by: r"),
+        "expected `sum += ...` to conflict with the live borrow 'r'. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+const TASK: &str = r#"
+#include <coroutine>
+
+struct task {
+    struct promise_type {
+        task get_return_object() { return {}; }
+        std::suspend_never initial_suspend() { return {}; }
+        std::suspend_never final_suspend() noexcept { return {}; }
+        void return_void() {}
+        void unhandled_exception() {}
+    };
+};
+
+// @safe
+void write(int value);
+"#;
+
+#[test]
+fn test_reference_parameter_used_after_co_await() {
+    let source = format!(
+        "{}{}",
+        TASK,
+        r#"
+// @safe
+task log(const int& value) {
+    write(value);
+    co_await std::suspend_always{};
+    write(value);
+}
+"#
+    );
+
+    let output = check(&source);
+    assert!(
+        output.contains("reference parameter 'value'")
+            && output.contains("after the coroutine suspends"),
+        "Expected coroutine reference warning, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_value_parameter_across_co_await_is_fine() {
+    let source = format!(
+        "{}{}",
+        TASK,
+        r#"
+// @safe
+task log(int value) {
+    co_await std::suspend_always{};
+    write(value);
+}
+"#
+    );
+
+    let output = check(&source);
+    assert!(
+        !output.contains("after the coroutine suspends"),
+        "By-value parameters live in the coroutine frame, got: {}",
+        output
+    );
+}
@@ -0,0 +1,230 @@
+//! `--daemon` answers analysis requests framed like LSP messages on stdin,
+//! one response per request, until stdin is closed.
+
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+fn write_message(input: &mut impl Write, message: &Value) {
+    let body = message.to_string();
+    write!(input, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    input.flush().unwrap();
+}
+
+fn read_message(output: &mut impl BufRead) -> Value {
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        assert!(
+            output.read_line(&mut line).unwrap() > 0,
+            "daemon closed stdout"
+        );
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse().unwrap();
+        }
+    }
+    let mut body = vec![0; length];
+    output.read_exact(&mut body).unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+const USE_AFTER_MOVE: &str = r#"
+namespace std {
+template <typename T>
+T&& move(T& value) { return static_cast<T&&>(value); }
+}
+
+struct Buffer {
+    Buffer() {}
+    Buffer(Buffer&& other) {}
+};
+
+// @safe
+void test() {
+    Buffer a;
+    Buffer b = std::move(a);
+    Buffer c = std::move(a);
+}
+"#;
+
+const CLEAN: &str = r#"
+// @safe
+int add(int a, int b) {
+    int sum = a + b;
+    return sum;
+}
+"#;
+
+#[test]
+fn test_two_requests_over_one_pipe() {
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--daemon")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the daemon");
+    let mut input = daemon.stdin.take().unwrap();
+    let mut output = BufReader::new(daemon.stdout.take().unwrap());
+
+    // The files don't exist: the unsaved contents are analyzed instead
+    write_message(
+        &mut input,
+        &json!({ "id": 1, "file": "moved.cpp", "contents": USE_AFTER_MOVE }),
+    );
+    let first = read_message(&mut output);
+
+    write_message(
+        &mut input,
+        &json!({ "id": "second", "file": "clean.cpp", "contents": CLEAN }),
+    );
+    let second = read_message(&mut output);
+
+    drop(input);
+    let status = daemon.wait().unwrap();
+
+    assert_eq!(first["id"], 1);
+    let violations = first["violations"].as_array().expect("violations array");
+    assert!(
+        violations
+            .iter()
+            .any(|violation| violation["kind"] == "use-after-move"
+                && violation["file"] == "moved.cpp"),
+        "Expected a use-after-move violation, got: {}",
+        first
+    );
+
+    assert_eq!(second["id"], "second");
+    assert_eq!(
+        second["violations"],
+        json!([]),
+        "Expected no violations, got: {}",
+        second
+    );
+
+    assert!(status.success(), "daemon should exit cleanly on EOF");
+}
+
+#[test]
+fn test_unreadable_file_reports_error() {
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--daemon")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the daemon");
+    let mut input = daemon.stdin.take().unwrap();
+    let mut output = BufReader::new(daemon.stdout.take().unwrap());
+
+    write_message(
+        &mut input,
+        &json!({ "id": 7, "file": "does/not/exist.cpp" }),
+    );
+    let response = read_message(&mut output);
+    drop(input);
+    daemon.wait().unwrap();
+
+    assert_eq!(response["id"], 7);
+    assert!(
+        response["error"]
+            .as_str()
+            .is_some_and(|error| error.contains("Failed to read")),
+        "Expected a read error, got: {}",
+        response
+    );
+}
+
+#[test]
+fn test_max_violations_applies_after_allow() {
+    // Under `--allow`, analysis runs without the limit; the daemon still
+    // cuts what it reports down to it
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args([
+            "--daemon",
+            "--max-violations",
+            "1",
+            "--allow",
+            "uninitialized",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the daemon");
+    let mut input = daemon.stdin.take().unwrap();
+    let mut output = BufReader::new(daemon.stdout.take().unwrap());
+
+    let contents = USE_AFTER_MOVE.replace(
+        "Buffer c = std::move(a);",
+        "Buffer c = std::move(a);\n    Buffer d = std::move(b);\n    Buffer e = std::move(b);",
+    );
+    write_message(
+        &mut input,
+        &json!({ "id": 1, "file": "moved.cpp", "contents": contents }),
+    );
+    let response = read_message(&mut output);
+    drop(input);
+    daemon.wait().unwrap();
+
+    assert_eq!(
+        response["violations"].as_array().map(Vec::len),
+        Some(1),
+        "{}",
+        response
+    );
+    assert_eq!(response["truncated"], true, "{}", response);
+}
+
+#[test]
+fn test_saved_header_is_parsed_again() {
+    let dir = TempDir::new().unwrap();
+    let header = dir.path().join("util.h");
+    let source = dir.path().join("main.cpp");
+    fs::write(&header, "void helper();\n").unwrap();
+    fs::write(
+        &source,
+        "#include \"util.h\"\n\n// @safe\nvoid run() {\n    helper();\n}\n",
+    )
+    .unwrap();
+
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--daemon")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the daemon");
+    let mut input = daemon.stdin.take().unwrap();
+    let mut output = BufReader::new(daemon.stdout.take().unwrap());
+    let mentions_helper = |response: &Value| {
+        response["violations"]
+            .as_array()
+            .expect("violations array")
+            .iter()
+            .any(|violation| violation["message"].as_str().unwrap().contains("'helper'"))
+    };
+
+    let request = json!({ "id": 1, "file": source.to_str().unwrap() });
+    write_message(&mut input, &request);
+    let before = read_message(&mut output);
+
+    // Mark the declaration @safe; the new mtime is what tells the daemon
+    fs::write(&header, "// @safe\nvoid helper();\n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&header)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+    write_message(&mut input, &request);
+    let after = read_message(&mut output);
+    drop(input);
+    daemon.wait().unwrap();
+
+    assert!(mentions_helper(&before), "{}", before);
+    assert!(!mentions_helper(&after), "{}", after);
+}
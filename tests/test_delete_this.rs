@@ -0,0 +1,82 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_member_access_after_delete_this() {
+    let source = r#"
+class Widget {
+public:
+    void destroy();
+
+private:
+    int member_ = 1;
+};
+
+// @safe
+void Widget::destroy() {
+    // @unsafe
+    {
+        delete this;
+    }
+    member_ = 0;
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("use after free: member 'member_'"),
+        "Expected delete-this error, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_return_after_delete_this_is_fine() {
+    let source = r#"
+class Widget {
+public:
+    void release();
+
+private:
+    int refs_ = 1;
+};
+
+// @safe
+void Widget::release() {
+    refs_ = refs_ - 1;
+    if (refs_ == 0) {
+        // @unsafe
+        {
+            delete this;
+        }
+        return;
+    }
+    refs_ = refs_ + 0;
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("delete this"),
+        "Returning right after delete this should be fine, got: {}",
+        output
+    );
+}
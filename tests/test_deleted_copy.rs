@@ -0,0 +1,72 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_copying_deleted_copy_type_is_reported() {
+    let source = r#"
+#include <utility>
+
+struct Handle {
+    Handle() = default;
+    Handle(const Handle&) = delete;
+    Handle(Handle&&) = default;
+};
+
+// @safe
+void duplicate() {
+    Handle a;
+    Handle b = a;
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("copy of non-copyable type 'Handle'"),
+        "Expected deleted-copy error, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_moving_deleted_copy_type_is_fine() {
+    let source = r#"
+#include <utility>
+
+struct Handle {
+    Handle() = default;
+    Handle(const Handle&) = delete;
+    Handle(Handle&&) = default;
+};
+
+// @safe
+void hand_off() {
+    Handle a;
+    Handle b = std::move(a);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("non-copyable"),
+        "Moving should be allowed, got: {}",
+        output
+    );
+}
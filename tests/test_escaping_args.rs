@@ -0,0 +1,97 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+const REGISTRY: &str = r#"
+struct Handler { int id; };
+
+// @escapes(handler)
+void register_handler(Handler* handler);
+
+// @escapes(handler)
+void register_ref(Handler& handler);
+"#;
+
+#[test]
+fn test_address_of_local_passed_to_escaping_param() {
+    let source = format!(
+        "{}{}",
+        REGISTRY,
+        r#"
+// @safe
+void setup() {
+    Handler h;
+    register_handler(&h);
+}
+"#
+    );
+
+    let output = check(&source);
+    assert!(
+        output.contains("Dangling pointer") && output.contains("address of local 'h'"),
+        "Expected escaping local address error, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_local_bound_to_escaping_reference_param() {
+    let source = format!(
+        "{}{}",
+        REGISTRY,
+        r#"
+// @safe
+void setup() {
+    Handler h;
+    register_ref(h);
+}
+"#
+    );
+
+    let output = check(&source);
+    assert!(
+        output.contains("Dangling pointer") && output.contains("local 'h'"),
+        "Expected escaping local reference error, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_static_or_pointer_argument_is_fine() {
+    let source = format!(
+        "{}{}",
+        REGISTRY,
+        r#"
+// @safe
+void setup(Handler* existing) {
+    static Handler h;
+    register_handler(&h);
+    register_handler(existing);
+}
+"#
+    );
+
+    let output = check(&source);
+    assert!(
+        !output.contains("@escapes"),
+        "Static locals and pointer parameters should be allowed, got: {}",
+        output
+    );
+}
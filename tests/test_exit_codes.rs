@@ -0,0 +1,68 @@
+//! Exit codes: 0 when clean, 1 when violations are found, 2 when the
+//! checker could not analyze the file at all.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn exit_code(source: &str) -> Option<i32> {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    output.status.code()
+}
+
+#[test]
+fn test_clean_file_exits_0() {
+    let code = exit_code(
+        r#"
+// @safe
+int add(int a, int b) {
+    return a + b;
+}
+"#,
+    );
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn test_violations_exit_1() {
+    let code = exit_code(
+        r#"
+// @safe
+void f() {
+    int x = 1;
+    int* p = &x;
+}
+"#,
+    );
+    assert_eq!(code, Some(1));
+}
+
+#[test]
+fn test_unparseable_file_exits_2() {
+    // A missing include is a fatal libclang error
+    let code = exit_code(
+        r#"
+#include "no_such_header_anywhere.h"
+
+// @safe
+void f() {}
+"#,
+    );
+    assert_eq!(code, Some(2));
+}
+
+#[test]
+fn test_bad_flag_value_exits_2() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(["--format", "yaml", "main.cpp"])
+        .output()
+        .expect("run checker");
+    assert_eq!(output.status.code(), Some(2));
+}
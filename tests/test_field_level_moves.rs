@@ -426,3 +426,88 @@ void test() {
 "#;
     analyze_cpp_code(code, &["has been moved"], true);
 }
+
+#[test]
+fn test_whole_object_move_while_field_borrowed() {
+    let code = r#"
+#include <utility>
+
+struct Container {
+    int data;
+};
+
+// @safe
+void test() {
+    Container c;
+    int& r = c.data;
+    Container c2 = std::move(c);  // ERROR: c.data is borrowed by r
+    int x = r;
+}
+"#;
+    analyze_cpp_code(
+        code,
+        &["Cannot move 'c' because field 'data' is borrowed by: r"],
+        true,
+    );
+}
+
+#[test]
+fn test_whole_object_move_after_field_borrow_ends() {
+    let code = r#"
+#include <utility>
+
+struct Container {
+    int data;
+};
+
+// @safe
+void test() {
+    Container c;
+    int& r = c.data;
+    int x = r;
+    Container c2 = std::move(c);  // OK: r is no longer used
+}
+"#;
+    analyze_cpp_code(code, &[], false);
+}
+
+#[test]
+fn test_move_constructor_reads_moved_source_field() {
+    let code = r#"
+#include <string>
+#include <utility>
+
+// @safe
+class Widget {
+    std::string a;
+    std::string b;
+public:
+    Widget(Widget&& other) noexcept : a(std::move(other.a)) {
+        b = other.a;  // ERROR: other.a was moved by the initializer
+    }
+};
+"#;
+    analyze_cpp_code(
+        code,
+        &["Cannot read field 'other.a' because it has been moved"],
+        true,
+    );
+}
+
+#[test]
+fn test_move_constructor_resets_moved_source_field() {
+    let code = r#"
+#include <string>
+#include <utility>
+
+// @safe
+class Widget {
+    std::string a;
+public:
+    Widget(Widget&& other) noexcept : a(std::move(other.a)) {
+        other.a = std::string();  // OK: leave the source in a valid state
+    }
+};
+"#;
+    analyze_cpp_code(code, &[], false);
+}
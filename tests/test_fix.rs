@@ -0,0 +1,147 @@
+//! `--fix` inserts the suggested annotations in place, and the fixed file
+//! then passes.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_fix_inserts_elided_lifetime() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(
+        &file_path,
+        r#"
+// @safe
+const int& first(const int& x) {
+    return x;
+}
+
+// @safe
+int& bump(int& counter) {
+    counter += 1;
+    return counter;
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--fix")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let fixed = fs::read_to_string(&file_path).expect("read fixed source");
+    assert!(
+        fixed.contains("// @safe\n// @lifetime: (&'a) -> &'a\nconst int& first"),
+        "{}",
+        fixed
+    );
+    assert!(
+        fixed.contains("// @safe\n// @lifetime: (&'a mut) -> &'a mut\nint& bump"),
+        "{}",
+        fixed
+    );
+
+    let recheck = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    assert_eq!(recheck.status.code(), Some(0));
+}
+
+#[test]
+fn test_fix_skips_ambiguous_functions() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    let source = r#"
+// @safe
+const int& pick(const int& a, const int& b) {
+    return a;
+}
+"#;
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--fix")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), source);
+}
+
+#[test]
+fn test_fix_leaves_allowed_violations_alone() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    let source = r#"
+// @safe
+const int& first(const int& x) {
+    return x;
+}
+"#;
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--fix")
+        .arg("--allow")
+        .arg("lifetime-violation")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), source);
+}
+
+#[test]
+fn test_fix_looks_past_max_violations() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(
+        &file_path,
+        r#"
+// @safe
+const int& first(const int& x) {
+    return x;
+}
+
+// @safe
+const int& second(const int& y) {
+    return y;
+}
+"#,
+    )
+    .expect("write source");
+
+    // Only the first violation is reported, but the second is fixed as well
+    // rather than mistaken for one the edits introduced
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(["--fix", "--max-violations", "1"])
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("fixes were not applied"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+
+    let fixed = fs::read_to_string(&file_path).expect("read fixed source");
+    assert_eq!(
+        fixed.matches("// @lifetime: (&'a) -> &'a\n").count(),
+        2,
+        "{}",
+        fixed
+    );
+}
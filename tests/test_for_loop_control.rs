@@ -45,3 +45,45 @@ void f() {
         stdout
     );
 }
+
+#[test]
+fn for_loop_increment_move_is_use_after_move() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("for_loop_increment.cpp");
+
+    fs::write(
+        &file_path,
+        r#"
+#include <string>
+#include <utility>
+
+// @safe
+void f(int n) {
+    std::string current;
+    std::string next = "x";
+    for (int i = 0; i < n; current = std::move(next)) {
+        i += 1;
+    }
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        !output.status.success(),
+        "moving in the increment clause moves a moved-from value on the second iteration. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("next") && stdout.contains("moved"),
+        "the repeated move of 'next' should be reported. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,138 @@
+/// Tests for `--function`, which restricts reporting to the named function(s)
+/// while the rest of the file is still parsed for context
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer(cpp_file: &Path, extra_args: &[&str]) -> (bool, String) {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--", cpp_file.to_str().unwrap()])
+        .args(extra_args)
+        .env("Z3_SYS_Z3_HEADER", z3_header);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    (output.status.success(), format!("{}{}", stdout, stderr))
+}
+
+const TWO_BAD_FUNCTIONS: &str = r#"
+#include <string>
+#include <utility>
+
+namespace app {
+
+class Widget {
+public:
+    // @safe
+    void first() {
+        std::string a = "a";
+        std::string b = std::move(a);
+        std::string c = a;
+    }
+
+    // @safe
+    void second() {
+        std::string x = "x";
+        std::string y = std::move(x);
+        std::string z = x;
+    }
+};
+
+}
+"#;
+
+#[test]
+fn test_only_named_function_reported() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("widget.cpp");
+    fs::write(&cpp_file, TWO_BAD_FUNCTIONS).unwrap();
+
+    let (success, output) = run_analyzer(&cpp_file, &["--function", "Widget::second"]);
+
+    assert!(
+        !success,
+        "second() has a use-after-move. Output: {}",
+        output
+    );
+    assert!(
+        output.contains("'x'"),
+        "violation in the selected function should be reported. Output: {}",
+        output
+    );
+    assert!(
+        !output.contains("'a'"),
+        "violation in another function should be filtered out. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_unmatched_function_reports_nothing() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("widget.cpp");
+    fs::write(&cpp_file, TWO_BAD_FUNCTIONS).unwrap();
+
+    let (success, output) = run_analyzer(&cpp_file, &["--function", "Widget::third"]);
+
+    assert!(
+        success && output.contains("no violations found"),
+        "no function is selected. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_selected_function_sees_the_whole_program() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("counter.cpp");
+    fs::write(
+        &cpp_file,
+        r#"
+// @safe
+void bump(int& value) {
+    value += 1;
+}
+
+class Counter {
+    int count_;
+public:
+    // @safe
+    void peek() const {
+        bump(count_);
+    }
+};
+"#,
+    )
+    .unwrap();
+
+    // `bump` isn't selected, but its `int&` parameter still decides what
+    // `peek` may pass it
+    let (_, unfiltered) = run_analyzer(&cpp_file, &[]);
+    let (success, filtered) = run_analyzer(&cpp_file, &["--function", "Counter::peek"]);
+
+    assert!(
+        unfiltered.contains("Cannot pass field 'count_' to non-const reference parameter"),
+        "Output: {}",
+        unfiltered
+    );
+    assert!(
+        !success
+            && filtered.contains("Cannot pass field 'count_' to non-const reference parameter"),
+        "a filtered run should report what an unfiltered run does. Output: {}",
+        filtered
+    );
+}
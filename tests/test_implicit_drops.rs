@@ -297,6 +297,71 @@ int main() { return 0; }
     );
 }
 
+// ============================================================================
+// TEST: Holder declared before its referent in the same scope
+// Locals are destroyed in reverse declaration order, so `a` is destroyed
+// while `b` still points at it
+// ============================================================================
+
+#[test]
+fn test_holder_declared_before_referent_reported() {
+    let source = r#"
+struct B {
+    int* ref;
+};
+
+// @safe
+void test() {
+    B b;
+    int a = 1;
+    // @unsafe
+    {
+        b.ref = &a;
+    }
+}
+
+int main() { return 0; }
+"#;
+
+    let (success, output) = compile_and_check(source);
+
+    assert!(!success, "Expected drop order error, got: {}", output);
+    assert!(
+        output.contains("'b' declared before 'a' but borrows it; 'a' is destroyed first"),
+        "Expected drop order message, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_holder_declared_after_referent_ok() {
+    let source = r#"
+struct B {
+    int* ref;
+};
+
+// @safe
+void test() {
+    int a = 1;
+    B b;
+    // @unsafe
+    {
+        b.ref = &a;
+    }
+}
+
+int main() { return 0; }
+"#;
+
+    let (_success, output) = compile_and_check(source);
+
+    assert!(
+        !output.contains("declared before"),
+        "Referent outlives holder, should not report drop order: {}",
+        output
+    );
+}
+
 // ============================================================================
 // DOCUMENTATION TEST - What we DON'T need to catch
 // ============================================================================
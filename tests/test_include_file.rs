@@ -0,0 +1,58 @@
+//! `--include-file <path>`: include directories listed one per line, with
+//! relative entries resolved against the list file's directory.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_header_found_through_include_file() {
+    let dir = TempDir::new().expect("create temp dir");
+    let include_dir = dir.path().join("vendor/include");
+    fs::create_dir_all(&include_dir).expect("create include dir");
+    fs::write(
+        include_dir.join("vendor_api.h"),
+        r#"
+// @unsafe
+void vendor_raw_call();
+"#,
+    )
+    .expect("write header");
+
+    let source = dir.path().join("main.cpp");
+    fs::write(
+        &source,
+        r#"
+#include "vendor_api.h"
+
+// @safe
+void caller() {
+    vendor_raw_call();
+}
+"#,
+    )
+    .expect("write source");
+
+    let list = dir.path().join("includes.txt");
+    fs::write(&list, "# project include directories\nvendor/include\n").expect("write list");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--include-file")
+        .arg(&list)
+        .arg(&source)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        !stderr.contains("file not found"),
+        "vendor_api.h should be found via the include file. Stderr: {}",
+        stderr
+    );
+    assert!(
+        stdout.contains("vendor_raw_call"),
+        "the @unsafe annotation from the header should apply. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,90 @@
+//! Functions from system and library headers are skipped unless
+//! `--include-system` is given
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// `main.cpp` includes `ext/vendored.h`; `/ext/` is one of the system
+/// header path patterns
+fn check(extra_args: &[&str]) -> (String, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    fs::create_dir(dir.path().join("ext")).expect("create ext dir");
+    fs::write(
+        dir.path().join("ext").join("vendored.h"),
+        r#"
+#pragma once
+
+// @safe
+inline int vendored_bad() {
+    int x = 1;
+    int* p = &x;
+    return *p;
+}
+"#,
+    )
+    .expect("write header");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(
+        &file_path,
+        r#"
+#include "ext/vendored.h"
+
+// @safe
+int add(int a, int b) {
+    return a + b;
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(extra_args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    (
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn test_system_header_skipped_by_default() {
+    let (stdout, _) = check(&[]);
+    assert!(
+        !stdout.contains("vendored_bad"),
+        "system header functions are not analyzed by default. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_include_system_analyzes_system_header() {
+    let (stdout, stderr) = check(&["--include-system"]);
+    assert!(
+        stdout.contains("vendored_bad"),
+        "--include-system should analyze the vendored header. Output: {}",
+        stdout
+    );
+    assert!(
+        stderr.contains("expect noisy results"),
+        "--include-system should warn about noise. Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_include_system_warning_respects_quiet() {
+    let (stdout, stderr) = check(&["--include-system", "--quiet"]);
+    assert!(
+        stdout.contains("vendored_bad"),
+        "--quiet still reports the violations. Output: {}",
+        stdout
+    );
+    assert!(
+        !stderr.contains("expect noisy results"),
+        "--quiet should drop the noise warning. Stderr: {}",
+        stderr
+    );
+}
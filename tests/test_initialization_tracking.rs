@@ -278,6 +278,59 @@ void process() {
     println!("Output: {}", output);
 }
 
+#[test]
+fn test_infinite_loop_body_initializes() {
+    // A loop without an exit condition runs its body at least once
+    for header in ["for (;;)", "while (true)"] {
+        let code = format!(
+            r#"
+int compute();
+
+// @safe
+int process() {{
+    int x;
+    {} {{
+        x = compute();
+        break;
+    }}
+    return x;
+}}
+"#,
+            header
+        );
+        let output = run_checker(&code);
+        assert!(
+            !output.contains("uninitialized variable 'x'"),
+            "{}: x is assigned before the only exit. Output: {}",
+            header,
+            output
+        );
+    }
+}
+
+#[test]
+fn test_conditional_loop_body_may_not_initialize() {
+    let code = r#"
+int compute();
+
+// @safe
+int process(bool again) {
+    int x;
+    while (again) {
+        x = compute();
+        break;
+    }
+    return x;
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("potentially uninitialized variable 'x'"),
+        "The loop body may never run. Output: {}",
+        output
+    );
+}
+
 // ============================================================================
 // Test: Pointer to initialized memory
 // ============================================================================
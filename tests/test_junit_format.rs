@@ -0,0 +1,70 @@
+//! `--format junit` reports each checked function as a JUnit test case with
+//! a failure per violation.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn junit(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(["--format", "junit"])
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// `(name, failures)` of each test case
+fn test_cases(xml: &str) -> Vec<(String, usize)> {
+    let case = regex::Regex::new(
+        r#"(?s)<testcase classname="[^"]*" name="([^"]*)"(/>|>(.*?)</testcase>)"#,
+    )
+    .unwrap();
+    case.captures_iter(xml)
+        .map(|case| {
+            let body = case.get(3).map_or("", |body| body.as_str());
+            (case[1].to_string(), body.matches("<failure ").count())
+        })
+        .collect()
+}
+
+#[test]
+fn test_junit_failures_per_function() {
+    let source = r#"
+// @safe
+void bad() {
+    int x = 1;
+    int* p = &x;
+}
+
+// @safe
+int good(int a) {
+    return a + 1;
+}
+"#;
+
+    let xml = junit(source);
+    assert!(xml.starts_with("<?xml"), "Expected JUnit XML, got: {}", xml);
+    let suite = regex::Regex::new(r#"<testsuite [^>]*tests="(\d+)" failures="(\d+)""#).unwrap();
+    let counts = suite.captures(&xml).expect("a testsuite element");
+    assert_eq!(&counts[1], "2", "{}", xml);
+    assert_eq!(&counts[2], "1", "{}", xml);
+
+    let cases = test_cases(&xml);
+    assert!(
+        cases
+            .iter()
+            .any(|(name, failures)| name == "bad" && *failures >= 1),
+        "Expected failures in 'bad', got: {:?}",
+        cases
+    );
+    assert!(
+        cases.contains(&("good".to_string(), 0)),
+        "Expected 'good' to pass, got: {:?}",
+        cases
+    );
+}
@@ -92,3 +92,42 @@ void f() {
         stderr
     );
 }
+
+#[test]
+fn lambda_body_use_after_move_of_capture_is_reported() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("lambda_body_move.cpp");
+
+    fs::write(
+        &file_path,
+        r#"
+#include <string>
+#include <utility>
+
+// @safe
+void f() {
+    std::string name = "x";
+    auto local = [name]() mutable {
+        std::string taken = std::move(name);
+        std::string again = std::move(name);
+    };
+
+    local();
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        !output.status.success() && stdout.contains("Use after move"),
+        "moving a captured value twice inside the lambda body should be reported. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,84 @@
+//! A `std::lock_guard` / `std::unique_lock` holds its mutex for the guard's
+//! scope. Locking the same mutex again while a guard is live deadlocks.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_overlapping_guards_on_same_mutex() {
+    let (success, stdout) = check(
+        r#"
+#include <mutex>
+
+// @safe
+void update(std::mutex& m) {
+    std::lock_guard<std::mutex> outer(m);
+    {
+        std::lock_guard<std::mutex> inner(m);
+    }
+}
+"#,
+    );
+    assert!(!success, "double lock should fail. Output: {}", stdout);
+    assert!(
+        stdout.contains("already locked by 'outer'"),
+        "Expected double-lock error. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_sequential_guards_on_same_mutex() {
+    let (success, stdout) = check(
+        r#"
+#include <mutex>
+
+// @safe
+void update(std::mutex& m) {
+    {
+        std::lock_guard<std::mutex> first(m);
+    }
+    std::unique_lock<std::mutex> second(m);
+}
+"#,
+    );
+    assert!(
+        success,
+        "guards in disjoint scopes should pass. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_deferred_unique_lock_does_not_lock() {
+    let (success, stdout) = check(
+        r#"
+#include <mutex>
+
+// @safe
+void update(std::mutex& m) {
+    std::lock_guard<std::mutex> held(m);
+    std::unique_lock<std::mutex> later(m, std::defer_lock);
+}
+"#,
+    );
+    assert!(
+        success,
+        "a deferred unique_lock does not lock. Output: {}",
+        stdout
+    );
+}
@@ -833,3 +833,135 @@ void test() {
     // Clean up
     let _ = fs::remove_file("test_while_nested_if_ok.cpp");
 }
+
+#[test]
+fn test_outer_variable_moved_into_call_in_while_loop_error() {
+    // Outer variable passed by move to a call each iteration - SHOULD be detected
+    let test_code = r#"
+namespace std {
+    template<typename T> T&& move(T& x) { return static_cast<T&&>(x); }
+}
+
+// @safe
+struct Box { int data; };  // Simple movable type for testing
+
+// @safe
+Box make() { return Box(); }
+
+// @safe
+void consume(Box b) {}
+
+// @safe
+void test(bool flag) {
+    Box b = make();
+    int count = 0;
+    while (count < 5) {
+        if (flag) {
+            b = make();  // Only re-initialized on some paths
+        }
+        consume(std::move(b));  // ERROR: b may already be moved
+        count++;
+    }
+}
+"#;
+
+    fs::write("test_outer_move_call_while.cpp", test_code).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "test_outer_move_call_while.cpp"])
+        .output()
+        .expect("Failed to run borrow checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Use after move in loop: variable 'b'"),
+        "Should detect outer variable moved into a call in a while loop. Output: {}",
+        stdout
+    );
+
+    // Clean up
+    let _ = fs::remove_file("test_outer_move_call_while.cpp");
+}
+
+#[test]
+fn test_outer_variable_reinitialized_each_iteration_ok() {
+    // Outer variable re-initialized before every move - should be OK
+    let test_code = r#"
+namespace std {
+    template<typename T> T&& move(T& x) { return static_cast<T&&>(x); }
+}
+
+// @safe
+struct Box { int data; };  // Simple movable type for testing
+
+// @safe
+Box make() { return Box(); }
+
+// @safe
+void consume(Box b) {}
+
+// @safe
+void test() {
+    Box b;
+    for (int i = 0; i < 5; i++) {
+        b = make();
+        consume(std::move(b));
+    }
+}
+"#;
+
+    fs::write("test_outer_reinit_loop_ok.cpp", test_code).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "test_outer_reinit_loop_ok.cpp"])
+        .output()
+        .expect("Failed to run borrow checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        !stdout.contains("Use after move"),
+        "Re-initializing an outer variable each iteration should be OK. Output: {}",
+        stdout
+    );
+
+    // Clean up
+    let _ = fs::remove_file("test_outer_reinit_loop_ok.cpp");
+}
+
+#[test]
+fn test_do_while_body_initializes_outer_variable_ok() {
+    // A do-while body always runs once, so `x` is initialized afterward
+    let test_code = r#"
+// @safe
+int next();
+
+// @safe
+int test() {
+    int x;
+    do {
+        x = next();
+    } while (x < 0);
+    return x;
+}
+"#;
+
+    fs::write("test_do_while_init_ok.cpp", test_code).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "test_do_while_init_ok.cpp"])
+        .output()
+        .expect("Failed to run borrow checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        !stdout.contains("uninitialized variable 'x'"),
+        "A variable assigned in a do-while body is initialized after it. Output: {}",
+        stdout
+    );
+
+    // Clean up
+    let _ = fs::remove_file("test_do_while_init_ok.cpp");
+}
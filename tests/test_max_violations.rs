@@ -0,0 +1,68 @@
+//! `--max-violations N` stops the checker after N findings, so a
+//! pathological file doesn't bury the first (usually root-cause) ones.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"
+// @safe
+void many() {
+    int a = 1;
+    int* p1 = &a;
+    int* p2 = &a;
+    int* p3 = &a;
+    int* p4 = &a;
+    int* p5 = &a;
+}
+"#;
+
+fn run(extra_args: &[&str]) -> (Option<i32>, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("many.cpp");
+    fs::write(&file_path, SOURCE).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(extra_args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+fn violation_lines(output: &str) -> usize {
+    output
+        .lines()
+        .filter(|line| line.starts_with("In function 'many'"))
+        .count()
+}
+
+#[test]
+fn test_stops_at_max_violations() {
+    let (code, output) = run(&["--max-violations", "2", "--no-summary"]);
+    assert_eq!(code, Some(1), "{}", output);
+    assert_eq!(violation_lines(&output), 2, "{}", output);
+    assert!(output.contains("Found 2 violation(s)"), "{}", output);
+    assert!(
+        output.contains("output truncated at 2 violations"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_no_notice_below_the_limit() {
+    let (code, output) = run(&["--max-violations", "100", "--no-summary"]);
+    assert_eq!(code, Some(1), "{}", output);
+    assert!(violation_lines(&output) > 2, "{}", output);
+    assert!(!output.contains("output truncated"), "{}", output);
+}
+
+#[test]
+fn test_zero_limit_is_rejected() {
+    let (code, output) = run(&["--max-violations", "0"]);
+    assert_eq!(code, Some(2), "{}", output);
+}
@@ -334,3 +334,198 @@ void test_shared_borrow_const_method() {
         output
     );
 }
+
+#[test]
+fn test_two_live_mut_accessor_borrows_error() {
+    let code = r#"
+struct Pair {
+    int first;
+    int second;
+
+    // @safe
+    // @lifetime: (&'a mut self) -> &'a mut int
+    int& first_mut() {
+        return first;
+    }
+
+    // @safe
+    // @lifetime: (&'a mut self) -> &'a mut int
+    int& second_mut() {
+        return second;
+    }
+};
+
+// @safe
+void test_two_live_accessors() {
+    Pair p;
+    p.first = 0;
+    p.second = 0;
+
+    auto& a = p.first_mut();
+    auto& b = p.second_mut();  // Should ERROR: p already mutably borrowed through 'a'
+    a += 1;
+    b += 1;
+}
+"#;
+
+    let temp_file = create_temp_file("two_live_accessors", code);
+    let output = run_analyzer(&temp_file);
+    cleanup(&temp_file);
+
+    assert!(
+        output.contains("Cannot create mutable reference to 'p': already mutably borrowed"),
+        "Should reject a second mutable method-return borrow while the first is live. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_mut_accessor_while_field_borrowed_error() {
+    let code = r#"
+struct Foo {
+    int value;
+
+    // @safe
+    // @lifetime: (&'a mut self) -> &'a mut int
+    int& borrow_mut() {
+        return value;
+    }
+};
+
+// @safe
+void test_accessor_after_field_borrow() {
+    Foo x;
+    x.value = 0;
+
+    int& field = x.value;
+    int& whole = x.borrow_mut();  // Should ERROR: x.value is already borrowed
+    field += 1;
+    whole += 1;
+}
+"#;
+
+    let temp_file = create_temp_file("accessor_after_field", code);
+    let output = run_analyzer(&temp_file);
+    cleanup(&temp_file);
+
+    assert!(
+        output.contains("Cannot mutably borrow 'x': fields are already borrowed"),
+        "Should reject a mutable method-return borrow while a field is borrowed. Output: {}",
+        output
+    );
+}
+
+// =============================================================================
+// `auto` vs `auto&` bindings of the same reference-returning accessor
+// =============================================================================
+
+const HOLDER_WITH_ACCESSOR: &str = r#"
+struct Widget {
+    int size;
+};
+
+struct Holder {
+    Widget widget;
+
+    // @safe
+    // @lifetime: (&'a mut self) -> &'a mut Widget
+    Widget& widget_mut() {
+        return widget;
+    }
+};
+"#;
+
+#[test]
+fn test_auto_copy_of_accessor_does_not_borrow() {
+    let code = format!(
+        "{}{}",
+        HOLDER_WITH_ACCESSOR,
+        r#"
+// @safe
+void copy_then_borrow() {
+    Holder h;
+    auto copy = h.widget_mut();     // Deduces Widget: a copy, no borrow of h
+    auto& w = h.widget_mut();       // OK - nothing else borrows h
+    w.size = copy.size;
+}
+"#
+    );
+
+    let temp_file = create_temp_file("auto_copy_no_borrow", &code);
+    let output = run_analyzer(&temp_file);
+    cleanup(&temp_file);
+
+    assert!(
+        !output.contains("already mutably borrowed"),
+        "A plain auto copy should not keep the receiver borrowed. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_auto_ref_of_accessor_borrows() {
+    let code = format!(
+        "{}{}",
+        HOLDER_WITH_ACCESSOR,
+        r#"
+// @safe
+void borrow_twice() {
+    Holder h;
+    auto& first = h.widget_mut();   // Deduces Widget&: borrows h
+    auto& second = h.widget_mut();  // Should ERROR: h already mutably borrowed
+    second.size = first.size;
+}
+"#
+    );
+
+    let temp_file = create_temp_file("auto_ref_borrows", &code);
+    let output = run_analyzer(&temp_file);
+    cleanup(&temp_file);
+
+    assert!(
+        output.contains("Cannot create mutable reference to 'h': already mutably borrowed"),
+        "An auto& binding should keep the receiver mutably borrowed. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_shared_borrow_then_non_const_method_error() {
+    let code = r#"
+struct Foo {
+    int value;
+
+    // @safe
+    // @lifetime: (&'a self) -> &'a int
+    const int& peek() const {
+        return value;
+    }
+
+    // @safe
+    void bump() {
+        value += 1;
+    }
+};
+
+// @safe
+int test_shared_borrow_non_const_method() {
+    Foo x;
+    x.value = 42;
+
+    const int& ref = x.peek();  // Shared borrow
+    x.bump();                   // Should ERROR: bump() needs x mutably while ref reads it
+    return ref;
+}
+"#;
+
+    let temp_file = create_temp_file("shared_non_const_method", code);
+    let output = run_analyzer(&temp_file);
+    cleanup(&temp_file);
+
+    assert!(
+        output.contains("Cannot call non-const method")
+            && output.contains("immutably borrowed by: ref"),
+        "Should reject a non-const method while a shared borrow is live. Output: {}",
+        output
+    );
+}
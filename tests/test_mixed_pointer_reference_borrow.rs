@@ -106,6 +106,28 @@ void test() {
     );
 }
 
+#[test]
+fn test_writes_through_mutable_ref_and_pointer_conflict() {
+    // Both aliases stay live and are written through: the pointer is a
+    // second mutable borrow of x, exactly like a second int&
+    let code = r#"
+// @unsafe
+void test() {
+    int x = 42;
+    int& ref = x;
+    int* ptr = &x;      // ERROR: x is already mutably borrowed by ref
+    ref = 1;
+    *ptr = 2;
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("Cannot create mutable reference to 'x': already mutably borrowed"),
+        "Should detect aliasing between a mutable reference and a pointer. Output: {}",
+        output
+    );
+}
+
 // ============================================================================
 // MULTIPLE IMMUTABLE BORROWS (SHOULD BE ALLOWED)
 // ============================================================================
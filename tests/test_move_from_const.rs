@@ -0,0 +1,73 @@
+//! `std::move` of a const container element copies instead of moving. The
+//! check is pedantic-only, so every run here passes `--pedantic`.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("move_from_const.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--pedantic")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_move_of_const_vector_element_warns() {
+    let stdout = check(
+        r#"
+#include <string>
+#include <utility>
+#include <vector>
+
+// @safe
+void f(const std::vector<std::string>& names) {
+    // @unsafe
+    {
+        std::string first = std::move(names[0]);
+    }
+    const std::vector<std::string> local = names;
+    std::string second = std::move(local[0]);
+}
+"#,
+    );
+
+    assert!(
+        stdout.contains("warning: std::move of const 'local[...]'"),
+        "expected a warning for moving a const element. Output: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("'names[...]'"),
+        "@unsafe blocks are not checked. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_move_of_non_const_vector_element_ok() {
+    let stdout = check(
+        r#"
+#include <string>
+#include <utility>
+#include <vector>
+
+// @safe
+void f(std::vector<std::string> names) {
+    std::string first = std::move(names[0]);
+}
+"#,
+    );
+
+    assert!(
+        !stdout.contains("std::move of const"),
+        "moving from a non-const element is a real move. Output: {}",
+        stdout
+    );
+}
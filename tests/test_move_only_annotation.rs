@@ -0,0 +1,73 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_copy_of_move_only_type_consumes_source() {
+    let source = r#"
+// @move_only
+struct Token {
+    int id;
+};
+
+// @safe
+void consume(const Token& t);
+
+// @safe
+void hand_off() {
+    Token a;
+    Token b = a;
+    consume(a);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Use after move") && output.contains("'a'"),
+        "Expected use-after-move for a copied @move_only value, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_copy_of_plain_type_keeps_source() {
+    let source = r#"
+struct Token {
+    int id;
+};
+
+// @safe
+void consume(const Token& t);
+
+// @safe
+void hand_off() {
+    Token a;
+    Token b = a;
+    consume(a);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("Use after move"),
+        "Copying an unannotated type should not move it, got: {}",
+        output
+    );
+}
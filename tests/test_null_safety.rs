@@ -534,3 +534,329 @@ void process(int* ptr) {
     // Assert patterns could narrow state if we had annotation support
     println!("Output: {}", output);
 }
+
+// ============================================================================
+// Test: Smart pointers that are provably null
+// ============================================================================
+
+#[test]
+fn test_deref_default_constructed_unique_ptr() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process() {
+    std::unique_ptr<int> p;
+    return *p;  // ERROR: p is empty
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("dereference of null pointer 'p'"),
+        "Expected null dereference error for empty unique_ptr. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_deref_unique_ptr_after_reset_on_one_branch() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process(bool cond) {
+    std::unique_ptr<int> p = std::make_unique<int>(1);
+    int result = 0;
+    if (cond) {
+        p.reset();
+        result = *p;  // ERROR: p was just reset
+    } else {
+        result = *p;  // OK: p still owns its value
+    }
+    return result;
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("dereference of null pointer 'p'"),
+        "Expected null dereference error on the reset branch. Output: {}",
+        output
+    );
+    assert_eq!(
+        output.matches("dereference of null pointer").count(),
+        1,
+        "Only the reset branch should be flagged. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_unique_ptr_reassigned_after_nullptr_ok() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process() {
+    std::unique_ptr<int> p = nullptr;
+    p = std::make_unique<int>(2);
+    return *p;  // OK: reassigned to a fresh allocation
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        !output.contains("dereference of null pointer"),
+        "Reassigned unique_ptr should not be flagged. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_unique_ptr_param_not_flagged() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process(std::unique_ptr<int> p) {
+    return *p;  // Not provably null - no error
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        !output.contains("dereference of null pointer"),
+        "Smart pointer parameters are not provably null. Output: {}",
+        output
+    );
+}
+
+// ============================================================================
+// Test: Null-check narrowing for smart pointers
+// ============================================================================
+
+#[test]
+fn test_guarded_smart_pointer_deref_accepted() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process() {
+    std::unique_ptr<int> p;
+    if (p) {
+        return *p;  // OK: guard proves p non-null
+    }
+    if (p != nullptr) {
+        return *p;  // OK
+    }
+    return 0;
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        !output.contains("dereference of null pointer"),
+        "Guarded dereference should be accepted. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_else_branch_smart_pointer_deref_rejected() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process(std::unique_ptr<int> p) {
+    if (p) {
+        return *p;  // OK
+    } else {
+        return *p;  // ERROR: p is null here
+    }
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("dereference of null pointer 'p'"),
+        "Dereference in the else-branch of if (p) should be rejected. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_inverted_guard_smart_pointer() {
+    let code = r#"
+#include <memory>
+
+// @safe
+int process(std::unique_ptr<int> p) {
+    if (!p) {
+        return *p;  // ERROR: p is null here
+    }
+    return *p;  // OK: early return proved p non-null
+}
+"#;
+    let output = run_checker(code);
+    assert_eq!(
+        output.matches("dereference of null pointer 'p'").count(),
+        1,
+        "Only the dereference inside if (!p) should be rejected. Output: {}",
+        output
+    );
+}
+
+// ============================================================================
+// Test: weak_ptr::lock() results must be checked before use
+// ============================================================================
+
+#[test]
+fn test_unchecked_weak_ptr_lock_deref_rejected() {
+    let code = r#"
+#include <memory>
+
+struct Widget { int value; };
+
+// @safe
+int process(std::weak_ptr<Widget> wp) {
+    std::shared_ptr<Widget> sp = wp.lock();
+    return sp->value;  // ERROR: the object may have expired
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        output.contains("dereference of possibly-empty locked weak_ptr 'sp'"),
+        "Unchecked lock() result should be rejected. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_checked_weak_ptr_lock_deref_accepted() {
+    let code = r#"
+#include <memory>
+
+struct Widget { int value; };
+
+// @safe
+int process(std::weak_ptr<Widget> wp) {
+    std::shared_ptr<Widget> sp = wp.lock();
+    if (sp) {
+        return sp->value;  // OK: checked
+    }
+    std::shared_ptr<Widget> other = wp.lock();
+    if (!other) {
+        return 0;
+    }
+    return other->value;  // OK: early return on empty
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        !output.contains("possibly-empty locked weak_ptr"),
+        "Checked lock() results should be accepted. Output: {}",
+        output
+    );
+}
+
+// ============================================================================
+// Test: gsl::not_null is never null
+// ============================================================================
+
+#[test]
+fn test_gsl_not_null_deref_accepted() {
+    let code = r#"
+namespace gsl {
+template <class T>
+class not_null {
+public:
+    not_null(T p) : ptr_(p) {}
+    T get() const { return ptr_; }
+    operator T() const { return ptr_; }
+    decltype(auto) operator*() const { return *ptr_; }
+private:
+    T ptr_;
+};
+}
+
+// @safe
+int read(gsl::not_null<int*> p) {
+    return *p;  // OK: not_null needs no check
+}
+"#;
+    let output = run_checker(code);
+    assert!(
+        !output.contains("potentially null") && !output.contains("MaybeNull"),
+        "gsl::not_null should be known non-null. Output: {}",
+        output
+    );
+}
+
+// ============================================================================
+// std::optional engagement
+// ============================================================================
+
+#[test]
+fn test_reset_optional_value_rejected() {
+    let code = r#"
+#include <optional>
+
+struct Box {
+    int v;
+};
+
+// @safe
+int read_after_reset() {
+    std::optional<Box> o;
+    o.emplace();
+    o.reset();
+    return o.value().v;  // ERROR: o is disengaged
+}
+
+// @safe
+int read_checked() {
+    std::optional<Box> o;
+    o.emplace();
+    o.reset();
+    if (o.has_value()) {
+        return o.value().v;  // OK: narrowed by has_value()
+    }
+    return 0;
+}
+"#;
+    let output = run_checker(code);
+    assert_eq!(
+        output.matches("access to disengaged optional 'o'").count(),
+        1,
+        "only the unchecked value() after reset() should be reported. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_optional_value_read_after_move_rejected() {
+    let code = r#"
+#include <optional>
+#include <string>
+#include <utility>
+
+// @safe
+int read_after_move() {
+    std::optional<std::string> o = std::string("hello");
+    std::string taken = std::move(o).value();
+    return o.value().size();  // ERROR: the string was moved out
+}
+
+// @safe
+int read_after_refill() {
+    std::optional<std::string> o = std::string("hello");
+    std::string taken = std::move(*o);
+    o.emplace("again");
+    return o.value().size();  // OK: emplace gave it a new value
+}
+"#;
+    let output = run_checker(code);
+    assert_eq!(
+        output
+            .matches("the value in optional 'o' was moved out")
+            .count(),
+        1,
+        "only the read after the move should be reported. Output: {}",
+        output
+    );
+}
@@ -0,0 +1,86 @@
+/// Tests for `--output`, which writes the `--format` report to a file while
+/// the human-readable listing still goes to stdout
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer(cpp_file: &Path, extra_args: &[&str]) -> (bool, String) {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--", cpp_file.to_str().unwrap()])
+        .args(extra_args)
+        .env("Z3_SYS_Z3_HEADER", z3_header);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+const USE_AFTER_MOVE: &str = r#"
+#include <utility>
+#include <string>
+
+// @safe
+void moves() {
+    std::string a = "a";
+    std::string b = std::move(a);
+    std::string c = a;
+}
+"#;
+
+#[test]
+fn test_json_output_file_is_valid_json() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("moves.cpp");
+    fs::write(&cpp_file, USE_AFTER_MOVE).unwrap();
+    let out_file = dir.path().join("out.json");
+
+    let (success, stdout) = run_analyzer(
+        &cpp_file,
+        &["--format", "json", "--output", out_file.to_str().unwrap()],
+    );
+
+    assert!(!success, "use-after-move should still fail the run");
+    assert!(
+        stdout.contains("Found 1 violation(s)"),
+        "human listing should still be printed. Stdout: {}",
+        stdout
+    );
+
+    let contents = fs::read_to_string(&out_file).expect("out.json should be written");
+    let json: serde_json::Value =
+        serde_json::from_str(&contents).expect("out.json should be valid JSON");
+    assert_eq!(json["summary"]["total"], 1, "Report: {}", contents);
+    assert_eq!(json["violations"][0]["kind"], "use-after-move");
+}
+
+#[test]
+fn test_text_output_file() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("moves.cpp");
+    fs::write(&cpp_file, USE_AFTER_MOVE).unwrap();
+    let out_file = dir.path().join("report.txt");
+
+    run_analyzer(&cpp_file, &["--output", out_file.to_str().unwrap()]);
+
+    let contents = fs::read_to_string(&out_file).expect("report.txt should be written");
+    assert!(
+        contents.contains("Summary: 1 use-after-move, 1 file affected"),
+        "Report: {}",
+        contents
+    );
+}
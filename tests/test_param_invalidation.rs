@@ -0,0 +1,62 @@
+//! A reference parameter may point into a container parameter of the same
+//! element type; resizing the container then leaves it dangling. The check
+//! is noisy, so it only runs with `--pedantic` or when enabled explicitly.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str, args: &[&str]) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("param_invalidation.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+const APPEND_TWICE: &str = r#"
+#include <vector>
+
+// @safe
+void append_twice(std::vector<int>& v, int& x) {
+    v.push_back(x);
+    v.push_back(x);
+}
+"#;
+
+#[test]
+fn test_reference_into_resized_container_warns() {
+    let stdout = check(APPEND_TWICE, &["--enable", "param-invalidation"]);
+    assert!(
+        stdout.contains(
+            "reference parameter 'x' used at line 7 may be invalidated by 'v.push_back()' at line 6"
+        ),
+        "expected a possible-invalidation warning. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_check_is_off_by_default() {
+    let stdout = check(APPEND_TWICE, &[]);
+    assert!(
+        !stdout.contains("may be invalidated"),
+        "param-invalidation should not run by default. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_pedantic_enables_check() {
+    let stdout = check(APPEND_TWICE, &["--pedantic"]);
+    assert!(
+        stdout.contains("reference parameter 'x' used at line 7"),
+        "--pedantic should run param-invalidation. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,59 @@
+//! `return std::move(local);` prevents copy elision of a local that already
+//! has the function's return type.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("pessimizing_move.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_return_std_move_of_local_warns() {
+    let stdout = check(
+        r#"
+#include <string>
+#include <utility>
+
+// @safe
+std::string build() {
+    std::string s = "a";
+    return std::move(s);
+}
+"#,
+    );
+    assert!(
+        stdout.contains("std::move in return prevents copy elision of local 's'"),
+        "expected a pessimizing-move warning. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_return_std_move_of_parameter_ok() {
+    let stdout = check(
+        r#"
+#include <string>
+#include <utility>
+
+// @safe
+std::string pass(std::string s) {
+    return std::move(s);
+}
+"#,
+    );
+    assert!(
+        !stdout.contains("prevents copy elision"),
+        "moving a parameter loses no elision. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,109 @@
+//! Writing through a pointer while a reference to its target is live
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("pointee_borrow.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Check `source` next to an annotated `util.h`, which turns on the
+/// signature-based lifetime passes
+fn check_with_header(source: &str) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    fs::write(
+        dir.path().join("util.h"),
+        r#"
+#pragma once
+
+// @lifetime: (&'a) -> &'a
+const int& identity(const int& x);
+"#,
+    )
+    .expect("write header");
+    let file_path = dir.path().join("pointee_borrow.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn write_through_pointer_while_pointee_borrowed_is_rejected() {
+    let stdout = check(
+        r#"
+void use(const int& x);
+
+// @safe
+void f(int* p) {
+    const int& r = *p;
+    *p = 5;
+    use(r);
+}
+"#,
+    );
+
+    assert!(
+        stdout.contains("Cannot assign to '*p' because it is borrowed by: r"),
+        "expected `*p = 5` to conflict with the live borrow 'r'. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn reseating_pointer_does_not_conflict_with_pointee_borrow() {
+    let stdout = check(
+        r#"
+void use(const int& x);
+
+// @safe
+void f(int* p, int* q) {
+    const int& r = *p;
+    p = q;
+    *p = 5;
+    use(r);
+}
+"#,
+    );
+
+    assert!(
+        !stdout.contains("Cannot assign to"),
+        "re-seating 'p' leaves 'r' on the old target. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn pointee_borrow_with_annotated_header_is_alive() {
+    let stdout = check_with_header(
+        r#"
+#include "util.h"
+
+void use(const int& x);
+
+// @safe
+void f(int* p) {
+    const int& r = *p;
+    use(identity(r));
+}
+"#,
+    );
+
+    assert!(
+        !stdout.contains("Cannot borrow from '*p'") && !stdout.contains("'*p' which is not alive"),
+        "the target of 'p' outlives the function. Output: {}",
+        stdout
+    );
+}
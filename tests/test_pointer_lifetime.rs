@@ -258,3 +258,39 @@ void caller() {
     // Clean up
     let _ = fs::remove_file("/tmp/test_ptr_ref_mixed.cpp");
 }
+
+#[test]
+fn test_pointer_to_local_outlives_scope() {
+    // Test: storing &local into a pointer declared in an outer scope
+    let test_code = r#"
+// @safe
+void outlives() {
+    int* p = nullptr;
+    {
+        int local = 42;
+        // @unsafe
+        {
+            p = &local;
+        }
+    }
+}
+"#;
+
+    fs::write("/tmp/test_ptr_outlives_scope.cpp", test_code).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "/tmp/test_ptr_outlives_scope.cpp"])
+        .output()
+        .expect("Failed to run borrow checker");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Dangling reference: 'p' borrows from 'local' which goes out of scope"),
+        "Should detect 'p' outliving 'local'. Output: {}",
+        stdout
+    );
+
+    // Clean up
+    let _ = fs::remove_file("/tmp/test_ptr_outlives_scope.cpp");
+}
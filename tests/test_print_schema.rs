@@ -0,0 +1,27 @@
+//! `--print-schema` describes the `--format json` report
+
+use std::process::Command;
+
+#[test]
+fn test_print_schema_lists_every_kind() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--print-schema")
+        .output()
+        .expect("run checker");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let schema: serde_json::Value = serde_json::from_str(&stdout).expect("schema is JSON");
+    assert_eq!(schema["type"], "object");
+
+    let kinds = schema["$defs"]["violation"]["properties"]["kind"]["enum"]
+        .as_array()
+        .expect("kind enum");
+    for kind in rusty_cpp::analysis::ErrorKind::ALL {
+        assert!(
+            kinds.contains(&kind.name().into()),
+            "schema is missing kind '{}'",
+            kind.name()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+/// Tests for whole-program `--project` mode, where annotations from one
+/// translation unit inform checks in another
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_project(compile_commands: &Path) -> (bool, String) {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "run",
+        "--quiet",
+        "--",
+        "--project",
+        compile_commands.to_str().unwrap(),
+    ])
+    .env("Z3_SYS_Z3_HEADER", z3_header);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    (output.status.success(), format!("{}{}", stdout, stderr))
+}
+
+/// Write `lib.cpp` (defines a lifetime-annotated function) and `app.cpp`
+/// (calls it through a plain declaration) plus a compile_commands.json
+fn write_project(dir: &Path, app_body: &str) -> std::path::PathBuf {
+    fs::write(
+        dir.join("lib.cpp"),
+        r#"
+// @safe
+// @lifetime: (&'a) -> &'a
+const int& identity(const int& x) {
+    return x;
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("app.cpp"),
+        format!(
+            "const int& identity(const int& x);\n\n// @safe\nvoid caller() {{\n{}\n}}\n",
+            app_body
+        ),
+    )
+    .unwrap();
+
+    let directory = dir.to_str().unwrap();
+    let compile_commands = dir.join("compile_commands.json");
+    fs::write(
+        &compile_commands,
+        format!(
+            r#"[
+  {{"directory": "{0}", "file": "lib.cpp", "arguments": ["clang++", "-std=c++17", "-c", "lib.cpp"]}},
+  {{"directory": "{0}", "file": "app.cpp", "arguments": ["clang++", "-std=c++17", "-c", "app.cpp"]}}
+]"#,
+            directory
+        ),
+    )
+    .unwrap();
+    compile_commands
+}
+
+#[test]
+fn test_project_cross_tu_lifetime_violation() {
+    let dir = TempDir::new().unwrap();
+    let compile_commands = write_project(
+        dir.path(),
+        "    const int& ref = identity(42);  // ERROR: tied to a temporary",
+    );
+
+    let (success, output) = run_project(&compile_commands);
+    assert!(!success, "Cross-TU misuse should fail. Output: {}", output);
+    assert!(
+        output.contains("app.cpp") && output.contains("temporary"),
+        "Lifetime annotation from lib.cpp should apply in app.cpp. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_project_cross_tu_safe_call_allowed() {
+    let dir = TempDir::new().unwrap();
+    let compile_commands = write_project(
+        dir.path(),
+        "    int value = 1;\n    const int& ref = identity(value);",
+    );
+
+    let (success, output) = run_project(&compile_commands);
+    assert!(
+        success,
+        "@safe annotation from lib.cpp should make the call in app.cpp safe. Output: {}",
+        output
+    );
+}
@@ -0,0 +1,56 @@
+//! `--quiet` is meant for pre-commit hooks: stdout carries only violations
+//! and the exit code carries the result.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run(source: &str) -> (Option<i32>, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--quiet")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_quiet_clean_file_prints_nothing() {
+    let (code, output) = run(r#"
+// @safe
+int add(int a, int b) {
+    return a + b;
+}
+"#);
+    assert_eq!(code, Some(0), "{}", output);
+    assert!(output.is_empty(), "expected no output, got: {}", output);
+}
+
+#[test]
+fn test_quiet_prints_only_violations() {
+    let (code, output) = run(r#"
+// @safe
+void bad() {
+    int x = 1;
+    int* p = &x;
+}
+"#);
+    assert_eq!(code, Some(1), "{}", output);
+    assert!(!output.contains("Rusty C++ Checker"), "{}", output);
+    assert!(!output.contains("Analyzing"), "{}", output);
+    assert!(!output.contains("Found"), "{}", output);
+    assert!(
+        output
+            .lines()
+            .all(|line| line.starts_with("In function 'bad'")),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,104 @@
+//! Modifying the container a range-for iterates over invalidates the loop's
+//! iterators and the reference to the current element.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_push_back_inside_range_for() {
+    let source = r#"
+#include <vector>
+
+// @safe
+void extend(std::vector<int>& v, int x) {
+    for (auto& e : v) {
+        if (e == x) {
+            v.push_back(x);
+        }
+    }
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Container 'v' is modified by 'push_back()'"),
+        "Expected the push_back inside the loop to be flagged, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_modifying_other_container_is_ok() {
+    let source = r#"
+#include <vector>
+
+// @safe
+void copy_matching(const std::vector<int>& v, std::vector<int>& out, int x) {
+    for (const auto& e : v) {
+        if (e == x) {
+            out.push_back(e);
+        }
+    }
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("is modified by"),
+        "Only the iterated container matters, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_modifying_then_leaving_the_loop_is_ok() {
+    let source = r#"
+#include <vector>
+
+// @safe
+void remove_first(std::vector<int>& v, int x) {
+    for (auto& e : v) {
+        if (e == x) {
+            v.clear();
+            break;
+        }
+    }
+}
+
+// @safe
+bool add_once(std::vector<int>& v, int x) {
+    for (auto& e : v) {
+        if (e == x) {
+            v.push_back(x);
+            return true;
+        }
+    }
+    return false;
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("is modified by"),
+        "The loop is left before its iterators are used again, got: {}",
+        output
+    );
+}
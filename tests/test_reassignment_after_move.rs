@@ -387,3 +387,32 @@ int main() { return 0; }
         output
     );
 }
+
+#[test]
+fn test_factory_reassignment_after_move() {
+    // A factory call gives the moved-from pointer a fresh value
+    let source = r#"
+#include <memory>
+#include <utility>
+
+void consume(std::unique_ptr<int> p);
+void use(const std::unique_ptr<int>& p);
+
+// @safe
+void test() {
+    auto p = std::make_unique<int>(1);
+    consume(std::move(p));  // p is moved
+    p = std::make_unique<int>(2);
+    use(p);                 // Should be OK - p holds a new object
+}
+
+int main() { return 0; }
+"#;
+
+    let (success, output) = analyze(source);
+    assert!(
+        success,
+        "Factory reassignment after move should be valid. Got error: {}",
+        output
+    );
+}
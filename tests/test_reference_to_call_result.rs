@@ -0,0 +1,81 @@
+//! A reference bound to a call result borrows from the receiver only when
+//! the callee returns a reference. A by-value result is a temporary whose
+//! lifetime is extended to the reference's scope.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+const HOLDER: &str = r#"
+#include <string>
+#include <utility>
+
+// @safe
+class Holder {
+public:
+    const std::string& label() const { return label_; }
+    std::string copy() const { return label_; }
+private:
+    std::string label_;
+};
+
+// @safe
+void consume(const std::string& s) {}
+"#;
+
+#[test]
+fn test_reference_returning_call_borrows_receiver() {
+    let source = format!(
+        "{}{}",
+        HOLDER,
+        r#"
+// @safe
+void by_reference() {
+    Holder h;
+    const std::string& r = h.label();
+    Holder moved = std::move(h);
+    consume(r);
+}
+"#
+    );
+    let (success, output) = check(&source);
+    assert!(!success, "{}", output);
+    assert!(
+        output.contains("Cannot move 'h' because it is borrowed by"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_value_returning_call_extends_temporary() {
+    let source = format!(
+        "{}{}",
+        HOLDER,
+        r#"
+// @safe
+void by_value() {
+    Holder h;
+    const std::string& r = h.copy();
+    Holder moved = std::move(h);
+    consume(r);
+}
+"#
+    );
+    let (success, output) = check(&source);
+    assert!(!output.contains("borrowed"), "{}", output);
+    assert!(success, "{}", output);
+}
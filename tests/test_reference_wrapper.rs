@@ -0,0 +1,95 @@
+//! `std::reference_wrapper` (and `std::ref`/`std::cref`, which build one)
+//! stores a reference, so a wrapper that outlives its referent dangles.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_ref_to_local_stored_in_outer_wrapper() {
+    let (success, output) = check(
+        r#"
+#include <functional>
+
+// @safe
+int dangling() {
+    int outer = 1;
+    std::reference_wrapper<int> w = std::ref(outer);
+    {
+        int local = 2;
+        w = std::ref(local);
+    }
+    return w.get();
+}
+"#,
+    );
+    assert!(!success, "{}", output);
+    assert!(
+        output.contains("Dangling reference: 'w' borrows from 'local'"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_wrapper_in_same_scope_as_referent_ok() {
+    let (success, output) = check(
+        r#"
+#include <functional>
+
+// @safe
+int fine() {
+    int value = 1;
+    std::reference_wrapper<const int> w = std::cref(value);
+    return w.get();
+}
+"#,
+    );
+    assert!(success, "{}", output);
+    assert!(!output.contains("Dangling reference"), "{}", output);
+}
+
+#[test]
+fn test_copied_wrapper_borrows_the_referent() {
+    let (success, output) = check(
+        r#"
+#include <functional>
+
+// @safe
+int copied() {
+    int outer = 1;
+    std::reference_wrapper<int> w = std::ref(outer);
+    {
+        std::reference_wrapper<int> inner = std::ref(outer);
+        w = inner;
+    }
+    {
+        int local = 2;
+        std::reference_wrapper<int> inner = std::ref(local);
+        w = inner;
+    }
+    return w.get();
+}
+"#,
+    );
+    assert!(!success, "{}", output);
+    assert!(
+        output.contains("Dangling reference: 'w' borrows from 'local'"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("borrows from 'inner'"), "{}", output);
+}
@@ -107,3 +107,43 @@ void misuse() {
         output
     );
 }
+
+#[test]
+fn test_return_of_moved_local_points_at_move() {
+    // `consume(std::move(x)); return x;` returns a moved-from value. The
+    // diagnostic should name the line of the earlier move.
+    let temp_dir = TempDir::new().unwrap();
+
+    let cpp = r#"
+#include <utility>
+
+struct Owned {
+    int x;
+};
+
+void consume(Owned o);
+
+// @safe
+Owned misuse() {
+    Owned x{42};
+    consume(std::move(x));
+    return x;
+}
+"#;
+
+    let cpp_path = temp_dir.path().join("return_moved_local.cpp");
+    fs::write(&cpp_path, cpp).unwrap();
+
+    let (success, output) = run_analyzer_on_file(&cpp_path);
+
+    assert!(
+        !success,
+        "returning a moved local must fail.\nOutput:\n{}",
+        output
+    );
+    assert!(
+        output.contains("Cannot return 'x' because it has been moved (moved at line 13)"),
+        "expected the move site in the diagnostic.\nOutput:\n{}",
+        output
+    );
+}
@@ -0,0 +1,93 @@
+//! Returning a `std::pair`/`std::tuple` whose reference elements refer to
+//! locals or by-value parameters leaves the caller with dangling references.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_make_pair_of_reference_to_local() {
+    let (success, stdout) = check(
+        r#"
+#include <utility>
+
+// @safe
+std::pair<const int&, const int&> pick(const int& x) {
+    int local = 1;
+    const int& ref_to_local = local;
+    return std::make_pair(ref_to_local, x);
+}
+"#,
+    );
+    assert!(
+        !success,
+        "dangling pair element should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("element 0 of the returned pair refers to local 'local'"),
+        "should name the dangling element. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_tie_of_by_value_parameter() {
+    let (success, stdout) = check(
+        r#"
+#include <tuple>
+
+// @safe
+std::tuple<int&, int&> both(int a, int& b) {
+    return std::tie(a, b);
+}
+"#,
+    );
+    assert!(
+        !success,
+        "tie of a by-value parameter should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("element 0 of the returned tuple refers to by-value parameter 'a'"),
+        "Output: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("element 1"),
+        "'b' outlives the call. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_pair_of_reference_parameters_ok() {
+    let (_, stdout) = check(
+        r#"
+#include <utility>
+
+// @safe
+std::pair<int&, int&> both(int& a, int& b) {
+    return {a, b};
+}
+"#,
+    );
+    assert!(
+        !stdout.contains("returned pair"),
+        "reference parameters outlive the call. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,107 @@
+//! An `operator=` that frees a member and then copies it from the argument
+//! reads freed memory on `x = x` unless it checks `this != &other` first.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_unguarded_delete_then_copy() {
+    let (success, stdout) = check(
+        r#"
+// @safe
+class Buffer {
+    int* data_;
+public:
+    Buffer& operator=(const Buffer& other) {
+        // @unsafe
+        {
+            delete data_;
+            data_ = new int(*other.data_);
+        }
+        return *this;
+    }
+};
+"#,
+    );
+    assert!(
+        !success,
+        "unguarded operator= should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("self-assignment would use after free: member 'data_'"),
+        "Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_unguarded_reset_then_copy() {
+    let (success, stdout) = check(
+        r#"
+#include <memory>
+
+// @safe
+class Holder {
+    std::unique_ptr<int> value_;
+public:
+    Holder& operator=(const Holder& other) {
+        value_.reset();
+        value_ = std::make_unique<int>(*other.value_);
+        return *this;
+    }
+};
+"#,
+    );
+    assert!(
+        !success,
+        "unguarded operator= should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("member 'value_' is released"),
+        "Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_guarded_operator_not_flagged() {
+    let (_, stdout) = check(
+        r#"
+#include <memory>
+
+// @safe
+class Holder {
+    std::unique_ptr<int> value_;
+public:
+    Holder& operator=(const Holder& other) {
+        if (this != &other) {
+            value_.reset();
+            value_ = std::make_unique<int>(*other.value_);
+        }
+        return *this;
+    }
+};
+"#,
+    );
+    assert!(
+        !stdout.contains("self-assignment"),
+        "guarded operator= is fine. Output: {}",
+        stdout
+    );
+}
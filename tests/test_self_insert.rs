@@ -0,0 +1,83 @@
+//! `push_back`/`emplace_back` passed an element of the same vector: growing
+//! the vector can invalidate the argument while the call is still using it.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_push_back_of_back() {
+    let source = r#"
+#include <string>
+#include <vector>
+
+// @safe
+void duplicate_last(std::vector<std::string>& v) {
+    v.push_back(v.back());
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("'v.push_back()'") && output.contains("'v.back()'"),
+        "Expected a self-insert warning for 'v', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_push_back_of_subscript() {
+    let source = r#"
+#include <string>
+#include <vector>
+
+// @safe
+void duplicate_first(std::vector<std::string>& v) {
+    v.emplace_back(v[0]);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("'v.emplace_back()'") && output.contains("'v[...]'"),
+        "Expected a self-insert warning for 'v', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_push_back_of_other_container() {
+    let source = r#"
+#include <string>
+#include <vector>
+
+// @safe
+void append_last(std::vector<std::string>& v, const std::vector<std::string>& w) {
+    v.push_back(w.back());
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("an element of 'v' itself"),
+        "The argument comes from another vector, got: {}",
+        output
+    );
+}
@@ -0,0 +1,105 @@
+//! Tests for `--allow <kind>` (silence a kind of violation) and
+//! `--deny-warnings` (warnings set the exit code too).
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer(args: &[&str]) -> (bool, String) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--"]).args(args);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("Z3_SYS_Z3_HEADER", "/opt/homebrew/include/z3.h");
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("Z3_SYS_Z3_HEADER", "/usr/include/z3.h");
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let full_output = format!("{}{}", stdout, stderr);
+
+    (output.status.success(), full_output)
+}
+
+const USE_AFTER_MOVE_CODE: &str = r#"
+#include <memory>
+#include <utility>
+
+// @safe
+void consume() {
+    std::unique_ptr<int> a = std::make_unique<int>(1);
+    std::unique_ptr<int> b = std::move(a);
+    int v = *a;
+}
+"#;
+
+const CLEAN_CODE: &str = r#"
+// @safe
+int add(int a, int b) {
+    return a + b;
+}
+"#;
+
+#[test]
+fn test_allow_silences_kind() {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_path = temp_dir.path().join("moved.cpp");
+    fs::write(&cpp_path, USE_AFTER_MOVE_CODE).unwrap();
+    let cpp = cpp_path.to_str().unwrap();
+
+    let (success, output) = run_analyzer(&[cpp]);
+    assert!(
+        !success && output.contains("moved"),
+        "use after move should be reported by default.\nOutput:\n{}",
+        output
+    );
+
+    let (success, output) = run_analyzer(&["--allow", "use-after-move", cpp]);
+    assert!(
+        success && output.contains("no violations found"),
+        "--allow use-after-move should silence the violation.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_unknown_allow_kind_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_path = temp_dir.path().join("clean.cpp");
+    fs::write(&cpp_path, CLEAN_CODE).unwrap();
+
+    let (success, output) = run_analyzer(&["--allow", "no-such-kind", cpp_path.to_str().unwrap()]);
+
+    assert!(
+        !success && output.contains("Unknown violation kind 'no-such-kind'"),
+        "unknown kind must be an error.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_deny_warnings_keeps_errors_and_clean_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let clean_path = temp_dir.path().join("clean.cpp");
+    fs::write(&clean_path, CLEAN_CODE).unwrap();
+    let moved_path = temp_dir.path().join("moved.cpp");
+    fs::write(&moved_path, USE_AFTER_MOVE_CODE).unwrap();
+
+    let (success, output) = run_analyzer(&["--deny-warnings", clean_path.to_str().unwrap()]);
+    assert!(
+        success,
+        "a clean file passes under --deny-warnings.\nOutput:\n{}",
+        output
+    );
+
+    let (success, output) = run_analyzer(&["--deny-warnings", moved_path.to_str().unwrap()]);
+    assert!(
+        !success,
+        "errors still fail under --deny-warnings.\nOutput:\n{}",
+        output
+    );
+}
@@ -0,0 +1,99 @@
+//! Copying a `std::shared_ptr` adds an owner and leaves the source usable;
+//! only `std::move` hands the source's ownership over.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_shared_ptr_copy_leaves_source_usable() {
+    let source = r#"
+#include <memory>
+
+void consume(std::shared_ptr<int> p);
+
+// @safe
+void test() {
+    std::shared_ptr<int> p = std::make_shared<int>(1);
+    std::shared_ptr<int> q = p;
+    consume(p);
+    consume(q);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("Use after move"),
+        "A copy should not move 'p', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_shared_ptr_move_flags_source() {
+    let source = r#"
+#include <memory>
+#include <utility>
+
+void consume(std::shared_ptr<int> p);
+
+// @safe
+void test() {
+    std::shared_ptr<int> p = std::make_shared<int>(1);
+    auto q = std::move(p);
+    consume(p);
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Use after move") && output.contains("'p'"),
+        "Expected a use-after-move error for 'p', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_wrapper_around_shared_ptr_still_borrows() {
+    // Only `std::shared_ptr<T>` itself is a shared pointer; a wrapper or
+    // container with a shared_ptr argument keeps its own semantics
+    let source = r#"
+#include <functional>
+#include <memory>
+
+// @safe
+int dangling() {
+    std::shared_ptr<int> outer = std::make_shared<int>(1);
+    std::reference_wrapper<std::shared_ptr<int>> w = std::ref(outer);
+    {
+        std::shared_ptr<int> local = std::make_shared<int>(2);
+        w = std::ref(local);
+    }
+    return *w.get();
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Dangling reference: 'w' borrows from 'local'"),
+        "Expected the wrapper to dangle, got: {}",
+        output
+    );
+}
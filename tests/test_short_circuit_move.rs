@@ -0,0 +1,95 @@
+//! The right-hand side of `&&` / `||` is only evaluated on one outcome of
+//! the left-hand side, so a move there is a conditional move: like a move in
+//! one branch of an `if`, the variable counts as moved afterwards.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let cpp_file = temp_dir.path().join("test.cpp");
+    fs::write(&cpp_file, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&cpp_file)
+        .output()
+        .expect("Failed to run checker");
+
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+fn test_move_in_and_rhs_then_use() {
+    let source = r#"
+#include <memory>
+#include <utility>
+
+bool consume(std::unique_ptr<int> p);
+
+// @safe
+void test(bool ready) {
+    std::unique_ptr<int> x = std::make_unique<int>(1);
+    ready && consume(std::move(x));
+    consume(std::move(x));
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Use after move") && output.contains("'x'"),
+        "Expected a use-after-move error for 'x', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_move_in_or_rhs_then_use() {
+    let source = r#"
+#include <memory>
+#include <utility>
+
+bool consume(std::unique_ptr<int> p);
+
+// @safe
+void test(bool done) {
+    std::unique_ptr<int> x = std::make_unique<int>(1);
+    done || consume(std::move(x));
+    consume(std::move(x));
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        output.contains("Use after move") && output.contains("'x'"),
+        "Expected a use-after-move error for 'x', got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_move_in_and_rhs_without_later_use() {
+    let source = r#"
+#include <memory>
+#include <utility>
+
+bool consume(std::unique_ptr<int> p);
+
+// @safe
+void test(bool ready) {
+    std::unique_ptr<int> x = std::make_unique<int>(1);
+    ready && consume(std::move(x));
+}
+"#;
+
+    let output = check(source);
+    assert!(
+        !output.contains("Use after move"),
+        "No use follows the move, got: {}",
+        output
+    );
+}
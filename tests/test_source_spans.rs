@@ -0,0 +1,105 @@
+/// Tests that violations carry the full libclang source range of the
+/// statement they point at, surfaced as `span` in `--format json`
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_json_report(cpp_file: &Path, out_file: &Path) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(cpp_file)
+        .args(["--pedantic", "--format", "json", "--output"])
+        .arg(out_file)
+        .output()
+        .expect("Failed to execute analyzer");
+    let report = fs::read_to_string(out_file).unwrap_or_else(|_| {
+        panic!(
+            "no report written. Stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    serde_json::from_str(&report).expect("report is JSON")
+}
+
+#[test]
+fn test_move_expression_span_covers_whole_call() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("span.cpp");
+    // `std::move(s)` starts at column 5 and its closing paren is column 16
+    fs::write(
+        &cpp_file,
+        r#"#include <string>
+#include <utility>
+
+// @safe
+void f() {
+    const std::string s = "a";
+    std::move(s);
+}
+"#,
+    )
+    .unwrap();
+    let out_file = dir.path().join("out.json");
+
+    let json = run_json_report(&cpp_file, &out_file);
+    let violations = json["violations"].as_array().unwrap();
+    let violation = violations
+        .iter()
+        .find(|v| {
+            v["message"]
+                .as_str()
+                .unwrap()
+                .contains("std::move of const 's'")
+        })
+        .unwrap_or_else(|| panic!("expected a move-from-const warning: {:#}", json));
+
+    let span = &violation["span"];
+    assert_eq!(span["line"], 7);
+    assert_eq!(span["column"], 5);
+    assert_eq!(span["end_line"], 7);
+    assert_eq!(
+        span["end_column"], 17,
+        "span must end after `)`: {:#}",
+        span
+    );
+}
+
+#[test]
+fn test_span_is_the_statement_nested_on_the_line() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("span.cpp");
+    // The `if` starts on line 7 too, but the move is its branch at column 20
+    fs::write(
+        &cpp_file,
+        r#"#include <string>
+#include <utility>
+
+// @safe
+void f() {
+    const std::string s = "a";
+    if (s.empty()) std::move(s);
+}
+"#,
+    )
+    .unwrap();
+    let out_file = dir.path().join("out.json");
+
+    let json = run_json_report(&cpp_file, &out_file);
+    let violation = json["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| {
+            v["message"]
+                .as_str()
+                .unwrap()
+                .contains("std::move of const 's'")
+        })
+        .unwrap_or_else(|| panic!("expected a move-from-const warning: {:#}", json));
+
+    let span = &violation["span"];
+    assert_eq!(span["line"], 7);
+    assert_eq!(span["column"], 20, "{:#}", span);
+    assert_eq!(span["end_line"], 7);
+    assert_eq!(span["end_column"], 32, "{:#}", span);
+}
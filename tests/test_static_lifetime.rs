@@ -0,0 +1,73 @@
+//! `@lifetime(static)` marks a function returning a reference to a static
+//! local or global. It satisfies the annotation requirement for @safe
+//! functions returning references, and call results borrow from nothing.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_static_local_reference_accepted_with_annotation() {
+    for annotation in ["// @lifetime(static)", "// @lifetime: &'static"] {
+        let source = format!(
+            r#"
+// @safe
+{}
+int& counter() {{
+    static int count = 0;
+    return count;
+}}
+
+// @safe
+void bump(int& other) {{
+    int& c = counter();
+    c += 1;
+    other = c;
+}}
+"#,
+            annotation
+        );
+        let (success, stdout) = check(&source);
+        assert!(
+            success,
+            "'static return should be accepted with {}. Output: {}",
+            annotation, stdout
+        );
+    }
+}
+
+#[test]
+fn test_static_local_reference_flagged_without_annotation() {
+    let (success, stdout) = check(
+        r#"
+// @safe
+int& counter() {
+    static int count = 0;
+    return count;
+}
+"#,
+    );
+    assert!(
+        !success,
+        "missing annotation should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("returns a reference but has no @lifetime annotation"),
+        "Output: {}",
+        stdout
+    );
+}
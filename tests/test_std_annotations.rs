@@ -404,3 +404,84 @@ fn test_external_unsafe_with_block_succeeds() {
         output
     );
 }
+
+// ============================================================================
+// Tests for namespace-wide external annotations
+// ============================================================================
+
+#[test]
+fn test_external_namespace_safe_annotation_allows_direct_calls() {
+    // Every function under an annotated namespace prefix picks up its safety
+    let code = r#"
+    // @external: {
+    //   namespace: [safe, "legacy::"]
+    // }
+
+    namespace legacy {
+        void open();
+        namespace io { void flush(); }
+    }
+
+    // @safe
+    void caller() {
+        legacy::open();      // OK: legacy:: is marked [safe]
+        legacy::io::flush(); // OK: nested namespaces share the prefix
+    }
+
+    int main() {
+        caller();
+        return 0;
+    }
+    "#;
+
+    let temp_file = create_temp_cpp_file(code);
+    let (success, output) = run_analyzer(temp_file.path());
+
+    assert!(
+        success,
+        "Namespace [safe] annotation should allow direct calls from @safe code. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_external_namespace_unsafe_annotation_requires_unsafe_block() {
+    let code = r#"
+    // @external: {
+    //   namespace: [unsafe, "legacy::"]
+    // }
+
+    namespace legacy {
+        void open();
+        void close();
+    }
+
+    // @safe
+    void caller() {
+        // @unsafe
+        {
+            legacy::open();  // OK: in @unsafe block
+        }
+        legacy::close();     // ERROR: legacy:: is marked [unsafe]
+    }
+
+    int main() {
+        caller();
+        return 0;
+    }
+    "#;
+
+    let temp_file = create_temp_cpp_file(code);
+    let (success, output) = run_analyzer(temp_file.path());
+
+    assert!(
+        !success,
+        "Namespace [unsafe] annotation should require @unsafe block. Output: {}",
+        output
+    );
+    assert!(
+        output.contains("close") && !output.contains("legacy::open"),
+        "Only the call outside the @unsafe block should be reported. Output: {}",
+        output
+    );
+}
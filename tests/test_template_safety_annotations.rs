@@ -290,3 +290,48 @@ void safe_caller() {
         full_output
     );
 }
+
+#[test]
+fn test_safe_template_annotation_covers_specialization() {
+    // The explicit specialization has no annotation of its own; it is an
+    // instantiation of the @safe template, so it is checked too
+    let source = r#"
+#include <utility>
+
+struct Widget {
+    int value = 0;
+    void touch() { value += 1; }
+};
+
+// @safe
+template <typename T>
+void consume(T item) {
+    T kept = std::move(item);
+}
+
+template <>
+void consume<Widget>(Widget item) {
+    Widget kept = std::move(item);
+    item.touch();
+}
+
+void run() {
+    consume<int>(1);
+    consume<Widget>(Widget{});
+}
+"#;
+
+    let (_temp_dir, source_path) = create_temp_file_with_suffix(source, ".cpp");
+    let (success, output) = run_analyzer(&source_path);
+
+    assert!(
+        !success,
+        "Use after move in the specialization should be reported. Output: {}",
+        output
+    );
+    assert!(
+        output.contains("moved"),
+        "Expected a use-after-move violation. Output: {}",
+        output
+    );
+}
@@ -425,6 +425,65 @@ int main() { return 0; }
     );
 }
 
+// =============================================================================
+// Tests for std::move(*this) (only && methods own the whole object)
+// =============================================================================
+
+#[test]
+fn test_nonconst_method_cannot_move_self() {
+    let source = r#"
+#include <utility>
+
+// @safe
+class Builder {
+    int data;
+public:
+    Builder build() {
+        return std::move(*this);  // ERROR: &mut self can't give itself away
+    }
+};
+
+int main() { return 0; }
+"#;
+
+    let (success, output) = analyze(source);
+    assert!(
+        !success,
+        "std::move(*this) should be rejected in non-const method. Output: {}",
+        output
+    );
+    assert!(
+        output.contains("Cannot move '*this' from &mut self method"),
+        "Expected error about moving *this. Output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_rvalue_method_can_move_self() {
+    let source = r#"
+#include <utility>
+
+// @safe
+class Builder {
+    int data;
+public:
+    Builder build() && {
+        return std::move(*this);  // OK: && method owns *this
+    }
+};
+
+int main() { return 0; }
+"#;
+
+    let (success, output) = analyze(source);
+    assert!(
+        success,
+        "std::move(*this) should be allowed in && method. Output: {}",
+        output
+    );
+}
+
 // =============================================================================
 // Combined tests
 // =============================================================================
@@ -0,0 +1,74 @@
+//! `std::tie(a, b) = std::move(p);` moves `p`'s components into `a` and `b`:
+//! `p` is moved afterwards, and `a`/`b` hold values again.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_source_moved_by_tie_assignment() {
+    let (success, stdout) = check(
+        r#"
+#include <string>
+#include <tuple>
+#include <utility>
+
+void use(const std::pair<std::string, std::string>& p);
+
+// @safe
+void split(std::pair<std::string, std::string> p) {
+    std::string a;
+    std::string b;
+    std::tie(a, b) = std::move(p);
+    use(p);
+}
+"#,
+    );
+    assert!(
+        !success,
+        "reading the moved pair should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("moved"),
+        "Expected use-after-move error. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_tied_targets_owned_again() {
+    let (success, stdout) = check(
+        r#"
+#include <string>
+#include <tuple>
+#include <utility>
+
+void consume(std::string s);
+void use(const std::string& s);
+
+// @safe
+void refill(std::pair<std::string, std::string> p) {
+    std::string a;
+    std::string b;
+    consume(std::move(a));
+    std::tie(a, b) = std::move(p);
+    use(a);
+}
+"#,
+    );
+    assert!(success, "a is re-assigned by std::tie. Output: {}", stdout);
+}
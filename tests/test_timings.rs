@@ -0,0 +1,52 @@
+//! `--timings` prints per-phase durations to stderr
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_timings_reports_parse_phase() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(
+        &file_path,
+        r#"
+// @safe
+int add(int a, int b) {
+    return a + b;
+}
+"#,
+    )
+    .expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg("--timings")
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.contains("Timings:"), "stderr: {}", stderr);
+    assert!(
+        stderr
+            .lines()
+            .any(|line| line.trim_start().starts_with("parse ")),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_no_timings_by_default() {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, "// @safe\nvoid f() {}\n").expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!stderr.contains("Timings:"), "stderr: {}", stderr);
+}
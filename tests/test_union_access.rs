@@ -0,0 +1,65 @@
+//! Reading a union member other than the one last written is undefined
+//! behavior (type punning); @safe code may only read the active member.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_read_of_inactive_union_member() {
+    let (success, output) = check(
+        r#"
+union Value {
+    int i;
+    float f;
+};
+
+// @safe
+float punned() {
+    Value v;
+    v.i = 42;
+    return v.f;
+}
+"#,
+    );
+    assert!(!success, "{}", output);
+    assert!(
+        output.contains("reading inactive union member 'f' of 'v'"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_read_of_active_union_member_ok() {
+    let (success, output) = check(
+        r#"
+union Value {
+    int i;
+    float f;
+};
+
+// @safe
+float active() {
+    Value v;
+    v.i = 42;
+    v.f = 1.5f;
+    return v.f;
+}
+"#,
+    );
+    assert!(success, "{}", output);
+}
@@ -0,0 +1,96 @@
+//! An `@unsafe` block that hides no diagnostic is reported as unnecessary.
+//! Like the other style checks, it only runs with `--pedantic` or when
+//! enabled explicitly.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str, args: &[&str]) -> String {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("unnecessary_unsafe.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+const SAFE_OPERATIONS_ONLY: &str = r#"
+// @safe
+void update(int& total, int delta) {
+    // @unsafe
+    {
+        total += delta;
+    }
+}
+"#;
+
+#[test]
+fn test_block_of_safe_operations_warns() {
+    let stdout = check(SAFE_OPERATIONS_ONLY, &["--pedantic"]);
+    assert!(
+        stdout.contains("unnecessary @unsafe block at line 7"),
+        "expected an unnecessary-unsafe warning. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_check_is_off_by_default() {
+    let stdout = check(SAFE_OPERATIONS_ONLY, &[]);
+    assert!(
+        !stdout.contains("unnecessary @unsafe block"),
+        "unnecessary-unsafe should not run by default. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_block_around_pointer_dereference_not_flagged() {
+    let source = r#"
+// @safe
+int read(int* p) {
+    int value = 0;
+    // @unsafe
+    {
+        value = *p;
+    }
+    return value;
+}
+"#;
+
+    let stdout = check(source, &["--pedantic"]);
+    assert!(
+        !stdout.contains("unnecessary @unsafe block"),
+        "the dereference needs the block. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_block_around_out_of_bounds_index_not_flagged() {
+    // Only the array-bounds check skips the indexing inside the block
+    let source = r#"
+// @safe
+int read() {
+    int arr[5] = {1, 2, 3, 4, 5};
+    int value = 0;
+    // @unsafe
+    {
+        value = arr[10];
+    }
+    return value;
+}
+"#;
+
+    let stdout = check(source, &["--pedantic"]);
+    assert!(
+        !stdout.contains("unnecessary @unsafe block"),
+        "the out-of-bounds index needs the block. Output: {}",
+        stdout
+    );
+}
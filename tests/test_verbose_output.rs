@@ -0,0 +1,98 @@
+/// Tests for `-v` verbosity levels
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer_stderr(cpp_file: &Path, extra_args: &[&str]) -> String {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--", cpp_file.to_str().unwrap()])
+        .args(extra_args)
+        .env("Z3_SYS_Z3_HEADER", z3_header)
+        .env_remove("RUSTY_CPP_DEBUG");
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+fn write_project(dir: &TempDir) -> std::path::PathBuf {
+    fs::write(
+        dir.path().join("helper.h"),
+        r#"
+#pragma once
+// @safe
+int helper(int x);
+"#,
+    )
+    .unwrap();
+    let cpp_file = dir.path().join("main.cpp");
+    fs::write(
+        &cpp_file,
+        r#"
+#include "helper.h"
+
+// @safe
+int run() {
+    return helper(1);
+}
+"#,
+    )
+    .unwrap();
+    cpp_file
+}
+
+#[test]
+fn test_double_verbose_prints_include_resolution() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = write_project(&dir);
+
+    let stderr = run_analyzer_stderr(&cpp_file, &["-vv"]);
+    assert!(
+        stderr.contains("Resolved include 'helper.h'"),
+        "-vv should report include resolution. Stderr: {}",
+        stderr
+    );
+    assert!(
+        !stderr.contains("DEBUG"),
+        "-vv should not enable debug output. Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_default_verbosity_is_silent_about_includes() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = write_project(&dir);
+
+    let stderr = run_analyzer_stderr(&cpp_file, &[]);
+    assert!(
+        !stderr.contains("Resolved include"),
+        "Include resolution should only be reported with -vv. Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_triple_verbose_prints_function_safety() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = write_project(&dir);
+
+    let stderr = run_analyzer_stderr(&cpp_file, &["-vvv"]);
+    assert!(
+        stderr.contains("Function 'run': Safe"),
+        "-vvv should report per-function safety decisions. Stderr: {}",
+        stderr
+    );
+}
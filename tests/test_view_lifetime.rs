@@ -0,0 +1,140 @@
+//! Tests for non-owning views (`std::string_view`, `std::span`) outliving
+//! the storage they point into.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer_on_file(cpp_file: &std::path::Path) -> (bool, String) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--", cpp_file.to_str().unwrap()]);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("Z3_SYS_Z3_HEADER", "/opt/homebrew/include/z3.h");
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("Z3_SYS_Z3_HEADER", "/usr/include/z3.h");
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let full_output = format!("{}{}", stdout, stderr);
+
+    (output.status.success(), full_output)
+}
+
+#[test]
+fn test_string_view_into_temporary_string() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let cpp = r#"
+#include <string>
+#include <string_view>
+
+// @safe
+void view_temporary() {
+    std::string_view sv = std::string("hello");
+}
+"#;
+
+    let cpp_path = temp_dir.path().join("view_temporary.cpp");
+    fs::write(&cpp_path, cpp).unwrap();
+
+    let (success, output) = run_analyzer_on_file(&cpp_path);
+
+    assert!(
+        !success,
+        "view into a temporary must fail.\nOutput:\n{}",
+        output
+    );
+    assert!(
+        output.contains("'sv' views a temporary"),
+        "expected a dangling view diagnostic.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_string_view_outlives_backing_string() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let cpp = r#"
+#include <string>
+#include <string_view>
+
+// @safe
+void view_outlives() {
+    std::string_view sv;
+    {
+        std::string s = "hello";
+        sv = s;
+    }
+}
+"#;
+
+    let cpp_path = temp_dir.path().join("view_outlives.cpp");
+    fs::write(&cpp_path, cpp).unwrap();
+
+    let (_success, output) = run_analyzer_on_file(&cpp_path);
+
+    assert!(
+        output.contains("Dangling reference: 'sv' borrows from 's'"),
+        "view outliving its string must be flagged.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_string_view_of_live_string_ok() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let cpp = r#"
+#include <string>
+#include <string_view>
+
+// @safe
+void view_ok() {
+    std::string s = "hello";
+    std::string_view sv = s;
+}
+"#;
+
+    let cpp_path = temp_dir.path().join("view_ok.cpp");
+    fs::write(&cpp_path, cpp).unwrap();
+
+    let (_success, output) = run_analyzer_on_file(&cpp_path);
+
+    assert!(
+        !output.contains("Dangling"),
+        "a view of a string that outlives it is fine.\nOutput:\n{}",
+        output
+    );
+}
+
+#[test]
+fn test_returning_view_of_by_value_parameter() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let cpp = r#"
+#include <string>
+#include <string_view>
+
+// @safe
+std::string_view first_word(std::string s) {
+    return s;
+}
+"#;
+
+    let cpp_path = temp_dir.path().join("view_by_value_param.cpp");
+    fs::write(&cpp_path, cpp).unwrap();
+
+    let (success, output) = run_analyzer_on_file(&cpp_path);
+
+    assert!(
+        !success && output.contains("returned view borrows by-value parameter 's'"),
+        "a view of a by-value parameter dangles at return.\nOutput:\n{}",
+        output
+    );
+}
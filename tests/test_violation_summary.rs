@@ -0,0 +1,76 @@
+/// Tests for the per-kind violation summary printed after the violation list
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_analyzer(cpp_file: &Path, extra_args: &[&str]) -> String {
+    let z3_header = if cfg!(target_os = "macos") {
+        "/opt/homebrew/include/z3.h"
+    } else {
+        "/usr/include/z3.h"
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--", cpp_file.to_str().unwrap()])
+        .args(extra_args)
+        .env("Z3_SYS_Z3_HEADER", z3_header);
+
+    if cfg!(target_os = "macos") {
+        cmd.env("DYLD_LIBRARY_PATH", "/opt/homebrew/Cellar/llvm/19.1.7/lib");
+    } else {
+        cmd.env("LD_LIBRARY_PATH", "/usr/lib/llvm-14/lib");
+    }
+
+    let output = cmd.output().expect("Failed to execute analyzer");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+const TWO_USE_AFTER_MOVES: &str = r#"
+#include <utility>
+#include <string>
+
+// @safe
+void moves() {
+    std::string a = "a";
+    std::string b = std::move(a);
+    std::string c = a;
+
+    std::string d = "d";
+    std::string e = std::move(d);
+    std::string f = d;
+}
+"#;
+
+#[test]
+fn test_summary_counts_use_after_move() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("summary.cpp");
+    fs::write(&cpp_file, TWO_USE_AFTER_MOVES).unwrap();
+
+    let stdout = run_analyzer(&cpp_file, &[]);
+    assert!(
+        stdout.contains("Summary: 2 use-after-move, 1 file affected"),
+        "Expected summary with two use-after-move violations. Stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_no_summary_flag_suppresses_summary() {
+    let dir = TempDir::new().unwrap();
+    let cpp_file = dir.path().join("summary.cpp");
+    fs::write(&cpp_file, TWO_USE_AFTER_MOVES).unwrap();
+
+    let stdout = run_analyzer(&cpp_file, &["--no-summary"]);
+    assert!(
+        stdout.contains("violation"),
+        "Violations should still be listed. Stdout: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("Summary:"),
+        "--no-summary should suppress the summary line. Stdout: {}",
+        stdout
+    );
+}
@@ -0,0 +1,82 @@
+//! Reads and writes through a pointer to `volatile` memory are side effects
+//! the checker can't model, so @safe code must do them inside @unsafe.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn check(source: &str) -> (bool, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let file_path = dir.path().join("main.cpp");
+    fs::write(&file_path, source).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn test_volatile_dereference_in_safe_code() {
+    let (success, stdout) = check(
+        r#"
+// @safe
+int read_status(volatile int* reg) {
+    return *reg;
+}
+"#,
+    );
+    assert!(
+        !success,
+        "volatile dereference in @safe code should fail. Output: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("volatile dereference"),
+        "Expected volatile dereference error. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_volatile_dereference_in_unsafe_block() {
+    let (success, stdout) = check(
+        r#"
+// @safe
+int read_status(volatile int* reg) {
+    int value = 0;
+    // @unsafe
+    {
+        value = *reg;
+    }
+    return value;
+}
+"#,
+    );
+    assert!(
+        success,
+        "volatile dereference inside @unsafe should pass. Output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_volatile_value_is_not_a_pointer_access() {
+    let (success, stdout) = check(
+        r#"
+// @safe
+int bump(int x) {
+    volatile int counter = x;
+    counter = counter + 1;
+    return counter;
+}
+"#,
+    );
+    assert!(
+        success,
+        "plain volatile locals should pass. Output: {}",
+        stdout
+    );
+}
@@ -0,0 +1,53 @@
+//! `--warn-unchecked-safe` reports functions annotated @safe that the
+//! checker skips anyway, so annotation mistakes don't go unnoticed.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"
+// @safe
+void helper() {
+    int x = 1;
+    int* p = &x;
+}
+"#;
+
+/// A user file whose path matches the system-header heuristic (`/bits/`)
+fn run(extra_args: &[&str]) -> (Option<i32>, String) {
+    let dir = TempDir::new().expect("create temp dir");
+    let bits = dir.path().join("bits");
+    fs::create_dir(&bits).expect("create dir");
+    let file_path = bits.join("helpers.cpp");
+    fs::write(&file_path, SOURCE).expect("write source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-cpp-checker"))
+        .args(extra_args)
+        .arg(&file_path)
+        .output()
+        .expect("run checker");
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_safe_function_in_system_like_path_is_reported() {
+    let (code, output) = run(&["--warn-unchecked-safe"]);
+    assert!(
+        output.contains("In function 'helper': warning: annotated @safe but never checked"),
+        "{}",
+        output
+    );
+    assert!(output.contains("system header"), "{}", output);
+    // A warning alone doesn't fail the run
+    assert_eq!(code, Some(0), "{}", output);
+}
+
+#[test]
+fn test_unchecked_safe_is_opt_in() {
+    let (code, output) = run(&[]);
+    assert!(!output.contains("never checked"), "{}", output);
+    assert_eq!(code, Some(0), "{}", output);
+}